@@ -30,6 +30,11 @@ run_test_suite() {
   if [ $TARGET = x86_64-unknown-linux-gnu ]; then
     cargo build --target $TARGET --no-default-features --verbose
     cargo test --target $TARGET --no-default-features --verbose
+
+    # Every feature on its own, so that a feature can not miss one that it needs
+    for feature in output html cli watch serve scripting debug; do
+      cargo build --target $TARGET --no-default-features --features $feature --verbose
+    done
     cargo clean
   fi
 
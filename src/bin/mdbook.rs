@@ -26,7 +26,7 @@ extern crate ws;
 use std::env;
 use std::error::Error;
 use std::ffi::OsStr;
-use std::io::{self, Write};
+use std::io::{self, IsTerminal, Write};
 use std::path::{Path, PathBuf};
 
 use clap::{App, ArgMatches, SubCommand, AppSettings};
@@ -38,9 +38,14 @@ use notify::Watcher;
 use std::time::Duration;
 #[cfg(feature = "watch")]
 use std::sync::mpsc::channel;
+#[cfg(feature = "watch")]
+use std::collections::HashSet;
+#[cfg(feature = "watch")]
+use mdbook::utils::ignore::IGNORE_FILE;
 
 
-use mdbook::MDBook;
+use mdbook::{MDBook, Workspace};
+use mdbook::utils;
 
 const NAME: &'static str = "mdbook";
 
@@ -60,17 +65,32 @@ fn main() {
                         // the {n} denotes a newline which will properly aligned in all help messages
                         .arg_from_usage("[dir] 'A directory for your book{n}(Defaults to Current Directory when omitted)'")
                         .arg_from_usage("--theme 'Copies the default theme into your source folder'")
-                        .arg_from_usage("--force 'skip confirmation prompts'"))
+                        .arg_from_usage("--title=[title] 'Title of the book, written to book.toml'")
+                        .arg_from_usage("--authors=[authors] 'Authors of the book separated by commas, written to book.toml'")
+                        .arg_from_usage("--force 'Overwrites book.toml and the theme and skips the prompts'"))
+                    .subcommand(SubCommand::with_name("add")
+                        .about("Add a chapter to SUMMARY.md and create its file")
+                        .arg_from_usage("<title> 'Title of the chapter'")
+                        .arg_from_usage("--after=[chapter] 'The chapter of SUMMARY.md the new one comes after, with the same indentation{n}(Defaults to the last numbered chapter)'")
+                        .arg_from_usage("--path=[path] 'File of the chapter, relative to the source directory{n}(Defaults to the title in lowercase, in the directory of --after)'")
+                        .arg_from_usage("[dir] 'A directory for your book{n}(Defaults to Current Directory when omitted)'"))
                     .subcommand(SubCommand::with_name("build")
                         .about("Build the book from the markdown files")
                         .arg_from_usage("-o, --open 'Open the compiled book in a web browser'")
                         .arg_from_usage("-d, --dest-dir=[dest-dir] 'The output directory for your book{n}(Defaults to ./book when omitted)'")
-                        .arg_from_usage("[dir] 'A directory for your book{n}(Defaults to Current Directory when omitted)'"))
+                        .arg_from_usage("[dir] 'A directory for your book{n}(Defaults to Current Directory when omitted)'")
+                        .arg_from_usage("--profile=[profile] 'Applies the [profile.<profile>] table of book.toml over the rest of the config'")
+                        .arg_from_usage("--strict 'Fails the build when there are warnings'")
+                        .arg_from_usage("--timings 'Prints the time spent parsing, preprocessing, rendering and copying'")
+                        .arg_from_usage("--dry-run 'Renders the book without writing anything and lists the files it would write'")
+                        .arg_from_usage("--workspace 'Builds the books of the [workspace] table of book.toml into one site with a landing page'"))
                     .subcommand(SubCommand::with_name("watch")
                         .about("Watch the files for changes")
                         .arg_from_usage("-o, --open 'Open the compiled book in a web browser'")
                         .arg_from_usage("-d, --dest-dir=[dest-dir] 'The output directory for your book{n}(Defaults to ./book when omitted)'")
-                        .arg_from_usage("[dir] 'A directory for your book{n}(Defaults to Current Directory when omitted)'"))
+                        .arg_from_usage("[dir] 'A directory for your book{n}(Defaults to Current Directory when omitted)'")
+                        .arg_from_usage("--profile=[profile] 'Applies the [profile.<profile>] table of book.toml over the rest of the config'")
+                        .arg_from_usage("--workspace 'Watches and builds every book of the [workspace] table of book.toml'"))
                     .subcommand(SubCommand::with_name("serve")
                         .about("Serve the book at http://localhost:3000. Rebuild and reload on change.")
                         .arg_from_usage("[dir] 'A directory for your book{n}(Defaults to Current Directory when omitted)'")
@@ -79,20 +99,49 @@ fn main() {
                         .arg_from_usage("-w, --websocket-port=[ws-port] 'Use another port for the websocket connection (livereload){n}(Defaults to 3001)'")
                         .arg_from_usage("-i, --interface=[interface] 'Interface to listen on{n}(Defaults to localhost)'")
                         .arg_from_usage("-a, --address=[address] 'Address that the browser can reach the websocket server from{n}(Defaults to the interface address)'")
-                        .arg_from_usage("-o, --open 'Open the book server in a web browser'"))
+                        .arg_from_usage("-o, --open 'Open the book server in a web browser'")
+                        .arg_from_usage("--profile=[profile] 'Applies the [profile.<profile>] table of book.toml over the rest of the config'")
+                        .arg_from_usage("--workspace 'Serves and watches every book of the [workspace] table of book.toml'"))
                     .subcommand(SubCommand::with_name("test")
                         .about("Test that code samples compile"))
+                    .subcommand(SubCommand::with_name("versions")
+                        .about("Build the git refs listed in book.toml next to each other, with a version picker")
+                        .arg_from_usage("-o, --open 'Open the latest version in a web browser'")
+                        .arg_from_usage("-d, --dest-dir=[dest-dir] 'The output directory for your book{n}(Defaults to ./book when omitted)'")
+                        .arg_from_usage("[dir] 'A directory for your book{n}(Defaults to Current Directory when omitted)'")
+                        .arg_from_usage("--profile=[profile] 'Applies the [profile.<profile>] table of book.toml over the rest of the config'"))
+                    .subcommand(SubCommand::with_name("deploy")
+                        .about("Build the book and push it to the gh-pages branch, or the one configured in book.toml")
+                        .arg_from_usage("-d, --dest-dir=[dest-dir] 'The output directory for your book{n}(Defaults to ./book when omitted)'")
+                        .arg_from_usage("[dir] 'A directory for your book{n}(Defaults to Current Directory when omitted)'")
+                        .arg_from_usage("--profile=[profile] 'Applies the [profile.<profile>] table of book.toml over the rest of the config'"))
+                    .subcommand(SubCommand::with_name("lint")
+                        .about("Check the prose of the chapters with the command configured in book.toml")
+                        .arg_from_usage("[dir] 'A directory for your book{n}(Defaults to Current Directory when omitted)'"))
+                    .subcommand(SubCommand::with_name("check")
+                        .about("Check SUMMARY.md, the links of the chapters and the files of the source without building the book")
+                        .arg_from_usage("[dir] 'A directory for your book{n}(Defaults to Current Directory when omitted)'")
+                        .arg_from_usage("--profile=[profile] 'Applies the [profile.<profile>] table of book.toml over the rest of the config'"))
+                    .subcommand(SubCommand::with_name("check-theme")
+                        .about("Check the templates of the theme without building the book")
+                        .arg_from_usage("[dir] 'A directory for your book{n}(Defaults to Current Directory when omitted)'"))
                     .get_matches();
 
     // Check which subcomamnd the user ran...
     let res = match matches.subcommand() {
         ("init", Some(sub_matches)) => init(sub_matches),
+        ("add", Some(sub_matches)) => add(sub_matches),
         ("build", Some(sub_matches)) => build(sub_matches),
         #[cfg(feature = "watch")]
         ("watch", Some(sub_matches)) => watch(sub_matches),
         #[cfg(feature = "serve")]
         ("serve", Some(sub_matches)) => serve(sub_matches),
         ("test", Some(sub_matches)) => test(sub_matches),
+        ("versions", Some(sub_matches)) => versions(sub_matches),
+        ("deploy", Some(sub_matches)) => deploy(sub_matches),
+        ("lint", Some(sub_matches)) => lint(sub_matches),
+        ("check", Some(sub_matches)) => check(sub_matches),
+        ("check-theme", Some(sub_matches)) => check_theme(sub_matches),
         (_, _) => unreachable!(),
     };
 
@@ -115,20 +164,55 @@ fn confirm() -> bool {
 }
 
 
+// The answer to a question, `None` when it is empty
+fn prompt(question: &str) -> Option<String> {
+    print!("\n{} ", question);
+    io::stdout().flush().unwrap();
+    let mut s = String::new();
+    io::stdin().read_line(&mut s).ok();
+    Some(s.trim().to_owned()).filter(|s| !s.is_empty())
+}
+
+
 // Init command implementation
 fn init(args: &ArgMatches) -> Result<(), Box<Error>> {
 
     let book_dir = get_book_dir(args);
-    let mut book = MDBook::new(&book_dir);
+    // The output directory of an existing `book.toml` is the one ignored by git
+    let mut book = MDBook::new(&book_dir).read_config();
+    let force = args.is_present("force");
+    // Without a terminal, e.g. in a script, the questions are not asked
+    let interactive = !force && io::stdin().is_terminal();
 
     // Call the function that does the initialization
     try!(book.init());
 
+    // Title and authors of book.toml, from the flags or the answers to the questions
+    let config_exists = book.get_root().join("book.toml").exists();
+    let ask = interactive && !config_exists;
+    let title = args.value_of("title")
+        .map(|t| t.to_owned())
+        .or_else(|| if ask { prompt("What title would you like to give the book?") } else { None });
+    let authors: Vec<String> = args.value_of("authors")
+        .map(|a| a.to_owned())
+        .or_else(|| if ask { prompt("Who are the authors of the book? (separated by commas)") } else { None })
+        .map(|a| a.split(',').map(|a| a.trim().to_owned()).filter(|a| !a.is_empty()).collect())
+        .unwrap_or_default();
+
+    if title.is_some() || !authors.is_empty() {
+        let title = title.unwrap_or_else(|| book.get_title().to_owned());
+        if try!(book.create_config(&title, &authors, force)) {
+            println!("\nbook.toml created.");
+        } else {
+            println!("\nbook.toml already exists, use --force to replace it.");
+        }
+    }
+
     // If flag `--theme` is present, copy theme to src
     if args.is_present("theme") {
 
         // Skip this if `--force` is present
-        if !args.is_present("force") {
+        if !force {
             // Print warning
             print!("\nCopying the default theme to {:?}", book.get_src());
             println!("could potentially overwrite files already present in that directory.");
@@ -149,15 +233,9 @@ fn init(args: &ArgMatches) -> Result<(), Box<Error>> {
     }
 
     // Because of `src/book/mdbook.rs#L37-L39`, `dest` will always start with `root`
-    let is_dest_inside_root = book.get_dest().starts_with(book.get_root());
-
-    if !args.is_present("force") && is_dest_inside_root {
-        println!("\nDo you want a .gitignore to be created? (y/n)");
-
-        if confirm() {
-            book.create_gitignore();
-            println!("\n.gitignore created.");
-        }
+    if book.get_dest().starts_with(book.get_root()) {
+        book.create_gitignore();
+        println!("\n.gitignore ignores {}.", book.get_dest().strip_prefix(book.get_root()).unwrap().display());
     }
 
     println!("\nAll done, no errors...");
@@ -166,19 +244,62 @@ fn init(args: &ArgMatches) -> Result<(), Box<Error>> {
 }
 
 
+// Add command implementation
+fn add(args: &ArgMatches) -> Result<(), Box<Error>> {
+    let book_dir = get_book_dir(args);
+    let book = MDBook::new(&book_dir).read_config();
+
+    let title = args.value_of("title").unwrap();
+    let path = args.value_of("path").map(Path::new);
+    let path = try!(book.add_chapter(title, path, args.value_of("after").map(Path::new)));
+    if book.get_config().auto_summary {
+        println!("Created {}", book.get_src().join(path).display());
+    } else {
+        println!("Added {} to SUMMARY.md", book.get_src().join(path).display());
+    }
+    Ok(())
+}
+
+
 // Build command implementation
 fn build(args: &ArgMatches) -> Result<(), Box<Error>> {
     let book_dir = get_book_dir(args);
-    let book = MDBook::new(&book_dir).read_config();
+    if args.is_present("workspace") {
+        if args.is_present("dry-run") {
+            return Err(Box::new(io::Error::new(io::ErrorKind::Other, "--dry-run can not be used with --workspace")));
+        }
+        let workspace = try!(read_workspace(&book_dir, args));
+        let mut books: Vec<MDBook> = workspace.books()
+            .into_iter()
+            .map(|book| book.set_strict(args.is_present("strict")).set_timings(args.is_present("timings")))
+            .collect();
+        let built = workspace.build(&mut books);
+        for book in &books {
+            print_build_report(book);
+        }
+        try!(built);
+
+        if args.is_present("open") {
+            open(workspace.get_dest().join("index.html"));
+        }
+        return Ok(());
+    }
+
+    let book = read_book(&book_dir, args)
+        .set_strict(args.is_present("strict"))
+        .set_timings(args.is_present("timings"))
+        .set_dry_run(args.is_present("dry-run"));
 
     let mut book = match args.value_of("dest-dir") {
         Some(dest_dir) => book.set_dest(Path::new(dest_dir)),
         None => book
     };
 
-    try!(book.build());
+    let built = book.build();
+    print_build_report(&book);
+    try!(built);
 
-    if args.is_present("open") {
+    if args.is_present("open") && !book.is_dry_run() {
         open(book.get_dest().join("index.html"));
     }
 
@@ -190,23 +311,19 @@ fn build(args: &ArgMatches) -> Result<(), Box<Error>> {
 #[cfg(feature = "watch")]
 fn watch(args: &ArgMatches) -> Result<(), Box<Error>> {
     let book_dir = get_book_dir(args);
-    let book = MDBook::new(&book_dir).read_config();
-
-    let mut book = match args.value_of("dest-dir") {
-        Some(dest_dir) => book.set_dest(Path::new(dest_dir)),
-        None => book
-    };
+    let (workspace, mut books) = try!(read_books(&book_dir, args));
 
     if args.is_present("open") {
-        try!(book.build());
-        open(book.get_dest().join("index.html"));
+        try!(build_books(workspace.as_ref(), &mut books));
+        open(site_dest(workspace.as_ref(), &books).join("index.html"));
     }
 
-    trigger_on_change(&mut book, |path, book| {
+    trigger_on_change(&mut books, |path, book| {
         println!("File changed: {:?}\nBuilding book...\n", path);
-        match book.build() {
-            Err(e) => println!("Error while building: {:?}", e),
-            _ => {},
+        let built = book.build();
+        print_build_report(book);
+        if let Err(e) = built {
+            println!("Error while building: {}", e);
         }
         println!("");
     });
@@ -221,12 +338,7 @@ fn serve(args: &ArgMatches) -> Result<(), Box<Error>> {
     const RELOAD_COMMAND: &'static str = "reload";
 
     let book_dir = get_book_dir(args);
-    let book = MDBook::new(&book_dir).read_config();
-
-    let mut book = match args.value_of("dest-dir") {
-        Some(dest_dir) => book.set_dest(Path::new(dest_dir)),
-        None => book
-    };
+    let (workspace, mut books) = try!(read_books(&book_dir, args));
 
     let port = args.value_of("port").unwrap_or("3000");
     let ws_port = args.value_of("ws-port").unwrap_or("3001");
@@ -237,7 +349,7 @@ fn serve(args: &ArgMatches) -> Result<(), Box<Error>> {
     let address = format!("{}:{}", interface, port);
     let ws_address = format!("{}:{}", interface, ws_port);
 
-    book.set_livereload(format!(r#"
+    let livereload = format!(r#"
         <script type="text/javascript">
             var socket = new WebSocket("ws://{}:{}");
             socket.onmessage = function (event) {{
@@ -251,11 +363,14 @@ fn serve(args: &ArgMatches) -> Result<(), Box<Error>> {
                 socket.close();
             }}
         </script>
-    "#, public_address, ws_port, RELOAD_COMMAND).to_owned());
+    "#, public_address, ws_port, RELOAD_COMMAND);
+    for book in &mut books {
+        book.set_livereload(livereload.clone());
+    }
 
-    try!(book.build());
+    try!(build_books(workspace.as_ref(), &mut books));
 
-    let staticfile = staticfile::Static::new(book.get_dest());
+    let staticfile = staticfile::Static::new(site_dest(workspace.as_ref(), &books));
     let iron = iron::Iron::new(staticfile);
     let _iron = iron.http(&*address).unwrap();
 
@@ -277,11 +392,13 @@ fn serve(args: &ArgMatches) -> Result<(), Box<Error>> {
         open(format!("http://{}", address));
     }
 
-    trigger_on_change(&mut book, move |path, book| {
+    trigger_on_change(&mut books, move |path, book| {
         println!("File changed: {:?}\nBuilding book...\n", path);
-        match book.build() {
-            Err(e) => println!("Error while building: {:?}", e),
-            _ => broadcaster.send(RELOAD_COMMAND).unwrap(),
+        let built = book.build();
+        print_build_report(book);
+        match built {
+            Err(e) => println!("Error while building: {}", e),
+            Ok(_) => broadcaster.send(RELOAD_COMMAND).unwrap(),
         }
         println!("");
     });
@@ -300,6 +417,128 @@ fn test(args: &ArgMatches) -> Result<(), Box<Error>> {
 }
 
 
+// Versions command implementation
+fn versions(args: &ArgMatches) -> Result<(), Box<Error>> {
+    let book_dir = get_book_dir(args);
+    let book = read_book(&book_dir, args);
+
+    let book = match args.value_of("dest-dir") {
+        Some(dest_dir) => book.set_dest(Path::new(dest_dir)),
+        None => book
+    };
+
+    for (version, warnings) in try!(book.build_versions()) {
+        for warning in warnings {
+            println!("[*]: Warning: version {}: {}", version, warning);
+        }
+    }
+
+    if args.is_present("open") {
+        open(book.get_dest().join("latest").join("index.html"));
+    }
+
+    Ok(())
+}
+
+
+// Deploy command implementation
+fn deploy(args: &ArgMatches) -> Result<(), Box<Error>> {
+    let book_dir = get_book_dir(args);
+    let book = read_book(&book_dir, args);
+
+    let mut book = match args.value_of("dest-dir") {
+        Some(dest_dir) => book.set_dest(Path::new(dest_dir)),
+        None => book
+    };
+
+    let deployed = book.deploy();
+    print_build_report(&book);
+    try!(deployed);
+
+    Ok(())
+}
+
+
+fn lint(args: &ArgMatches) -> Result<(), Box<Error>> {
+    let book_dir = get_book_dir(args);
+    let mut book = MDBook::new(&book_dir).read_config();
+
+    let findings = try!(book.lint());
+    for finding in &findings {
+        println!("{}", finding);
+    }
+
+    if findings.is_empty() {
+        println!("\nAll done, no problems found...");
+        Ok(())
+    } else {
+        Err(Box::new(io::Error::new(io::ErrorKind::Other, format!("{} problems found", findings.len()))))
+    }
+}
+
+
+fn check(args: &ArgMatches) -> Result<(), Box<Error>> {
+    let book_dir = get_book_dir(args);
+    let mut book = read_book(&book_dir, args);
+
+    let warnings = try!(book.check());
+    for warning in &warnings {
+        println!("{}", warning);
+    }
+
+    if warnings.is_empty() {
+        println!("All done, no problems found...");
+        Ok(())
+    } else {
+        Err(Box::new(io::Error::new(io::ErrorKind::Other, format!("{} problems found", warnings.len()))))
+    }
+}
+
+
+fn check_theme(args: &ArgMatches) -> Result<(), Box<Error>> {
+    let book_dir = get_book_dir(args);
+    let mut book = MDBook::new(&book_dir).read_config();
+
+    try!(book.check_theme());
+    println!("All done, no problems found...");
+    Ok(())
+}
+
+
+// The report of the last build of the book: the timings with `--timings`, the files of a dry run, the warnings
+// and the totals of the chapters rendered
+fn print_build_report(book: &MDBook) {
+    if book.has_timings() && !book.get_timings().phases().is_empty() {
+        println!("[*]: Timings:\n{}", book.get_timings().report().trim_end());
+    }
+
+    if book.is_dry_run() {
+        let files = book.get_dry_run_files();
+        println!("[*]: Dry run, {} files would be written to {}:", files.len(), book.get_dest().display());
+        for file in &files {
+            println!("    {}", file.display());
+        }
+    }
+
+    for warning in book.get_warnings() {
+        println!("[*]: Warning: {}", warning);
+    }
+
+    let (chapters, words) = book.get_word_count();
+    if chapters == 0 {
+        return;
+    }
+    let minutes = utils::reading_time(words);
+    println!("[*]: Rendered {}, {}, about {} of reading",
+             plural(chapters, "chapter"),
+             plural(words, "word"),
+             plural(minutes, "minute"));
+}
+
+fn plural(count: usize, word: &str) -> String {
+    format!("{} {}{}", count, word, if count == 1 { "" } else { "s" })
+}
+
 fn get_book_dir(args: &ArgMatches) -> PathBuf {
     if let Some(dir) = args.value_of("dir") {
         // Check if path is relative from current dir, or absolute...
@@ -314,6 +553,63 @@ fn get_book_dir(args: &ArgMatches) -> PathBuf {
     }
 }
 
+// Reads the config of the book, with the profile given by `--profile`
+fn read_book(book_dir: &Path, args: &ArgMatches) -> MDBook {
+    match args.value_of("profile") {
+        Some(profile) => MDBook::new(book_dir).set_profile(profile).read_config(),
+        None => MDBook::new(book_dir).read_config(),
+    }
+}
+
+// The workspace at the root of the book directory, with the profile and `--dest-dir`
+fn read_workspace(book_dir: &Path, args: &ArgMatches) -> Result<Workspace, Box<Error>> {
+    let workspace = match args.value_of("profile") {
+        Some(profile) => try!(Workspace::with_profile(book_dir, profile)),
+        None => try!(Workspace::new(book_dir)),
+    };
+
+    Ok(match args.value_of("dest-dir") {
+        Some(dest_dir) => workspace.set_dest(Path::new(dest_dir)),
+        None => workspace,
+    })
+}
+
+// The books of the workspace with `--workspace`, otherwise the book of the directory, with `--dest-dir`
+#[cfg(feature = "watch")]
+fn read_books(book_dir: &Path, args: &ArgMatches) -> Result<(Option<Workspace>, Vec<MDBook>), Box<Error>> {
+    if args.is_present("workspace") {
+        let workspace = try!(read_workspace(book_dir, args));
+        let books = workspace.books();
+        return Ok((Some(workspace), books));
+    }
+
+    let book = read_book(book_dir, args);
+    let book = match args.value_of("dest-dir") {
+        Some(dest_dir) => book.set_dest(Path::new(dest_dir)),
+        None => book
+    };
+    Ok((None, vec![book]))
+}
+
+// Builds the books, and the landing page of the workspace
+#[cfg(feature = "watch")]
+fn build_books(workspace: Option<&Workspace>, books: &mut [MDBook]) -> Result<(), Box<Error>> {
+    let built = match workspace {
+        Some(workspace) => workspace.build(books),
+        None => books[0].build(),
+    };
+    for book in books.iter() {
+        print_build_report(book);
+    }
+    built
+}
+
+// The directory of the site, the one of the landing page for a workspace
+#[cfg(feature = "watch")]
+fn site_dest(workspace: Option<&Workspace>, books: &[MDBook]) -> PathBuf {
+    workspace.map_or(books[0].get_dest(), |w| w.get_dest()).to_owned()
+}
+
 fn open<P: AsRef<OsStr>>(path: P) {
     if let Err(e) = open::that(path) {
         println!("Error opening web browser: {}", e);
@@ -321,9 +617,10 @@ fn open<P: AsRef<OsStr>>(path: P) {
 }
 
 
-// Calls the closure when a book source file is changed. This is blocking!
+// Calls the closure when a source file of one of the books is changed, with the book it belongs to. This is
+// blocking!
 #[cfg(feature = "watch")]
-fn trigger_on_change<F>(book: &mut MDBook, closure: F) -> ()
+fn trigger_on_change<F>(books: &mut [MDBook], closure: F) -> ()
     where F: Fn(&Path, &mut MDBook) -> ()
 {
     use notify::RecursiveMode::*;
@@ -340,19 +637,51 @@ fn trigger_on_change<F>(book: &mut MDBook, closure: F) -> ()
         }
     };
 
-    // Add the source directory to the watcher
-    if let Err(e) = watcher.watch(book.get_src(), Recursive) {
-        println!("Error while watching {:?}:\n    {:?}", book.get_src(), e);
-        ::std::process::exit(0);
-    };
+    // The theme and the includes of a workspace are shared by several books
+    let mut shared = HashSet::new();
+    for book in books.iter_mut() {
+        // The embedded books are found in `SUMMARY.md`, its errors are reported by the build
+        if let Err(e) = book.read_summary() {
+            debug!("[*]: Could not read the summary: {}", e);
+        }
 
-    // Add the book.{json,toml} file to the watcher if it exists, because it's not
-    // located in the source directory
-    if let Err(_) = watcher.watch(book.get_root().join("book.json"), NonRecursive) {
-        // do nothing if book.json is not found
-    }
-    if let Err(_) = watcher.watch(book.get_root().join("book.toml"), NonRecursive) {
-        // do nothing if book.toml is not found
+        // Add the source directory to the watcher
+        if let Err(e) = watcher.watch(book.get_src(), Recursive) {
+            println!("Error while watching {:?}:\n    {:?}", book.get_src(), e);
+            ::std::process::exit(0);
+        };
+
+        // Add the book.{json,toml} file to the watcher if it exists, because it's not
+        // located in the source directory
+        if let Err(_) = watcher.watch(book.get_root().join("book.json"), NonRecursive) {
+            // do nothing if book.json is not found
+        }
+        if let Err(_) = watcher.watch(book.get_root().join("book.toml"), NonRecursive) {
+            // do nothing if book.toml is not found
+        }
+        if let Err(_) = watcher.watch(book.get_root().join(IGNORE_FILE), NonRecursive) {
+            // do nothing if .mdbookignore is not found
+        }
+
+        // A change of a template or an asset rebuilds the book too, with the theme loaded again, and so does a
+        // change of the themes it extends
+        for theme_path in book.get_theme_dirs() {
+            if !theme_path.starts_with(book.get_src()) && shared.insert(theme_path.clone()) {
+                if let Err(e) = watcher.watch(&theme_path, Recursive) {
+                    println!("Error while watching {:?}:\n    {:?}", theme_path, e);
+                }
+            }
+        }
+
+        // The files included from outside of the source directory, and the embedded books
+        let sub_books = book.get_sub_books().iter().map(|b| &b.src);
+        for dir in book.get_config().includes.iter().chain(sub_books) {
+            if dir.exists() && !dir.starts_with(book.get_src()) && shared.insert(dir.to_owned()) {
+                if let Err(e) = watcher.watch(dir, Recursive) {
+                    println!("Error while watching {:?}:\n    {:?}", dir, e);
+                }
+            }
+        }
     }
 
     println!("\nListening for changes...\n");
@@ -366,7 +695,23 @@ fn trigger_on_change<F>(book: &mut MDBook, closure: F) -> ()
                 Write(path) |
                 Remove(path) |
                 Rename(_, path) => {
-                    closure(&path, book);
+                    // The books the file belongs to, every book when it is elsewhere
+                    let contains = |book: &MDBook| {
+                        path.starts_with(book.get_root()) || path.starts_with(book.get_src()) ||
+                        book.get_theme_dirs().iter().any(|dir| path.starts_with(dir)) ||
+                        book.get_config().includes.iter().any(|dir| path.starts_with(dir)) ||
+                        book.get_sub_books().iter().any(|b| path.starts_with(&b.src))
+                    };
+                    let all = !books.iter().any(&contains);
+
+                    for book in books.iter_mut().filter(|book| all || contains(book)) {
+                        // Swap files of the editors and other ignored files do not rebuild the book
+                        if book.get_ignore_patterns().is_ignored(&path) {
+                            debug!("[*]: {:?} is ignored", path);
+                            continue;
+                        }
+                        closure(&path, book);
+                    }
                 }
                 _ => {}
             },
@@ -0,0 +1,172 @@
+use std::path::{Path, PathBuf};
+use std::error::Error;
+use std::fs;
+
+use pulldown_cmark::{Event, Parser, Tag};
+
+use book::bookitem::{BookItem, Chapter};
+use book::frontmatter::FrontMatter;
+use parse;
+use utils::ignore::IgnorePatterns;
+
+// Files that are the chapter of their directory, the first one found
+const DIR_CHAPTERS: &'static [&'static str] = &["README.md", "index.md"];
+
+// A chapter and the `weight` of its front matter
+type Weighted = (Option<i64>, Chapter);
+
+/// The chapters of the markdown files of the source directory `src`, used instead of `SUMMARY.md` with
+/// `auto-summary`. The `README.md` or `index.md` of the source directory is the prefix chapter, and every other
+/// directory is a chapter with its files and directories as sub-chapters, its `README.md` or `index.md` being the
+/// page of the chapter, or a draft chapter without one.
+///
+/// In a directory the chapters with a `weight` in their front matter come first, the lightest first, then the
+/// other ones by file name. The weight of a directory is the one of its chapter. The name of a chapter is the
+/// `title` of its front matter, or its first heading, or the name of its file.
+pub fn generate_summary(src: &Path, ignore: &IgnorePatterns) -> Result<Vec<BookItem>, Box<Error>> {
+    let mut items = vec![];
+    if let Some(index) = dir_chapter(src) {
+        let (name, _) = chapter_info(&src.join(index), Path::new(index));
+        items.push(BookItem::Affix(Chapter::new(name, PathBuf::from(index))));
+    }
+
+    let chapters = try!(dir_chapters(src, Path::new(""), ignore));
+    items.extend(chapters.into_iter().map(|(_, ch)| BookItem::Chapter(String::new(), ch)));
+    parse::summary::renumber(&mut items, "");
+    Ok(items)
+}
+
+// The chapters of the directory `dir` of the source, with their weight
+fn dir_chapters(src: &Path, dir: &Path, ignore: &IgnorePatterns) -> Result<Vec<Weighted>, Box<Error>> {
+    let mut entries: Vec<PathBuf> = try!(fs::read_dir(src.join(dir)))
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .collect();
+    entries.sort();
+
+    let own = dir_chapter(&src.join(dir));
+    let mut chapters = vec![];
+    for entry in entries {
+        let file_name = entry.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+        if file_name.starts_with('.') || ignore.is_ignored(&entry) {
+            continue;
+        }
+        let path = dir.join(&file_name);
+
+        if entry.is_dir() {
+            let sub_items = try!(dir_chapters(src, &path, ignore));
+            let (weight, name, chapter_path) = match dir_chapter(&entry) {
+                Some(index) => {
+                    let (name, weight) = chapter_info(&entry.join(index), &path);
+                    (weight, name, path.join(index))
+                },
+                None if sub_items.is_empty() => continue,
+                None => (None, file_name.clone(), PathBuf::new()),
+            };
+            let mut chapter = Chapter::new(name, chapter_path);
+            chapter.sub_items = sub_items.into_iter().map(|(_, ch)| BookItem::Chapter(String::new(), ch)).collect();
+            chapters.push((weight, chapter));
+        } else if entry.extension().is_some_and(|ext| ext == "md") && file_name != "SUMMARY.md" &&
+                  own.as_ref().is_none_or(|own| *own != file_name) {
+            let (name, weight) = chapter_info(&entry, &path);
+            chapters.push((weight, Chapter::new(name, path)));
+        }
+    }
+
+    // The sort is stable, the chapters without weight stay in the order of their file names
+    chapters.sort_by_key(|&(weight, _)| (weight.is_none(), weight));
+    Ok(chapters)
+}
+
+fn dir_chapter(dir: &Path) -> Option<&'static str> {
+    DIR_CHAPTERS.iter().cloned().find(|name| dir.join(name).is_file())
+}
+
+// The name and the weight of the chapter in `file`, whose path in the source is `path`
+fn chapter_info(file: &Path, path: &Path) -> (String, Option<i64>) {
+    let fallback = || path.file_stem().map(|s| s.to_string_lossy().into_owned()).unwrap_or_default();
+    // A file that can not be read, or whose front matter is invalid, fails the build when it is rendered
+    let content = match fs::read(file) {
+        Ok(bytes) => String::from_utf8_lossy(&bytes).into_owned(),
+        Err(_) => return (fallback(), None),
+    };
+    let (front_matter, markdown) = match FrontMatter::split(&content) {
+        Ok((front_matter, markdown)) => (front_matter, markdown),
+        Err(_) => (FrontMatter::new(), &content[..]),
+    };
+
+    let weight = front_matter.get("weight").and_then(|w| w.as_integer());
+    let name = front_matter.get_str("title")
+        .map(|title| title.to_owned())
+        .or_else(|| first_heading(markdown))
+        .unwrap_or_else(fallback);
+    (name, weight)
+}
+
+fn first_heading(markdown: &str) -> Option<String> {
+    let mut heading: Option<String> = None;
+    for event in Parser::new(markdown) {
+        match event {
+            Event::Start(Tag::Header(_)) => heading = Some(String::new()),
+            Event::End(Tag::Header(_)) => break,
+            Event::Text(ref text) => {
+                if let Some(ref mut heading) = heading {
+                    heading.push_str(text);
+                }
+            },
+            _ => {},
+        }
+    }
+    heading.map(|heading| heading.trim().to_owned()).filter(|heading| !heading.is_empty())
+}
+
+
+#[cfg(test)]
+mod tests {
+    extern crate tempdir;
+
+    use super::*;
+    use std::fs::File;
+    use std::io::Write;
+
+    fn write(path: &Path, content: &str) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        File::create(path).unwrap().write_all(content.as_bytes()).unwrap();
+    }
+
+    #[test]
+    fn it_orders_the_chapters_by_weight() {
+        let tmp = tempdir::TempDir::new("mdbook").expect("Could not create a temp dir");
+        let src = tmp.path();
+        write(&src.join("README.md"), "# Introduction\n");
+        write(&src.join("SUMMARY.md"), "# Summary\n\n- [Old](old.md)\n");
+        write(&src.join("faq.md"), "# FAQ\n");
+        write(&src.join("install.md"), "+++\nweight = 1\n+++\n\n# Installation\n");
+        write(&src.join("cli/README.md"), "+++\nweight = 2\ntitle = \"Command line\"\n+++\n");
+        write(&src.join("cli/init.md"), "+++\nweight = 10\n+++\n\n# The init command\n");
+        write(&src.join("cli/build.md"), "+++\nweight = 20\n+++\n\n# The *build* command\n");
+        write(&src.join("cli/serve.md"), "Serves the book.\n");
+        write(&src.join("drafts/next.md"), "# Next\n");
+        write(&src.join("images/logo.png"), "");
+        write(&src.join(".hidden/notes.md"), "# Notes\n");
+
+        let ignore = IgnorePatterns::new(src, &["/drafts"]);
+        let items = generate_summary(src, &ignore).unwrap();
+        assert_eq!(parse::summary::summary_to_markdown(&items),
+                   "# Summary\n\n\
+                    [Introduction](README.md)\n\n\
+                    - [Installation](install.md)\n\
+                    - [Command line](cli/README.md)\n    \
+                        - [The init command](cli/init.md)\n    \
+                        - [The build command](cli/build.md)\n    \
+                        - [serve](cli/serve.md)\n\
+                    - [FAQ](faq.md)\n");
+        match items[2] {
+            BookItem::Chapter(ref section, ref ch) => {
+                assert_eq!(section, "2.");
+                assert_eq!(ch.name, "Command line");
+            },
+            _ => panic!("Expected a chapter"),
+        }
+    }
+}
@@ -0,0 +1,181 @@
+use std::path::{Path, PathBuf};
+use std::collections::BTreeMap;
+use toml;
+
+use book::bookitem::BookItem;
+use book::frontmatter::FrontMatter;
+use utils;
+
+/// Options of the blog section, read from the `[blog]` table of `book.toml`
+#[derive(Debug, Clone)]
+pub struct BlogConfig {
+    /// The chapter of `SUMMARY.md` whose sub-chapters are the posts, e.g. `news/README.md`
+    pub chapter: PathBuf,
+    /// Title of the feed, the name of the chapter by default
+    pub title: Option<String>,
+    /// Number of posts in the feed, the newest ones
+    pub feed_entries: usize,
+}
+
+/// A post of the blog section
+#[derive(Debug, Clone, PartialEq)]
+pub struct Post {
+    pub name: String,
+    /// Path of the chapter, relative to the source directory
+    pub path: PathBuf,
+    /// The `date` of its front matter
+    pub date: Option<String>,
+}
+
+impl BlogConfig {
+    /// Returns `None` when there is no `chapter`
+    pub fn from_btreemap(config: &BTreeMap<String, toml::Value>) -> Option<Self> {
+        let chapter = match config.get("chapter").and_then(|v| v.as_str()) {
+            Some(chapter) => PathBuf::from(chapter),
+            None => {
+                warn!("[*]: [blog] needs the `chapter` of SUMMARY.md containing the posts");
+                return None;
+            },
+        };
+
+        Some(BlogConfig {
+            chapter: chapter,
+            title: config.get("title").and_then(|v| v.as_str()).map(|s| s.to_owned()),
+            feed_entries: config.get("feed-entries")
+                .and_then(|v| v.as_integer())
+                .filter(|&n| n > 0)
+                .map_or(20, |n| n as usize),
+        })
+    }
+
+    /// Path of the feed next to the blog chapter, e.g. `news/atom.xml`
+    pub fn feed_path(&self) -> PathBuf {
+        self.chapter.strip_prefix("./").unwrap_or(&self.chapter).with_file_name("atom.xml")
+    }
+}
+
+/// Sorts the sub-chapters of the blog chapter by the date of their front matter, newest first, the posts
+/// without a date come last in the order of `SUMMARY.md`. Returns the posts in their new order, the
+/// sections of the chapters have to be numbered again.
+pub fn sort_posts(items: &mut [BookItem], config: &BlogConfig, src: &Path) -> Vec<Post> {
+    for item in items.iter_mut() {
+        let chapter = match *item {
+            BookItem::Chapter(_, ref mut ch) => ch,
+            _ => continue,
+        };
+        if !same_chapter(&chapter.path, &config.chapter) {
+            let posts = sort_posts(&mut chapter.sub_items, config, src);
+            if !posts.is_empty() {
+                return posts;
+            }
+            continue;
+        }
+
+        let mut posts: Vec<(Post, BookItem)> = chapter.sub_items
+            .drain(..)
+            .map(|item| {
+                let post = match item {
+                    BookItem::Chapter(_, ref ch) => {
+                        Post {
+                            name: ch.name.clone(),
+                            path: ch.path.clone(),
+                            date: post_date(&src.join(&ch.path)),
+                        }
+                    },
+                    _ => Post { name: String::new(), path: PathBuf::new(), date: None },
+                };
+                (post, item)
+            })
+            .collect();
+        // A stable sort, the posts of the same day stay in the order of `SUMMARY.md`
+        posts.sort_by(|a, b| match (&a.0.date, &b.0.date) {
+            (Some(a), Some(b)) => b.cmp(a),
+            (a, b) => b.is_some().cmp(&a.is_some()),
+        });
+
+        let (posts, sub_items): (Vec<Post>, Vec<BookItem>) = posts.into_iter().unzip();
+        chapter.sub_items = sub_items;
+        return posts.into_iter().filter(|p| p.path != PathBuf::new()).collect();
+    }
+    vec![]
+}
+
+/// Whether the paths are the same chapter of `SUMMARY.md`, `./news/README.md` and `news/README.md` are
+pub fn same_chapter(a: &Path, b: &Path) -> bool {
+    a.strip_prefix("./").unwrap_or(a) == b.strip_prefix("./").unwrap_or(b)
+}
+
+// The date of the front matter of a post, as a string or a TOML date
+fn post_date(path: &Path) -> Option<String> {
+    let content = utils::fs::file_to_string(path).ok()?;
+    let (front_matter, _) = FrontMatter::split(&content).ok()?;
+    front_matter.get("date").and_then(|v| v.as_str().or_else(|| v.as_datetime())).map(|d| d.to_owned())
+}
+
+/// The date of a post as an RFC 3339 timestamp, for the feed, `2017-03-01` is the start of the day in UTC
+pub fn rfc3339_date(date: &str) -> String {
+    if date.len() == 10 {
+        format!("{}T00:00:00Z", date)
+    } else {
+        date.to_owned()
+    }
+}
+
+
+// ---------------------------------------------------------------------------------------------------------------
+//      Tests
+//
+
+#[cfg(test)]
+mod tests {
+    extern crate tempdir;
+
+    use super::*;
+    use std::fs::{self, File};
+    use std::io::Write;
+    use book::bookitem::Chapter;
+
+    #[test]
+    fn it_sorts_the_posts_by_date() {
+        let tmp = tempdir::TempDir::new("mdbook").expect("Could not create a temp dir");
+        fs::create_dir(tmp.path().join("news")).unwrap();
+        let posts = [("news/v1.md", "date = 2017-01-03T00:00:00Z"),
+                     ("news/draft.md", "title = \"Draft\""),
+                     ("news/v2.md", "date = \"2017-06-20\""),
+                     ("news/v1-1.md", "date = \"2017-03-01\"")];
+
+        let mut news = Chapter::new("News".to_owned(), PathBuf::from("./news/README.md"));
+        for (i, &(path, front_matter)) in posts.iter().enumerate() {
+            File::create(tmp.path().join(path))
+                .unwrap()
+                .write_all(format!("+++\n{}\n+++\n# Post\n", front_matter).as_bytes())
+                .unwrap();
+            let post = Chapter::new(path.to_owned(), PathBuf::from(path));
+            news.sub_items.push(BookItem::Chapter(format!("2.{}.", i + 1), post));
+        }
+        let mut items = vec![BookItem::Chapter("1.".to_owned(), Chapter::new("Intro".to_owned(), "intro.md".into())),
+                             BookItem::Chapter("2.".to_owned(), news)];
+
+        let table = toml::Parser::new("chapter = \"news/README.md\"").parse().unwrap();
+        let config = BlogConfig::from_btreemap(&table).unwrap();
+        assert_eq!(config.feed_entries, 20);
+        let posts = sort_posts(&mut items, &config, tmp.path());
+
+        let paths: Vec<_> = posts.iter().map(|p| p.path.to_str().unwrap()).collect();
+        assert_eq!(paths, vec!["news/v2.md", "news/v1-1.md", "news/v1.md", "news/draft.md"]);
+        assert_eq!(posts[2].date, Some("2017-01-03T00:00:00Z".to_owned()));
+        assert_eq!(posts[3].date, None);
+        match items[1] {
+            BookItem::Chapter(_, ref news) => {
+                match news.sub_items[0] {
+                    BookItem::Chapter(_, ref post) => assert_eq!(post.path, Path::new("news/v2.md")),
+                    _ => panic!("the posts should be chapters"),
+                }
+            },
+            _ => panic!("the blog should be a chapter"),
+        }
+
+        assert_eq!(rfc3339_date("2017-06-20"), "2017-06-20T00:00:00Z");
+        assert_eq!(rfc3339_date("2017-01-03T00:00:00Z"), "2017-01-03T00:00:00Z");
+    }
+}
@@ -1,5 +1,3 @@
-extern crate toml;
-
 use std::process::exit;
 use std::fs::File;
 use std::io::Read;
@@ -7,6 +5,17 @@ use std::path::{Path, PathBuf};
 use std::collections::BTreeMap;
 use std::str::FromStr;
 use serde_json;
+use toml;
+
+use book::htmlconfig::HtmlConfig;
+use book::markdownconfig::MarkdownConfig;
+use book::lint::LintConfig;
+use book::testing::TestConfig;
+use book::versions::VersionsConfig;
+use book::blog::BlogConfig;
+use book::workspace::WorkspaceConfig;
+use book::deploy::DeployConfig;
+use book::plugins::PluginConfig;
 
 #[derive(Debug, Clone)]
 pub struct BookConfig {
@@ -19,8 +28,67 @@ pub struct BookConfig {
     pub author: String,
     pub description: String,
 
+    /// Title page of the print version and of the other renderers
+    pub subtitle: Option<String>,
+    /// Every author of the book, see `get_authors()` for the fallback on `author`
+    pub authors: Vec<String>,
+    pub publisher: Option<String>,
+    pub isbn: Option<String>,
+    /// Cover image, relative to the source directory
+    pub cover: Option<PathBuf>,
+    /// Version and date of the edition, shown on the title page
+    pub version: Option<String>,
+    pub date: Option<String>,
+    /// Markdown file printed after the title page, relative to the source directory
+    pub colophon: Option<PathBuf>,
+
     pub indent_spaces: i32,
     multilingual: bool,
+
+    pub html_config: HtmlConfig,
+    pub markdown_config: MarkdownConfig,
+    pub lint_config: Option<LintConfig>,
+    pub test_config: TestConfig,
+    pub versions_config: Option<VersionsConfig>,
+    pub blog_config: Option<BlogConfig>,
+    pub workspace_config: Option<WorkspaceConfig>,
+    pub deploy_config: DeployConfig,
+    /// WebAssembly plugins of the `[plugins]` table
+    pub plugins: Vec<PluginConfig>,
+    /// Values of the `{{ book.name }}` variables of the chapters, from the `[variables]` table
+    pub variables: BTreeMap<String, String>,
+    /// Chapters left out of the book, with their sub-chapters. A directory excludes every chapter in it.
+    pub exclude_chapters: Vec<PathBuf>,
+    /// The chapters are the markdown files of the source directory, ordered by the `weight` of their front matter,
+    /// instead of the ones of `SUMMARY.md`
+    pub auto_summary: bool,
+    /// Writes a file with the title of the chapter for the chapters of `SUMMARY.md` without a file, otherwise
+    /// the build fails listing them
+    pub create_missing: bool,
+    /// How the chapters that are not valid UTF-8 are read
+    pub invalid_utf8: InvalidUtf8,
+    /// Patterns of the files of the source directory that are not copied to the book, added to the ones of
+    /// `.mdbookignore`
+    pub ignore: Vec<String>,
+    /// Directories of the files that `{{#include}}` finds when they are not next to the chapter, shared by
+    /// several books
+    pub includes: Vec<PathBuf>,
+    /// Every key of the config as it was read, with the profile applied, for the themes and other renderers
+    pub values: BTreeMap<String, toml::Value>,
+
+    /// Name of the `[profile.name]` table applied over the rest of the config
+    profile: Option<String>,
+}
+
+/// How the chapters that are not valid UTF-8 are read, the `invalid-utf8` key of `book.toml`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum InvalidUtf8 {
+    /// The build fails with the file and the offset of the first invalid byte
+    Error,
+    /// The invalid bytes are replaced by `U+FFFD`, with a warning
+    Lossy,
+    /// Every byte is read as a Latin-1 character, with a warning
+    Latin1,
 }
 
 impl BookConfig {
@@ -35,8 +103,37 @@ impl BookConfig {
             author: String::new(),
             description: String::new(),
 
+            subtitle: None,
+            authors: vec![],
+            publisher: None,
+            isbn: None,
+            cover: None,
+            version: None,
+            date: None,
+            colophon: None,
+
             indent_spaces: 4, // indentation used for SUMMARY.md
             multilingual: false,
+
+            html_config: HtmlConfig::new(),
+            markdown_config: MarkdownConfig::new(),
+            lint_config: None,
+            test_config: TestConfig::new(),
+            versions_config: None,
+            blog_config: None,
+            workspace_config: None,
+            deploy_config: DeployConfig::new(),
+            plugins: Vec::new(),
+            variables: BTreeMap::new(),
+            exclude_chapters: vec![],
+            auto_summary: false,
+            create_missing: true,
+            invalid_utf8: InvalidUtf8::Error,
+            ignore: vec![],
+            includes: vec![],
+            values: BTreeMap::new(),
+
+            profile: None,
         }
     }
 
@@ -117,6 +214,24 @@ impl BookConfig {
 
     pub fn parse_from_btreemap(&mut self, config: &BTreeMap<String, toml::Value>) -> &mut Self {
 
+        // The keys of the selected profile replace the others, tables are merged
+        let with_profile;
+        let config = match self.profile {
+            Some(ref profile) => {
+                let overlay = config.get("profile").and_then(|p| p.lookup(profile)).and_then(|p| p.as_table());
+                if overlay.is_none() {
+                    warn!("[*]: There is no [profile.{}] table in the config", profile);
+                }
+
+                let mut merged = config.clone();
+                merge_tables(&mut merged, overlay.unwrap_or(&BTreeMap::new()));
+                with_profile = merged;
+                &with_profile
+            },
+            None => config,
+        };
+        self.values = config.clone();
+
         // Title, author, description
         if let Some(a) = config.get("title") {
             self.title = a.to_string().replace("\"", "");
@@ -128,6 +243,33 @@ impl BookConfig {
             self.description = a.to_string().replace("\"", "");
         }
 
+        // Title page
+        let get_str = |key: &str| config.get(key).and_then(|v| v.as_str()).map(|s| s.to_owned());
+        if let Some(a) = get_str("subtitle") {
+            self.subtitle = Some(a);
+        }
+        if let Some(a) = config.get("authors").and_then(|v| v.as_slice()) {
+            self.authors = a.iter().filter_map(|v| v.as_str()).map(|s| s.to_owned()).collect();
+        }
+        if let Some(a) = get_str("publisher") {
+            self.publisher = Some(a);
+        }
+        if let Some(a) = get_str("isbn") {
+            self.isbn = Some(a);
+        }
+        if let Some(a) = get_str("cover") {
+            self.cover = Some(PathBuf::from(a));
+        }
+        if let Some(a) = get_str("version") {
+            self.version = Some(a);
+        }
+        if let Some(a) = config.get("date").and_then(|v| v.as_str().or_else(|| v.as_datetime())) {
+            self.date = Some(a.to_owned());
+        }
+        if let Some(a) = get_str("colophon") {
+            self.colophon = Some(PathBuf::from(a));
+        }
+
         // Destination folder
         if let Some(a) = config.get("dest") {
             let mut dest = PathBuf::from(&a.to_string().replace("\"", ""));
@@ -157,9 +299,128 @@ impl BookConfig {
             self.set_theme_path(&theme_path);
         }
 
+        // HTML renderer options
+        if let Some(a) = config.get("output").and_then(|o| o.lookup("html")).and_then(|h| h.as_table()) {
+            self.html_config.parse_from_btreemap(a);
+        }
+
+        // Markdown extensions
+        if let Some(a) = config.get("markdown").and_then(|m| m.as_table()) {
+            self.markdown_config.parse_from_btreemap(a);
+        }
+
+        // Checker used by `mdbook lint`
+        if let Some(a) = config.get("lint").and_then(|l| l.as_table()) {
+            self.lint_config = LintConfig::from_btreemap(a);
+        }
+
+        // Runners of the code blocks tested by `mdbook test`
+        if let Some(a) = config.get("test").and_then(|t| t.as_table()) {
+            self.test_config.parse_from_btreemap(a);
+        }
+
+        // Git refs built by `mdbook versions`
+        if let Some(a) = config.get("versions").and_then(|v| v.as_table()) {
+            self.versions_config = VersionsConfig::from_btreemap(a);
+        }
+
+        // Chapter whose sub-chapters are the posts of a blog
+        if let Some(a) = config.get("blog").and_then(|v| v.as_table()) {
+            self.blog_config = BlogConfig::from_btreemap(a);
+        }
+
+        // Books built into one site by `mdbook build --workspace`
+        if let Some(a) = config.get("workspace").and_then(|v| v.as_table()) {
+            self.workspace_config = WorkspaceConfig::from_btreemap(a);
+        }
+
+        // Branch `mdbook deploy` pushes to
+        if let Some(a) = config.get("deploy").and_then(|v| v.as_table()) {
+            self.deploy_config.parse_from_btreemap(a);
+        }
+
+        // WebAssembly preprocessors and renderers
+        if let Some(a) = config.get("plugins").and_then(|v| v.as_table()) {
+            self.plugins = PluginConfig::from_plugins_table(a);
+        }
+
+        // Chapters left out of the book
+        if let Some(a) = config.get("exclude-chapters").and_then(|v| v.as_slice()) {
+            self.exclude_chapters = a.iter().filter_map(|v| v.as_str()).map(PathBuf::from).collect();
+        }
+
+        // Summary generated from the files of the source
+        if let Some(a) = config.get("auto-summary").and_then(|v| v.as_bool()) {
+            self.auto_summary = a;
+        }
+
+        // Chapters of the summary without a file
+        if let Some(a) = config.get("create-missing").and_then(|v| v.as_bool()) {
+            self.create_missing = a;
+        }
+
+        // Chapters that are not valid UTF-8
+        if let Some(a) = config.get("invalid-utf8").and_then(|v| v.as_str()) {
+            match a {
+                "error" => self.invalid_utf8 = InvalidUtf8::Error,
+                "lossy" => self.invalid_utf8 = InvalidUtf8::Lossy,
+                "latin1" => self.invalid_utf8 = InvalidUtf8::Latin1,
+                _ => warn!("[*]: Unknown `invalid-utf8` `{}`, expected `error`, `lossy` or `latin1`", a),
+            }
+        }
+
+        // Files of the source directory left out of the book
+        if let Some(a) = config.get("ignore").and_then(|v| v.as_slice()) {
+            self.ignore = a.iter().filter_map(|v| v.as_str()).map(|s| s.to_owned()).collect();
+        }
+
+        // Shared directories of the included files, one or a list
+        if let Some(a) = config.get("includes") {
+            let dirs = match *a {
+                toml::Value::Array(ref a) => a.iter().filter_map(|v| v.as_str()).collect(),
+                _ => a.as_str().into_iter().collect::<Vec<_>>(),
+            };
+            self.includes = dirs.into_iter().map(|dir| self.get_root().join(dir)).collect();
+        }
+
+        // Variables of the chapters, numbers, booleans and dates can be used too
+        if let Some(a) = config.get("variables").and_then(|v| v.as_table()) {
+            for (name, value) in a {
+                let value = match *value {
+                    toml::Value::String(ref s) => s.clone(),
+                    toml::Value::Integer(_) | toml::Value::Float(_) | toml::Value::Boolean(_) |
+                    toml::Value::Datetime(_) => value.to_string(),
+                    _ => {
+                        warn!("[*]: The variable `{}` should be a string, a number, a boolean or a date", name);
+                        continue;
+                    },
+                };
+                self.variables.insert(name.clone(), value);
+            }
+        }
+
         self
     }
 
+    /// The `authors` list, or `author` when there is none
+    pub fn get_authors(&self) -> Vec<String> {
+        if self.authors.is_empty() && !self.author.is_empty() {
+            vec![self.author.clone()]
+        } else {
+            self.authors.clone()
+        }
+    }
+
+    /// Selects the `[profile.name]` table of the config, it has to be called before the config is read
+    pub fn set_profile(&mut self, profile: &str) -> &mut Self {
+        self.profile = Some(profile.to_owned());
+        self
+    }
+
+    pub fn get_profile(&self) -> Option<&str> {
+        self.profile.as_ref().map(|p| &p[..])
+    }
+
     pub fn get_root(&self) -> &Path {
         &self.root
     }
@@ -226,3 +487,28 @@ pub fn json_value_to_toml_value(json: serde_json::Value) -> toml::Value {
         },
     }
 }
+
+pub fn toml_value_to_json_value(toml: &toml::Value) -> serde_json::Value {
+    match *toml {
+        toml::Value::String(ref x) |
+        toml::Value::Datetime(ref x) => serde_json::Value::String(x.clone()),
+        toml::Value::Integer(x) => serde_json::Value::I64(x),
+        toml::Value::Float(x) => serde_json::Value::F64(x),
+        toml::Value::Boolean(x) => serde_json::Value::Bool(x),
+        toml::Value::Array(ref x) => serde_json::Value::Array(x.iter().map(toml_value_to_json_value).collect()),
+        toml::Value::Table(ref x) => {
+            serde_json::Value::Object(x.iter().map(|(k, v)| (k.clone(), toml_value_to_json_value(v))).collect())
+        },
+    }
+}
+
+// Copies the values of `overlay` into `config`, the tables present in both are merged recursively
+fn merge_tables(config: &mut BTreeMap<String, toml::Value>, overlay: &BTreeMap<String, toml::Value>) {
+    for (key, value) in overlay {
+        if let (Some(toml::Value::Table(table)), toml::Value::Table(other)) = (config.get_mut(key), value) {
+            merge_tables(table, other);
+            continue;
+        }
+        config.insert(key.clone(), value.clone());
+    }
+}
@@ -1,7 +1,9 @@
 #![cfg(test)]
 
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use serde_json;
+use toml;
+use pulldown_cmark::OPTION_ENABLE_TABLES;
 use book::bookconfig::*;
 
 #[test]
@@ -22,6 +24,7 @@ fn it_parses_json_config() {
     expected.title = "mdBook Documentation".to_string();
     expected.author = "Mathieu David".to_string();
     expected.description = "Create book from markdown files. Like Gitbook but implemented in Rust".to_string();
+    expected.values = json_object_to_btreemap(serde_json::from_str::<serde_json::Value>(text).unwrap().as_object().unwrap());
 
     assert_eq!(format!("{:#?}", config), format!("{:#?}", expected));
 }
@@ -43,6 +46,7 @@ author = "Mathieu David"
     expected.title = "mdBook Documentation".to_string();
     expected.author = "Mathieu David".to_string();
     expected.description = "Create book from markdown files. Like Gitbook but implemented in Rust".to_string();
+    expected.values = toml::Parser::new(text).parse().unwrap();
 
     assert_eq!(format!("{:#?}", config), format!("{:#?}", expected));
 }
@@ -347,3 +351,550 @@ fn it_parses_json_arrays_to_toml() {
 
     assert_eq!(format!("{:#?}", result), expected);
 }
+
+#[test]
+fn it_parses_html_config() {
+    let text = r#"
+title = "mdBook Documentation"
+
+[output.html]
+subresource-integrity = true
+toc-depth = 2
+glossary = true
+numbered-headings = true
+chapter-authors = ["Mathieu David", "Michael-F-Bryan"]
+chapter-date = 2017-01-01T00:00:00Z
+sanitize = true
+print = false
+logo = "assets/logo.svg"
+favicon = "assets/logo.svg"
+apple-touch-icon = "assets/icon-180.png"
+sidebar-title = "<strong>mdBook</strong> Guide"
+progress-bar = true
+
+[output.html.doc-crates]
+mdbook = "../src/lib.rs"
+"#;
+
+    let mut config = BookConfig::new(Path::new("."));
+
+    config.parse_from_toml_string(&text.to_string());
+
+    assert!(config.html_config.subresource_integrity);
+    assert_eq!(config.html_config.toc_depth, Some(2));
+    assert!(config.html_config.glossary);
+    assert!(config.html_config.numbered_headings);
+    assert_eq!(config.html_config.chapter_authors, vec!["Mathieu David", "Michael-F-Bryan"]);
+    assert_eq!(config.html_config.chapter_date, Some("2017-01-01T00:00:00Z".to_owned()));
+    assert_eq!(config.html_config.doc_crates["mdbook"], Path::new("../src/lib.rs"));
+    assert!(config.html_config.sanitize);
+    assert!(!config.html_config.print);
+    assert_eq!(config.html_config.logo, Some(PathBuf::from("assets/logo.svg")));
+    assert_eq!(config.html_config.favicon, Some(PathBuf::from("assets/logo.svg")));
+    assert_eq!(config.html_config.apple_touch_icon, Some(PathBuf::from("assets/icon-180.png")));
+    assert_eq!(config.html_config.sidebar_title, Some("<strong>mdBook</strong> Guide".to_owned()));
+    assert!(config.html_config.progress_bar);
+    assert!(!config.html_config.back_to_top);
+}
+
+#[test]
+fn it_parses_search_config() {
+    let text = r#"
+[output.html.search]
+enable = false
+boost-heading = 3
+boost-body = 0.5
+limit-results = 10
+page-size = 25
+"#;
+
+    let mut config = BookConfig::new(Path::new("."));
+
+    config.parse_from_toml_string(&text.to_string());
+
+    let search = &config.html_config.search;
+    assert!(!search.enable);
+    assert_eq!(search.boost_heading, 3.0);
+    assert_eq!(search.boost_body, 0.5);
+    assert_eq!(search.limit_results, 10);
+    assert_eq!(search.teaser_word_count, 30);
+    assert!(search.page);
+    assert_eq!(search.page_size, 25);
+}
+
+#[test]
+fn it_parses_docsearch_config() {
+    let text = r#"
+[output.html.search.docsearch]
+api-key = "f2b1ae27"
+index-name = "mdbook"
+"#;
+
+    let mut config = BookConfig::new(Path::new("."));
+
+    config.parse_from_toml_string(&text.to_string());
+
+    let docsearch = config.html_config.search.docsearch.expect("DocSearch should be configured");
+    assert_eq!(docsearch.app_id, None);
+    assert_eq!(docsearch.api_key, "f2b1ae27");
+    assert_eq!(docsearch.index_name, "mdbook");
+}
+
+#[test]
+fn it_parses_code_config() {
+    let text = r#"
+[output.html.code]
+highlight-languages = ["rust", "toml", "bash"]
+additional-languages = ["languages/gleam.js"]
+line-anchors = true
+"#;
+
+    let mut config = BookConfig::new(Path::new("."));
+    assert_eq!(config.html_config.code.highlight_languages, None);
+    assert!(!config.html_config.code.line_anchors);
+
+    config.parse_from_toml_string(&text.to_string());
+
+    assert_eq!(config.html_config.code.highlight_languages,
+               Some(vec!["rust".to_owned(), "toml".to_owned(), "bash".to_owned()]));
+    assert_eq!(config.html_config.code.additional_languages, vec![PathBuf::from("languages/gleam.js")]);
+    assert!(config.html_config.code.line_anchors);
+}
+
+#[test]
+fn it_ignores_incomplete_docsearch_config() {
+    let text = r#"
+[output.html.search.docsearch]
+api-key = "f2b1ae27"
+"#;
+
+    let mut config = BookConfig::new(Path::new("."));
+
+    config.parse_from_toml_string(&text.to_string());
+
+    assert!(config.html_config.search.docsearch.is_none());
+}
+
+#[test]
+fn it_parses_lint_config() {
+    let text = r#"
+[lint]
+command = "hunspell -l -d en_US"
+dictionary = "dictionary.txt"
+timeout = 60
+"#;
+
+    let mut config = BookConfig::new(Path::new("."));
+
+    config.parse_from_toml_string(&text.to_string());
+
+    let lint = config.lint_config.expect("The lint command should be configured");
+    assert_eq!(lint.command, vec!["hunspell", "-l", "-d", "en_US"]);
+    assert_eq!(lint.dictionary, Some(Path::new("dictionary.txt").to_owned()));
+    assert_eq!(lint.timeout, 60);
+}
+
+#[test]
+fn it_parses_run_config() {
+    let text = r#"
+[output.html.run]
+enable = true
+timeout = 5
+
+[output.html.run.commands]
+python = "python3 -B"
+"#;
+
+    let mut config = BookConfig::new(Path::new("."));
+
+    config.parse_from_toml_string(&text.to_string());
+
+    let run = &config.html_config.run;
+    assert!(run.enable);
+    assert_eq!(run.timeout, 5);
+    assert_eq!(run.commands.get("python").map(|s| &s[..]), Some("python3 -B"));
+}
+
+#[test]
+fn it_parses_variables() {
+    let text = r#"
+[variables]
+version = "1.2.0"
+edition = 2
+stable = true
+"#;
+
+    let mut config = BookConfig::new(Path::new("."));
+
+    config.parse_from_toml_string(&text.to_string());
+
+    assert_eq!(config.variables["version"], "1.2.0");
+    assert_eq!(config.variables["edition"], "2");
+    assert_eq!(config.variables["stable"], "true");
+}
+
+#[test]
+fn it_applies_the_profile() {
+    let text = r#"
+title = "Public book"
+exclude-chapters = ["internal"]
+ignore = ["*.psd", "drafts/"]
+
+[variables]
+edition = "public"
+version = "1.0"
+
+[output.html]
+glossary = true
+
+[profile.internal]
+title = "Internal book"
+exclude-chapters = []
+create-missing = false
+
+[profile.internal.variables]
+edition = "internal"
+
+[profile.internal.output.html]
+toc-depth = 2
+"#;
+
+    let mut config = BookConfig::new(Path::new("."));
+    config.parse_from_toml_string(&text.to_string());
+
+    assert_eq!(config.title, "Public book");
+    assert_eq!(config.exclude_chapters, vec![PathBuf::from("internal")]);
+    assert_eq!(config.ignore, vec!["*.psd".to_owned(), "drafts/".to_owned()]);
+    assert_eq!(config.html_config.toc_depth, None);
+    assert!(config.create_missing);
+
+    let mut config = BookConfig::new(Path::new("."));
+    config.set_profile("internal").parse_from_toml_string(&text.to_string());
+
+    assert_eq!(config.title, "Internal book");
+    assert!(config.exclude_chapters.is_empty());
+    assert!(!config.create_missing);
+    assert_eq!(config.variables["edition"], "internal");
+    assert_eq!(config.variables["version"], "1.0");
+    assert!(config.html_config.glossary);
+    assert_eq!(config.html_config.toc_depth, Some(2));
+    assert_eq!(config.values["title"].as_str(), Some("Internal book"));
+    assert_eq!(config.values["output"].lookup("html.toc-depth").and_then(|v| v.as_integer()), Some(2));
+}
+
+#[test]
+fn it_parses_versions_config() {
+    let text = r#"
+[versions]
+refs = ["v1.0", "release/2"]
+"#;
+
+    let mut config = BookConfig::new(Path::new("."));
+    config.parse_from_toml_string(&text.to_string());
+
+    let versions = config.versions_config.unwrap();
+    assert_eq!(versions.refs, vec!["v1.0", "release/2"]);
+    assert_eq!(versions.latest_version(), "release-2");
+}
+
+#[test]
+fn it_parses_deploy_config() {
+    let text = r#"
+[deploy]
+branch = "pages"
+message = "Publish {sha}"
+"#;
+
+    let mut config = BookConfig::new(Path::new("."));
+    config.parse_from_toml_string(&text.to_string());
+
+    assert_eq!(config.deploy_config.branch, "pages");
+    assert_eq!(config.deploy_config.remote, "origin");
+    assert_eq!(config.deploy_config.message, Some("Publish {sha}".to_owned()));
+}
+
+#[test]
+fn it_parses_invalid_utf8() {
+    let mut config = BookConfig::new(Path::new("."));
+    assert_eq!(config.invalid_utf8, InvalidUtf8::Error);
+
+    config.parse_from_toml_string(&"invalid-utf8 = \"latin1\"".to_string());
+    assert_eq!(config.invalid_utf8, InvalidUtf8::Latin1);
+    config.parse_from_toml_string(&"invalid-utf8 = \"utf-16\"".to_string());
+    assert_eq!(config.invalid_utf8, InvalidUtf8::Latin1);
+}
+
+#[test]
+fn it_enables_the_gfm_extensions() {
+    let text = r#"
+[markdown]
+gfm = true
+strikethrough = false
+"#;
+
+    let mut config = BookConfig::new(Path::new("."));
+    config.parse_from_toml_string(&text.to_string());
+
+    let markdown = &config.markdown_config;
+    assert!(markdown.tables);
+    assert!(!markdown.strikethrough);
+    assert!(markdown.autolinks);
+    assert!(!markdown.smart_punctuation);
+}
+
+#[test]
+fn it_parses_markdown_config() {
+    let text = r#"
+[markdown]
+footnotes = false
+strikethrough = true
+smart-punctuation = true
+emoji = true
+image-captions = true
+"#;
+
+    let mut config = BookConfig::new(Path::new("."));
+    config.parse_from_toml_string(&text.to_string());
+
+    let markdown = &config.markdown_config;
+    assert!(markdown.tables);
+    assert!(!markdown.footnotes);
+    assert!(markdown.strikethrough);
+    assert!(markdown.smart_punctuation);
+    assert!(!markdown.heading_attributes);
+    assert!(markdown.emoji);
+    assert!(markdown.image_captions);
+    assert_eq!(markdown.options(), OPTION_ENABLE_TABLES);
+}
+
+#[test]
+fn it_derives_the_cname_from_the_site_url() {
+    let cname = |toml: &str| {
+        let mut config = BookConfig::new(Path::new("."));
+        config.parse_from_toml_string(&format!("[output.html]\n{}", toml));
+        config.html_config.cname()
+    };
+
+    assert_eq!(cname(r#"site-url = "https://docs.example.com/""#), Some("docs.example.com".to_owned()));
+    assert_eq!(cname(r#"site-url = "https://example.com/docs/""#), None);
+    assert_eq!(cname(r#"site-url = "https://user.github.io/""#), None);
+    assert_eq!(cname("site-url = \"https://example.com\"\ncname = \"book.example.com\""),
+               Some("book.example.com".to_owned()));
+    assert_eq!(cname(""), None);
+}
+
+#[test]
+fn it_parses_external_links_config() {
+    let text = r##"
+[output.html.external-links]
+new-tab = true
+exempt-domains = ["rust-lang.org", "github.com"]
+"##;
+
+    let mut config = BookConfig::new(Path::new("."));
+    config.parse_from_toml_string(&text.to_string());
+
+    let external_links = &config.html_config.external_links;
+    assert!(external_links.new_tab);
+    assert!(!external_links.icon);
+    assert_eq!(external_links.exempt_domains, vec!["rust-lang.org", "github.com"]);
+}
+
+#[test]
+fn it_parses_robots_config() {
+    let text = r##"
+[output.html]
+noindex = true
+structured-data = false
+
+[output.html.robots]
+disallow = ["internal/", "drafts/"]
+"##;
+
+    let mut config = BookConfig::new(Path::new("."));
+    config.parse_from_toml_string(&text.to_string());
+
+    assert!(config.html_config.noindex);
+    assert!(!config.html_config.structured_data);
+    let robots = config.html_config.robots.unwrap();
+    assert_eq!(robots.user_agent, "*");
+    assert_eq!(robots.disallow, vec!["internal/", "drafts/"]);
+    assert!(robots.allow.is_empty());
+
+    let mut config = BookConfig::new(Path::new("."));
+    config.parse_from_toml_string(&"[output.html]\nprint = true".to_owned());
+    assert_eq!(config.html_config.robots, None);
+    assert!(config.html_config.structured_data);
+}
+
+#[test]
+fn it_parses_changes_config() {
+    let text = r##"
+[output.html.changes]
+title = "What's new"
+entries = 5
+commits = 0
+"##;
+
+    let mut config = BookConfig::new(Path::new("."));
+    config.parse_from_toml_string(&text.to_string());
+
+    let changes = config.html_config.changes.unwrap();
+    assert_eq!(changes.title, Some("What's new".to_owned()));
+    assert_eq!(changes.entries, 5);
+    assert_eq!(changes.commits, 100);
+}
+
+#[test]
+fn it_parses_style_config() {
+    let text = r##"
+[output.html.style]
+sidebar-width = 250
+accent-color = "#c0392b"
+font-family = "Lato, sans-serif"
+sidebar-color = "red"
+"##;
+
+    let mut config = BookConfig::new(Path::new("."));
+    config.parse_from_toml_string(&text.to_string());
+
+    let variables: Vec<_> = config.html_config
+        .style
+        .variables
+        .iter()
+        .map(|(key, value)| format!("{}: {}", key, value))
+        .collect();
+    assert_eq!(variables, vec!["accent-color: #c0392b", "font-family: Lato, sans-serif", "sidebar-width: 250px"]);
+}
+
+#[test]
+fn it_parses_manifest_config() {
+    let text = r##"
+[output.html.manifest]
+enable = true
+theme-color = "#f5f5f5"
+icons = ["icon-192.png", "icon-512.png"]
+"##;
+
+    let mut config = BookConfig::new(Path::new("."));
+    config.parse_from_toml_string(&text.to_string());
+
+    let manifest = &config.html_config.manifest;
+    assert!(manifest.enable);
+    assert_eq!(manifest.short_name, None);
+    assert_eq!(manifest.theme_color, "#f5f5f5");
+    assert_eq!(manifest.background_color, "#ffffff");
+    assert_eq!(manifest.icons, vec!["icon-192.png", "icon-512.png"]);
+}
+
+#[test]
+fn it_parses_title_page_metadata() {
+    let text = r#"
+title = "The Book"
+author = "Mathieu David"
+subtitle = "A guide"
+publisher = "No Starch Press"
+isbn = "978-1-59327-828-1"
+cover = "images/cover.png"
+version = "2.0"
+date = "2018-05-01"
+colophon = "colophon.md"
+"#;
+
+    let mut config = BookConfig::new(Path::new("."));
+    config.parse_from_toml_string(&text.to_string());
+
+    assert_eq!(config.subtitle, Some("A guide".to_owned()));
+    assert_eq!(config.get_authors(), vec!["Mathieu David"]);
+    assert_eq!(config.publisher, Some("No Starch Press".to_owned()));
+    assert_eq!(config.isbn, Some("978-1-59327-828-1".to_owned()));
+    assert_eq!(config.cover, Some(PathBuf::from("images/cover.png")));
+    assert_eq!(config.version, Some("2.0".to_owned()));
+    assert_eq!(config.date, Some("2018-05-01".to_owned()));
+    assert_eq!(config.colophon, Some(PathBuf::from("colophon.md")));
+
+    let mut config = BookConfig::new(Path::new("."));
+    config.parse_from_toml_string(&"author = \"A\"\nauthors = [\"B\", \"C\"]".to_owned());
+    assert_eq!(config.get_authors(), vec!["B", "C"]);
+}
+
+#[test]
+fn it_parses_the_layout() {
+    use book::htmlconfig::Layout;
+
+    let mut config = BookConfig::new(Path::new("."));
+    assert_eq!(config.html_config.layout, Layout::Sidebar);
+
+    config.parse_from_toml_string(&"[output.html]\nlayout = \"navbar\"".to_owned());
+    assert_eq!(config.html_config.layout, Layout::Navbar);
+
+    config.parse_from_toml_string(&"[output.html]\nlayout = \"tabs\"".to_owned());
+    assert_eq!(config.html_config.layout, Layout::Navbar);
+}
+
+#[test]
+fn it_parses_the_split_config() {
+    let text = r##"
+[output.html.split]
+enable = true
+level = 1
+min-words = 500
+"##;
+
+    let mut config = BookConfig::new(Path::new("."));
+    assert!(!config.html_config.split.enable);
+    config.parse_from_toml_string(&text.to_string());
+
+    let split = &config.html_config.split;
+    assert!(split.enable);
+    assert_eq!(split.level, 1);
+    assert_eq!(split.min_words, 500);
+
+    config.parse_from_toml_string(&"[output.html.split]\nlevel = 3".to_owned());
+    assert_eq!(config.html_config.split.level, 1);
+}
+
+#[test]
+fn it_parses_the_headings_config() {
+    use book::htmlconfig::{HeadingsConfig, Permalink, PermalinkVisibility};
+
+    let text = r##"
+[output.html.headings]
+permalink = "icon"
+visibility = "always"
+min-level = 2
+"##;
+
+    let mut config = BookConfig::new(Path::new("."));
+    assert_eq!(config.html_config.headings, HeadingsConfig::new());
+    config.parse_from_toml_string(&text.to_string());
+
+    let headings = &config.html_config.headings;
+    assert_eq!(headings.permalink, Permalink::Icon);
+    assert_eq!(headings.visibility, PermalinkVisibility::Always);
+    assert_eq!(headings.min_level, 2);
+
+    config.parse_from_toml_string(&"[output.html.headings]\npermalink = \"none\"\nmin-level = 7".to_owned());
+    assert_eq!(config.html_config.headings.permalink, Permalink::Icon);
+    assert_eq!(config.html_config.headings.min_level, 2);
+}
+
+#[test]
+fn it_parses_the_math_config() {
+    use book::htmlconfig::MathMode;
+
+    let text = r##"
+[output.html.math]
+mode = "katex"
+katex-command = "npx katex"
+"##;
+
+    let mut config = BookConfig::new(Path::new("."));
+    assert_eq!(config.html_config.math.mode, MathMode::MathJax);
+    config.parse_from_toml_string(&text.to_string());
+
+    let math = &config.html_config.math;
+    assert_eq!(math.mode, MathMode::Katex);
+    assert_eq!(math.katex_command, "npx katex");
+    assert!(math.katex_stylesheet.ends_with("katex.min.css"));
+}
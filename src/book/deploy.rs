@@ -0,0 +1,110 @@
+use std::path::Path;
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fs::{self, File};
+use std::io::Write;
+use std::env;
+use std::process;
+use toml;
+
+use utils::git::{git, git_with_env};
+
+/// Options of `mdbook deploy`, read from the `[deploy]` table of `book.toml`
+#[derive(Debug, Clone)]
+pub struct DeployConfig {
+    /// Branch the rendered book is pushed to
+    pub branch: String,
+    pub remote: String,
+    /// Message of the commits, `{sha}` is replaced by the commit the book was built from
+    pub message: Option<String>,
+}
+
+impl DeployConfig {
+    pub fn new() -> Self {
+        DeployConfig {
+            branch: "gh-pages".to_owned(),
+            remote: "origin".to_owned(),
+            message: None,
+        }
+    }
+
+    pub fn parse_from_btreemap(&mut self, config: &BTreeMap<String, toml::Value>) -> &mut Self {
+
+        if let Some(a) = config.get("branch").and_then(|v| v.as_str()) {
+            self.branch = a.to_owned();
+        }
+        if let Some(a) = config.get("remote").and_then(|v| v.as_str()) {
+            self.remote = a.to_owned();
+        }
+        if let Some(a) = config.get("message").and_then(|v| v.as_str()) {
+            self.message = Some(a.to_owned());
+        }
+
+        self
+    }
+}
+
+/// Commits the rendered book in `dest` on top of the deploy branch of the remote and pushes it,
+/// for the git repository containing `root`. The working tree, the index and the current branch of
+/// the repository are left untouched.
+///
+/// A `.nojekyll` file is added so that GitHub Pages serves the files starting with `_`, and the
+/// `CNAME` of the deployed branch is kept when the book has none. Returns `false` when the book
+/// did not change since the last deployment.
+pub fn deploy(root: &Path, dest: &Path, config: &DeployConfig, title: &str) -> Result<bool, Box<Error>> {
+    let git_dir = try!(git(root, &["rev-parse", "--absolute-git-dir"]));
+    let git_dir = Path::new(git_dir.trim());
+
+    // The branch does not exist yet on the first deployment
+    let parent = git(root, &["fetch", "--quiet", &config.remote, &config.branch])
+        .and_then(|_| git(root, &["rev-parse", "--verify", "FETCH_HEAD^{commit}"]))
+        .ok()
+        .map(|sha| sha.trim().to_owned());
+
+    let nojekyll = dest.join(".nojekyll");
+    if !nojekyll.exists() {
+        try!(File::create(&nojekyll));
+    }
+
+    let cname = dest.join("CNAME");
+    if let Some(ref parent) = parent {
+        if !cname.exists() {
+            if let Ok(content) = git(root, &["show", &format!("{}:CNAME", parent)]) {
+                try!(try!(File::create(&cname)).write_all(content.as_bytes()));
+            }
+        }
+    }
+
+    // Stage the book in a separate index, with `dest` as work tree
+    let index = env::temp_dir().join(format!("mdbook-deploy-{}.index", process::id()));
+    let git_dir_arg = format!("--git-dir={}", git_dir.display());
+    let env = [("GIT_INDEX_FILE", index.as_path())];
+    let tree = git_with_env(dest, &[&git_dir_arg, "--work-tree=.", "add", "--all", "--force", "."], &env)
+        .and_then(|_| git_with_env(dest, &[&git_dir_arg, "write-tree"], &env));
+    fs::remove_file(&index).ok();
+    let tree = try!(tree).trim().to_owned();
+
+    if let Some(ref parent) = parent {
+        if try!(git(root, &["rev-parse", &format!("{}^{{tree}}", parent)])).trim() == tree {
+            return Ok(false);
+        }
+    }
+
+    let sha = git(root, &["rev-parse", "--short", "HEAD"]).map(|s| s.trim().to_owned()).unwrap_or_default();
+    let message = match config.message {
+        Some(ref message) => message.replace("{sha}", &sha),
+        None if sha.is_empty() => format!("Deploy {}", title),
+        None => format!("Deploy {} from {}", title, sha),
+    };
+
+    let mut args = vec!["commit-tree", &tree[..], "-m", &message[..]];
+    if let Some(ref parent) = parent {
+        args.push("-p");
+        args.push(parent);
+    }
+    let commit = try!(git(root, &args)).trim().to_owned();
+
+    try!(git(root, &["push", "--quiet", &config.remote, &format!("{}:refs/heads/{}", commit, config.branch)]));
+
+    Ok(true)
+}
@@ -0,0 +1,164 @@
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::io;
+use toml;
+
+const DELIMITER: &'static str = "+++";
+
+/// Metadata written at the top of a chapter in TOML, between two `+++` lines
+///
+/// ```text
+/// +++
+/// search = false
+/// +++
+///
+/// # Chapter title
+/// ```
+///
+/// Chapters without front matter get an empty `FrontMatter`, every getter then returns `None`.
+#[derive(Debug, Clone)]
+pub struct FrontMatter {
+    values: BTreeMap<String, toml::Value>,
+}
+
+impl FrontMatter {
+    pub fn new() -> Self {
+        FrontMatter { values: BTreeMap::new() }
+    }
+
+    /// Splits the content of a chapter in its front matter and the remaining markdown
+    pub fn split(content: &str) -> Result<(FrontMatter, &str), Box<Error>> {
+        // The front matter has to start on the very first line
+        let first_line_end = content.find('\n').unwrap_or(content.len());
+        if content[..first_line_end].trim_end() != DELIMITER {
+            return Ok((FrontMatter::new(), content));
+        }
+
+        let mut start = first_line_end + 1;
+        while start <= content.len() {
+            let line_end = content[start..].find('\n').map(|i| start + i).unwrap_or(content.len());
+
+            if content[start..line_end].trim_end() == DELIMITER {
+                let toml = &content[first_line_end + 1..start];
+                let remaining = if line_end < content.len() { &content[line_end + 1..] } else { "" };
+                return Ok((FrontMatter::parse(toml)?, remaining));
+            }
+
+            start = line_end + 1;
+        }
+
+        Err(Box::new(io::Error::new(io::ErrorKind::Other, "The front matter is not closed by a `+++` line")))
+    }
+
+    fn parse(toml: &str) -> Result<FrontMatter, Box<Error>> {
+        let mut parser = toml::Parser::new(toml);
+
+        match parser.parse() {
+            Some(values) => Ok(FrontMatter { values: values }),
+            None => {
+                let errors = parser.errors
+                    .iter()
+                    .map(|e| e.desc.clone())
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                Err(Box::new(io::Error::new(io::ErrorKind::Other, format!("Invalid front matter: {}", errors))))
+            },
+        }
+    }
+
+    pub fn values(&self) -> &BTreeMap<String, toml::Value> {
+        &self.values
+    }
+
+    pub fn get(&self, key: &str) -> Option<&toml::Value> {
+        self.values.get(key)
+    }
+
+    pub fn get_bool(&self, key: &str) -> Option<bool> {
+        self.get(key).and_then(|v| v.as_bool())
+    }
+
+    pub fn get_str(&self, key: &str) -> Option<&str> {
+        self.get(key).and_then(|v| v.as_str())
+    }
+
+    /// Accepts both a single string and an array of strings
+    pub fn get_str_list(&self, key: &str) -> Option<Vec<&str>> {
+        self.get_str(key).map(|s| vec![s]).or_else(|| self.get_str_array(key))
+    }
+
+    /// Returns the strings of an array, elements that are not strings are skipped
+    pub fn get_str_array(&self, key: &str) -> Option<Vec<&str>> {
+        self.get(key)
+            .and_then(|v| v.as_slice())
+            .map(|a| a.iter().filter_map(|v| v.as_str()).collect())
+    }
+
+    /// Whether the chapter is rendered by `output`: `html` for its page, `print` for `print.html` or the
+    /// name of a renderer plugin. The chapters listing their outputs in `render` are left out of the other
+    /// ones, the other chapters are rendered by all of them.
+    pub fn renders(&self, output: &str) -> bool {
+        self.get_str_list("render").is_none_or(|outputs| outputs.contains(&output))
+    }
+}
+
+
+// ------------------------------------------------------------------------------------------------
+// ------------------------------------------------------------------------------------------------
+
+// tests
+
+#[cfg(test)]
+mod tests {
+    use super::FrontMatter;
+
+    #[test]
+    fn split_without_front_matter() {
+        let content = "# Chapter 1\n\n+++\n";
+        let (front_matter, markdown) = FrontMatter::split(content).unwrap();
+
+        assert!(front_matter.get("search").is_none());
+        assert_eq!(markdown, content);
+    }
+
+    #[test]
+    fn split_front_matter() {
+        let content = "+++\nsearch = false\ntags = [\"async\", \"ffi\"]\n+++\n# Chapter 1\n";
+        let (front_matter, markdown) = FrontMatter::split(content).unwrap();
+
+        assert_eq!(front_matter.get_bool("search"), Some(false));
+        assert_eq!(front_matter.get_str_array("tags"), Some(vec!["async", "ffi"]));
+        assert_eq!(front_matter.get_str_list("tags"), Some(vec!["async", "ffi"]));
+        assert_eq!(markdown, "# Chapter 1\n");
+    }
+
+    #[test]
+    fn split_front_matter_with_crlf() {
+        let content = "+++\r\ntitle = \"Intro\"\r\n+++\r\n# Chapter 1\r\n";
+        let (front_matter, markdown) = FrontMatter::split(content).unwrap();
+
+        assert_eq!(front_matter.get_str("title"), Some("Intro"));
+        assert_eq!(front_matter.get_str_list("title"), Some(vec!["Intro"]));
+        assert_eq!(markdown, "# Chapter 1\r\n");
+    }
+
+    #[test]
+    fn split_unclosed_front_matter() {
+        assert!(FrontMatter::split("+++\nsearch = false\n# Chapter 1\n").is_err());
+    }
+
+    #[test]
+    fn split_invalid_front_matter() {
+        assert!(FrontMatter::split("+++\nsearch = \n+++\n").is_err());
+    }
+
+    #[test]
+    fn renders_the_listed_outputs() {
+        let (front_matter, _) = FrontMatter::split("+++\nrender = [\"html\", \"epub\"]\n+++\n").unwrap();
+        assert!(front_matter.renders("html"));
+        assert!(front_matter.renders("epub"));
+        assert!(!front_matter.renders("print"));
+
+        assert!(FrontMatter::new().renders("print"));
+    }
+}
@@ -0,0 +1,842 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use toml;
+
+/// Options for the HTML renderer, read from the `[output.html]` table of `book.toml`
+#[derive(Debug, Clone)]
+pub struct HtmlConfig {
+    pub subresource_integrity: bool,
+    /// Deepest chapter level listed in the sidebar, `None` lists every chapter
+    pub toc_depth: Option<usize>,
+    /// Link the terms defined in `glossary.md` to their definition
+    pub glossary: bool,
+    /// Number the `h2` and `h3` headings after the number of their chapter
+    pub numbered_headings: bool,
+    /// Authors shown on the chapters without `authors` in their front matter
+    pub chapter_authors: Vec<String>,
+    /// Date shown on the chapters without `date` in their front matter
+    pub chapter_date: Option<String>,
+    pub search: SearchConfig,
+    pub run: RunConfig,
+    pub code: CodeConfig,
+    pub manifest: ManifestConfig,
+    pub style: StyleConfig,
+    pub external_links: ExternalLinksConfig,
+    pub split: SplitConfig,
+    pub headings: HeadingsConfig,
+    pub math: MathConfig,
+    /// Write `robots.txt`, when there is a `[output.html.robots]` table
+    pub robots: Option<RobotsConfig>,
+    /// Write the Atom feed of the chapters changed by the recent commits, when there is a `[output.html.changes]`
+    /// table
+    pub changes: Option<ChangesConfig>,
+    /// Ask the search engines not to index the pages, the chapters can set `noindex` in their front matter
+    /// instead
+    pub noindex: bool,
+    /// Describes the book and the chapters to the search engines with schema.org JSON-LD
+    pub structured_data: bool,
+    /// Generate `print.html` with all the chapters, and the print button of the menu bar
+    pub print: bool,
+    /// Copy the markdown of the chapters to `_sources`, linked from their page
+    pub sources: bool,
+    /// Generate `book.zip` with the rendered book, linked from every page
+    pub archive: bool,
+    /// Generate a service worker so that the book can be read offline
+    pub offline: bool,
+    /// Address the book is published at, e.g. `https://docs.example.com/`
+    pub site_url: Option<String>,
+    /// Custom domain written to the `CNAME` file of GitHub Pages
+    pub cname: Option<String>,
+    /// Crate roots used by `{{#doc}}`, keyed by crate name
+    pub doc_crates: BTreeMap<String, PathBuf>,
+    /// Removes the tags and attributes that are not in the allowlist from the html of the chapters,
+    /// the chapters can set `sanitize` in their front matter instead
+    pub sanitize: bool,
+    /// Renders the ```` ```quiz ```` code blocks as self-grading questions
+    pub quizzes: bool,
+    /// Copies the symbolic links of the source directory as links instead of copying the files they point to
+    pub preserve_symlinks: bool,
+    /// Image shown at the top of the sidebar, relative to the root of the book
+    pub logo: Option<PathBuf>,
+    /// Icon of the pages, a PNG or an SVG image relative to the root of the book, instead of the `favicon.png` of
+    /// the theme
+    pub favicon: Option<PathBuf>,
+    /// Icon of the book on the home screens of iOS, a PNG image relative to the root of the book, the favicon when
+    /// it is a PNG image
+    pub apple_touch_icon: Option<PathBuf>,
+    /// Html shown at the top of the sidebar, after the logo
+    pub sidebar_title: Option<String>,
+    /// Where the chapters are listed
+    pub layout: Layout,
+    /// Bar across the top of the pages showing how much of the chapter was read
+    pub progress_bar: bool,
+    /// Floating button scrolling back to the top of the chapter
+    pub back_to_top: bool,
+}
+
+/// Layout of the navigation of the pages, the `layout` of `[output.html]`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Layout {
+    /// Every chapter in the sidebar
+    Sidebar,
+    /// The top level chapters in a bar above the content, and the chapters of the current part in the sidebar
+    Navbar,
+}
+
+/// Options for the search bar, read from the `[output.html.search]` table of `book.toml`
+#[derive(Debug, Clone)]
+pub struct SearchConfig {
+    pub enable: bool,
+    /// Weight of a match in a chapter title or heading
+    pub boost_heading: f64,
+    /// Weight of a match in the text of a chapter
+    pub boost_body: f64,
+    /// Number of words in the excerpt shown below each result
+    pub teaser_word_count: u32,
+    /// Maximum number of results shown below the search bar
+    pub limit_results: u32,
+    /// Generate `search.html`, listing all the results of the query in its address
+    pub page: bool,
+    /// Number of chapters listed on each page of `search.html`
+    pub page_size: u32,
+    /// Use Algolia DocSearch instead of the index generated by mdBook
+    pub docsearch: Option<DocSearchConfig>,
+}
+
+/// Options for the code blocks run at build time, read from the `[output.html.run]` table of `book.toml`
+#[derive(Debug, Clone)]
+pub struct RunConfig {
+    pub enable: bool,
+    /// Seconds after which a program is killed
+    pub timeout: u64,
+    /// Command running the code blocks of each language, other than Rust
+    pub commands: BTreeMap<String, String>,
+}
+
+/// Options of the code blocks, read from the `[output.html.code]` table of `book.toml`
+#[derive(Debug, Clone, PartialEq)]
+pub struct CodeConfig {
+    /// Languages kept in the highlight.js bundle of the theme, all of them by default
+    pub highlight_languages: Option<Vec<String>>,
+    /// Files of the theme directory with the definitions of other languages, added to the bundle
+    pub additional_languages: Vec<PathBuf>,
+    /// Give an id to every line of the code blocks, so that they can be linked to
+    pub line_anchors: bool,
+}
+
+/// Options of the Web App Manifest, read from the `[output.html.manifest]` table of `book.toml`
+#[derive(Debug, Clone)]
+pub struct ManifestConfig {
+    pub enable: bool,
+    /// Name shown below the icon of the installed book, the title of the book by default
+    pub short_name: Option<String>,
+    pub theme_color: String,
+    pub background_color: String,
+    /// Icons, relative to the source directory. The favicon is used when there are none.
+    pub icons: Vec<String>,
+}
+
+/// CSS variables of the theme, read from the `[output.html.style]` table of `book.toml`
+#[derive(Debug, Clone, PartialEq)]
+pub struct StyleConfig {
+    /// Values keyed by the name of the variable without `--`, e.g. `sidebar-width`
+    pub variables: BTreeMap<String, String>,
+}
+
+/// Links to other sites, read from the `[output.html.external-links]` table of `book.toml`
+#[derive(Debug, Clone)]
+pub struct ExternalLinksConfig {
+    /// Open them in a new tab, with `rel="noopener"`
+    pub new_tab: bool,
+    /// Append an icon to their text
+    pub icon: bool,
+    /// Domains whose links, and the links of their subdomains, are left as they are
+    pub exempt_domains: Vec<String>,
+}
+
+/// Splitting of the long chapters in several pages, read from the `[output.html.split]` table of `book.toml`
+#[derive(Debug, Clone, PartialEq)]
+pub struct SplitConfig {
+    pub enable: bool,
+    /// Deepest heading level starting a page, 1 or 2
+    pub level: u32,
+    /// Only the chapters with at least this many words are split
+    pub min_words: usize,
+}
+
+/// Links to the headings of the chapters, read from the `[output.html.headings]` table of `book.toml`
+#[derive(Debug, Clone, PartialEq)]
+pub struct HeadingsConfig {
+    pub permalink: Permalink,
+    pub visibility: PermalinkVisibility,
+    /// Headings of a lower level, like the `h1` headings with 2, have an anchor but no link
+    pub min_level: u32,
+}
+
+/// What links to a heading, the `permalink` of `[output.html.headings]`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Permalink {
+    /// The whole heading is the link
+    Heading,
+    /// An icon after the text of the heading
+    Icon,
+}
+
+/// When the icon linking to a heading is shown, the `visibility` of `[output.html.headings]`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PermalinkVisibility {
+    Hover,
+    Always,
+}
+
+/// Rendering of the math of the chapters, read from the `[output.html.math]` table of `book.toml`
+#[derive(Debug, Clone, PartialEq)]
+pub struct MathConfig {
+    pub mode: MathMode,
+    /// Command line of KaTeX, reading an expression on its standard input and printing its html
+    pub katex_command: String,
+    /// Stylesheet of KaTeX linked by the pages in the `katex` mode
+    pub katex_stylesheet: String,
+}
+
+/// Where the math is rendered, the `mode` of `[output.html.math]`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MathMode {
+    /// By MathJax in the browser
+    MathJax,
+    /// By KaTeX at build time, to html and MathML
+    Katex,
+}
+
+/// Rules of `robots.txt`, read from the `[output.html.robots]` table of `book.toml`
+#[derive(Debug, Clone, PartialEq)]
+pub struct RobotsConfig {
+    pub user_agent: String,
+    /// Paths the crawlers should not visit, relative to the root of the site
+    pub disallow: Vec<String>,
+    pub allow: Vec<String>,
+}
+
+/// Atom feed of the recently changed chapters, from the history of git, read from `[output.html.changes]`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChangesConfig {
+    /// Title of the feed, from the title of the book by default
+    pub title: Option<String>,
+    /// Number of chapters in the feed, the last changed ones
+    pub entries: usize,
+    /// Number of commits of the history read, the newest ones
+    pub commits: usize,
+}
+
+/// The CSS variables of the theme that `[output.html.style]` can set
+pub const STYLE_VARIABLES: &'static [&'static str] = &["sidebar-width",
+                                                       "content-max-width",
+                                                       "font-family",
+                                                       "content-font-family",
+                                                       "mono-font-family",
+                                                       "accent-color"];
+
+/// Credentials of an Algolia DocSearch index, read from `[output.html.search.docsearch]`
+#[derive(Debug, Clone)]
+pub struct DocSearchConfig {
+    pub app_id: Option<String>,
+    pub api_key: String,
+    pub index_name: String,
+}
+
+impl HtmlConfig {
+    pub fn new() -> Self {
+        HtmlConfig {
+            subresource_integrity: false,
+            toc_depth: None,
+            glossary: false,
+            numbered_headings: false,
+            chapter_authors: vec![],
+            chapter_date: None,
+            search: SearchConfig::new(),
+            run: RunConfig::new(),
+            code: CodeConfig::new(),
+            manifest: ManifestConfig::new(),
+            style: StyleConfig::new(),
+            external_links: ExternalLinksConfig::new(),
+            split: SplitConfig::new(),
+            headings: HeadingsConfig::new(),
+            math: MathConfig::new(),
+            robots: None,
+            changes: None,
+            noindex: false,
+            structured_data: true,
+            print: true,
+            sources: false,
+            archive: false,
+            offline: false,
+            site_url: None,
+            cname: None,
+            doc_crates: BTreeMap::new(),
+            sanitize: false,
+            quizzes: false,
+            preserve_symlinks: false,
+            logo: None,
+            favicon: None,
+            apple_touch_icon: None,
+            sidebar_title: None,
+            layout: Layout::Sidebar,
+            progress_bar: false,
+            back_to_top: false,
+        }
+    }
+
+    pub fn parse_from_btreemap(&mut self, config: &BTreeMap<String, toml::Value>) -> &mut Self {
+
+        // Subresource integrity hashes for the static assets
+        if let Some(a) = config.get("subresource-integrity").and_then(|v| v.as_bool()) {
+            self.subresource_integrity = a;
+        }
+
+        // Table of contents
+        if let Some(a) = config.get("toc-depth").and_then(|v| v.as_integer()) {
+            if a > 0 {
+                self.toc_depth = Some(a as usize);
+            } else {
+                warn!("[*]: `toc-depth` should be at least 1, ignoring it");
+            }
+        }
+
+        // Numbered headings
+        if let Some(a) = config.get("numbered-headings").and_then(|v| v.as_bool()) {
+            self.numbered_headings = a;
+        }
+
+        // Default chapter metadata
+        if let Some(a) = config.get("chapter-authors") {
+            if let Some(a) = a.as_str() {
+                self.chapter_authors = vec![a.to_owned()];
+            } else if let Some(a) = a.as_slice() {
+                self.chapter_authors = a.iter().filter_map(|v| v.as_str()).map(|s| s.to_owned()).collect();
+            }
+        }
+        if let Some(a) = config.get("chapter-date").and_then(|v| v.as_str().or_else(|| v.as_datetime())) {
+            self.chapter_date = Some(a.to_owned());
+        }
+
+        // Glossary
+        if let Some(a) = config.get("glossary").and_then(|v| v.as_bool()) {
+            self.glossary = a;
+        }
+
+        // Search
+        if let Some(a) = config.get("search").and_then(|v| v.as_table()) {
+            self.search.parse_from_btreemap(a);
+        }
+
+        // Print version
+        if let Some(a) = config.get("print").and_then(|v| v.as_bool()) {
+            self.print = a;
+        }
+
+        // Downloadable copy of the book
+        if let Some(a) = config.get("archive").and_then(|v| v.as_bool()) {
+            self.archive = a;
+        }
+
+        // Markdown of the chapters
+        if let Some(a) = config.get("sources").and_then(|v| v.as_bool()) {
+            self.sources = a;
+        }
+
+        // Service worker
+        if let Some(a) = config.get("offline").and_then(|v| v.as_bool()) {
+            self.offline = a;
+        }
+
+        // Where the book is published
+        if let Some(a) = config.get("site-url").and_then(|v| v.as_str()) {
+            self.site_url = Some(a.to_owned());
+        }
+        if let Some(a) = config.get("cname").and_then(|v| v.as_str()) {
+            self.cname = Some(a.to_owned());
+        }
+
+        // Crates for `{{#doc}}`
+        if let Some(a) = config.get("doc-crates").and_then(|v| v.as_table()) {
+            for (name, root) in a {
+                if let Some(root) = root.as_str() {
+                    self.doc_crates.insert(name.clone(), PathBuf::from(root));
+                }
+            }
+        }
+
+        // Html of the chapters written by untrusted contributors
+        if let Some(a) = config.get("sanitize").and_then(|v| v.as_bool()) {
+            self.sanitize = a;
+        }
+
+        // Quizzes of the training books
+        if let Some(a) = config.get("quizzes").and_then(|v| v.as_bool()) {
+            self.quizzes = a;
+        }
+
+        // Symbolic links of the source directory
+        if let Some(a) = config.get("preserve-symlinks").and_then(|v| v.as_bool()) {
+            self.preserve_symlinks = a;
+        }
+
+        // Header of the sidebar
+        if let Some(a) = config.get("logo").and_then(|v| v.as_str()) {
+            self.logo = Some(PathBuf::from(a));
+        }
+        if let Some(a) = config.get("favicon").and_then(|v| v.as_str()) {
+            self.favicon = Some(PathBuf::from(a));
+        }
+        if let Some(a) = config.get("apple-touch-icon").and_then(|v| v.as_str()) {
+            self.apple_touch_icon = Some(PathBuf::from(a));
+        }
+        if let Some(a) = config.get("sidebar-title").and_then(|v| v.as_str()) {
+            self.sidebar_title = Some(a.to_owned());
+        }
+
+        // Navigation
+        if let Some(a) = config.get("layout").and_then(|v| v.as_str()) {
+            match a {
+                "sidebar" => self.layout = Layout::Sidebar,
+                "navbar" => self.layout = Layout::Navbar,
+                _ => warn!("[*]: Unknown `layout` `{}`, expected `sidebar` or `navbar`", a),
+            }
+        }
+
+        // Reading aids of the long chapters
+        if let Some(a) = config.get("progress-bar").and_then(|v| v.as_bool()) {
+            self.progress_bar = a;
+        }
+        if let Some(a) = config.get("back-to-top").and_then(|v| v.as_bool()) {
+            self.back_to_top = a;
+        }
+
+        // Code blocks run at build time
+        if let Some(a) = config.get("run").and_then(|v| v.as_table()) {
+            self.run.parse_from_btreemap(a);
+        }
+
+        // Code blocks
+        if let Some(a) = config.get("code").and_then(|v| v.as_table()) {
+            self.code.parse_from_btreemap(a);
+        }
+
+        // Web App Manifest
+        if let Some(a) = config.get("manifest").and_then(|v| v.as_table()) {
+            self.manifest.parse_from_btreemap(a);
+        }
+
+        // Variables of the theme
+        if let Some(a) = config.get("style").and_then(|v| v.as_table()) {
+            self.style.parse_from_btreemap(a);
+        }
+
+        // Links to other sites
+        if let Some(a) = config.get("external-links").and_then(|v| v.as_table()) {
+            self.external_links.parse_from_btreemap(a);
+        }
+
+        // Long chapters
+        if let Some(a) = config.get("split").and_then(|v| v.as_table()) {
+            self.split.parse_from_btreemap(a);
+        }
+
+        // Links to the headings
+        if let Some(a) = config.get("headings").and_then(|v| v.as_table()) {
+            self.headings.parse_from_btreemap(a);
+        }
+
+        // Math
+        if let Some(a) = config.get("math").and_then(|v| v.as_table()) {
+            self.math.parse_from_btreemap(a);
+        }
+
+        // Crawlers and search engines
+        if let Some(a) = config.get("robots").and_then(|v| v.as_table()) {
+            self.robots = Some(RobotsConfig::from_btreemap(a));
+        }
+        if let Some(a) = config.get("changes").and_then(|v| v.as_table()) {
+            self.changes = Some(ChangesConfig::from_btreemap(a));
+        }
+        if let Some(a) = config.get("noindex").and_then(|v| v.as_bool()) {
+            self.noindex = a;
+        }
+        if let Some(a) = config.get("structured-data").and_then(|v| v.as_bool()) {
+            self.structured_data = a;
+        }
+
+        self
+    }
+
+    /// Domain of the `CNAME` file, `cname` or else the host of `site-url` when the book is at the
+    /// root of a custom domain. Books on `github.io` don't need a `CNAME`.
+    pub fn cname(&self) -> Option<String> {
+        if self.cname.is_some() {
+            return self.cname.clone();
+        }
+
+        let url = match self.site_url {
+            Some(ref url) => url,
+            None => return None,
+        };
+        let without_scheme = url.find("://").map_or(&url[..], |i| &url[i + 3..]);
+        let (host, path) = match without_scheme.find('/') {
+            Some(i) => without_scheme.split_at(i),
+            None => (without_scheme, "/"),
+        };
+
+        if host.is_empty() || host.ends_with("github.io") || !path.trim_matches('/').is_empty() {
+            None
+        } else {
+            Some(host.to_owned())
+        }
+    }
+}
+
+impl SearchConfig {
+    pub fn new() -> Self {
+        SearchConfig {
+            enable: true,
+            boost_heading: 2.0,
+            boost_body: 1.0,
+            teaser_word_count: 30,
+            limit_results: 30,
+            page: true,
+            page_size: 10,
+            docsearch: None,
+        }
+    }
+
+    pub fn parse_from_btreemap(&mut self, config: &BTreeMap<String, toml::Value>) -> &mut Self {
+
+        if let Some(a) = config.get("enable").and_then(|v| v.as_bool()) {
+            self.enable = a;
+        }
+        if let Some(a) = config.get("boost-heading").and_then(as_number) {
+            self.boost_heading = a;
+        }
+        if let Some(a) = config.get("boost-body").and_then(as_number) {
+            self.boost_body = a;
+        }
+        if let Some(a) = config.get("teaser-word-count").and_then(|v| v.as_integer()) {
+            self.teaser_word_count = a as u32;
+        }
+        if let Some(a) = config.get("limit-results").and_then(|v| v.as_integer()) {
+            self.limit_results = a as u32;
+        }
+        if let Some(a) = config.get("page").and_then(|v| v.as_bool()) {
+            self.page = a;
+        }
+        if let Some(a) = config.get("page-size").and_then(|v| v.as_integer()) {
+            if a > 0 {
+                self.page_size = a as u32;
+            } else {
+                warn!("[*]: The search `page-size` should be positive, ignoring it");
+            }
+        }
+        if let Some(a) = config.get("docsearch").and_then(|v| v.as_table()) {
+            self.docsearch = DocSearchConfig::from_btreemap(a);
+        }
+
+        self
+    }
+}
+
+impl CodeConfig {
+    pub fn new() -> Self {
+        CodeConfig {
+            highlight_languages: None,
+            additional_languages: vec![],
+            line_anchors: false,
+        }
+    }
+
+    pub fn parse_from_btreemap(&mut self, config: &BTreeMap<String, toml::Value>) -> &mut Self {
+
+        if let Some(a) = config.get("highlight-languages").and_then(|v| v.as_slice()) {
+            self.highlight_languages = Some(a.iter().filter_map(|v| v.as_str()).map(|s| s.to_owned()).collect());
+        }
+        if let Some(a) = config.get("additional-languages").and_then(|v| v.as_slice()) {
+            self.additional_languages = a.iter().filter_map(|v| v.as_str()).map(PathBuf::from).collect();
+        }
+        if let Some(a) = config.get("line-anchors").and_then(|v| v.as_bool()) {
+            self.line_anchors = a;
+        }
+
+        self
+    }
+}
+
+impl RunConfig {
+    pub fn new() -> Self {
+        RunConfig {
+            enable: false,
+            timeout: 10,
+            commands: BTreeMap::new(),
+        }
+    }
+
+    pub fn parse_from_btreemap(&mut self, config: &BTreeMap<String, toml::Value>) -> &mut Self {
+
+        if let Some(a) = config.get("enable").and_then(|v| v.as_bool()) {
+            self.enable = a;
+        }
+        if let Some(a) = config.get("timeout").and_then(|v| v.as_integer()) {
+            self.timeout = a as u64;
+        }
+        if let Some(a) = config.get("commands").and_then(|v| v.as_table()) {
+            for (language, command) in a {
+                if let Some(command) = command.as_str() {
+                    self.commands.insert(language.clone(), command.to_owned());
+                }
+            }
+        }
+
+        self
+    }
+}
+
+impl ManifestConfig {
+    pub fn new() -> Self {
+        ManifestConfig {
+            enable: false,
+            short_name: None,
+            theme_color: "#ffffff".to_owned(),
+            background_color: "#ffffff".to_owned(),
+            icons: vec![],
+        }
+    }
+
+    pub fn parse_from_btreemap(&mut self, config: &BTreeMap<String, toml::Value>) -> &mut Self {
+
+        if let Some(a) = config.get("enable").and_then(|v| v.as_bool()) {
+            self.enable = a;
+        }
+        if let Some(a) = config.get("short-name").and_then(|v| v.as_str()) {
+            self.short_name = Some(a.to_owned());
+        }
+        if let Some(a) = config.get("theme-color").and_then(|v| v.as_str()) {
+            self.theme_color = a.to_owned();
+        }
+        if let Some(a) = config.get("background-color").and_then(|v| v.as_str()) {
+            self.background_color = a.to_owned();
+        }
+        if let Some(a) = config.get("icons").and_then(|v| v.as_slice()) {
+            self.icons = a.iter().filter_map(|v| v.as_str()).map(|s| s.to_owned()).collect();
+        }
+
+        self
+    }
+}
+
+impl ExternalLinksConfig {
+    pub fn new() -> Self {
+        ExternalLinksConfig {
+            new_tab: false,
+            icon: false,
+            exempt_domains: vec![],
+        }
+    }
+
+    pub fn parse_from_btreemap(&mut self, config: &BTreeMap<String, toml::Value>) -> &mut Self {
+
+        if let Some(a) = config.get("new-tab").and_then(|v| v.as_bool()) {
+            self.new_tab = a;
+        }
+        if let Some(a) = config.get("icon").and_then(|v| v.as_bool()) {
+            self.icon = a;
+        }
+        if let Some(a) = config.get("exempt-domains").and_then(|v| v.as_slice()) {
+            self.exempt_domains = a.iter().filter_map(|v| v.as_str()).map(|s| s.to_owned()).collect();
+        }
+
+        self
+    }
+}
+
+impl SplitConfig {
+    pub fn new() -> Self {
+        SplitConfig {
+            enable: false,
+            level: 2,
+            min_words: 2000,
+        }
+    }
+
+    pub fn parse_from_btreemap(&mut self, config: &BTreeMap<String, toml::Value>) -> &mut Self {
+
+        if let Some(a) = config.get("enable").and_then(|v| v.as_bool()) {
+            self.enable = a;
+        }
+        if let Some(a) = config.get("level").and_then(|v| v.as_integer()) {
+            if a == 1 || a == 2 {
+                self.level = a as u32;
+            } else {
+                warn!("[*]: The split `level` should be 1 or 2, ignoring it");
+            }
+        }
+        if let Some(a) = config.get("min-words").and_then(|v| v.as_integer()) {
+            if a >= 0 {
+                self.min_words = a as usize;
+            } else {
+                warn!("[*]: The split `min-words` should not be negative, ignoring it");
+            }
+        }
+
+        self
+    }
+}
+
+impl HeadingsConfig {
+    pub fn new() -> Self {
+        HeadingsConfig {
+            permalink: Permalink::Heading,
+            visibility: PermalinkVisibility::Hover,
+            min_level: 1,
+        }
+    }
+
+    pub fn parse_from_btreemap(&mut self, config: &BTreeMap<String, toml::Value>) -> &mut Self {
+
+        if let Some(a) = config.get("permalink").and_then(|v| v.as_str()) {
+            match a {
+                "heading" => self.permalink = Permalink::Heading,
+                "icon" => self.permalink = Permalink::Icon,
+                _ => warn!("[*]: Unknown heading `permalink` `{}`, expected `heading` or `icon`", a),
+            }
+        }
+        if let Some(a) = config.get("visibility").and_then(|v| v.as_str()) {
+            match a {
+                "hover" => self.visibility = PermalinkVisibility::Hover,
+                "always" => self.visibility = PermalinkVisibility::Always,
+                _ => warn!("[*]: Unknown heading `visibility` `{}`, expected `hover` or `always`", a),
+            }
+        }
+        if let Some(a) = config.get("min-level").and_then(|v| v.as_integer()) {
+            if (1..6).contains(&a) {
+                self.min_level = a as u32;
+            } else {
+                warn!("[*]: The headings `min-level` should be between 1 and 5, ignoring it");
+            }
+        }
+
+        self
+    }
+}
+
+impl MathConfig {
+    pub fn new() -> Self {
+        MathConfig {
+            mode: MathMode::MathJax,
+            katex_command: "katex".to_owned(),
+            katex_stylesheet: "https://cdn.jsdelivr.net/npm/katex@0.16.9/dist/katex.min.css".to_owned(),
+        }
+    }
+
+    pub fn parse_from_btreemap(&mut self, config: &BTreeMap<String, toml::Value>) -> &mut Self {
+
+        if let Some(a) = config.get("mode").and_then(|v| v.as_str()) {
+            match a {
+                "mathjax" => self.mode = MathMode::MathJax,
+                "katex" => self.mode = MathMode::Katex,
+                _ => warn!("[*]: Unknown math `mode` `{}`, expected `mathjax` or `katex`", a),
+            }
+        }
+        if let Some(a) = config.get("katex-command").and_then(|v| v.as_str()) {
+            self.katex_command = a.to_owned();
+        }
+        if let Some(a) = config.get("katex-stylesheet").and_then(|v| v.as_str()) {
+            self.katex_stylesheet = a.to_owned();
+        }
+
+        self
+    }
+}
+
+impl RobotsConfig {
+    pub fn from_btreemap(config: &BTreeMap<String, toml::Value>) -> Self {
+        let list = |key: &str| {
+            config.get(key)
+                .and_then(|v| v.as_slice())
+                .map(|a| a.iter().filter_map(|v| v.as_str()).map(|s| s.to_owned()).collect())
+                .unwrap_or_default()
+        };
+
+        RobotsConfig {
+            user_agent: config.get("user-agent").and_then(|v| v.as_str()).unwrap_or("*").to_owned(),
+            disallow: list("disallow"),
+            allow: list("allow"),
+        }
+    }
+}
+
+impl ChangesConfig {
+    pub fn from_btreemap(config: &BTreeMap<String, toml::Value>) -> Self {
+        let count = |key: &str, default: usize| {
+            config.get(key).and_then(|v| v.as_integer()).filter(|&n| n > 0).map_or(default, |n| n as usize)
+        };
+
+        ChangesConfig {
+            title: config.get("title").and_then(|v| v.as_str()).map(|s| s.to_owned()),
+            entries: count("entries", 20),
+            commits: count("commits", 100),
+        }
+    }
+}
+
+impl StyleConfig {
+    pub fn new() -> Self {
+        StyleConfig { variables: BTreeMap::new() }
+    }
+
+    pub fn parse_from_btreemap(&mut self, config: &BTreeMap<String, toml::Value>) -> &mut Self {
+        for (key, value) in config {
+            if !STYLE_VARIABLES.contains(&&key[..]) {
+                warn!("[*]: [output.html.style] has no variable `{}`, it should be one of {}",
+                      key,
+                      STYLE_VARIABLES.join(", "));
+                continue;
+            }
+
+            // Lengths can be numbers of pixels
+            match (value.as_str(), value.as_integer()) {
+                (Some(a), _) => self.variables.insert(key.clone(), a.to_owned()),
+                (_, Some(a)) => self.variables.insert(key.clone(), format!("{}px", a)),
+                _ => {
+                    warn!("[*]: `{}` of [output.html.style] should be a string, ignoring it", key);
+                    continue;
+                },
+            };
+        }
+
+        self
+    }
+}
+
+impl DocSearchConfig {
+    /// Returns `None` when the API key or the index name is missing
+    pub fn from_btreemap(config: &BTreeMap<String, toml::Value>) -> Option<Self> {
+        let get = |key: &str| config.get(key).and_then(|v| v.as_str()).map(|s| s.to_owned());
+
+        match (get("api-key"), get("index-name")) {
+            (Some(api_key), Some(index_name)) => {
+                Some(DocSearchConfig {
+                    app_id: get("app-id"),
+                    api_key: api_key,
+                    index_name: index_name,
+                })
+            },
+            _ => {
+                warn!("[*]: [output.html.search.docsearch] needs an `api-key` and an `index-name`, \
+                       using the built-in search instead");
+                None
+            },
+        }
+    }
+}
+
+// Accepts both `2` and `2.0` where a float is expected
+fn as_number(value: &toml::Value) -> Option<f64> {
+    value.as_float().or_else(|| value.as_integer().map(|i| i as f64))
+}
@@ -0,0 +1,277 @@
+use std::fmt;
+use std::fs::File;
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::collections::{BTreeMap, HashSet};
+use std::error::Error;
+use std::thread;
+use std::time::Duration;
+use toml;
+
+use utils::process::wait_with_timeout;
+
+/// Options of `mdbook lint`, read from the `[lint]` table of `book.toml`
+#[derive(Debug, Clone)]
+pub struct LintConfig {
+    /// The checker and its arguments, `{}` is replaced by the path of the chapter. Without `{}`
+    /// the chapter is written to the standard input of the checker.
+    pub command: Vec<String>,
+    /// File with the words that the checker should accept, one per line
+    pub dictionary: Option<PathBuf>,
+    /// Seconds after which the checker is killed
+    pub timeout: u64,
+}
+
+/// Something reported by the checker
+#[derive(Debug, Clone, PartialEq)]
+pub struct LintFinding {
+    /// Path of the chapter, relative to the source directory
+    pub path: PathBuf,
+    pub line: usize,
+    pub column: Option<usize>,
+    pub message: String,
+}
+
+impl LintConfig {
+    /// Returns `None` when there is no `command`
+    pub fn from_btreemap(config: &BTreeMap<String, toml::Value>) -> Option<Self> {
+        let command: Vec<String> = match config.get("command") {
+            Some(toml::Value::String(command)) => command.split_whitespace().map(|s| s.to_owned()).collect(),
+            Some(toml::Value::Array(command)) => {
+                command.iter().filter_map(|v| v.as_str()).map(|s| s.to_owned()).collect()
+            },
+            _ => vec![],
+        };
+
+        if command.is_empty() {
+            warn!("[*]: [lint] needs a `command`");
+            return None;
+        }
+
+        Some(LintConfig {
+            command: command,
+            dictionary: config.get("dictionary").and_then(|v| v.as_str()).map(PathBuf::from),
+            timeout: config.get("timeout").and_then(|v| v.as_integer()).map_or(30, |a| a as u64),
+        })
+    }
+}
+
+impl fmt::Display for LintFinding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.column {
+            Some(column) => write!(f, "{}:{}:{}: {}", self.path.display(), self.line, column, self.message),
+            None => write!(f, "{}:{}: {}", self.path.display(), self.line, self.message),
+        }
+    }
+}
+
+/// Reads the words of a project dictionary, lines starting with `#` are comments
+pub fn read_dictionary(path: &Path) -> Result<HashSet<String>, Box<Error>> {
+    let mut content = String::new();
+    try!(try!(File::open(path)).read_to_string(&mut content));
+
+    Ok(content.lines()
+        .map(|l| l.trim())
+        .filter(|l| !l.is_empty() && !l.starts_with('#'))
+        .map(|l| l.to_lowercase())
+        .collect())
+}
+
+/// Runs the checker on a chapter. `path` is relative to `src`, the findings point to the lines
+/// of the chapter file.
+pub fn lint_chapter(config: &LintConfig,
+                    src: &Path,
+                    path: &Path,
+                    dictionary: &HashSet<String>)
+                    -> Result<Vec<LintFinding>, Box<Error>> {
+    let full_path = src.join(path);
+    let mut content = String::new();
+    try!(try!(File::open(&full_path)).read_to_string(&mut content));
+
+    let uses_path = config.command.iter().any(|arg| arg.contains("{}"));
+    let args: Vec<String> = config.command[1..]
+        .iter()
+        .map(|arg| arg.replace("{}", &full_path.to_string_lossy()))
+        .collect();
+
+    let mut child = try!(Command::new(&config.command[0])
+        .args(&args)
+        .stdin(if uses_path { Stdio::null() } else { Stdio::piped() })
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| io::Error::new(e.kind(), format!("Could not run `{}`: {}", config.command[0], e))));
+
+    // Written while the output is read, so that a long chapter can not block both programs
+    let writer = child.stdin.take().map(|mut stdin| {
+        let content = content.clone();
+        thread::spawn(move || {
+            let _ = stdin.write_all(content.as_bytes());
+        })
+    });
+
+    let (success, stdout, stderr) = try!(wait_with_timeout(child, Duration::from_secs(config.timeout)).map_err(|e| {
+        io::Error::new(io::ErrorKind::Other,
+                       format!("`{}` failed on {}: {}", config.command.join(" "), path.display(), e))
+    }));
+    if let Some(writer) = writer {
+        let _ = writer.join();
+    }
+
+    // Most checkers exit with an error when they find something, only fail when they complain
+    if !success && stdout.trim().is_empty() && !stderr.is_empty() {
+        return Err(Box::new(io::Error::new(io::ErrorKind::Other,
+                                           format!("`{}` failed on {}:\n{}",
+                                                   config.command.join(" "),
+                                                   path.display(),
+                                                   stderr))));
+    }
+
+    Ok(parse_output(&stdout, path, &content, dictionary))
+}
+
+/// The checker can either print `file:line:column: message` lines, like vale, or one misspelled
+/// word per line, like `hunspell -l`. The words are looked up in the chapter to find their lines.
+pub fn parse_output(output: &str, path: &Path, content: &str, dictionary: &HashSet<String>) -> Vec<LintFinding> {
+    let mut findings = vec![];
+
+    for line in output.lines().map(|l| l.trim()).filter(|l| !l.is_empty()) {
+        if let Some((line_number, column, message)) = parse_location(line) {
+            // Messages like "Did you really mean 'mdbook'?" are accepted by the dictionary too
+            let accepted = dictionary.iter()
+                .any(|w| message.to_lowercase().contains(&format!("'{}'", w)));
+            if !accepted {
+                findings.push(LintFinding {
+                    path: path.to_owned(),
+                    line: line_number,
+                    column: column,
+                    message: message,
+                });
+            }
+        } else if !line.contains(char::is_whitespace) && !dictionary.contains(&line.to_lowercase()) {
+            for (line_number, column) in find_word(content, line) {
+                findings.push(LintFinding {
+                    path: path.to_owned(),
+                    line: line_number,
+                    column: Some(column),
+                    message: format!("unknown word `{}`", line),
+                });
+            }
+        }
+    }
+
+    findings
+}
+
+// `file:line:column: message` or `file:line: message`
+fn parse_location(line: &str) -> Option<(usize, Option<usize>, String)> {
+    let parts: Vec<&str> = line.splitn(4, ':').collect();
+    if parts.len() < 3 {
+        return None;
+    }
+
+    let line_number = match parts[1].trim().parse() {
+        Ok(n) => n,
+        Err(_) => return None,
+    };
+
+    match parts[2].trim().parse() {
+        Ok(column) if parts.len() == 4 => Some((line_number, Some(column), parts[3].trim().to_owned())),
+        _ => Some((line_number, None, parts[2..].join(":").trim().to_owned())),
+    }
+}
+
+// Lines and columns, starting at 1, of the occurrences of `word` that are not part of a longer word
+fn find_word(content: &str, word: &str) -> Vec<(usize, usize)> {
+    let mut found = vec![];
+
+    for (i, line) in content.lines().enumerate() {
+        let mut start = 0;
+        while let Some(pos) = line[start..].find(word) {
+            let pos = start + pos;
+            let end = pos + word.len();
+            let starts_word = !line[..pos].chars().next_back().map(char::is_alphanumeric).unwrap_or(false);
+            let ends_word = !line[end..].chars().next().map(char::is_alphanumeric).unwrap_or(false);
+
+            if starts_word && ends_word {
+                found.push((i + 1, line[..pos].chars().count() + 1));
+            }
+            start = end;
+        }
+    }
+
+    found
+}
+
+
+// ------------------------------------------------------------------------------------------------
+// ------------------------------------------------------------------------------------------------
+
+// tests
+
+#[cfg(test)]
+mod tests {
+    extern crate tempdir;
+
+    use std::path::Path;
+    use std::collections::HashSet;
+    use std::fs::File;
+    use std::io::Write;
+    use super::{parse_output, lint_chapter, LintConfig, LintFinding};
+
+    fn finding(line: usize, column: Option<usize>, message: &str) -> LintFinding {
+        LintFinding {
+            path: Path::new("intro.md").to_owned(),
+            line: line,
+            column: column,
+            message: message.to_owned(),
+        }
+    }
+
+    #[test]
+    fn parse_word_list() {
+        let content = "# Intro\n\nThe teh mdbook.\nteh again, tehs\n";
+        let findings = parse_output("teh\n", Path::new("intro.md"), content, &HashSet::new());
+
+        assert_eq!(findings,
+                   vec![finding(3, Some(5), "unknown word `teh`"), finding(4, Some(1), "unknown word `teh`")]);
+    }
+
+    #[test]
+    fn parse_locations() {
+        let output = "intro.md:3:5:Vale.Spelling:Did you really mean 'mdbook'?\nintro.md:4: Too wordy\n";
+        let findings = parse_output(output, Path::new("intro.md"), "", &HashSet::new());
+
+        assert_eq!(findings,
+                   vec![finding(3, Some(5), "Vale.Spelling:Did you really mean 'mdbook'?"),
+                        finding(4, None, "Too wordy")]);
+    }
+
+    #[test]
+    fn dictionary_is_honored() {
+        let dictionary: HashSet<String> = vec!["mdbook".to_owned()].into_iter().collect();
+        let output = "mdbook\nintro.md:3:5:Did you really mean 'mdBook'?\n";
+
+        assert!(parse_output(output, Path::new("intro.md"), "mdbook", &dictionary).is_empty());
+    }
+
+    #[test]
+    fn lint_long_chapters() {
+        let dir = tempdir::TempDir::new("mdbook-lint").unwrap();
+        // Larger than the buffer of the pipes, the checker prints while it reads
+        File::create(dir.path().join("long.md")).unwrap().write_all("word\n".repeat(400000).as_bytes()).unwrap();
+        let mut config = LintConfig {
+            command: vec!["tr".to_owned(), "-d".to_owned(), "a-z".to_owned()],
+            dictionary: None,
+            timeout: 1,
+        };
+
+        let findings = lint_chapter(&config, dir.path(), Path::new("long.md"), &HashSet::new()).unwrap();
+        assert!(findings.is_empty());
+
+        config.command = vec!["sh".to_owned(), "-c".to_owned(), "sleep 5".to_owned(), "{}".to_owned()];
+        let err = lint_chapter(&config, dir.path(), Path::new("long.md"), &HashSet::new()).unwrap_err();
+        assert!(err.to_string().contains("it was still running after 1 seconds"));
+    }
+}
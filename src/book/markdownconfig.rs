@@ -0,0 +1,87 @@
+use std::collections::BTreeMap;
+use toml;
+
+use pulldown_cmark::{Options, OPTION_ENABLE_TABLES, OPTION_ENABLE_FOOTNOTES};
+
+/// Markdown extensions of the chapters, read from the `[markdown]` table of `book.toml`
+#[derive(Debug, Clone, PartialEq)]
+pub struct MarkdownConfig {
+    pub tables: bool,
+    pub footnotes: bool,
+    /// `~~text~~` is struck through
+    pub strikethrough: bool,
+    /// The urls written in the text are links
+    pub autolinks: bool,
+    /// Straight quotes become curly quotes, `--` and `---` dashes and `...` an ellipsis
+    pub smart_punctuation: bool,
+    /// `{#id .class}` at the end of a heading sets its id and classes
+    pub heading_attributes: bool,
+    /// Shortcodes like `:crab:` are replaced by their emoji
+    pub emoji: bool,
+    /// The paragraphs with only an image and its caption are figures
+    pub image_captions: bool,
+}
+
+impl MarkdownConfig {
+    pub fn new() -> Self {
+        MarkdownConfig {
+            tables: true,
+            footnotes: true,
+            strikethrough: false,
+            autolinks: false,
+            smart_punctuation: false,
+            heading_attributes: false,
+            emoji: false,
+            image_captions: false,
+        }
+    }
+
+    pub fn parse_from_btreemap(&mut self, config: &BTreeMap<String, toml::Value>) -> &mut Self {
+
+        // The extensions of GitHub Flavored Markdown, the keys of each extension override it
+        if let Some(a) = config.get("gfm").and_then(|v| v.as_bool()) {
+            self.tables = a;
+            self.strikethrough = a;
+            self.autolinks = a;
+        }
+
+        if let Some(a) = config.get("tables").and_then(|v| v.as_bool()) {
+            self.tables = a;
+        }
+        if let Some(a) = config.get("footnotes").and_then(|v| v.as_bool()) {
+            self.footnotes = a;
+        }
+        if let Some(a) = config.get("strikethrough").and_then(|v| v.as_bool()) {
+            self.strikethrough = a;
+        }
+        if let Some(a) = config.get("autolinks").and_then(|v| v.as_bool()) {
+            self.autolinks = a;
+        }
+        if let Some(a) = config.get("smart-punctuation").and_then(|v| v.as_bool()) {
+            self.smart_punctuation = a;
+        }
+        if let Some(a) = config.get("heading-attributes").and_then(|v| v.as_bool()) {
+            self.heading_attributes = a;
+        }
+        if let Some(a) = config.get("emoji").and_then(|v| v.as_bool()) {
+            self.emoji = a;
+        }
+        if let Some(a) = config.get("image-captions").and_then(|v| v.as_bool()) {
+            self.image_captions = a;
+        }
+
+        self
+    }
+
+    /// The options of the pulldown-cmark parser, the other extensions are applied on its events
+    pub fn options(&self) -> Options {
+        let mut opts = Options::empty();
+        if self.tables {
+            opts.insert(OPTION_ENABLE_TABLES);
+        }
+        if self.footnotes {
+            opts.insert(OPTION_ENABLE_FOOTNOTES);
+        }
+        opts
+    }
+}
@@ -1,22 +1,59 @@
 pub mod bookitem;
 pub mod bookconfig;
-
-pub mod bookconfig_test;
-
-pub use self::bookitem::{BookItem, BookItems};
+pub mod htmlconfig;
+pub mod markdownconfig;
+pub mod frontmatter;
+pub mod lint;
+pub mod testing;
+pub mod versions;
+pub mod deploy;
+pub mod plugins;
+pub mod timings;
+pub mod blog;
+pub mod workspace;
+pub mod subbook;
+pub mod autosummary;
+
+mod bookconfig_test;
+
+pub use self::bookitem::{BookItem, BookItems, Chapter};
 pub use self::bookconfig::BookConfig;
-
-use std::path::{Path, PathBuf};
+pub use self::htmlconfig::HtmlConfig;
+pub use self::markdownconfig::MarkdownConfig;
+pub use self::frontmatter::FrontMatter;
+pub use self::lint::{LintConfig, LintFinding};
+pub use self::testing::{TestConfig, TestRunner, SnippetFailure};
+pub use self::versions::{VersionsConfig, VersionPicker};
+pub use self::deploy::DeployConfig;
+pub use self::plugins::{PluginConfig, PluginKind, PluginChapter};
+pub use self::timings::Timings;
+pub use self::blog::{BlogConfig, Post};
+pub use self::workspace::{Workspace, WorkspaceConfig};
+pub use self::subbook::SubBook;
+
+use std::path::{Component, Path, PathBuf};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::cell::{Cell, RefCell};
 use std::fs::{self, File};
 use std::error::Error;
 use std::io;
 use std::io::Write;
 use std::io::ErrorKind;
-use std::process::Command;
+use std::process::{self, Command};
+use std::env;
+use std::time::Instant;
+
+use toml;
 
 use {theme, parse, utils};
-use renderer::{Renderer, HtmlHandlebars};
+use utils::ignore::{IgnorePatterns, IGNORE_FILE};
+use renderer::{self, Renderer, Warning};
 
+/// File of the root of the book with the content of the new chapters
+pub const CHAPTER_TEMPLATE: &'static str = "templates/chapter.md";
+
+/// The warnings of the build of a version, with its name
+pub type VersionWarnings = (String, Vec<Warning>);
 
 pub struct MDBook {
     root: PathBuf,
@@ -32,6 +69,21 @@ pub struct MDBook {
     renderer: Box<Renderer>,
 
     livereload: Option<String>,
+    profile: Option<String>,
+    version_picker: Option<VersionPicker>,
+    blog_posts: Vec<Post>,
+    sub_books: Vec<SubBook>,
+    strict: bool,
+    warnings: Vec<Warning>,
+    timings: Timings,
+    print_timings: bool,
+    dry_run: bool,
+    // The files of the output the dry run would write, relative to the output directory
+    dry_run_files: RefCell<BTreeSet<PathBuf>>,
+    // The chapters and the words of the last build
+    word_count: Cell<(usize, usize)>,
+
+    config: BookConfig,
 }
 
 impl MDBook {
@@ -62,9 +114,22 @@ impl MDBook {
             description: String::new(),
 
             content: vec![],
-            renderer: Box::new(HtmlHandlebars::new()),
+            renderer: renderer::default_renderer(),
 
             livereload: None,
+            profile: None,
+            version_picker: None,
+            blog_posts: vec![],
+            sub_books: vec![],
+            strict: false,
+            warnings: vec![],
+            timings: Timings::new(),
+            print_timings: false,
+            dry_run: false,
+            dry_run_files: RefCell::new(BTreeSet::new()),
+            word_count: Cell::new((0, 0)),
+
+            config: BookConfig::new(root),
         }
     }
 
@@ -118,15 +183,22 @@ impl MDBook {
     /// `chapter_1.md` to the source directory.
 
     pub fn init(&mut self) -> Result<(), Box<Error>> {
+        self.create_structure(true)
+    }
+
+    // The directories and `SUMMARY.md`, the files of the chapters missing from the source are created with
+    // `create_missing`, otherwise they fail the build
+    fn create_structure(&mut self, create_missing: bool) -> Result<(), Box<Error>> {
 
         debug!("[fn]: init");
 
-        if !self.root.exists() {
+        if !self.root.exists() && !self.dry_run {
             fs::create_dir_all(&self.root).unwrap();
             info!("{:?} created", &self.root);
         }
 
-        {
+        // A dry run creates nothing, a book without `SUMMARY.md` fails to parse
+        if !self.dry_run {
 
             if !self.dest.exists() {
                 debug!("[*]: {:?} does not exist, trying to create directory", self.dest);
@@ -140,7 +212,7 @@ impl MDBook {
 
             let summary = self.src.join("SUMMARY.md");
 
-            if !summary.exists() {
+            if !summary.exists() && !self.config.auto_summary {
 
                 // Summary does not exist, create it
 
@@ -159,74 +231,278 @@ impl MDBook {
         try!(self.parse_summary());
 
         debug!("[*]: constructing paths for missing files");
+        let mut missing = vec![];
         for item in self.iter() {
             debug!("[*]: item: {:?}", item);
             match *item {
                 BookItem::Spacer => continue,
                 BookItem::Chapter(_, ref ch) |
                 BookItem::Affix(ref ch) => {
-                    if ch.path != PathBuf::new() {
-                        let path = self.src.join(&ch.path);
-
-                        if !path.exists() {
-                            debug!("[*]: {:?} does not exist, trying to create file", path);
-                            try!(::std::fs::create_dir_all(path.parent().unwrap()));
-                            let mut f = try!(File::create(path));
-
-                            // debug!("[*]: Writing to {:?}", path);
-                            try!(writeln!(f, "# {}", ch.name));
-                        }
+                    if ch.path != PathBuf::new() && !self.get_chapter_file(&ch.path).exists() {
+                        missing.push((ch.path.clone(), ch.name.clone()));
                     }
                 },
             }
         }
 
+        // Every missing chapter is reported at once, before rendering anything
+        if !missing.is_empty() && !create_missing {
+            let chapters: Vec<String> = missing.iter()
+                .map(|(path, name)| format!("    {} ({})", path.display(), name))
+                .collect();
+            return Err(Box::new(io::Error::new(ErrorKind::Other,
+                                               format!("The files of {} chapters of SUMMARY.md are missing:\n{}",
+                                                       missing.len(),
+                                                       chapters.join("\n")))));
+        }
+
+        for (path, name) in missing {
+            let path = self.get_chapter_file(&path);
+            debug!("[*]: {:?} does not exist, trying to create file", path);
+            try!(::std::fs::create_dir_all(path.parent().unwrap()));
+            let content = try!(self.new_chapter_file(&path, &name));
+            try!(File::create(&path).and_then(|mut f| f.write_all(content.as_bytes())));
+            println!("[*]: Created {} for the chapter \"{}\"", path.display(), name);
+        }
+
         debug!("[*]: init done");
         Ok(())
     }
 
-    pub fn create_gitignore(&self) {
-        let gitignore = self.get_gitignore();
-
-        if !gitignore.exists() {
-            // Gitignore does not exist, create it
+    // Two chapters of SUMMARY.md with the same file, or with the same page like `intro.md` and `intro.markdown`,
+    // a chapter whose page is also a static file of the source directory, or another page of the renderer like
+    // `print.html` having the path of a chapter, of a static file or of another one, would overwrite each other in
+    // the output. They are all reported at once, before writing anything.
+    fn check_pages(&self) -> Result<(), Box<Error>> {
+        let ignore = self.get_ignore_patterns();
+        let mut pages: BTreeMap<PathBuf, String> = BTreeMap::new();
+        let mut collisions = vec![];
+        for item in self.iter() {
+            let ch = match *item {
+                BookItem::Chapter(_, ref ch) |
+                BookItem::Affix(ref ch) if ch.path != PathBuf::new() => ch,
+                _ => continue,
+            };
+
+            let page = without_cur_dir(&ch.path).with_extension("html");
+            if let Some(first) = pages.get(&page) {
+                collisions.push(format!("    {} ({} and \"{}\")", page.display(), first, ch.name));
+                continue;
+            }
+            pages.insert(page.clone(), format!("\"{}\"", ch.name));
+
+            let source = self.get_chapter_file(&ch.path);
+            let file = source.with_extension("html");
+            let ignore = self.get_sub_book(&ch.path).map_or(&ignore, |b| &b.ignore);
+            if file != source && file.is_file() && !ignore.is_ignored(&file) {
+                collisions.push(format!("    {} (\"{}\" and the static file {})",
+                                        page.display(),
+                                        ch.name,
+                                        file.display()));
+            }
+        }
 
-            // Because of `src/book/mdbook.rs#L37-L39`, `dest` will always start with `root`. If it
-            // is not, `strip_prefix` will return an Error.
-            if !self.get_dest().starts_with(&self.root) {
-                return;
+        for (page, what) in try!(self.renderer.pages(self)) {
+            if let Some(first) = pages.get(&page) {
+                collisions.push(format!("    {} ({} and {})", page.display(), first, what));
+                continue;
             }
+            let file = self.src.join(&page);
+            if file.is_file() && !ignore.is_ignored(&file) {
+                collisions.push(format!("    {} ({} and the static file {})", page.display(), what, file.display()));
+            }
+            pages.insert(page, what);
+        }
+
+        if collisions.is_empty() {
+            return Ok(());
+        }
+        Err(Box::new(io::Error::new(ErrorKind::Other,
+                                    format!("{} pages of the book would be written twice:\n{}",
+                                            collisions.len(),
+                                            collisions.join("\n")))))
+    }
 
-            let relative = self.get_dest()
-                               .strip_prefix(&self.root)
-                               .expect("Destination is not relative to root.");
-            let relative = relative.to_str()
-                                   .expect("Path could not be yielded into a string slice.");
+    /// Creates a `.gitignore` ignoring the output directory, or adds the output directory to the existing one
+    pub fn create_gitignore(&self) {
+        let gitignore = self.get_gitignore();
 
-            debug!("[*]: {:?} does not exist, trying to create .gitignore", gitignore);
+        // Because of `src/book/mdbook.rs#L37-L39`, `dest` will always start with `root`. If it
+        // is not, `strip_prefix` will return an Error.
+        if !self.get_dest().starts_with(&self.root) {
+            return;
+        }
 
-            let mut f = File::create(&gitignore).expect("Could not create file.");
+        let relative = self.get_dest()
+                           .strip_prefix(&self.root)
+                           .expect("Destination is not relative to root.");
+        let relative = utils::fs::path_to_url(relative);
+
+        let existing = utils::fs::file_to_string(&gitignore).unwrap_or_default();
+        let ignored = existing.lines()
+            .map(|l| l.trim().trim_matches('/'))
+            .any(|l| l == relative);
+        if ignored {
+            return;
+        }
+
+        debug!("[*]: Writing to .gitignore");
+        let mut f = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&gitignore)
+            .expect("Could not create file.");
+        if !existing.is_empty() && !existing.ends_with('\n') {
+            writeln!(f).expect("Could not write to file.");
+        }
+        writeln!(f, "{}", relative).expect("Could not write to file.");
+    }
 
-            debug!("[*]: Writing to .gitignore");
+    /// Writes a `book.toml` with the title and the authors of the book, an existing one is only replaced with
+    /// `overwrite`. Returns whether it was written.
+    pub fn create_config(&self, title: &str, authors: &[String], overwrite: bool) -> Result<bool, Box<Error>> {
+        let path = self.root.join("book.toml");
+        if path.exists() && !overwrite {
+            return Ok(false);
+        }
 
-            writeln!(f, "{}", relative).expect("Could not write to file.");
+        let mut config = BTreeMap::new();
+        config.insert("title".to_owned(), toml::Value::String(title.to_owned()));
+        if !authors.is_empty() {
+            config.insert("author".to_owned(), toml::Value::String(authors.join(", ")));
+        }
+        if authors.len() > 1 {
+            let authors = authors.iter().map(|a| toml::Value::String(a.clone())).collect();
+            config.insert("authors".to_owned(), toml::Value::Array(authors));
         }
+
+        debug!("[*]: Writing to book.toml");
+        let mut f = try!(File::create(&path));
+        try!(write!(f, "{}", toml::Value::Table(config)));
+        Ok(true)
     }
 
     /// The `build()` method is the one where everything happens. First it parses `SUMMARY.md` to
     /// construct the book's structure in the form of a `Vec<BookItem>` and then calls `render()`
     /// method of the current renderer.
     ///
-    /// It is the renderer who generates all the output files.
+    /// It is the renderer who generates all the output files. The warnings of the renderer are
+    /// kept for [`get_warnings()`](#method.get_warnings), in strict mode they fail the build.
     pub fn build(&mut self) -> Result<(), Box<Error>> {
         debug!("[fn]: build");
+        self.timings.clear();
+        self.word_count.set((0, 0));
+        let mut clock = Instant::now();
+
+        // A dry run does not create the missing chapters either
+        let create_missing = self.config.create_missing && !self.dry_run;
+        try!(self.create_structure(create_missing));
+        try!(self.check_pages());
+        self.timings.lap("parse", &mut clock);
+
+        // Clean output directory, the unchanged copies of the static files of the source are kept
+        if self.dry_run {
+            self.dry_run_files.borrow_mut().clear();
+        } else {
+            let ignore = self.get_ignore_patterns();
+            try!(utils::fs::remove_dir_content_except_copies(&self.dest, &self.src, &["md"], &ignore));
+        }
+        self.timings.lap("copy", &mut clock);
+
+        // The chapters whose front matter has a `render` list without `html` are left out of the pages and the
+        // table of contents, the plugins still get every chapter
+        let render_lists = try!(self.render_lists());
+        let excluded: HashSet<&Path> = render_lists.iter()
+            .filter(|&(_, outputs)| !outputs.iter().any(|o| o == "html"))
+            .map(|(path, _)| path.as_path())
+            .collect();
+        let content = self.content.clone();
+        if !excluded.is_empty() {
+            self.content = parse::summary::remove_chapters(content.clone(), &|path| excluded.contains(path));
+        }
+        let warnings = self.renderer.render(self);
+        self.content = content;
+        self.warnings = try!(warnings);
+        for (path, outputs) in &render_lists {
+            for output in outputs {
+                let plugin = self.config.plugins.iter().any(|p| p.kind == PluginKind::Renderer && p.name == *output);
+                if output != "html" && output != "print" && !plugin {
+                    self.warnings.push(Warning::new(path, format!("`render` lists the unknown output `{}`", output)));
+                }
+            }
+        }
+        clock = Instant::now();
+        try!(self.render_plugins());
+        if self.config.plugins.iter().any(|p| p.kind == PluginKind::Renderer) {
+            self.timings.lap("plugins", &mut clock);
+        }
 
-        try!(self.init());
+        if self.strict && !self.warnings.is_empty() {
+            return Err(Box::new(io::Error::new(ErrorKind::Other,
+                                               format!("The build failed with {} warnings in strict mode",
+                                                       self.warnings.len()))));
+        }
 
-        // Clean output directory
-        try!(utils::fs::remove_dir_content(&self.dest));
+        Ok(())
+    }
+
+    /// The outputs listed by the chapters with `render` in their front matter, keyed by the path of the chapter
+    fn render_lists(&self) -> Result<BTreeMap<PathBuf, Vec<String>>, Box<Error>> {
+        let mut lists = BTreeMap::new();
+        for item in self.iter() {
+            if let BookItem::Chapter(_, ref ch) | BookItem::Affix(ref ch) = *item {
+                let file = self.get_chapter_file(&ch.path);
+                if ch.path == PathBuf::new() || !file.is_file() {
+                    continue;
+                }
+                let (content, _) = try!(self.read_chapter(&ch.path));
+                let front_matter = try!(FrontMatter::split(&content)).0;
+                if let Some(outputs) = front_matter.get_str_list("render") {
+                    lists.insert(ch.path.clone(), outputs.iter().map(|o| o.to_string()).collect());
+                }
+            }
+        }
+        Ok(lists)
+    }
+
+    /// Runs the renderer plugins, each one writes its files in a directory of the destination named after it
+    fn render_plugins(&self) -> Result<(), Box<Error>> {
+        let renderers = self.config.plugins.iter().filter(|p| p.kind == PluginKind::Renderer).collect::<Vec<_>>();
+        if renderers.is_empty() {
+            return Ok(());
+        }
+
+        // The markdown of the chapters, without their front matter
+        let mut chapters = vec![];
+        for item in self.iter() {
+            if let BookItem::Chapter(_, ref ch) | BookItem::Affix(ref ch) = *item {
+                if ch.path != PathBuf::new() {
+                    let (content, _) = try!(self.read_chapter(&ch.path));
+                    let (front_matter, markdown) = try!(FrontMatter::split(&content));
+                    chapters.push((item, ch, markdown.to_owned(), front_matter));
+                }
+            }
+        }
 
-        try!(self.renderer.render(&self));
+        for plugin in renderers {
+            // Without the chapters whose `render` list does not name the plugin
+            let chapters = chapters.iter()
+                .filter(|&(_, _, _, front_matter)| front_matter.renders(&plugin.name))
+                .map(|(item, ch, markdown, _)| PluginChapter {
+                    name: &ch.name,
+                    path: &ch.path,
+                    section: match **item {
+                        BookItem::Chapter(ref s, _) => Some(&s[..]),
+                        _ => None,
+                    },
+                    content: markdown,
+                })
+                .collect::<Vec<_>>();
+            for (path, content) in try!(plugins::render(plugin, &self.config, &chapters)) {
+                try!(self.write_file(Path::new(&plugin.name).join(path), content.as_bytes()));
+            }
+            println!("[*]: The plugin {} rendered the book in {}", plugin.name, self.dest.join(&plugin.name).display());
+        }
 
         Ok(())
     }
@@ -270,10 +546,24 @@ impl MDBook {
         let mut highlight_js = try!(File::create(&theme_dir.join("highlight.js")));
         try!(highlight_js.write_all(theme::HIGHLIGHT_JS));
 
+        // partials/*.hbs
+        try!(fs::create_dir_all(&theme_dir.join("partials")));
+        for &(name, content) in theme::PARTIALS {
+            let mut partial = try!(File::create(&theme_dir.join("partials").join(name).with_extension("hbs")));
+            try!(partial.write_all(content));
+        }
+
         Ok(())
     }
 
+    /// Writes a file of the output, `filename` is relative to the output directory. A dry run only
+    /// records it.
     pub fn write_file<P: AsRef<Path>>(&self, filename: P, content: &[u8]) -> Result<(), Box<Error>> {
+        if self.dry_run {
+            self.add_dry_run_file(filename);
+            return Ok(());
+        }
+
         let path = self.get_dest().join(filename);
         try!(utils::fs::create_file(&path).and_then(|mut file| {
             file.write_all(content)
@@ -301,23 +591,27 @@ impl MDBook {
 
     pub fn read_config(mut self) -> Self {
 
-        let config = BookConfig::new(&self.root)
-                         .read_config(&self.root)
-                         .to_owned();
+        let mut config = BookConfig::new(&self.root);
+        if let Some(ref profile) = self.profile {
+            config.set_profile(profile);
+        }
+        config.read_config(&self.root);
+
+        self.title = config.title.clone();
+        self.description = config.description.clone();
+        self.author = config.author.clone();
 
-        self.title = config.title;
-        self.description = config.description;
-        self.author = config.author;
+        self.dest = config.dest.clone();
+        self.src = config.src.clone();
+        self.theme_path = config.theme_path.clone();
 
-        self.dest = config.dest;
-        self.src = config.src;
-        self.theme_path = config.theme_path;
+        self.config = config;
 
         self
     }
 
     /// You can change the default renderer to another one by using this method. The only requirement
-    /// is for your renderer to implement the [Renderer trait](../../renderer/renderer/trait.Renderer.html)
+    /// is for your renderer to implement the [Renderer trait](../renderer/trait.Renderer.html)
     ///
     /// ```no_run
     /// extern crate mdbook;
@@ -344,13 +638,15 @@ impl MDBook {
     pub fn test(&mut self) -> Result<(), Box<Error>> {
         // read in the chapters
         try!(self.parse_summary());
+        let mut failures = 0;
         for item in self.iter() {
 
             match *item {
                 BookItem::Chapter(_, ref ch) => {
                     if ch.path != PathBuf::new() {
 
-                        let path = self.get_src().join(&ch.path);
+                        let (src, source_path) = self.get_chapter_source(&ch.path);
+                        let path = src.join(&source_path);
 
                         println!("[*]: Testing file: {:?}", path);
 
@@ -366,14 +662,272 @@ impl MDBook {
                                             String::from_utf8_lossy(&output.stdout),
                                             String::from_utf8_lossy(&output.stderr)))) as Box<Error>);
                         }
+
+                        // The code blocks of the other languages, with the runners of `[test.runners]`
+                        for failure in try!(testing::test_chapter(&self.config.test_config, src, &source_path)) {
+                            println!("{}", failure);
+                            failures += 1;
+                        }
+                    }
+                },
+                _ => {},
+            }
+        }
+
+        if failures > 0 {
+            return Err(Box::new(io::Error::new(ErrorKind::Other, format!("{} of the code blocks failed", failures))));
+        }
+        Ok(())
+    }
+
+    /// Adds a chapter to `SUMMARY.md` after the chapter `after`, or after the last numbered chapter, and creates
+    /// its file with the title as heading. Without `path` the file is named after the title, in the directory of
+    /// `after`. Returns the path of the chapter, relative to the source directory.
+    ///
+    /// With `auto-summary` only the file is created, the chapter is at the place of its file in the book.
+    pub fn add_chapter(&self, title: &str, path: Option<&Path>, after: Option<&Path>) -> Result<PathBuf, Box<Error>> {
+        let path = match path {
+            Some(path) => path.to_owned(),
+            None => {
+                let dir = after.and_then(|a| a.parent()).unwrap_or_else(|| Path::new(""));
+                dir.join(format!("{}.md", file_name_of(title)))
+            },
+        };
+        if self.src.join(&path).exists() {
+            return Err(Box::new(io::Error::new(ErrorKind::Other,
+                                               format!("{} already exists", self.src.join(&path).display()))));
+        }
+
+        let file = self.src.join(&path);
+        let content = try!(self.new_chapter_file(&path, title));
+        if self.config.auto_summary {
+            try!(utils::fs::create_file(&file).and_then(|mut f| f.write_all(content.as_bytes())));
+            return Ok(path);
+        }
+
+        let summary_path = self.src.join("SUMMARY.md");
+        let summary = try!(utils::fs::file_to_string(&summary_path).map_err(|e| {
+            io::Error::new(ErrorKind::Other, format!("Could not read {}: {}", summary_path.display(), e))
+        }));
+        let summary = try!(parse::summary::insert_chapter(&summary, title, &path, after));
+        // The chapter can not be added everywhere, e.g. after a suffix chapter when there are none before it
+        try!(parse::summary::parse_summary(&summary)
+            .map_err(|e| io::Error::new(ErrorKind::Other, format!("{}:{}", summary_path.display(), e))));
+
+        try!(utils::fs::create_file(&file).and_then(|mut f| f.write_all(content.as_bytes())));
+        try!(File::create(&summary_path).and_then(|mut f| f.write_all(summary.as_bytes())));
+        Ok(path)
+    }
+
+    // Content of the chapter created in `path`, an html chapter only has the title as heading
+    fn new_chapter_file(&self, path: &Path, title: &str) -> Result<String, Box<Error>> {
+        if path.extension().is_some_and(|ext| ext == "html") {
+            return Ok(format!("<h1>{}</h1>\n", utils::escape_html(title)));
+        }
+        self.new_chapter(title)
+    }
+
+    /// Content of a chapter created by `init`, `add` or `create-missing`: the `templates/chapter.md` file of the
+    /// root of the book with `{{title}}` replaced by the title of the chapter, or the title as heading
+    pub fn new_chapter(&self, title: &str) -> Result<String, Box<Error>> {
+        let template = self.root.join(CHAPTER_TEMPLATE);
+        if !template.is_file() {
+            return Ok(format!("# {}\n", title));
+        }
+
+        let content = try!(utils::fs::file_to_string(&template).map_err(|e| {
+            io::Error::new(ErrorKind::Other, format!("Could not read {}: {}", template.display(), e))
+        }));
+        Ok(content.replace("{{title}}", title).replace("{{ title }}", title))
+    }
+
+    /// Checks the templates of the theme with the renderer, without building the book
+    pub fn check_theme(&mut self) -> Result<(), Box<Error>> {
+        // read in the chapters
+        try!(self.parse_summary());
+        self.renderer.check_theme(self)
+    }
+
+    /// Checks the book without writing anything: `SUMMARY.md` and `book.toml` are parsed, every chapter has a
+    /// file and a page of its own, the links of the chapters point to chapters, anchors and files of the book,
+    /// and the markdown files of the source directory are all used. The problems are returned as warnings.
+    pub fn check(&mut self) -> Result<Vec<Warning>, Box<Error>> {
+        // read in the chapters
+        try!(self.parse_summary());
+        try!(self.check_pages());
+
+        let mut warnings = vec![];
+        for item in self.iter() {
+            match *item {
+                BookItem::Chapter(_, ref ch) |
+                BookItem::Affix(ref ch) if ch.path != PathBuf::new() => {
+                    if !self.get_chapter_file(&ch.path).exists() {
+                        warnings.push(Warning::new(&ch.path, format!("the file of \"{}\" does not exist", ch.name)));
                     }
                 },
                 _ => {},
             }
         }
+
+        warnings.extend(try!(self.renderer.check(self)));
+        Ok(warnings)
+    }
+
+    /// Runs the checker of the `[lint]` table of `book.toml` on every chapter and returns what it
+    /// found
+    pub fn lint(&mut self) -> Result<Vec<LintFinding>, Box<Error>> {
+        let config = match self.config.lint_config {
+            Some(ref config) => config.clone(),
+            None => return Err(Box::new(io::Error::new(ErrorKind::Other, "There is no [lint] command in book.toml"))),
+        };
+
+        let dictionary = match config.dictionary {
+            Some(ref path) => try!(lint::read_dictionary(&self.root.join(path))),
+            None => HashSet::new(),
+        };
+
+        // read in the chapters
+        try!(self.parse_summary());
+
+        let mut findings = vec![];
+        for item in self.iter() {
+            match *item {
+                BookItem::Chapter(_, ref ch) |
+                BookItem::Affix(ref ch) if ch.path != PathBuf::new() => {
+                    println!("[*]: Linting file: {:?}", ch.path);
+                    let (src, source_path) = self.get_chapter_source(&ch.path);
+                    findings.extend(try!(lint::lint_chapter(&config, src, &source_path, &dictionary)));
+                },
+                _ => {},
+            }
+        }
+
+        Ok(findings)
+    }
+
+    /// Builds every git ref of the `[versions]` table of `book.toml` in a sub-directory of the
+    /// destination named after the version, with a version picker on every page. The latest
+    /// version is also copied to `latest/`, where the `index.html` of the destination redirects.
+    /// Returns the warnings of every version.
+    pub fn build_versions(&self) -> Result<Vec<VersionWarnings>, Box<Error>> {
+        let config = match self.config.versions_config {
+            Some(ref config) => config.clone(),
+            None => return Err(Box::new(io::Error::new(ErrorKind::Other, "There is no [versions] table in book.toml"))),
+        };
+
+        let versions: Vec<String> = config.refs.iter().map(|r| versions::version_name(r)).collect();
+        let latest = config.latest_version();
+        if !versions.contains(&latest) {
+            return Err(Box::new(io::Error::new(ErrorKind::Other,
+                                               format!("The latest version `{}` is not in the refs", latest))));
+        }
+
+        try!(fs::create_dir_all(&self.dest));
+        try!(utils::fs::remove_dir_content(&self.dest));
+
+        let mut warnings = vec![];
+        for (git_ref, version) in config.refs.iter().zip(&versions) {
+            println!("[*]: Building version {}", version);
+
+            let checkout = env::temp_dir().join(format!("mdbook-version-{}-{}", process::id(), version));
+            let root = try!(versions::checkout(&self.root, git_ref, &checkout));
+
+            let mut book = MDBook::new(&root);
+            book.profile = self.profile.clone();
+            let mut book = book.read_config().set_dest(&self.dest.join(version));
+            book.version_picker = Some(VersionPicker {
+                current: version.clone(),
+                latest: latest.clone(),
+                versions: versions.clone(),
+            });
+
+            let built = book.build();
+            try!(fs::remove_dir_all(&checkout));
+            try!(built);
+            warnings.push((version.clone(), book.get_warnings().to_vec()));
+        }
+
+        let latest_dir = self.dest.join("latest");
+        try!(fs::create_dir_all(&latest_dir));
+        try!(utils::fs::copy_files_except_ext(&self.dest.join(&latest),
+                                              &latest_dir,
+                                              true,
+                                              &[],
+                                              false,
+                                              &IgnorePatterns::default()));
+
+        let mut redirect = try!(File::create(self.dest.join("index.html")));
+        try!(write!(redirect,
+                    "<!DOCTYPE html>\n<meta charset=\"utf-8\">\n\
+                     <meta http-equiv=\"refresh\" content=\"0; url=latest/index.html\">\n\
+                     <a href=\"latest/index.html\">{}</a>\n",
+                    utils::escape_html(&self.title)));
+
+        Ok(warnings)
+    }
+
+    /// Builds the book and pushes it to the branch of the `[deploy]` table of `book.toml`,
+    /// `gh-pages` by default
+    pub fn deploy(&mut self) -> Result<(), Box<Error>> {
+        try!(self.build());
+
+        let config = &self.config.deploy_config;
+        if try!(deploy::deploy(&self.root, &self.dest, config, &self.title)) {
+            println!("[*]: Pushed the book to the {} branch of {}", config.branch, config.remote);
+        } else {
+            println!("[*]: The book did not change since the last deployment");
+        }
+
         Ok(())
     }
 
+    /// Returns the versions listed on the pages when the book is built by
+    /// [`build_versions()`](#method.build_versions)
+    pub fn get_version_picker(&self) -> Option<&VersionPicker> {
+        self.version_picker.as_ref()
+    }
+
+    /// The posts of the `[blog]` chapter, newest first
+    pub fn get_blog_posts(&self) -> &[Post] {
+        &self.blog_posts
+    }
+
+    /// The books embedded by the chapters of `SUMMARY.md` linking to their root
+    pub fn get_sub_books(&self) -> &[SubBook] {
+        &self.sub_books
+    }
+
+    /// The embedded book containing a chapter, the innermost one
+    pub fn get_sub_book(&self, path: &Path) -> Option<&SubBook> {
+        let path = without_cur_dir(path);
+        self.sub_books
+            .iter()
+            .filter(|b| path.starts_with(&b.dir))
+            .max_by_key(|b| b.dir.components().count())
+    }
+
+    /// The source directory of a chapter and its path in it, the chapters of an embedded book are in the source
+    /// directory of that book
+    pub fn get_chapter_source(&self, path: &Path) -> (&Path, PathBuf) {
+        let path = without_cur_dir(path);
+        match self.get_sub_book(&path) {
+            Some(sub_book) => (&sub_book.src, path.strip_prefix(&sub_book.dir).unwrap_or(&path).to_owned()),
+            None => (&self.src, path),
+        }
+    }
+
+    /// The file of a chapter, see [`get_chapter_source()`](#method.get_chapter_source)
+    pub fn get_chapter_file(&self, path: &Path) -> PathBuf {
+        let (src, path) = self.get_chapter_source(path);
+        src.join(path)
+    }
+
+    /// The content of the file of a chapter. A file that is not valid UTF-8 is decoded as the `invalid-utf8`
+    /// key of `book.toml` says, the second value is then a warning saying how.
+    pub fn read_chapter(&self, path: &Path) -> Result<(String, Option<String>), Box<Error>> {
+        utils::fs::chapter_to_string(&self.get_chapter_file(path), self.config.invalid_utf8)
+    }
+
     pub fn get_root(&self) -> &Path {
         &self.root
     }
@@ -418,6 +972,25 @@ impl MDBook {
         &self.src
     }
 
+    /// The files of the source directory that are not copied to the book and do not rebuild it when they
+    /// change, the patterns of `.mdbookignore` followed by the `ignore` key of `book.toml`
+    pub fn get_ignore_patterns(&self) -> IgnorePatterns {
+        let mut lines = match utils::fs::file_to_string(&self.root.join(IGNORE_FILE)) {
+            Ok(content) => content.lines().map(|l| l.to_owned()).collect(),
+            Err(_) => vec![],
+        };
+        lines.extend(self.config.ignore.iter().cloned());
+        IgnorePatterns::new(&self.src, &lines)
+    }
+
+    /// Adds a directory to the `includes` of the config, where `{{#include}}` looks for the files that are not
+    /// next to the chapter
+    pub fn add_include_dir(mut self, dir: &Path) -> Self {
+        let dir = self.root.join(dir);
+        self.config.includes.push(dir);
+        self
+    }
+
     pub fn set_title(mut self, title: &str) -> Self {
         self.title = title.to_owned();
         self
@@ -474,10 +1047,183 @@ impl MDBook {
         &self.theme_path
     }
 
+    /// Returns the theme directory and the directories of the themes it extends with the `extends` key of its
+    /// `theme.toml`, the first one extending the default theme first
+    pub fn get_theme_dirs(&self) -> Vec<PathBuf> {
+        theme::theme_dirs(&self.theme_path)
+    }
+
+    /// Returns the configuration read from `book.toml` / `book.json`. Renderers can use it to
+    /// look up their own options, e.g. `get_config().html_config` for the HTML renderer.
+    pub fn get_config(&self) -> &BookConfig {
+        &self.config
+    }
+
+    /// Selects a `[profile.name]` table of `book.toml` whose keys replace the other ones, so that
+    /// the same sources can be built with other chapters, variables or options. It has to be
+    /// called before [`read_config()`](#method.read_config).
+    ///
+    /// ```no_run
+    /// # extern crate mdbook;
+    /// # use mdbook::MDBook;
+    /// # use std::path::Path;
+    /// # fn main() {
+    /// let mut book = MDBook::new(Path::new("mybook"))
+    ///                     .set_profile("internal")
+    ///                     .read_config();
+    /// # }
+    /// ```
+    pub fn set_profile(mut self, profile: &str) -> Self {
+        self.profile = Some(profile.to_owned());
+        self
+    }
+
+    /// In strict mode the warnings of the renderer, like links to missing chapters, fail the build
+    pub fn set_strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// The warnings of the last build
+    pub fn get_warnings(&self) -> &[Warning] {
+        &self.warnings
+    }
+
+    /// The time spent in each phase is printed at the end of the build, see [`get_timings()`](#method.get_timings)
+    pub fn set_timings(mut self, timings: bool) -> Self {
+        self.print_timings = timings;
+        self
+    }
+
+    pub fn has_timings(&self) -> bool {
+        self.print_timings
+    }
+
+    /// Builds the book without writing anything: the chapters are parsed, preprocessed and rendered as usual,
+    /// and the files that would be written are kept for [`get_dry_run_files()`](#method.get_dry_run_files) instead
+    pub fn set_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    pub fn is_dry_run(&self) -> bool {
+        self.dry_run
+    }
+
+    /// Records a file of the output written by a renderer without [`write_file()`](#method.write_file), like
+    /// the copies of the static files, during a dry run
+    pub fn add_dry_run_file<P: AsRef<Path>>(&self, filename: P) {
+        self.dry_run_files.borrow_mut().insert(filename.as_ref().to_owned());
+    }
+
+    /// The files of the output the last dry run would have written, relative to the output directory
+    pub fn get_dry_run_files(&self) -> Vec<PathBuf> {
+        self.dry_run_files.borrow().iter().cloned().collect()
+    }
+
+    /// The time spent in each phase of the last build, renderers add their own phases to it
+    pub fn get_timings(&self) -> &Timings {
+        &self.timings
+    }
+
+    /// Records the number of chapters and words a renderer rendered, for the summary of the build
+    pub fn set_word_count(&self, chapters: usize, words: usize) {
+        self.word_count.set((chapters, words));
+    }
+
+    /// The number of chapters and words rendered by the last build
+    pub fn get_word_count(&self) -> (usize, usize) {
+        self.word_count.get()
+    }
+
+    /// Reads `SUMMARY.md` and the books it embeds without building the book, e.g. to know the source
+    /// directories of the embedded books. [`build()`](#method.build) reads it again.
+    pub fn read_summary(&mut self) -> Result<(), Box<Error>> {
+        self.parse_summary()
+    }
+
     // Construct book
     fn parse_summary(&mut self) -> Result<(), Box<Error>> {
         // When append becomes stable, use self.content.append() ...
-        self.content = try!(parse::construct_bookitems(&self.src.join("SUMMARY.md")));
+        self.content = if self.config.auto_summary {
+            let items = try!(autosummary::generate_summary(&self.src, &self.get_ignore_patterns()));
+            debug!("[*]: Summary generated from the files:\n{}", parse::summary::summary_to_markdown(&items));
+            items
+        } else {
+            try!(parse::construct_bookitems(&self.src.join("SUMMARY.md")))
+        };
+
+        // The chapters of the books embedded by linking to their root
+        self.sub_books = try!(subbook::embed_sub_books(&mut self.content, &self.src));
+        if !self.sub_books.is_empty() {
+            parse::summary::renumber(&mut self.content, "");
+        }
+
+        if !self.config.exclude_chapters.is_empty() {
+            let excluded = &self.config.exclude_chapters;
+            let content = ::std::mem::take(&mut self.content);
+            self.content = parse::summary::remove_chapters(content, &|path| excluded.iter().any(|e| path.starts_with(e)));
+        }
+
+        // The posts of the blog, newest first
+        if let Some(ref blog) = self.config.blog_config {
+            self.blog_posts = blog::sort_posts(&mut self.content, blog, &self.src);
+            if self.blog_posts.is_empty() {
+                warn!("[*]: [blog] {} is not a chapter of SUMMARY.md with sub-chapters", blog.chapter.display());
+            }
+            parse::summary::renumber(&mut self.content, "");
+        }
         Ok(())
     }
 }
+
+// Without the `./` of `SUMMARY.md`
+fn without_cur_dir(path: &Path) -> PathBuf {
+    path.components().filter(|c| *c != Component::CurDir).collect()
+}
+
+// `My New Chapter` => `my-new-chapter`
+fn file_name_of(title: &str) -> String {
+    let mut name = String::new();
+    for c in title.chars().flat_map(|c| c.to_lowercase()) {
+        if c.is_alphanumeric() {
+            name.push(c);
+        } else if !name.is_empty() && !name.ends_with('-') {
+            name.push('-');
+        }
+    }
+    let name = name.trim_end_matches('-');
+    if name.is_empty() { "chapter".to_owned() } else { name.to_owned() }
+}
+
+
+#[cfg(test)]
+mod tests {
+    extern crate tempdir;
+
+    use super::*;
+
+    fn write(path: &Path, content: &str) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        File::create(path).unwrap().write_all(content.as_bytes()).unwrap();
+    }
+
+    #[test]
+    fn it_reports_the_pages_written_twice_before_writing() {
+        let tmp = tempdir::TempDir::new("mdbook").expect("Could not create a temp dir");
+        let root = tmp.path();
+        write(&root.join("src/SUMMARY.md"), "# Summary\n\n- [Intro](intro.md)\n- [Printing](print.md)\n");
+        write(&root.join("src/intro.md"), "+++\ntags = [\"Cli\"]\n+++\n# Intro\n");
+        write(&root.join("src/print.md"), "# Printing\n");
+        write(&root.join("src/tags/cli.html"), "<p>Static</p>");
+
+        let mut book = MDBook::new(root).read_config();
+        let error = book.build().unwrap_err().to_string();
+
+        assert_eq!(error,
+                   format!("2 pages of the book would be written twice:\n    print.html (\"Printing\" and the print \
+                            version)\n    tags/cli.html (the page of the tag `Cli` and the static file {})",
+                           root.join("src").join("tags/cli.html").display()));
+        assert!(fs::read_dir(root.join("book")).unwrap().next().is_none());
+    }
+}
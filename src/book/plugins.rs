@@ -0,0 +1,330 @@
+use std::path::{Path, PathBuf, Component};
+use std::process::{Command, Stdio};
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::io::{self, Write};
+use std::thread;
+use std::time::Duration;
+use toml;
+
+use serde_json;
+use serde_json::value::ToJson;
+
+use book::bookconfig::BookConfig;
+use parse;
+use utils::process::wait_with_timeout;
+
+/// What a plugin does in the build
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PluginKind {
+    /// Transforms the markdown of every chapter before the renderer
+    Preprocessor,
+    /// Writes its own output from the chapters, in a directory of the destination named after the plugin
+    Renderer,
+}
+
+/// A WebAssembly plugin, read from a `[plugins.name]` table of `book.toml`
+///
+/// Plugins are WASI programs run by a WebAssembly runtime, so the same `.wasm` file works on every
+/// platform and only has access to what mdBook writes to its standard input. The request is a JSON
+/// object on the standard input, preprocessors print the new markdown of the chapter and renderers a
+/// JSON object mapping the paths of their files to their content.
+#[derive(Debug, Clone)]
+pub struct PluginConfig {
+    pub name: String,
+    /// The WebAssembly module, relative to the root of the book
+    pub wasm: PathBuf,
+    pub kind: PluginKind,
+    /// The runtime and its arguments, the path of the module is added at the end
+    pub runtime: Vec<String>,
+    /// Seconds after which the plugin is killed
+    pub timeout: u64,
+}
+
+impl PluginConfig {
+    /// Returns `None` when the plugin has no `wasm` module or an unknown `kind`
+    pub fn from_btreemap(name: &str, config: &BTreeMap<String, toml::Value>) -> Option<Self> {
+        let wasm = match config.get("wasm").and_then(|v| v.as_str()) {
+            Some(wasm) => PathBuf::from(wasm),
+            None => {
+                warn!("[*]: [plugins.{}] needs a `wasm` module", name);
+                return None;
+            },
+        };
+
+        let kind = match config.get("kind").and_then(|v| v.as_str()).unwrap_or("preprocessor") {
+            "preprocessor" => PluginKind::Preprocessor,
+            "renderer" => PluginKind::Renderer,
+            kind => {
+                warn!("[*]: [plugins.{}] has an unknown kind `{}`, expected `preprocessor` or `renderer`", name, kind);
+                return None;
+            },
+        };
+
+        let runtime: Vec<String> = match config.get("runtime") {
+            Some(toml::Value::String(runtime)) => runtime.split_whitespace().map(|s| s.to_owned()).collect(),
+            Some(toml::Value::Array(runtime)) => {
+                runtime.iter().filter_map(|v| v.as_str()).map(|s| s.to_owned()).collect()
+            },
+            _ => vec![],
+        };
+
+        Some(PluginConfig {
+            name: name.to_owned(),
+            wasm: wasm,
+            kind: kind,
+            runtime: if runtime.is_empty() { vec!["wasmtime".to_owned(), "run".to_owned()] } else { runtime },
+            timeout: config.get("timeout").and_then(|v| v.as_integer()).map_or(30, |a| a as u64),
+        })
+    }
+
+    /// Reads every plugin of the `[plugins]` table
+    pub fn from_plugins_table(config: &BTreeMap<String, toml::Value>) -> Vec<Self> {
+        config.iter()
+            .filter_map(|(name, plugin)| plugin.as_table().and_then(|t| PluginConfig::from_btreemap(name, t)))
+            .collect()
+    }
+}
+
+/// A chapter, as sent to the plugins
+pub struct PluginChapter<'a> {
+    pub name: &'a str,
+    /// Path of the chapter, relative to the source directory
+    pub path: &'a Path,
+    pub section: Option<&'a str>,
+    /// The markdown of the chapter, without its front matter
+    pub content: &'a str,
+}
+
+impl<'a> PluginChapter<'a> {
+    fn to_json(&self) -> serde_json::Value {
+        let mut chapter = serde_json::Map::new();
+        chapter.insert("name".to_owned(), self.name.to_json());
+        chapter.insert("path".to_owned(), self.path.to_string_lossy().to_json());
+        chapter.insert("section".to_owned(), self.section.to_json());
+        chapter.insert("content".to_owned(), self.content.to_json());
+        serde_json::Value::Object(chapter)
+    }
+}
+
+/// Metadata of the book sent with every request
+fn book_json(config: &BookConfig) -> serde_json::Value {
+    let mut book = serde_json::Map::new();
+    book.insert("title".to_owned(), config.title.to_json());
+    book.insert("authors".to_owned(), config.get_authors().to_json());
+    book.insert("description".to_owned(), config.description.to_json());
+    serde_json::Value::Object(book)
+}
+
+/// Runs a preprocessor on a chapter for `renderer`, returns the new markdown of the chapter
+pub fn preprocess(plugin: &PluginConfig,
+                  config: &BookConfig,
+                  renderer: &str,
+                  chapter: &PluginChapter)
+                  -> Result<String, Box<Error>> {
+    let mut request = serde_json::Map::new();
+    request.insert("kind".to_owned(), "preprocess".to_json());
+    request.insert("renderer".to_owned(), renderer.to_json());
+    request.insert("book".to_owned(), book_json(config));
+    request.insert("chapter".to_owned(), chapter.to_json());
+
+    run(plugin, config.get_root(), &serde_json::Value::Object(request))
+}
+
+/// Runs a renderer on the chapters, returns the files to write in its directory
+pub fn render(plugin: &PluginConfig,
+              config: &BookConfig,
+              chapters: &[PluginChapter])
+              -> Result<BTreeMap<PathBuf, String>, Box<Error>> {
+    let output = try!(run(plugin, config.get_root(), &render_request(config, chapters)));
+    parse_files(plugin, &output)
+}
+
+fn render_request(config: &BookConfig, chapters: &[PluginChapter]) -> serde_json::Value {
+    let mut request = serde_json::Map::new();
+    request.insert("kind".to_owned(), "render".to_json());
+    request.insert("book".to_owned(), book_json(config));
+    // Renderers also get the parsed markdown, so that they don't need a markdown parser
+    let chapters = chapters.iter()
+        .map(|c| {
+            let mut chapter = c.to_json();
+            if let serde_json::Value::Object(ref mut chapter) = chapter {
+                chapter.insert("events".to_owned(), parse::events_json(c.content, &config.markdown_config));
+            }
+            chapter
+        })
+        .collect();
+    request.insert("chapters".to_owned(), serde_json::Value::Array(chapters));
+    serde_json::Value::Object(request)
+}
+
+/// Runs the plugin with the JSON `request` on its standard input and returns what it printed
+pub fn run(plugin: &PluginConfig, root: &Path, request: &serde_json::Value) -> Result<String, Box<Error>> {
+    let wasm = root.join(&plugin.wasm);
+
+    let mut child = try!(Command::new(&plugin.runtime[0])
+        .args(&plugin.runtime[1..])
+        .arg(&wasm)
+        .current_dir(root)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| {
+            io::Error::new(e.kind(),
+                           format!("Could not run the plugin {} with `{}`: {}", plugin.name, plugin.runtime[0], e))
+        }));
+
+    // Written while the output is read, a request larger than the pipe would block both programs otherwise. A
+    // plugin exiting without reading all of it only fails by its status.
+    let writer = child.stdin.take().map(|mut stdin| {
+        let request = request.to_string();
+        thread::spawn(move || {
+            let _ = stdin.write_all(request.as_bytes());
+        })
+    });
+
+    let (success, stdout, stderr) = try!(wait_with_timeout(child, Duration::from_secs(plugin.timeout))
+        .map_err(|e| io::Error::new(io::ErrorKind::Other, format!("The plugin {} failed: {}", plugin.name, e))));
+    if let Some(writer) = writer {
+        let _ = writer.join();
+    }
+    if !success {
+        return Err(Box::new(io::Error::new(io::ErrorKind::Other,
+                                           format!("The plugin {} failed:\n{}", plugin.name, stderr))));
+    }
+
+    Ok(stdout)
+}
+
+/// Parses the output of a renderer plugin, the paths of the files have to stay in its directory
+pub fn parse_files(plugin: &PluginConfig, output: &str) -> Result<BTreeMap<PathBuf, String>, Box<Error>> {
+    let files: BTreeMap<String, String> = try!(serde_json::from_str(output).map_err(|e| {
+        io::Error::new(io::ErrorKind::Other,
+                       format!("The plugin {} did not print a JSON object of files: {}", plugin.name, e))
+    }));
+
+    let mut parsed = BTreeMap::new();
+    for (path, content) in files {
+        let path = PathBuf::from(path);
+        let inside = path.components().all(|c| match c {
+            Component::Normal(_) => true,
+            _ => false,
+        });
+        if !inside {
+            return Err(Box::new(io::Error::new(io::ErrorKind::Other,
+                                               format!("The plugin {} can not write {}", plugin.name, path.display()))));
+        }
+        parsed.insert(path, content);
+    }
+    Ok(parsed)
+}
+
+
+// ------------------------------------------------------------------------------------------------
+// ------------------------------------------------------------------------------------------------
+
+// tests
+
+#[cfg(test)]
+mod tests {
+    extern crate tempdir;
+
+    use super::*;
+    use std::fs::File;
+
+    fn parse(toml: &str) -> Vec<PluginConfig> {
+        let config = toml::Parser::new(toml).parse().unwrap();
+        PluginConfig::from_plugins_table(config["plugins"].as_table().unwrap())
+    }
+
+    #[test]
+    fn it_parses_plugins() {
+        let plugins = parse(r#"
+            [plugins.emoji]
+            wasm = "plugins/emoji.wasm"
+
+            [plugins.epub]
+            wasm = "plugins/epub.wasm"
+            kind = "renderer"
+            runtime = "wasmer run --"
+            timeout = 5
+
+            [plugins.broken]
+            kind = "renderer"
+        "#);
+
+        assert_eq!(plugins.len(), 2);
+        assert_eq!(plugins[0].name, "emoji");
+        assert_eq!(plugins[0].kind, PluginKind::Preprocessor);
+        assert_eq!(plugins[0].runtime, vec!["wasmtime", "run"]);
+        assert_eq!(plugins[0].timeout, 30);
+        assert_eq!(plugins[1].wasm, PathBuf::from("plugins/epub.wasm"));
+        assert_eq!(plugins[1].kind, PluginKind::Renderer);
+        assert_eq!(plugins[1].runtime, vec!["wasmer", "run", "--"]);
+        assert_eq!(plugins[1].timeout, 5);
+    }
+
+    #[test]
+    fn it_runs_plugins() {
+        let dir = tempdir::TempDir::new("mdbook-plugins").unwrap();
+        // Any runtime works, `sh` runs a script standing in for a module
+        File::create(dir.path().join("plugin.sh")).unwrap().write_all(b"tr a-z A-Z").unwrap();
+        File::create(dir.path().join("broken.sh")).unwrap().write_all(b"echo oops >&2; exit 1").unwrap();
+        let mut plugin = parse("[plugins.upper]\nwasm = \"plugin.sh\"\nruntime = \"sh\"").remove(0);
+
+        let request = serde_json::from_str(r#"{"chapter": "intro"}"#).unwrap();
+        assert_eq!(run(&plugin, dir.path(), &request).unwrap(), r#"{"CHAPTER":"INTRO"}"#);
+
+        plugin.wasm = PathBuf::from("broken.sh");
+        let err = run(&plugin, dir.path(), &request).unwrap_err();
+        assert!(err.to_string().contains("The plugin upper failed:\noops"));
+    }
+
+    #[test]
+    fn it_runs_plugins_with_large_requests() {
+        let dir = tempdir::TempDir::new("mdbook-plugins").unwrap();
+        File::create(dir.path().join("plugin.sh")).unwrap().write_all(b"cat").unwrap();
+        File::create(dir.path().join("slow.sh")).unwrap().write_all(b"sleep 5").unwrap();
+        let mut plugin = parse("[plugins.cat]\nwasm = \"plugin.sh\"\nruntime = \"sh\"\ntimeout = 1").remove(0);
+
+        // Larger than the buffer of the pipes
+        let chapter = "word ".repeat(400000);
+        let request = serde_json::Value::String(chapter.clone());
+        assert_eq!(run(&plugin, dir.path(), &request).unwrap(), request.to_string());
+
+        plugin.wasm = PathBuf::from("slow.sh");
+        let err = run(&plugin, dir.path(), &request).unwrap_err();
+        assert!(err.to_string().contains("The plugin cat failed: it was still running after 1 seconds"));
+    }
+
+    #[test]
+    fn it_sends_the_events_of_the_chapters_to_renderers() {
+        let config = BookConfig::new(Path::new("."));
+        let chapter = PluginChapter {
+            name: "Intro",
+            path: Path::new("intro.md"),
+            section: Some("1."),
+            content: "Hello *world*",
+        };
+
+        let request = render_request(&config, &[chapter]);
+        let chapter = &request.find("chapters").and_then(|c| c.as_array()).unwrap()[0];
+        assert_eq!(chapter.find("content").and_then(|c| c.as_str()), Some("Hello *world*"));
+        let events = chapter.find("events").and_then(|e| e.as_array()).unwrap();
+        assert_eq!(events.len(), 6);
+        assert_eq!(events[2], serde_json::from_str(r#"{"type": "start", "tag": "emphasis"}"#).unwrap());
+    }
+
+    #[test]
+    fn it_parses_the_files_of_renderers() {
+        let plugin = parse("[plugins.txt]\nwasm = \"txt.wasm\"\nkind = \"renderer\"").remove(0);
+
+        let files = parse_files(&plugin, r#"{"index.txt": "Index", "chapters/intro.txt": "Intro"}"#).unwrap();
+        assert_eq!(files.keys().collect::<Vec<_>>(), vec![Path::new("chapters/intro.txt"), Path::new("index.txt")]);
+
+        assert!(parse_files(&plugin, r#"{"../index.txt": ""}"#).is_err());
+        assert!(parse_files(&plugin, r#"{"/etc/passwd": ""}"#).is_err());
+        assert!(parse_files(&plugin, "not json").is_err());
+    }
+}
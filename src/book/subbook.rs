@@ -0,0 +1,189 @@
+use std::path::{Component, Path, PathBuf};
+use std::error::Error;
+use std::io;
+
+use book::autosummary;
+use book::bookitem::{BookItem, Chapter};
+use book::MDBook;
+use parse;
+use utils::ignore::IgnorePatterns;
+
+/// A book embedded in another one by a chapter of `SUMMARY.md` linking to its root, e.g.
+/// `- [Networking](../../networking)`. Its chapters are in the directory of the book named after its root.
+#[derive(Debug, Clone)]
+pub struct SubBook {
+    /// Directory of its chapters in the book embedding it, e.g. `networking`
+    pub dir: PathBuf,
+    pub root: PathBuf,
+    pub src: PathBuf,
+    /// The files of its source directory that are not copied
+    pub ignore: IgnorePatterns,
+}
+
+/// Whether the path of a chapter is the root of a book instead of a file: a directory with a `book.toml`, a
+/// `book.json` or a `src/SUMMARY.md`
+pub fn is_book_root(path: &Path) -> bool {
+    path.is_dir() &&
+    (path.join("book.toml").is_file() || path.join("book.json").is_file() || path.join("src/SUMMARY.md").is_file())
+}
+
+/// Replaces the chapters of `SUMMARY.md` linking to the root of a book by the chapters of that book, with its
+/// config. The first chapter of the embedded book is the page of the chapter linking to it and the other ones
+/// are its sub-chapters, their paths start with the directory of the embedded book. Embedded books can embed
+/// other books too.
+///
+/// Returns the embedded books, the sections of the chapters have to be numbered again.
+pub fn embed_sub_books(items: &mut [BookItem], src: &Path) -> Result<Vec<SubBook>, Box<Error>> {
+    let mut sub_books = vec![];
+    try!(embed(items, src, Path::new(""), &mut vec![], &mut sub_books));
+    Ok(sub_books)
+}
+
+// `dir` is the directory of the chapters of `items` in the book at the top, and `roots` the books being
+// embedded, to detect a book embedding itself
+fn embed(items: &mut [BookItem],
+         src: &Path,
+         dir: &Path,
+         roots: &mut Vec<PathBuf>,
+         sub_books: &mut Vec<SubBook>)
+         -> Result<(), Box<Error>> {
+    for item in items.iter_mut() {
+        let ch = match *item {
+            BookItem::Chapter(_, ref mut ch) => ch,
+            _ => continue,
+        };
+
+        let root = src.join(&ch.path);
+        if ch.path == PathBuf::new() || !is_book_root(&root) {
+            try!(embed(&mut ch.sub_items, src, dir, roots, sub_books));
+            continue;
+        }
+
+        let name = ch.path
+            .file_name()
+            .map_or_else(|| PathBuf::from("book"), PathBuf::from);
+        let canonical = root.canonicalize().unwrap_or_else(|_| root.clone());
+        if roots.contains(&canonical) {
+            return Err(Box::new(io::Error::new(io::ErrorKind::Other,
+                                               format!("{} embeds itself", root.display()))));
+        }
+        if sub_books.iter().any(|b| b.dir == dir.join(&name)) {
+            return Err(Box::new(io::Error::new(io::ErrorKind::Other,
+                                               format!("Two embedded books are in the directory `{}`",
+                                                       dir.join(&name).display()))));
+        }
+
+        let book = MDBook::new(&canonical).read_config();
+        let mut book_items = if book.get_config().auto_summary {
+            try!(autosummary::generate_summary(book.get_src(), &book.get_ignore_patterns()))
+        } else {
+            try!(parse::construct_bookitems(&book.get_src().join("SUMMARY.md")))
+        };
+        if !book.get_config().exclude_chapters.is_empty() {
+            let excluded = &book.get_config().exclude_chapters;
+            book_items = parse::summary::remove_chapters(book_items,
+                                                         &|path| excluded.iter().any(|e| path.starts_with(e)));
+        }
+
+        roots.push(canonical.clone());
+        try!(embed(&mut book_items, book.get_src(), &dir.join(&name), roots, sub_books));
+        roots.pop();
+        sub_books.push(SubBook {
+            dir: dir.join(&name),
+            root: canonical,
+            src: book.get_src().to_owned(),
+            ignore: book.get_ignore_patterns(),
+        });
+
+        // The prefix and suffix chapters are sub-chapters like the other ones
+        let mut chapters: Vec<Chapter> = book_items.into_iter()
+            .filter_map(|item| match item {
+                BookItem::Chapter(_, ch) | BookItem::Affix(ch) => Some(ch),
+                BookItem::Spacer => None,
+            })
+            .collect();
+        for chapter in &mut chapters {
+            move_chapter(chapter, &name);
+        }
+
+        let mut sub_items = vec![];
+        ch.path = PathBuf::new();
+        if !chapters.is_empty() {
+            let first = chapters.remove(0);
+            ch.path = first.path;
+            sub_items.extend(first.sub_items);
+        }
+        sub_items.extend(chapters.into_iter().map(|ch| BookItem::Chapter("0".to_owned(), ch)));
+        sub_items.append(&mut ch.sub_items);
+        ch.sub_items = sub_items;
+    }
+    Ok(())
+}
+
+// Puts the chapter and its sub-chapters in the directory `dir`
+fn move_chapter(ch: &mut Chapter, dir: &Path) {
+    if ch.path != PathBuf::new() {
+        ch.path = dir.join(ch.path.components().filter(|c| *c != Component::CurDir).collect::<PathBuf>());
+    }
+    for item in &mut ch.sub_items {
+        if let BookItem::Chapter(_, ref mut sub) = *item {
+            move_chapter(sub, dir);
+        }
+    }
+}
+
+
+// ---------------------------------------------------------------------------------------------------------------
+//      Tests
+//
+
+#[cfg(test)]
+mod tests {
+    extern crate tempdir;
+
+    use super::*;
+    use std::fs::{self, File};
+    use std::io::Write;
+
+    fn write(path: &Path, content: &str) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        File::create(path).unwrap().write_all(content.as_bytes()).unwrap();
+    }
+
+    #[test]
+    fn it_embeds_the_chapters_of_the_sub_books() {
+        let tmp = tempdir::TempDir::new("mdbook").expect("Could not create a temp dir");
+        write(&tmp.path().join("main/src/SUMMARY.md"),
+              "# Summary\n\n- [Intro](intro.md)\n- [Networking](../../networking)\n    - [Team](team.md)\n");
+        write(&tmp.path().join("networking/book.toml"), "src = \"docs\"\nexclude-chapters = [\"drafts\"]");
+        write(&tmp.path().join("networking/docs/SUMMARY.md"),
+              "# Summary\n\n[Overview](README.md)\n\n- [Sockets](./sockets.md)\n    - [TCP](tcp.md)\n\
+               - [Drafts](drafts/next.md)\n");
+
+        let src = tmp.path().join("main/src");
+        let mut items = parse::construct_bookitems(&src.join("SUMMARY.md")).unwrap();
+        let sub_books = embed_sub_books(&mut items, &src).unwrap();
+
+        assert_eq!(sub_books.len(), 1);
+        assert_eq!(sub_books[0].dir, Path::new("networking"));
+        assert_eq!(sub_books[0].src, tmp.path().canonicalize().unwrap().join("networking/docs"));
+
+        let chapters: Vec<_> = ::book::bookitem::BookItems { items: &items, current_index: 0, stack: vec![] }
+            .filter_map(|item| match *item {
+                BookItem::Chapter(_, ref ch) => Some((ch.name.clone(), ch.path.to_str().unwrap().to_owned())),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(chapters,
+                   vec![("Intro".to_owned(), "intro.md".to_owned()),
+                        ("Networking".to_owned(), "networking/README.md".to_owned()),
+                        ("Sockets".to_owned(), "networking/sockets.md".to_owned()),
+                        ("TCP".to_owned(), "networking/tcp.md".to_owned()),
+                        ("Team".to_owned(), "team.md".to_owned())]);
+
+        // A book embedding itself
+        write(&tmp.path().join("networking/docs/SUMMARY.md"), "# Summary\n\n- [Loop](../../networking)\n");
+        let mut items = parse::construct_bookitems(&src.join("SUMMARY.md")).unwrap();
+        assert!(embed_sub_books(&mut items, &src).is_err());
+    }
+}
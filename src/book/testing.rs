@@ -0,0 +1,326 @@
+use std::env;
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{self, Command, Stdio};
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::time::Duration;
+use toml;
+
+use pulldown_cmark::{Parser, Event, Tag};
+
+use utils;
+use utils::process::wait_with_timeout;
+
+/// Options of `mdbook test`, read from the `[test]` table of `book.toml`
+#[derive(Debug, Clone)]
+pub struct TestConfig {
+    /// The commands testing the code blocks of other languages than Rust, by language
+    pub runners: BTreeMap<String, TestRunner>,
+    /// Seconds after which a code block is stopped
+    pub timeout: u64,
+}
+
+/// The command testing the code blocks of a language
+#[derive(Debug, Clone, PartialEq)]
+pub struct TestRunner {
+    /// The program and its arguments, `{file}` is replaced by the path of the code block. Without
+    /// `{file}` the path is added after the arguments.
+    pub command: Vec<String>,
+    /// Name of the file the code block is written to, `snippet.<language>` by default
+    pub file: Option<String>,
+}
+
+/// A code block that failed its test
+#[derive(Debug, Clone, PartialEq)]
+pub struct SnippetFailure {
+    /// Path of the chapter, relative to the source directory
+    pub path: PathBuf,
+    /// Line of the opening fence of the code block
+    pub line: usize,
+    pub language: String,
+    pub message: String,
+}
+
+// A code block tested with a runner, with the output expected from it
+#[derive(Debug, PartialEq)]
+struct Snippet {
+    language: String,
+    code: String,
+    line: usize,
+    expected_output: Option<String>,
+}
+
+impl TestConfig {
+    pub fn new() -> Self {
+        TestConfig {
+            runners: BTreeMap::new(),
+            timeout: 10,
+        }
+    }
+
+    pub fn parse_from_btreemap(&mut self, config: &BTreeMap<String, toml::Value>) -> &mut Self {
+
+        // `python = "python3 {file}"` or `go = { command = "go run {file}", file = "main.go" }`
+        if let Some(a) = config.get("runners").and_then(|v| v.as_table()) {
+            for (language, runner) in a {
+                let (command, file) = match *runner {
+                    toml::Value::String(ref command) => (Some(&command[..]), None),
+                    toml::Value::Table(ref runner) => {
+                        (runner.get("command").and_then(|c| c.as_str()),
+                         runner.get("file").and_then(|f| f.as_str()).map(|f| f.to_owned()))
+                    },
+                    _ => (None, None),
+                };
+
+                match command {
+                    Some(command) if !command.trim().is_empty() => {
+                        self.runners.insert(language.clone(),
+                                            TestRunner {
+                                                command: command.split_whitespace().map(|s| s.to_owned()).collect(),
+                                                file: file,
+                                            });
+                    },
+                    _ => warn!("[*]: [test.runners] needs a command for `{}`", language),
+                }
+            }
+        }
+
+        if let Some(a) = config.get("timeout").and_then(|v| v.as_integer()) {
+            if a > 0 {
+                self.timeout = a as u64;
+            }
+        }
+
+        self
+    }
+}
+
+impl fmt::Display for SnippetFailure {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}: `{}` code block {}", self.path.display(), self.line, self.language, self.message)
+    }
+}
+
+/// Tests the code blocks of a chapter that have a runner, `path` is relative to `src`. A block is
+/// tested by running its runner on it, which has to succeed. When the block is followed by an
+/// `output` block, like ```` ```text,output ````, it also has to print that output. Blocks
+/// annotated with `ignore` are not tested.
+pub fn test_chapter(config: &TestConfig, src: &Path, path: &Path) -> Result<Vec<SnippetFailure>, Box<Error>> {
+    if config.runners.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let mut markdown = String::new();
+    try!(try!(File::open(src.join(path))).read_to_string(&mut markdown));
+
+    let mut failures = vec![];
+    for (i, snippet) in snippets(&markdown).iter().enumerate() {
+        let runner = match config.runners.get(&snippet.language) {
+            Some(runner) => runner,
+            None => continue,
+        };
+
+        let dir = env::temp_dir().join(format!("mdbook-test-{}-{}", process::id(), i));
+        let result = fs::create_dir_all(&dir)
+            .map_err(|e| e.to_string())
+            .and_then(|_| run_snippet(runner, &dir, snippet, Duration::from_secs(config.timeout)));
+        let _ = fs::remove_dir_all(&dir);
+
+        if let Err(message) = result {
+            failures.push(SnippetFailure {
+                path: path.to_owned(),
+                line: snippet.line,
+                language: snippet.language.clone(),
+                message: message,
+            });
+        }
+    }
+
+    Ok(failures)
+}
+
+fn run_snippet(runner: &TestRunner, dir: &Path, snippet: &Snippet, timeout: Duration) -> Result<(), String> {
+    let file = dir.join(runner.file.clone().unwrap_or_else(|| format!("snippet.{}", snippet.language)));
+    try!(File::create(&file)
+        .and_then(|mut f| f.write_all(snippet.code.as_bytes()))
+        .map_err(|e| e.to_string()));
+
+    let file = file.to_string_lossy().into_owned();
+    let mut program: Vec<String> = runner.command.iter().map(|arg| arg.replace("{file}", &file)).collect();
+    if !runner.command.iter().any(|arg| arg.contains("{file}")) {
+        program.push(file);
+    }
+
+    let child = try!(Command::new(&program[0])
+        .args(&program[1..])
+        .current_dir(dir)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("could not run `{}`: {}", program[0], e)));
+    let (success, stdout, stderr) = try!(wait_with_timeout(child, timeout));
+
+    if !success {
+        return Err(format!("failed:\n{}{}", stdout, stderr));
+    }
+
+    match snippet.expected_output {
+        Some(ref expected) if normalize_output(expected) != normalize_output(&stdout) => {
+            Err(format!("printed:\n{}\ninstead of:\n{}", stdout.trim_end(), expected.trim_end()))
+        },
+        _ => Ok(()),
+    }
+}
+
+// The trailing whitespace of the lines and the empty lines at the end are not compared
+fn normalize_output(output: &str) -> String {
+    output.lines().map(|l| l.trim_end()).collect::<Vec<_>>().join("\n").trim_end().to_owned()
+}
+
+// The line of the block starting at `offset`, pulldown-cmark gives the offset of the empty lines before it
+fn line_of(markdown: &str, offset: usize) -> usize {
+    let rest = &markdown[offset..];
+    let start = offset + rest.len() - rest.trim_start().len();
+    markdown[..start].matches('\n').count() + 1
+}
+
+// The code blocks of a chapter, except the `output` and `ignore` ones, with the output block that follows them
+fn snippets(markdown: &str) -> Vec<Snippet> {
+    let mut snippets: Vec<Snippet> = vec![];
+    let mut parser = Parser::new_ext(markdown, utils::markdown_options());
+    // The code block being read, and whether it is an output block
+    let mut block: Option<(Snippet, bool)> = None;
+    // Whether the last event closed a tested code block, an output block has to come right after it
+    let mut after_snippet = false;
+
+    loop {
+        let offset = parser.get_offset();
+        let event = match parser.next() {
+            Some(event) => event,
+            None => break,
+        };
+
+        match event {
+            Event::Start(Tag::CodeBlock(ref info)) => {
+                let mut tokens = info.split(|c: char| c == ',' || c.is_whitespace()).filter(|t| !t.is_empty());
+                let language = tokens.next().unwrap_or_default().to_owned();
+                let attributes: Vec<&str> = tokens.collect();
+                let is_output = attributes.contains(&"output") || language == "output";
+
+                let snippet = Snippet {
+                    language: language,
+                    code: String::new(),
+                    line: line_of(markdown, offset),
+                    expected_output: None,
+                };
+                block = if is_output && !after_snippet {
+                    None
+                } else if is_output || !attributes.contains(&"ignore") {
+                    Some((snippet, is_output))
+                } else {
+                    None
+                };
+                after_snippet = false;
+            },
+            Event::Text(ref text) => {
+                if let Some((ref mut snippet, _)) = block {
+                    snippet.code.push_str(text);
+                }
+            },
+            Event::End(Tag::CodeBlock(_)) => {
+                match block.take() {
+                    Some((output, true)) => {
+                        if let Some(snippet) = snippets.last_mut() {
+                            snippet.expected_output = Some(output.code);
+                        }
+                    },
+                    Some((snippet, false)) => {
+                        snippets.push(snippet);
+                        after_snippet = true;
+                    },
+                    None => {},
+                }
+            },
+            _ => after_snippet = false,
+        }
+    }
+
+    snippets
+}
+
+
+// ---------------------------------------------------------------------------------------------------------------
+//      Tests
+//
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_parses_the_runners() {
+        let text = "timeout = 5\n\n[runners]\npython = \"python3 -B {file}\"\n\
+                    go = { command = \"go run\", file = \"main.go\" }\nruby = 1";
+        let table = toml::Parser::new(text).parse().unwrap();
+        let mut config = TestConfig::new();
+        config.parse_from_btreemap(&table);
+
+        assert_eq!(config.timeout, 5);
+        assert_eq!(config.runners.len(), 2);
+        assert_eq!(config.runners["python"],
+                   TestRunner {
+                       command: vec!["python3".to_owned(), "-B".to_owned(), "{file}".to_owned()],
+                       file: None,
+                   });
+        assert_eq!(config.runners["go"].file, Some("main.go".to_owned()));
+    }
+
+    #[test]
+    fn it_finds_the_snippets_and_their_output() {
+        let markdown = "# Shell\n\n```sh\necho hi\n```\n\n```text,output\nhi\n```\n\n\
+                        ```sh,ignore\nrm -rf /\n```\n\nText\n\n```output\nnot after a block\n```\n\n```python\n```";
+        let snippets = snippets(markdown);
+
+        assert_eq!(snippets,
+                   vec![Snippet {
+                            language: "sh".to_owned(),
+                            code: "echo hi\n".to_owned(),
+                            line: 3,
+                            expected_output: Some("hi\n".to_owned()),
+                        },
+                        Snippet {
+                            language: "python".to_owned(),
+                            code: String::new(),
+                            line: 21,
+                            expected_output: None,
+                        }]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn it_runs_the_snippets() {
+        let src = env::temp_dir().join(format!("mdbook-test-chapter-{}", process::id()));
+        fs::create_dir_all(&src).unwrap();
+        File::create(src.join("shell.md"))
+            .unwrap()
+            .write_all(b"```sh\necho hi\n```\n```output\nhi\n```\n\n```sh\necho bye\n```\n```output\nhi\n```\n\n\
+                         ```sh\nexit 3\n```\n\n```perl\nnot run\n```\n")
+            .unwrap();
+
+        let mut config = TestConfig::new();
+        config.runners.insert("sh".to_owned(),
+                              TestRunner {
+                                  command: vec!["sh".to_owned()],
+                                  file: None,
+                              });
+        let failures = test_chapter(&config, &src, Path::new("shell.md")).unwrap();
+        let _ = fs::remove_dir_all(&src);
+
+        assert_eq!(failures.iter().map(|f| f.line).collect::<Vec<_>>(), vec![8, 15]);
+        assert!(failures[0].message.contains("bye"));
+    }
+}
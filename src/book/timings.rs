@@ -0,0 +1,135 @@
+use std::cell::RefCell;
+use std::io::{self, IsTerminal, Write};
+use std::time::{Duration, Instant};
+
+// The order of the phases in the report, the other phases come after them
+const PHASES: &'static [&'static str] = &["parse", "preprocess", "render", "copy"];
+
+/// Time spent in each phase of a build, printed at the end with `mdbook build --timings`. The renderer
+/// adds its phases with [`lap`](#method.lap), the time of a phase run several times is added up.
+#[derive(Debug)]
+pub struct Timings {
+    phases: RefCell<Vec<(&'static str, Duration)>>,
+}
+
+/// A `N/M chapters` line updated while the chapters are rendered, only when the output is a terminal
+pub struct Progress {
+    total: usize,
+    done: usize,
+    start: Instant,
+    enabled: bool,
+}
+
+impl Timings {
+    pub fn new() -> Self {
+        Timings { phases: RefCell::new(vec![]) }
+    }
+
+    /// Adds the time elapsed since `clock` to `phase`, and restarts the clock for the next phase
+    pub fn lap(&self, phase: &'static str, clock: &mut Instant) {
+        let now = Instant::now();
+        self.add(phase, now - *clock);
+        *clock = now;
+    }
+
+    pub fn add(&self, phase: &'static str, duration: Duration) {
+        let mut phases = self.phases.borrow_mut();
+        match phases.iter_mut().find(|&&mut (p, _)| p == phase) {
+            Some(&mut (_, ref mut total)) => *total += duration,
+            None => phases.push((phase, duration)),
+        }
+    }
+
+    /// The phases in the order they were first timed, with their total time
+    pub fn phases(&self) -> Vec<(&'static str, Duration)> {
+        self.phases.borrow().clone()
+    }
+
+    pub fn clear(&self) {
+        self.phases.borrow_mut().clear();
+    }
+
+    /// One phase by line, with its share of the whole build
+    pub fn report(&self) -> String {
+        let mut phases = self.phases();
+        phases.sort_by_key(|&(phase, _)| PHASES.iter().position(|&p| p == phase).unwrap_or(PHASES.len()));
+        let total = phases.iter().fold(Duration::new(0, 0), |total, &(_, d)| total + d);
+
+        let mut report = String::new();
+        for &(phase, duration) in phases.iter() {
+            let share = if total.as_secs_f64() > 0.0 {
+                duration.as_secs_f64() / total.as_secs_f64() * 100.0
+            } else {
+                0.0
+            };
+            report.push_str(&format!("    {:<12}{:>8.3}s {:>5.1}%\n", phase, duration.as_secs_f64(), share));
+        }
+        report.push_str(&format!("    {:<12}{:>8.3}s\n", "total", total.as_secs_f64()));
+        report
+    }
+}
+
+impl Progress {
+    pub fn new(total: usize) -> Self {
+        Progress {
+            total: total,
+            done: 0,
+            start: Instant::now(),
+            enabled: io::stderr().is_terminal(),
+        }
+    }
+
+    /// Shows the chapter being rendered
+    pub fn chapter(&mut self, path: &str) {
+        self.done += 1;
+        if self.enabled {
+            let line = format!("[*]: {}/{} chapters, {:.1}s: {}",
+                               self.done,
+                               self.total,
+                               self.start.elapsed().as_secs_f64(),
+                               path);
+            // The line is cut to the usual width of a terminal so that it does not wrap
+            let line: String = line.chars().take(79).collect();
+            let _ = write!(io::stderr(), "\r{:<79}", line);
+            let _ = io::stderr().flush();
+        }
+    }
+
+    /// Removes the line, the summary of the build is printed instead
+    pub fn finish(&self) {
+        if self.enabled {
+            let _ = write!(io::stderr(), "\r{:79}\r", "");
+            let _ = io::stderr().flush();
+        }
+    }
+}
+
+
+// ---------------------------------------------------------------------------------------------------------------
+//      Tests
+//
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_adds_up_the_phases() {
+        let timings = Timings::new();
+        timings.add("parse", Duration::from_millis(300));
+        timings.add("plugins", Duration::from_millis(500));
+        timings.add("render", Duration::from_millis(400));
+        timings.add("parse", Duration::from_millis(100));
+
+        assert_eq!(timings.phases(),
+                   vec![("parse", Duration::from_millis(400)),
+                        ("plugins", Duration::from_millis(500)),
+                        ("render", Duration::from_millis(400))]);
+        assert_eq!(timings.report(),
+                   "    parse          0.400s  30.8%\n    render         0.400s  30.8%\n    \
+                    plugins        0.500s  38.5%\n    total          1.300s\n");
+
+        timings.clear();
+        assert!(timings.phases().is_empty());
+    }
+}
@@ -0,0 +1,105 @@
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fs;
+use std::io;
+use toml;
+
+use utils::git::git;
+
+/// Options of `mdbook versions`, read from the `[versions]` table of `book.toml`
+#[derive(Debug, Clone)]
+pub struct VersionsConfig {
+    /// Git branches, tags or commits built in the same order in the version picker
+    pub refs: Vec<String>,
+    /// Ref copied to `latest/`, the last of `refs` by default
+    pub latest: Option<String>,
+}
+
+/// Versions listed in the version picker of a page
+#[derive(Debug, Clone)]
+pub struct VersionPicker {
+    /// Version of the book being built
+    pub current: String,
+    pub latest: String,
+    pub versions: Vec<String>,
+}
+
+impl VersionsConfig {
+    /// Returns `None` when there are no `refs`
+    pub fn from_btreemap(config: &BTreeMap<String, toml::Value>) -> Option<Self> {
+        let refs: Vec<String> = config.get("refs")
+            .and_then(|v| v.as_slice())
+            .map(|a| a.iter().filter_map(|v| v.as_str()).map(|s| s.to_owned()).collect())
+            .unwrap_or_default();
+
+        if refs.is_empty() {
+            warn!("[*]: [versions] needs a list of git `refs`");
+            return None;
+        }
+
+        Some(VersionsConfig {
+            refs: refs,
+            latest: config.get("latest").and_then(|v| v.as_str()).map(|s| s.to_owned()),
+        })
+    }
+
+    /// Name of the latest version
+    pub fn latest_version(&self) -> String {
+        version_name(self.latest.as_ref().unwrap_or(&self.refs[self.refs.len() - 1]))
+    }
+}
+
+/// Name of the version built from a git ref, also the directory it is rendered in, e.g. `v1.0` for
+/// `v1.0` and `release-2` for `release/2`
+pub fn version_name(git_ref: &str) -> String {
+    git_ref.trim_start_matches("refs/").trim_start_matches("tags/").trim_start_matches("heads/").replace('/', "-")
+}
+
+/// Extracts the files of `git_ref` in `to`, from the git repository containing `dir`. Returns the
+/// directory of `dir` in `to`, for books that are not at the root of the repository.
+pub fn checkout(dir: &Path, git_ref: &str, to: &Path) -> Result<PathBuf, Box<Error>> {
+    let top_level = try!(git(dir, &["rev-parse", "--show-toplevel"]));
+    let prefix = try!(git(dir, &["rev-parse", "--show-prefix"]));
+    try!(fs::create_dir_all(to));
+
+    // Run at the top level, `git archive` only archives the current directory otherwise
+    let mut archive = try!(Command::new("git")
+        .args(&["archive", "--format=tar", git_ref])
+        .current_dir(top_level.trim())
+        .stdout(Stdio::piped())
+        .spawn());
+
+    let extracted = try!(Command::new("tar")
+        .arg("-x")
+        .arg("-C")
+        .arg(to)
+        .stdin(archive.stdout.take().expect("the output of git archive is piped"))
+        .status());
+
+    if !try!(archive.wait()).success() || !extracted.success() {
+        return Err(Box::new(io::Error::new(io::ErrorKind::Other, format!("Could not check out `{}`", git_ref))));
+    }
+
+    Ok(to.join(prefix.trim()))
+}
+
+
+
+// ------------------------------------------------------------------------------------------------
+// ------------------------------------------------------------------------------------------------
+
+// tests
+
+#[cfg(test)]
+mod tests {
+    use super::version_name;
+
+    #[test]
+    fn version_names() {
+        assert_eq!(version_name("v1.0"), "v1.0");
+        assert_eq!(version_name("release/2"), "release-2");
+        assert_eq!(version_name("refs/tags/v0.1.0"), "v0.1.0");
+    }
+}
@@ -0,0 +1,216 @@
+use std::path::{Path, PathBuf};
+use std::collections::{BTreeMap, HashSet};
+use std::error::Error;
+use std::io::{self, Write};
+use toml;
+
+use book::{BookConfig, MDBook};
+use utils;
+
+/// Books of a workspace, read from the `[workspace]` table of the `book.toml` at its root
+#[derive(Debug, Clone)]
+pub struct WorkspaceConfig {
+    /// Roots of the books, relative to the root of the workspace
+    pub books: Vec<PathBuf>,
+}
+
+/// Several books built into one site by `mdbook build --workspace`, every book in the directory of its name
+/// with a landing page linking them. The theme of the workspace is shared by the books without a theme, and
+/// the `includes` of the workspace by every book.
+pub struct Workspace {
+    root: PathBuf,
+    config: BookConfig,
+    books: Vec<PathBuf>,
+}
+
+impl WorkspaceConfig {
+    /// Returns `None` when there are no `books`
+    pub fn from_btreemap(config: &BTreeMap<String, toml::Value>) -> Option<Self> {
+        let books: Vec<PathBuf> = config.get("books")
+            .and_then(|v| v.as_slice())
+            .map(|a| a.iter().filter_map(|v| v.as_str()).map(PathBuf::from).collect())
+            .unwrap_or_default();
+
+        if books.is_empty() {
+            warn!("[*]: [workspace] needs a list of `books`");
+            return None;
+        }
+        Some(WorkspaceConfig { books: books })
+    }
+}
+
+impl Workspace {
+    /// Reads the `book.toml` of `root`, which needs a `[workspace]` table
+    pub fn new(root: &Path) -> Result<Self, Box<Error>> {
+        let mut config = BookConfig::new(root);
+        config.read_config(root);
+        Workspace::from_config(root, config)
+    }
+
+    /// Like [`new()`](#method.new), with the `[profile.name]` table applied to the workspace and every book
+    pub fn with_profile(root: &Path, profile: &str) -> Result<Self, Box<Error>> {
+        let mut config = BookConfig::new(root);
+        config.set_profile(profile).read_config(root);
+        Workspace::from_config(root, config)
+    }
+
+    fn from_config(root: &Path, config: BookConfig) -> Result<Self, Box<Error>> {
+        let books = match config.workspace_config {
+            Some(ref workspace) => workspace.books.clone(),
+            None => {
+                return Err(Box::new(io::Error::new(io::ErrorKind::Other,
+                                                   format!("{} has no [workspace] table listing the books",
+                                                           root.join("book.toml").display()))))
+            },
+        };
+
+        // Every book is in the directory of its name
+        let mut names = HashSet::new();
+        for book in &books {
+            if !names.insert(book_name(book)) {
+                return Err(Box::new(io::Error::new(io::ErrorKind::Other,
+                                                   format!("Two books of the workspace are named `{}`",
+                                                           book_name(book)))));
+            }
+        }
+
+        Ok(Workspace {
+            root: root.to_owned(),
+            config: config,
+            books: books,
+        })
+    }
+
+    pub fn get_root(&self) -> &Path {
+        &self.root
+    }
+
+    pub fn get_dest(&self) -> &Path {
+        self.config.get_dest()
+    }
+
+    pub fn set_dest(mut self, dest: &Path) -> Self {
+        let dest = self.root.join(dest);
+        self.config.set_dest(&dest);
+        self
+    }
+
+    /// The books with their config and the profile, built in `dest/<name>`
+    pub fn books(&self) -> Vec<MDBook> {
+        self.books
+            .iter()
+            .map(|path| {
+                let book = MDBook::new(&self.root.join(path));
+                let book = match self.config.get_profile() {
+                    Some(profile) => book.set_profile(profile),
+                    None => book,
+                };
+                let book = book.read_config().set_dest(&self.get_dest().join(book_name(path)));
+
+                // The files included by every book
+                let book = self.config.includes.iter().fold(book, |book, dir| book.add_include_dir(dir));
+
+                // The books without a theme share the one of the workspace
+                if !book.get_theme_path().exists() && self.config.get_theme_path().exists() {
+                    book.set_theme_path(self.config.get_theme_path())
+                } else {
+                    book
+                }
+            })
+            .collect()
+    }
+
+    /// Builds every book, then the landing page
+    pub fn build(&self, books: &mut [MDBook]) -> Result<(), Box<Error>> {
+        for book in books.iter_mut() {
+            println!("[*]: Building {}", book.get_root().display());
+            try!(book.build().map_err(|e| format!("{}: {}", book.get_root().display(), e)));
+        }
+        self.write_landing_page(books)
+    }
+
+    /// Writes `index.html` at the root of the site, with a link to every book
+    pub fn write_landing_page(&self, books: &[MDBook]) -> Result<(), Box<Error>> {
+        let links: Vec<(String, &str, &str)> = self.books
+            .iter()
+            .zip(books)
+            .map(|(path, book)| (book_name(path), book.get_title(), book.get_description()))
+            .collect();
+
+        let mut file = try!(utils::fs::create_file(&self.get_dest().join("index.html")));
+        try!(file.write_all(landing_page_html(&self.config.title, &self.config.description, &links).as_bytes()));
+        Ok(())
+    }
+}
+
+// The directory of a book in the site, the last directory of its root
+fn book_name(path: &Path) -> String {
+    path.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_else(|| "book".to_owned())
+}
+
+fn landing_page_html(title: &str, description: &str, books: &[(String, &str, &str)]) -> String {
+    let title = if title.is_empty() { "Books" } else { title };
+    let mut html = format!("<!DOCTYPE HTML>\n<html lang=\"en\">\n<head>\n<meta charset=\"UTF-8\">\n\
+                            <title>{0}</title>\n<meta name=\"viewport\" content=\"width=device-width, \
+                            initial-scale=1\">\n<style>\nbody {{ max-width: 750px; margin: 40px auto; padding: 0 \
+                            15px; font-family: \"Open Sans\", sans-serif; color: #333; }}\n.books {{ padding: 0; \
+                            list-style: none; }}\n.books li {{ margin: 20px 0; }}\n.books a {{ font-size: 1.4em; \
+                            color: #4183c4; text-decoration: none; }}\n</style>\n</head>\n<body>\n<h1>{0}</h1>\n",
+                           utils::escape_html(title));
+    if !description.is_empty() {
+        html.push_str(&format!("<p>{}</p>\n", utils::escape_html(description)));
+    }
+
+    html.push_str("<ul class=\"books\">\n");
+    for &(ref name, book_title, book_description) in books {
+        let book_title = if book_title.is_empty() { &name[..] } else { book_title };
+        html.push_str(&format!("<li><a href=\"{}/index.html\">{}</a>", name, utils::escape_html(book_title)));
+        if !book_description.is_empty() {
+            html.push_str(&format!("<p>{}</p>", utils::escape_html(book_description)));
+        }
+        html.push_str("</li>\n");
+    }
+    html.push_str("</ul>\n</body>\n</html>\n");
+    html
+}
+
+
+// ---------------------------------------------------------------------------------------------------------------
+//      Tests
+//
+
+#[cfg(test)]
+mod tests {
+    extern crate tempdir;
+
+    use super::*;
+    use std::fs::{self, File};
+
+    #[test]
+    fn it_reads_the_books_of_the_workspace() {
+        let tmp = tempdir::TempDir::new("mdbook").expect("Could not create a temp dir");
+        File::create(tmp.path().join("book.toml"))
+            .unwrap()
+            .write_all(b"title = \"Docs\"\n\n[workspace]\nbooks = [\"guide\", \"api/reference\"]\n")
+            .unwrap();
+        fs::create_dir_all(tmp.path().join("theme")).unwrap();
+        fs::create_dir_all(tmp.path().join("guide/theme")).unwrap();
+        fs::create_dir_all(tmp.path().join("api/reference")).unwrap();
+        File::create(tmp.path().join("api/reference/book.toml")).unwrap().write_all(b"title = \"API\"").unwrap();
+
+        let workspace = Workspace::new(tmp.path()).unwrap().set_dest(Path::new("site"));
+        let books = workspace.books();
+        assert_eq!(books.len(), 2);
+        assert_eq!(books[0].get_dest(), tmp.path().join("site/guide"));
+        assert_eq!(books[0].get_theme_path(), tmp.path().join("guide/theme"));
+        assert_eq!(books[1].get_dest(), tmp.path().join("site/reference"));
+        assert_eq!(books[1].get_theme_path(), tmp.path().join("theme"));
+
+        let html = landing_page_html("Docs", "", &[("guide".to_owned(), "", ""), ("reference".to_owned(), "API", "")]);
+        assert!(html.contains("<h1>Docs</h1>"));
+        assert!(html.contains("<li><a href=\"guide/index.html\">guide</a></li>\n\
+                               <li><a href=\"reference/index.html\">API</a></li>"));
+
+        assert!(Workspace::new(&tmp.path().join("guide")).is_err());
+    }
+}
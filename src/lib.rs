@@ -33,10 +33,17 @@
 //! }
 //! ```
 //!
+//! ## Features
+//!
+//! The HTML renderer is the `html` feature and the command line tool the `cli` feature, with `watch` and
+//! `serve` for its commands of the same name. They are enabled by default, with `default-features = false`
+//! the crate only has the book model, the parsing of `SUMMARY.md` and the markdown utilities, and a book
+//! needs a renderer set with `set_renderer()`.
+//!
 //! ## Implementing a new Renderer
 //!
 //! If you want to create a new renderer for mdBook, the only thing you have to do is to implement
-//! the [Renderer trait](renderer/renderer/trait.Renderer.html)
+//! the [Renderer trait](renderer/trait.Renderer.html)
 //!
 //! And then you can swap in your renderer like this:
 //!
@@ -68,20 +75,53 @@
 //! This function creates all the directories in a given path if they do not exist
 //!
 //! Make sure to take a look at it.
+//!
+//! ## Stability
+//!
+//! The types re-exported at the root of the crate are its stable API: [`MDBook`](book/struct.MDBook.html),
+//! [`Config`](book/struct.BookConfig.html) (also exported as `BookConfig`), [`BookItem`](book/enum.BookItem.html)
+//! and [`Chapter`](book/struct.Chapter.html), [`Renderer`](renderer/trait.Renderer.html),
+//! [`Warning`](renderer/struct.Warning.html) and [`Workspace`](book/struct.Workspace.html). Their public
+//! methods and fields follow semver: removing or changing one only happens in a release that breaks
+//! compatibility, and deprecated methods stay until then.
+//!
+//! The other items of `book`, `parse` and `utils` are public to write renderers and tools around mdBook, but
+//! they follow the needs of the command line tool and can change in any release. The theme files and the
+//! handlebars helpers of the HTML renderer are internal.
 
 extern crate serde;
 extern crate serde_json;
-extern crate handlebars;
 extern crate pulldown_cmark;
+extern crate toml;
+
+// Dependencies for the HTML renderer feature
+#[cfg(feature = "html")]
+extern crate handlebars;
+#[cfg(feature = "html")]
+extern crate sha2;
+#[cfg(feature = "html")]
+extern crate base64;
+#[cfg(feature = "html")]
+extern crate zip;
+
+// Dependencies for the Scripting feature
+#[cfg(feature = "scripting")]
+extern crate rhai;
 
 #[macro_use] extern crate log;
 pub mod book;
-mod parse;
+pub mod parse;
 pub mod renderer;
-pub mod theme;
 pub mod utils;
 
+// The files of the default theme, only used to render and to initialize books
+#[cfg_attr(not(feature = "html"), allow(dead_code))]
+mod theme;
+
+// The stable API, see "Stability" above
 pub use book::MDBook;
-pub use book::BookItem;
+pub use book::{BookItem, Chapter};
 pub use book::BookConfig;
-pub use renderer::Renderer;
+pub use book::BookConfig as Config;
+pub use book::Workspace;
+pub use renderer::{Renderer, Warning};
@@ -0,0 +1,153 @@
+use pulldown_cmark::{Parser, Event, Tag};
+use serde_json::{Map, Value};
+use serde_json::value::ToJson;
+
+use book::MarkdownConfig;
+
+
+/// The markdown of a chapter as a JSON array of the events of pulldown-cmark, parsed with the options of
+/// the `[markdown]` table, for the renderers that don't parse the markdown themselves.
+///
+/// Every event is an object with a `type`: `start` and `end` have the `tag` they open or close, with its
+/// fields, `text`, `html` and `inline_html` have a `text`, `footnote_reference` a `name`, and
+/// `soft_break` and `hard_break` have nothing else.
+///
+/// ```json
+/// [{"type": "start", "tag": "header", "level": 1}, {"type": "text", "text": "Intro"},
+///  {"type": "end", "tag": "header", "level": 1}]
+/// ```
+pub fn events_json(markdown: &str, config: &MarkdownConfig) -> Value {
+    Value::Array(Parser::new_ext(markdown, config.options()).map(|e| event_json(&e)).collect())
+}
+
+pub fn event_json(event: &Event) -> Value {
+    let mut object = Map::new();
+    let kind = match *event {
+        Event::Start(ref tag) => {
+            tag_json(tag, &mut object);
+            "start"
+        },
+        Event::End(ref tag) => {
+            tag_json(tag, &mut object);
+            "end"
+        },
+        Event::Text(ref text) => {
+            object.insert("text".to_owned(), text.to_json());
+            "text"
+        },
+        Event::Html(ref html) => {
+            object.insert("text".to_owned(), html.to_json());
+            "html"
+        },
+        Event::InlineHtml(ref html) => {
+            object.insert("text".to_owned(), html.to_json());
+            "inline_html"
+        },
+        Event::FootnoteReference(ref name) => {
+            object.insert("name".to_owned(), name.to_json());
+            "footnote_reference"
+        },
+        Event::SoftBreak => "soft_break",
+        Event::HardBreak => "hard_break",
+    };
+    object.insert("type".to_owned(), kind.to_json());
+    Value::Object(object)
+}
+
+fn tag_json(tag: &Tag, object: &mut Map<String, Value>) {
+    let name = match *tag {
+        Tag::Paragraph => "paragraph",
+        Tag::Rule => "rule",
+        Tag::Header(level) => {
+            object.insert("level".to_owned(), level.to_json());
+            "header"
+        },
+        Tag::BlockQuote => "block_quote",
+        Tag::CodeBlock(ref info) => {
+            object.insert("info".to_owned(), info.to_json());
+            "code_block"
+        },
+        Tag::List(start) => {
+            // `null` for the bullet lists
+            object.insert("start".to_owned(), start.to_json());
+            "list"
+        },
+        Tag::Item => "item",
+        Tag::FootnoteDefinition(ref name) => {
+            object.insert("name".to_owned(), name.to_json());
+            "footnote_definition"
+        },
+        Tag::Table(columns) => {
+            object.insert("columns".to_owned(), columns.to_json());
+            "table"
+        },
+        Tag::TableHead => "table_head",
+        Tag::TableRow => "table_row",
+        Tag::TableCell => "table_cell",
+        Tag::Emphasis => "emphasis",
+        Tag::Strong => "strong",
+        Tag::Code => "code",
+        Tag::Link(ref url, ref title) => {
+            object.insert("url".to_owned(), url.to_json());
+            object.insert("title".to_owned(), title.to_json());
+            "link"
+        },
+        Tag::Image(ref url, ref title) => {
+            object.insert("url".to_owned(), url.to_json());
+            object.insert("title".to_owned(), title.to_json());
+            "image"
+        },
+    };
+    object.insert("tag".to_owned(), name.to_json());
+}
+
+
+// ---------------------------------------------------------------------------------------------------------------
+//      Tests
+//
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json;
+
+    #[test]
+    fn it_converts_the_events_to_json() {
+        let markdown = "# Intro\n\nSee [the *guide*](guide.md \"Guide\")[^1].\n\n```rust\nfn main() {}\n```\n\n\
+                        [^1]: Soon";
+        let expected = r#"[
+            {"type": "start", "tag": "header", "level": 1}, {"type": "text", "text": "Intro"},
+            {"type": "end", "tag": "header", "level": 1},
+            {"type": "start", "tag": "paragraph"}, {"type": "text", "text": "See "},
+            {"type": "start", "tag": "link", "url": "guide.md", "title": "Guide"}, {"type": "text", "text": "the "},
+            {"type": "start", "tag": "emphasis"}, {"type": "text", "text": "guide"}, {"type": "end", "tag": "emphasis"},
+            {"type": "end", "tag": "link", "url": "guide.md", "title": "Guide"},
+            {"type": "footnote_reference", "name": "1"}, {"type": "text", "text": "."},
+            {"type": "end", "tag": "paragraph"},
+            {"type": "start", "tag": "code_block", "info": "rust"}, {"type": "text", "text": "fn main() {}\n"},
+            {"type": "end", "tag": "code_block", "info": "rust"},
+            {"type": "start", "tag": "footnote_definition", "name": "1"}, {"type": "start", "tag": "paragraph"},
+            {"type": "text", "text": "Soon"}, {"type": "end", "tag": "paragraph"},
+            {"type": "end", "tag": "footnote_definition", "name": "1"}
+        ]"#;
+
+        let expected: Value = serde_json::from_str(expected).unwrap();
+        assert_eq!(events_json(markdown, &MarkdownConfig::new()), expected);
+    }
+
+    #[test]
+    fn it_uses_the_markdown_options() {
+        let mut config = MarkdownConfig::new();
+        let tables = |config: &MarkdownConfig| {
+            events_json("a | b\n--|--\nc | d", config)
+                .as_array()
+                .unwrap()
+                .iter()
+                .filter(|e| e.find("tag").and_then(|t| t.as_str()) == Some("table"))
+                .count()
+        };
+        assert_eq!(tables(&config), 2);
+        config.tables = false;
+        assert_eq!(tables(&config), 0);
+    }
+}
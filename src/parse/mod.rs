@@ -1,3 +1,5 @@
 pub use self::summary::construct_bookitems;
+pub use self::events::events_json;
 
 pub mod summary;
+pub mod events;
@@ -1,7 +1,16 @@
-use std::path::PathBuf;
+use std::path::{Component, Path, PathBuf};
 use std::fs::File;
 use std::io::{Read, Result, Error, ErrorKind};
+use std::iter::Peekable;
+use pulldown_cmark::{Parser, Event, Tag};
 use book::bookitem::{BookItem, Chapter};
+#[cfg(test)]
+use book::bookitem::BookItems;
+
+// Section given to appendices while parsing, until they are numbered
+const APPENDIX: &'static str = "A";
+
+const LINK_EXPECTED: &'static str = "expected a link to a chapter, like `[Title](path/to/chapter.md)`";
 
 pub fn construct_bookitems(path: &PathBuf) -> Result<Vec<BookItem>> {
     debug!("[fn]: construct_bookitems");
@@ -9,217 +18,508 @@ pub fn construct_bookitems(path: &PathBuf) -> Result<Vec<BookItem>> {
     try!(try!(File::open(path)).read_to_string(&mut summary));
 
     debug!("[*]: Parse SUMMARY.md");
-    let top_items = try!(parse_level(&mut summary.split('\n').collect(), 0, vec![0]));
+    let top_items = try!(parse_summary(&summary)
+        .map_err(|e| Error::new(ErrorKind::Other, format!("{}:{}", path.display(), e))));
     debug!("[*]: Done parsing SUMMARY.md");
     Ok(top_items)
 }
 
-fn parse_level(summary: &mut Vec<&str>, current_level: i32, mut section: Vec<i32>) -> Result<Vec<BookItem>> {
-    debug!("[fn]: parse_level");
-    let mut items: Vec<BookItem> = vec![];
+/// Parses the content of a `SUMMARY.md`, the errors start with the line and the column of the
+/// malformed element, e.g. `12:5: ...`
+pub fn parse_summary(summary: &str) -> Result<Vec<BookItem>> {
+    let mut parser = SummaryParser {
+        summary: summary,
+        events: Events { parser: Parser::new(summary) }.peekable(),
+    };
+    let mut items = try!(parser.parse());
+    renumber(&mut items, "");
+    Ok(items)
+}
 
-    // Construct the book recursively
-    while !summary.is_empty() {
-        let item: BookItem;
-        // Indentation level of the line to parse
-        let level = try!(level(summary[0], 4));
+/// Writes `items` as a `SUMMARY.md`, which is parsed to the same items again. Every element is on its
+/// own line, sub-chapters are indented by 4 spaces.
+pub fn summary_to_markdown(items: &[BookItem]) -> String {
+    let mut summary = "# Summary\n".to_owned();
+
+    // Prefix and suffix chapters, numbered chapters, appendices and separators are blocks
+    // separated by an empty line
+    let block = |item: &BookItem| match *item {
+        BookItem::Affix(_) => 0,
+        BookItem::Chapter(ref s, _) if s.starts_with(char::is_alphabetic) => 1,
+        BookItem::Chapter(..) => 2,
+        BookItem::Spacer => 3,
+    };
+    let mut previous = None;
+
+    for item in items {
+        if previous != Some(block(item)) || block(item) == 3 {
+            summary.push('\n');
+        }
+        previous = Some(block(item));
 
-        // if level < current_level we remove the last digit of section, exit the current function,
-        // and return the parsed level to the calling function.
-        if level < current_level {
-            break;
+        match *item {
+            BookItem::Affix(ref ch) => summary.push_str(&format!("[{}]({})\n", ch.name, ch.path.display())),
+            BookItem::Chapter(ref s, ref ch) => {
+                write_chapter(&mut summary, ch, if s.starts_with(char::is_alphabetic) { '+' } else { '-' }, 0)
+            },
+            BookItem::Spacer => summary.push_str("---\n"),
         }
+    }
 
-        // if level > current_level we call ourselves to go one level deeper
-        if level > current_level {
-            // Level can not be root level !!
-            // Add a sub-number to section
-            section.push(0);
-            let last = items.pop().expect("There should be at least one item since this can't be the root level");
-
-            if let BookItem::Chapter(ref s, ref ch) = last {
-                let mut ch = ch.clone();
-                ch.sub_items = try!(parse_level(summary, level, section.clone()));
-                items.push(BookItem::Chapter(s.clone(), ch));
-
-                // Remove the last number from the section, because we got back to our level..
-                section.pop();
-                continue;
-            } else {
-                return Err(Error::new(ErrorKind::Other,
-                                      format!("Your summary.md is messed up\n\n
-                        Prefix, \
-                                               Suffix and Spacer elements can only exist on the root level.\n
-                        \
-                                               Prefix elements can only exist before any chapter and there can be \
-                                               no chapters after suffix elements.")));
-            };
+    summary
+}
 
-        } else {
-            // level and current_level are the same, parse the line
-            item = if let Some(parsed_item) = parse_line(summary[0]) {
-
-                // Eliminate possible errors and set section to -1 after suffix
-                match parsed_item {
-                    // error if level != 0 and BookItem is != Chapter
-                    BookItem::Affix(_) | BookItem::Spacer if level > 0 => {
-                        return Err(Error::new(ErrorKind::Other,
-                                              format!("Your summary.md is messed up\n\n
-                                \
-                                                       Prefix, Suffix and Spacer elements can only exist on the \
-                                                       root level.\n
-                                Prefix \
-                                                       elements can only exist before any chapter and there can be \
-                                                       no chapters after suffix elements.")))
-                    },
-
-                    // error if BookItem == Chapter and section == -1
-                    BookItem::Chapter(_, _) if section[0] == -1 => {
-                        return Err(Error::new(ErrorKind::Other,
-                                              format!("Your summary.md is messed up\n\n
-                                \
-                                                       Prefix, Suffix and Spacer elements can only exist on the \
-                                                       root level.\n
-                                Prefix \
-                                                       elements can only exist before any chapter and there can be \
-                                                       no chapters after suffix elements.")))
-                    },
-
-                    // Set section = -1 after suffix
-                    BookItem::Affix(_) if section[0] > 0 => {
-                        section[0] = -1;
-                    },
-
-                    _ => {},
-                }
+fn write_chapter(summary: &mut String, ch: &Chapter, marker: char, level: usize) {
+    summary.push_str(&format!("{}{} [{}]({})\n", "    ".repeat(level), marker, ch.name, ch.path.display()));
+    for item in &ch.sub_items {
+        if let BookItem::Chapter(_, ref sub) = *item {
+            write_chapter(summary, sub, '-', level + 1);
+        }
+    }
+}
 
-                match parsed_item {
-                    BookItem::Chapter(_, ch) => {
-                        // Increment section
-                        let len = section.len() - 1;
-                        section[len] += 1;
-                        let s = section.iter().fold("".to_owned(), |s, i| s + &i.to_string() + ".");
-                        BookItem::Chapter(s, ch)
-                    },
-                    _ => parsed_item,
-                }
+// The markdown events with the offset in the source where they start
+struct Events<'a> {
+    parser: Parser<'a>,
+}
 
-            } else {
-                // If parse_line does not return Some(_) continue...
-                summary.remove(0);
-                continue;
-            };
-        }
+impl<'a> Iterator for Events<'a> {
+    type Item = (usize, Event<'a>);
 
-        summary.remove(0);
-        items.push(item)
+    fn next(&mut self) -> Option<(usize, Event<'a>)> {
+        let offset = self.parser.get_offset();
+        self.parser.next().map(|event| (offset, event))
     }
-    debug!("[*]: Level: {:?}", items);
-    Ok(items)
 }
 
+struct SummaryParser<'a> {
+    summary: &'a str,
+    events: Peekable<Events<'a>>,
+}
 
-fn level(line: &str, spaces_in_tab: i32) -> Result<i32> {
-    debug!("[fn]: level");
-    let mut spaces = 0;
-    let mut level = 0;
-
-    for ch in line.chars() {
-        match ch {
-            ' ' => spaces += 1,
-            '\t' => level += 1,
-            _ => break,
+impl<'a> SummaryParser<'a> {
+    // The root level: prefix chapters, numbered chapters, appendices and suffix chapters, in this
+    // order, with separators anywhere
+    fn parse(&mut self) -> Result<Vec<BookItem>> {
+        let mut items = vec![];
+        let (mut numbered, mut appendices, mut suffix) = (false, false, false);
+
+        while let Some((offset, event)) = self.events.next() {
+            match event {
+                // The title, `# Summary`
+                Event::Start(Tag::Header(_)) => self.skip_to_end(),
+                Event::Start(Tag::Paragraph) => {
+                    let chapters = try!(self.parse_affixes());
+                    suffix = suffix || numbered || appendices;
+                    items.extend(chapters.into_iter().map(BookItem::Affix));
+                },
+                Event::Start(Tag::List(_)) => {
+                    let appendix = self.element_at(offset).starts_with('+');
+                    if suffix {
+                        return Err(self.error(offset, "chapters can not come after the suffix chapters"));
+                    }
+                    if appendices && !appendix {
+                        return Err(self.error(offset, "numbered chapters can not come after the appendices"));
+                    }
+                    numbered = numbered || !appendix;
+                    appendices = appendices || appendix;
+                    items.extend(try!(self.parse_list(if appendix { APPENDIX } else { "0" })));
+                },
+                Event::Start(Tag::Rule) => {
+                    self.skip_to_end();
+                    items.push(BookItem::Spacer);
+                },
+                Event::Html(_) | Event::SoftBreak | Event::HardBreak => {},
+                _ => return Err(self.error(offset, "expected a chapter, a list of chapters or a separator")),
+            }
         }
-        if spaces >= spaces_in_tab {
-            level += 1;
-            spaces = 0;
+
+        Ok(items)
+    }
+
+    // Links of a paragraph at the root level, the prefix or suffix chapters
+    fn parse_affixes(&mut self) -> Result<Vec<Chapter>> {
+        let mut chapters = vec![];
+        while let Some((offset, event)) = self.events.next() {
+            match event {
+                Event::End(Tag::Paragraph) => break,
+                Event::Start(Tag::Link(dest, _)) => chapters.push(try!(self.parse_link(&dest))),
+                Event::Text(ref text) if text.trim().is_empty() => {},
+                Event::InlineHtml(_) | Event::SoftBreak | Event::HardBreak => {},
+                _ => return Err(self.error(offset, LINK_EXPECTED)),
+            }
         }
+        Ok(chapters)
     }
 
-    // If there are spaces left, there is an indentation error
-    if spaces > 0 {
-        debug!("[SUMMARY.md]:");
-        debug!("\t[line]: {}", line);
-        debug!("[*]: There is an indentation error on this line. Indentation should be {} spaces", spaces_in_tab);
-        return Err(Error::new(ErrorKind::Other, format!("Indentation error on line:\n\n{}", line)));
+    // Items of a list, `section` is the one of the chapters until they are numbered
+    fn parse_list(&mut self, section: &str) -> Result<Vec<BookItem>> {
+        let mut items = vec![];
+        while let Some((offset, event)) = self.events.next() {
+            match event {
+                Event::End(Tag::List(_)) => break,
+                Event::Start(Tag::Item) => {
+                    items.push(BookItem::Chapter(section.to_owned(), try!(self.parse_item(offset))))
+                },
+                _ => return Err(self.error(offset, LINK_EXPECTED)),
+            }
+        }
+        Ok(items)
     }
 
-    Ok(level)
-}
+    // A chapter, with its sub-chapters in a nested list
+    fn parse_item(&mut self, start: usize) -> Result<Chapter> {
+        let mut chapter: Option<Chapter> = None;
+        while let Some((offset, event)) = self.events.next() {
+            match event {
+                Event::End(Tag::Item) => break,
+                Event::Start(Tag::Link(ref dest, _)) if chapter.is_none() => {
+                    chapter = Some(try!(self.parse_link(dest)))
+                },
+                Event::Start(Tag::Link(..)) => {
+                    return Err(self.error(offset, "a chapter has only one link, its sub-chapters go in a nested list"))
+                },
+                Event::Start(Tag::List(_)) => {
+                    match chapter {
+                        Some(ref mut ch) => ch.sub_items.extend(try!(self.parse_list("0"))),
+                        None => return Err(self.error(start, LINK_EXPECTED)),
+                    }
+                },
+                Event::Start(Tag::Rule) => return Err(self.error(offset, "separators can only be at the root level")),
+                Event::Start(Tag::Paragraph) | Event::End(Tag::Paragraph) => {},
+                Event::Text(ref text) if text.trim().is_empty() => {},
+                Event::InlineHtml(_) | Event::SoftBreak | Event::HardBreak => {},
+                _ => return Err(self.error(offset, LINK_EXPECTED)),
+            }
+        }
+        chapter.ok_or_else(|| self.error(start, LINK_EXPECTED))
+    }
 
+    // The title of a link is kept as markdown, inline code and emphasis are rendered in the table of contents
+    fn parse_link(&mut self, dest: &str) -> Result<Chapter> {
+        let mut name = String::new();
+        while let Some((offset, event)) = self.events.next() {
+            match event {
+                Event::End(Tag::Link(..)) => break,
+                Event::Text(text) => name.push_str(&text),
+                Event::InlineHtml(html) => name.push_str(&html),
+                Event::Start(Tag::Code) | Event::End(Tag::Code) => name.push('`'),
+                Event::Start(Tag::Emphasis) | Event::End(Tag::Emphasis) => name.push('*'),
+                Event::Start(Tag::Strong) | Event::End(Tag::Strong) => name.push_str("**"),
+                Event::SoftBreak | Event::HardBreak => name.push(' '),
+                _ => return Err(self.error(offset, "unexpected element in the title of a chapter")),
+            }
+        }
+        Ok(Chapter::new(name, PathBuf::from(dest)))
+    }
 
-fn parse_line(l: &str) -> Option<BookItem> {
-    debug!("[fn]: parse_line");
+    fn skip_to_end(&mut self) {
+        let mut depth = 1;
+        for (_, event) in &mut self.events {
+            match event {
+                Event::Start(_) => depth += 1,
+                Event::End(_) => depth -= 1,
+                _ => {},
+            }
+            if depth == 0 {
+                break;
+            }
+        }
+    }
 
-    // Remove leading and trailing spaces or tabs
-    let line = l.trim_matches(|c: char| c == ' ' || c == '\t');
+    // The source from `offset`, without the indentation of the element
+    fn element_at(&self, offset: usize) -> &'a str {
+        self.summary[offset..].trim_start()
+    }
 
-    // Spacers are "------"
-    if line.starts_with("--") {
-        debug!("[*]: Line is spacer");
-        return Some(BookItem::Spacer);
+    fn error(&self, offset: usize, message: &str) -> Error {
+        let offset = self.summary.len() - self.element_at(offset).len();
+        let before = &self.summary[..offset];
+        let line = before.matches('\n').count() + 1;
+        let column = before.rsplit('\n').next().unwrap_or("").chars().count() + 1;
+        Error::new(ErrorKind::Other, format!("{}:{}: {}", line, column, message))
     }
+}
 
-    if let Some(c) = line.chars().nth(0) {
-        match c {
-            // List item
-            '-' | '*' => {
-                debug!("[*]: Line is list element");
+/// Removes the chapters for which `excluded` returns `true`, with their sub-chapters, and numbers
+/// the remaining chapters again so that the numbers have no gaps
+pub fn remove_chapters<F>(items: Vec<BookItem>, excluded: &F) -> Vec<BookItem>
+    where F: Fn(&Path) -> bool
+{
+    let mut items = filter_chapters(items, excluded);
+    renumber(&mut items, "");
+    items
+}
 
-                if let Some((name, path)) = read_link(line) {
-                    return Some(BookItem::Chapter("0".to_owned(), Chapter::new(name, path)));
-                } else {
+fn filter_chapters<F>(items: Vec<BookItem>, excluded: &F) -> Vec<BookItem>
+    where F: Fn(&Path) -> bool
+{
+    items.into_iter()
+        .filter_map(|item| match item {
+            BookItem::Chapter(section, mut ch) => {
+                if excluded(&ch.path) {
                     return None;
                 }
+                ch.sub_items = filter_chapters(ch.sub_items, excluded);
+                Some(BookItem::Chapter(section, ch))
             },
-            // Non-list element
-            '[' => {
-                debug!("[*]: Line is a link element");
+            BookItem::Affix(ref ch) if excluded(&ch.path) => None,
+            item => Some(item),
+        })
+        .collect()
+}
 
-                if let Some((name, path)) = read_link(line) {
-                    return Some(BookItem::Affix(Chapter::new(name, path)));
-                } else {
-                    return None;
-                }
-            },
-            _ => {},
+/// Numbers the chapters again after they were removed or moved, `prefix` is the section of the parent
+/// chapter, e.g. "2.1."
+pub fn renumber(items: &mut [BookItem], prefix: &str) {
+    let (mut numbered, mut appendices) = (0, 0);
+
+    for item in items {
+        if let BookItem::Chapter(ref mut section, ref mut ch) = *item {
+            *section = if !prefix.is_empty() {
+                numbered += 1;
+                format!("{}{}.", prefix, numbered)
+            } else if section.starts_with(|c: char| c.is_ascii_alphabetic()) {
+                appendices += 1;
+                format!("{}.", letters(appendices))
+            } else {
+                numbered += 1;
+                format!("{}.", numbered)
+            };
+            renumber(&mut ch.sub_items, section);
         }
     }
-
-    None
 }
 
-fn read_link(line: &str) -> Option<(String, PathBuf)> {
-    let mut start_delimitor;
-    let mut end_delimitor;
+/// Adds a chapter to the text of a `SUMMARY.md`, after the chapter `after` and its sub-chapters, with the same
+/// indentation. Without `after` it comes after the last numbered chapter. The other lines are kept as they are.
+pub fn insert_chapter(summary: &str, title: &str, path: &Path, after: Option<&Path>) -> Result<String> {
+    let lines: Vec<&str> = summary.lines().collect();
+    let is_item = |line: &str| ["- ", "* ", "+ "].iter().any(|m| line.trim_start().starts_with(m));
+    let indent = |line: &str| line.len() - line.trim_start().len();
+
+    // The line of the chapter the new one comes after, or the line it is inserted at
+    let (line, at) = match after {
+        Some(after) => {
+            match lines.iter().position(|line| link_target(line).map(normalize) == Some(normalize(after))) {
+                Some(i) => (Some(i), i + 1),
+                None => {
+                    return Err(Error::new(ErrorKind::Other,
+                                          format!("there is no chapter `{}` in SUMMARY.md", after.display())))
+                },
+            }
+        },
+        None => {
+            let numbered = |line: &&str| indent(line) == 0 && (line.starts_with("- ") || line.starts_with("* "));
+            match lines.iter().rposition(|line| numbered(line) && link_target(line).is_some()) {
+                Some(i) => (Some(i), i + 1),
+                // Before the appendices and the suffix chapters
+                None => {
+                    let end = lines.iter()
+                        .position(|line| line.starts_with("+ ") || is_separator(line))
+                        .unwrap_or(lines.len());
+                    (None, end)
+                },
+            }
+        },
+    };
+
+    let (at, entry) = match line {
+        Some(i) => {
+            let spaces = &lines[i][..indent(lines[i])];
+            let marker = lines[i].trim_start().chars().next().unwrap_or('-');
+            // The sub-chapters of the chapter, indented further on the following lines
+            let mut end = at;
+            while end < lines.len() && !lines[end].trim().is_empty() && indent(lines[end]) > indent(lines[i]) {
+                end += 1;
+            }
+            let entry = if is_item(lines[i]) {
+                format!("{}{} [{}]({})", spaces, marker, title, path.display())
+            } else {
+                format!("[{}]({})", title, path.display())
+            };
+            (end, entry)
+        },
+        None => (at, format!("- [{}]({})", title, path.display())),
+    };
+    Ok(join_lines(&lines, at, &entry, summary.ends_with('\n')))
+}
 
-    // In the future, support for list item that is not a link
-    // Not sure if I should error on line I can't parse or just ignore them...
-    if let Some(i) = line.find('[') {
-        start_delimitor = i;
-    } else {
-        debug!("[*]: '[' not found, this line is not a link. Ignoring...");
-        return None;
+// The lines with `entry` inserted at `at`
+fn join_lines(lines: &[&str], at: usize, entry: &str, newline: bool) -> String {
+    let mut result: Vec<&str> = lines[..at].to_vec();
+    result.push(entry);
+    result.extend_from_slice(&lines[at..]);
+    let mut s = result.join("\n");
+    if newline || at == lines.len() {
+        s.push('\n');
     }
+    s
+}
+
+// `intro.md` in `- [Introduction](intro.md)`
+fn link_target(line: &str) -> Option<&str> {
+    let start = line.find("](")? + 2;
+    let end = line[start..].find(')')? + start;
+    Some(line[start..end].trim())
+}
+
+// The paths of `SUMMARY.md` can start with `./`
+fn normalize<P: AsRef<Path>>(path: P) -> PathBuf {
+    path.as_ref().components().filter(|c| *c != Component::CurDir).collect()
+}
 
-    if let Some(i) = line[start_delimitor..].find("](") {
-        end_delimitor = start_delimitor + i;
-    } else {
-        debug!("[*]: '](' not found, this line is not a link. Ignoring...");
-        return None;
+// `---` or `***` between the numbered chapters and the suffix chapters
+fn is_separator(line: &str) -> bool {
+    let line: String = line.chars().filter(|c| !c.is_whitespace()).collect();
+    line.len() >= 3 && (line.chars().all(|c| c == '-') || line.chars().all(|c| c == '*'))
+}
+
+// 1 => "A", 26 => "Z", 27 => "AA"
+fn letters(n: i32) -> String {
+    let mut n = n;
+    let mut letters = vec![];
+    while n > 0 {
+        n -= 1;
+        letters.insert(0, (b'A' + (n % 26) as u8) as char);
+        n /= 26;
     }
+    letters.into_iter().collect()
+}
+
 
-    let name = line[start_delimitor + 1..end_delimitor].to_owned();
+// ---------------------------------------------------------------------------------
+//      Tests
+//
+
+#[cfg(test)]
+fn sections(items: &[BookItem]) -> Vec<String> {
+    BookItems { items: items, current_index: 0, stack: vec![] }
+        .map(|item| match *item {
+            BookItem::Chapter(ref s, ref ch) => format!("{} {}", s, ch.name),
+            BookItem::Affix(ref ch) => ch.name.clone(),
+            BookItem::Spacer => "---".to_owned(),
+        })
+        .collect()
+}
+
+#[test]
+fn test_parse_summary_appendices() {
+    let summary = "# Summary\n\n\
+                   [Preface](preface.md)\n\n\
+                   - [Intro](intro.md)\n    \
+                       - [Install](install.md)\n\
+                   + [Glossary](glossary.md)\n    \
+                       - [Terms](terms.md)\n\
+                   + [Changelog](changelog.md)\n\n\
+                   ---\n\n\
+                   [Contributors](contributors.md)\n";
+    let items = parse_summary(summary).unwrap();
+
+    assert_eq!(sections(&items),
+               vec!["Preface", "1. Intro", "1.1. Install", "A. Glossary", "A.1. Terms", "B. Changelog", "---",
+                    "Contributors"]);
+}
 
-    start_delimitor = end_delimitor + 1;
-    if let Some(i) = line[start_delimitor..].find(')') {
-        end_delimitor = start_delimitor + i;
-    } else {
-        debug!("[*]: ')' not found, this line is not a link. Ignoring...");
-        return None;
+#[test]
+fn test_parse_summary_titles() {
+    let summary = "[The `mdbook` *tool*](intro.md)\n\n\
+                   - [**Usage**][usage]\n  \
+                     - [Draft]()\n\n\
+                   [usage]: usage/index.md\n";
+    let items = parse_summary(summary).unwrap();
+
+    assert_eq!(sections(&items), vec!["The `mdbook` *tool*", "1. **Usage**", "1.1. Draft"]);
+    if let BookItem::Chapter(_, ref ch) = items[1] {
+        assert_eq!(ch.path, PathBuf::from("usage/index.md"));
+        assert_eq!(ch.sub_items.len(), 1);
     }
+}
+
+#[test]
+fn test_parse_summary_errors() {
+    let error = |summary: &str| parse_summary(summary).unwrap_err().to_string();
+
+    assert_eq!(error("- [Intro](intro.md)\n- Usage\n"), format!("2:3: {}", LINK_EXPECTED));
+    assert_eq!(error("- [Intro](intro.md)\n    - ---\n"), "2:5: separators can only be at the root level");
+    assert_eq!(error("+ [Glossary](glossary.md)\n\n- [Intro](intro.md)\n"),
+               "3:1: numbered chapters can not come after the appendices");
+    assert_eq!(error("- [Intro](intro.md)\n\n[Notes](notes.md)\n\n- [Usage](usage.md)\n"),
+               "5:1: chapters can not come after the suffix chapters");
+    assert_eq!(error("[Intro](intro.md) and more\n"), format!("1:19: {}", LINK_EXPECTED));
+}
+
+#[test]
+fn test_summary_to_markdown() {
+    let summary = "# Summary\n\n\
+                   [Preface](preface.md)\n\
+                   [Foreword](foreword.md)\n\n\
+                   - [The `mdbook` tool](intro.md)\n    \
+                       - [*Install*](install.md)\n        \
+                           - [Draft]()\n\
+                   - [Usage](usage.md)\n\n\
+                   + [Glossary](glossary.md)\n\n\
+                   ---\n\n\
+                   [Contributors](contributors.md)\n";
+    let items = parse_summary(summary).unwrap();
+
+    assert_eq!(summary_to_markdown(&items), summary);
+    assert_eq!(sections(&parse_summary(&summary_to_markdown(&items)).unwrap()), sections(&items));
+}
+
+#[test]
+fn test_remove_chapters() {
+    let summary = "- [Intro](intro.md)\n\
+                   - [Internal](internal/index.md)\n    \
+                       - [Secrets](internal/secrets.md)\n\
+                   - [Usage](usage.md)\n    \
+                       - [Draft](draft.md)\n    \
+                       - [Build](build.md)\n\
+                   + [Notes](internal/notes.md)\n\
+                   + [Glossary](glossary.md)\n";
+    let items = parse_summary(summary).unwrap();
+    let items = remove_chapters(items, &|path| path.starts_with("internal") || path == Path::new("draft.md"));
+
+    assert_eq!(sections(&items), vec!["1. Intro", "2. Usage", "2.1. Build", "A. Glossary"]);
+}
 
-    let path = PathBuf::from(line[start_delimitor + 1..end_delimitor].to_owned());
+#[test]
+fn test_letters() {
+    assert_eq!(letters(1), "A");
+    assert_eq!(letters(26), "Z");
+    assert_eq!(letters(27), "AA");
+}
 
-    Some((name, path))
+#[test]
+fn test_insert_chapter() {
+    let summary = "# Summary\n\n\
+                   [Preface](preface.md)\n\n\
+                   - [Intro](./intro.md)\n    \
+                       - [Install](guide/install.md)\n        \
+                           - [Linux](guide/linux.md)\n    \
+                       - [Usage](guide/usage.md)\n\
+                   - [Reference](reference.md)\n\n\
+                   + [Glossary](glossary.md)\n\n\
+                   ---\n\n\
+                   [Contributors](contributors.md)\n";
+
+    let after = Some(Path::new("guide/install.md"));
+    let inserted = insert_chapter(summary, "Upgrade", Path::new("guide/upgrade.md"), after).unwrap();
+    assert!(inserted.contains("        - [Linux](guide/linux.md)\n    - [Upgrade](guide/upgrade.md)\n    - [Usage]"));
+    assert_eq!(sections(&parse_summary(&inserted).unwrap())[4..7],
+               ["1.2. Upgrade".to_owned(), "1.3. Usage".to_owned(), "2. Reference".to_owned()]);
+
+    let inserted = insert_chapter(summary, "FAQ", Path::new("faq.md"), Some(Path::new("intro.md"))).unwrap();
+    assert!(inserted.contains("    - [Usage](guide/usage.md)\n- [FAQ](faq.md)\n- [Reference]"));
+
+    let inserted = insert_chapter(summary, "FAQ", Path::new("faq.md"), None).unwrap();
+    assert!(inserted.contains("- [Reference](reference.md)\n- [FAQ](faq.md)\n\n+ [Glossary]"));
+
+    let inserted = insert_chapter(summary, "Thanks", Path::new("thanks.md"), Some(Path::new("./contributors.md")))
+        .unwrap();
+    assert!(inserted.ends_with("[Contributors](contributors.md)\n[Thanks](thanks.md)\n"));
+
+    assert_eq!(insert_chapter("# Summary\n", "One", Path::new("one.md"), None).unwrap(),
+               "# Summary\n- [One](one.md)\n");
+    assert!(insert_chapter(summary, "FAQ", Path::new("faq.md"), Some(Path::new("missing.md"))).is_err());
 }
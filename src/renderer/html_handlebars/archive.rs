@@ -0,0 +1,61 @@
+use std::path::Path;
+use std::fs::File;
+use std::io::{Read, Write};
+use std::error::Error;
+
+use zip::{ZipWriter, CompressionMethod};
+use zip::write::FileOptions;
+
+use utils;
+
+
+/// File name of the archive, at the root of the book
+pub const ARCHIVE: &'static str = "book.zip";
+
+/// Writes every file of the rendered book in `dest` to `book.zip`, in a `book/` directory
+pub fn write_archive(dest: &Path) -> Result<(), Box<Error>> {
+    let files = try!(utils::fs::list_files(dest));
+    let mut zip = ZipWriter::new(try!(File::create(dest.join(ARCHIVE))));
+    let options = FileOptions::default().compression_method(CompressionMethod::Deflated);
+
+    for file in files.iter().filter(|f| *f != ARCHIVE) {
+        let mut content = vec![];
+        try!(try!(File::open(dest.join(file))).read_to_end(&mut content));
+
+        try!(zip.start_file(format!("book/{}", file), options));
+        try!(zip.write_all(&content));
+    }
+
+    try!(zip.finish());
+    Ok(())
+}
+
+
+// ---------------------------------------------------------------------------------
+//      Tests
+//
+
+#[test]
+fn test_write_archive() {
+    extern crate tempdir;
+    use std::fs;
+    use zip::ZipArchive;
+
+    let dest = tempdir::TempDir::new("mdbook-archive").unwrap();
+    fs::create_dir(dest.path().join("cli")).unwrap();
+    File::create(dest.path().join("index.html")).unwrap().write_all(b"<h1>Intro</h1>").unwrap();
+    File::create(dest.path().join("cli/build.html")).unwrap();
+
+    write_archive(dest.path()).unwrap();
+    // Building again does not put the previous archive in the new one
+    write_archive(dest.path()).unwrap();
+
+    let mut archive = ZipArchive::new(File::open(dest.path().join(ARCHIVE)).unwrap()).unwrap();
+    let mut names: Vec<String> = (0..archive.len()).map(|i| archive.by_index(i).unwrap().name().to_owned()).collect();
+    names.sort();
+    assert_eq!(names, vec!["book/cli/build.html", "book/index.html"]);
+
+    let mut index = String::new();
+    archive.by_name("book/index.html").unwrap().read_to_string(&mut index).unwrap();
+    assert_eq!(index, "<h1>Intro</h1>");
+}
@@ -0,0 +1,108 @@
+use utils;
+
+
+/// An Atom feed, the links are absolute urls
+pub struct Feed {
+    pub title: String,
+    /// The page the feed is about
+    pub link: String,
+    /// Where the feed is published
+    pub url: String,
+    /// Author of the entries without authors
+    pub author: String,
+    /// Newest first, the date of the first one is the date of the feed
+    pub entries: Vec<Entry>,
+}
+
+pub struct Entry {
+    pub title: String,
+    pub link: String,
+    /// RFC 3339 timestamp
+    pub updated: String,
+    pub authors: Vec<String>,
+    /// Html of the entry, its relative links are resolved from `base`
+    pub content: String,
+    pub base: String,
+}
+
+impl Feed {
+    pub fn to_xml(&self) -> String {
+        let updated = self.entries.first().map_or("1970-01-01T00:00:00Z", |e| &e.updated);
+
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n\
+                                    <feed xmlns=\"http://www.w3.org/2005/Atom\">\n");
+        xml.push_str(&format!("  <title>{}</title>\n", utils::escape_html(&self.title)));
+        xml.push_str(&format!("  <link href=\"{}\"/>\n", utils::escape_html(&self.link)));
+        xml.push_str(&format!("  <link rel=\"self\" href=\"{}\"/>\n", utils::escape_html(&self.url)));
+        xml.push_str(&format!("  <id>{}</id>\n", utils::escape_html(&self.url)));
+        xml.push_str(&format!("  <updated>{}</updated>\n", utils::escape_html(updated)));
+        if !self.author.is_empty() {
+            xml.push_str(&author_xml(&self.author, "  "));
+        }
+
+        for entry in &self.entries {
+            xml.push_str("  <entry>\n");
+            xml.push_str(&format!("    <title>{}</title>\n", utils::escape_html(&entry.title)));
+            xml.push_str(&format!("    <link href=\"{}\"/>\n", utils::escape_html(&entry.link)));
+            xml.push_str(&format!("    <id>{}</id>\n", utils::escape_html(&entry.link)));
+            xml.push_str(&format!("    <updated>{}</updated>\n", utils::escape_html(&entry.updated)));
+            for author in &entry.authors {
+                xml.push_str(&author_xml(author, "    "));
+            }
+            xml.push_str(&format!("    <content type=\"html\" xml:base=\"{}\">{}</content>\n",
+                                  utils::escape_html(&entry.base),
+                                  utils::escape_html(&entry.content)));
+            xml.push_str("  </entry>\n");
+        }
+
+        xml.push_str("</feed>\n");
+        xml
+    }
+}
+
+fn author_xml(name: &str, indent: &str) -> String {
+    format!("{0}<author>\n{0}  <name>{1}</name>\n{0}</author>\n", indent, utils::escape_html(name))
+}
+
+
+// ---------------------------------------------------------------------------------
+//      Tests
+//
+
+#[test]
+fn test_feed_xml() {
+    let feed = Feed {
+        title: "News & updates".to_owned(),
+        link: "https://example.com/book/news/index.html".to_owned(),
+        url: "https://example.com/book/news/atom.xml".to_owned(),
+        author: "The team".to_owned(),
+        entries: vec![Entry {
+                          title: "Version 2".to_owned(),
+                          link: "https://example.com/book/news/v2.html".to_owned(),
+                          updated: "2017-06-20T00:00:00Z".to_owned(),
+                          authors: vec!["Ann".to_owned()],
+                          content: "<p>See <a href=\"cli/init.html\">init</a></p>".to_owned(),
+                          base: "https://example.com/book/".to_owned(),
+                      }],
+    };
+
+    assert_eq!(feed.to_xml(),
+               "<?xml version=\"1.0\" encoding=\"utf-8\"?>\n\
+                <feed xmlns=\"http://www.w3.org/2005/Atom\">\n  \
+                <title>News &amp; updates</title>\n  \
+                <link href=\"https://example.com/book/news/index.html\"/>\n  \
+                <link rel=\"self\" href=\"https://example.com/book/news/atom.xml\"/>\n  \
+                <id>https://example.com/book/news/atom.xml</id>\n  \
+                <updated>2017-06-20T00:00:00Z</updated>\n  \
+                <author>\n    <name>The team</name>\n  </author>\n  \
+                <entry>\n    \
+                <title>Version 2</title>\n    \
+                <link href=\"https://example.com/book/news/v2.html\"/>\n    \
+                <id>https://example.com/book/news/v2.html</id>\n    \
+                <updated>2017-06-20T00:00:00Z</updated>\n    \
+                <author>\n      <name>Ann</name>\n    </author>\n    \
+                <content type=\"html\" xml:base=\"https://example.com/book/\">&lt;p&gt;See &lt;a \
+                href=&quot;cli/init.html&quot;&gt;init&lt;/a&gt;&lt;/p&gt;</content>\n  \
+                </entry>\n\
+                </feed>\n");
+}
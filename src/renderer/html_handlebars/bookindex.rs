@@ -0,0 +1,131 @@
+use std::path::Path;
+use std::collections::BTreeMap;
+
+use utils;
+use super::helpers::directives::replace_directives;
+use renderer::Warning;
+
+
+/// Back-of-book index built from the `{{#index term}}` markers of the chapters. Every marker is
+/// replaced by an anchor and the terms are listed alphabetically on a generated page, with a link
+/// to each of their occurrences.
+pub struct BookIndex {
+    // Entries keyed by the lowercase term, so that the terms are sorted without regard to case
+    entries: BTreeMap<String, Entry>,
+    markers: usize,
+}
+
+struct Entry {
+    term: String,
+    links: Vec<(String, String)>,
+}
+
+impl BookIndex {
+    pub fn new() -> Self {
+        BookIndex {
+            entries: BTreeMap::new(),
+            markers: 0,
+        }
+    }
+
+    /// Collects the markers of a chapter and returns its markdown with the markers replaced by
+    /// anchors, `\{{#index ...}}` escapes a marker and the markers in code are left as they are
+    pub fn add_chapter(&mut self, path: &Path, name: &str, s: &str, warnings: &mut Vec<Warning>) -> String {
+        let page = utils::fs::path_to_link(path);
+
+        replace_directives(s, "index", |term| {
+            if term.is_empty() {
+                warnings.push(Warning::new(path, "{{#index}} without a term"));
+                return None;
+            }
+
+            self.markers += 1;
+            let id = format!("index-{}", self.markers);
+
+            self.entries
+                .entry(term.to_lowercase())
+                .or_insert_with(|| Entry { term: term.to_owned(), links: vec![] })
+                .links
+                .push((format!("{}#{}", page, id), name.to_owned()));
+
+            Some(format!("<a class=\"index-marker\" id=\"{}\"></a>", id))
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Html of the index page, the terms are grouped by their first letter
+    pub fn to_html(&self) -> String {
+        let mut html = String::from("<h1>Index</h1>\n");
+        let mut letter = None;
+
+        for (key, entry) in &self.entries {
+            let first = key.chars().next().map(|c| c.to_uppercase().collect::<String>());
+            if first != letter {
+                if letter.is_some() {
+                    html.push_str("</ul>\n");
+                }
+                html.push_str(&format!("<h2>{}</h2>\n<ul class=\"book-index\">\n", utils::escape_html(first.as_ref().unwrap())));
+                letter = first;
+            }
+
+            let links = entry.links
+                .iter()
+                .map(|(link, name)| format!("<a href=\"{}\">{}</a>", link, utils::escape_html(name)))
+                .collect::<Vec<_>>()
+                .join(", ");
+            html.push_str(&format!("<li>{}: {}</li>\n", utils::escape_html(&entry.term), links));
+        }
+
+        if letter.is_some() {
+            html.push_str("</ul>\n");
+        }
+
+        html
+    }
+}
+
+// ---------------------------------------------------------------------------------
+//      Tests
+//
+
+#[test]
+fn test_book_index_markers() {
+    let mut index = BookIndex::new();
+    let mut warnings = vec![];
+    let s = index.add_chapter(Path::new("cli/init.md"),
+                              "init",
+                              "{{#index Theme}}Themes \\{{#index escaped}}{{#index}}",
+                              &mut warnings);
+
+    assert_eq!(s, "<a class=\"index-marker\" id=\"index-1\"></a>Themes {{#index escaped}}{{#index}}");
+    assert!(!index.is_empty());
+    assert_eq!(warnings, vec![Warning::new(Path::new("cli/init.md"), "{{#index}} without a term")]);
+}
+
+#[test]
+fn test_book_index_markers_in_code() {
+    let mut index = BookIndex::new();
+    let s = "Mark a term with `{{#index term}}`:\n\n```markdown\n{{#index borrow checker}}\n```\n\n    {{#index a}}";
+
+    assert_eq!(index.add_chapter(Path::new("index.md"), "Index", s, &mut vec![]), s);
+    assert!(index.is_empty());
+}
+
+#[test]
+fn test_book_index_html() {
+    let mut index = BookIndex::new();
+    index.add_chapter(Path::new("cli/init.md"), "init", "{{#index theme}} {{#index Borrow checker}}", &mut vec![]);
+    index.add_chapter(Path::new("format/theme.md"), "Theme", "{{#index theme}}", &mut vec![]);
+
+    assert_eq!(index.to_html(),
+               "<h1>Index</h1>\n\
+                <h2>B</h2>\n<ul class=\"book-index\">\n\
+                <li>Borrow checker: <a href=\"cli/init.html#index-2\">init</a></li>\n\
+                </ul>\n\
+                <h2>T</h2>\n<ul class=\"book-index\">\n\
+                <li>theme: <a href=\"cli/init.html#index-1\">init</a>, <a href=\"format/theme.html#index-3\">Theme</a></li>\n\
+                </ul>\n");
+}
@@ -0,0 +1,102 @@
+use std::path::{Path, PathBuf};
+
+use utils;
+use utils::git::git;
+
+
+/// File name of the feed of the changed chapters, at the root of the book
+pub const CHANGES: &'static str = "changes.xml";
+
+/// A file of the source directory changed by the recent commits
+#[derive(Debug, Clone, PartialEq)]
+pub struct Change {
+    /// Relative to the source directory
+    pub path: PathBuf,
+    /// Date of the last commit changing it, RFC 3339
+    pub date: String,
+    /// Authors of the commits, the last one first
+    pub authors: Vec<String>,
+    /// Subjects of the commits, the last one first
+    pub subjects: Vec<String>,
+}
+
+/// The files of the source directory `src` changed by the last `commits` commits of its git repository, the last
+/// changed first. The merges are left out.
+pub fn recent_changes(src: &Path, commits: usize) -> Result<Vec<Change>, String> {
+    let count = format!("--max-count={}", commits);
+    // Without `core.quotePath=false` the paths with non-ASCII characters are quoted and escaped
+    let log = try!(git(src,
+                       &["-c", "core.quotePath=false", "log", &count, "--no-merges", "--format=%x00%aI%x09%an%x09%s",
+                         "--name-only", "--relative", "--", "."])
+        .map_err(|e| e.to_string()));
+    Ok(parse_log(&log))
+}
+
+// The commits start with a NUL, followed by their date, author and subject separated by tabs, then their files
+fn parse_log(log: &str) -> Vec<Change> {
+    let mut changes: Vec<Change> = vec![];
+    for commit in log.split('\0').skip(1) {
+        let mut lines = commit.lines();
+        let header: Vec<&str> = lines.next().unwrap_or_default().splitn(3, '\t').collect();
+        if header.len() < 3 {
+            continue;
+        }
+        let (date, author, subject) = (header[0], header[1], header[2]);
+
+        for file in lines.map(|line| line.trim()).filter(|line| !line.is_empty()) {
+            let path = PathBuf::from(file);
+            if let Some(change) = changes.iter_mut().find(|c| c.path == path) {
+                if !change.authors.iter().any(|a| a == author) {
+                    change.authors.push(author.to_owned());
+                }
+                change.subjects.push(subject.to_owned());
+                continue;
+            }
+            changes.push(Change {
+                path: path,
+                date: date.to_owned(),
+                authors: vec![author.to_owned()],
+                subjects: vec![subject.to_owned()],
+            });
+        }
+    }
+    changes
+}
+
+/// Html of the entry of a change in the feed, the subjects of its commits
+pub fn change_html(change: &Change) -> String {
+    let mut html = String::from("<ul>\n");
+    for subject in &change.subjects {
+        html.push_str(&format!("<li>{}</li>\n", utils::escape_html(subject)));
+    }
+    html.push_str("</ul>\n");
+    html
+}
+
+
+// ---------------------------------------------------------------------------------
+//      Tests
+//
+
+#[test]
+fn test_parse_log() {
+    let log = "\0\
+               2017-06-21T10:00:00+02:00\tAnn\tDocument the --open flag\n\ncli/build.md\ncli/serve.md\n\
+               \0\
+               2017-06-20T09:30:00+02:00\tBob\tFix a typo\n\ncli/build.md\n\
+               \0\
+               2017-06-19T08:00:00+02:00\tAnn\tAdd the <init> command\n\ncli/init.md\ncafé.md\n";
+    let changes = parse_log(log);
+
+    assert_eq!(changes.len(), 4);
+    assert_eq!(changes[0],
+               Change {
+                   path: PathBuf::from("cli/build.md"),
+                   date: "2017-06-21T10:00:00+02:00".to_owned(),
+                   authors: vec!["Ann".to_owned(), "Bob".to_owned()],
+                   subjects: vec!["Document the --open flag".to_owned(), "Fix a typo".to_owned()],
+               });
+    assert_eq!(changes[1].path, Path::new("cli/serve.md"));
+    assert_eq!(change_html(&changes[2]), "<ul>\n<li>Add the &lt;init&gt; command</li>\n</ul>\n");
+    assert_eq!(changes[3].path, Path::new("café.md"));
+}
@@ -0,0 +1,91 @@
+use std::path::{Component, Path, PathBuf};
+use std::error::Error;
+use std::collections::BTreeMap;
+
+use serde_json;
+use serde_json::value::{ToJson, Value};
+
+use utils;
+use book::bookitem::BookItem;
+
+
+/// File name of the list of the chapters, at the root of the book
+pub const CHAPTERS: &'static str = "chapters.json";
+
+/// Lists the chapters of `SUMMARY.md` in their order for the tools reading the structure of the book, like
+/// link checkers or search crawlers. Every chapter has the path of its source relative to the source directory,
+/// the path of its page, its title, its section and its `level`, 0 for the chapters at the root. The chapters
+/// without a file have no source and no page, and the prefix and suffix chapters have no section.
+pub fn chapters_json(title: &str, items: &[BookItem]) -> Result<String, Box<Error>> {
+    let mut chapters = vec![];
+    list_chapters(items, 0, &mut chapters);
+
+    let mut json = BTreeMap::new();
+    json.insert("title".to_owned(), title.to_json());
+    json.insert("chapters".to_owned(), chapters.to_json());
+    Ok(try!(serde_json::to_string_pretty(&json)))
+}
+
+fn list_chapters(items: &[BookItem], level: usize, chapters: &mut Vec<BTreeMap<String, Value>>) {
+    for item in items {
+        let (section, ch) = match *item {
+            BookItem::Chapter(ref section, ref ch) => (Some(section), ch),
+            BookItem::Affix(ref ch) => (None, ch),
+            BookItem::Spacer => continue,
+        };
+
+        let source = if ch.path == PathBuf::new() {
+            None
+        } else {
+            // Without the `./` of `SUMMARY.md`, and with `/` on windows too
+            let path: PathBuf = ch.path.components().filter(|c| *c != Component::CurDir).collect();
+            Some(utils::fs::path_to_url(&path))
+        };
+        let page = source.as_ref().map(|s| utils::fs::path_to_link(Path::new(s)));
+
+        let mut chapter = BTreeMap::new();
+        chapter.insert("title".to_owned(), ch.name.to_json());
+        chapter.insert("source".to_owned(), source.map_or(Value::Null, |s| s.to_json()));
+        chapter.insert("path".to_owned(), page.map_or(Value::Null, |p| p.to_json()));
+        chapter.insert("section".to_owned(), section.map_or(Value::Null, |s| s.to_json()));
+        chapter.insert("level".to_owned(), level.to_json());
+        chapters.push(chapter);
+
+        list_chapters(&ch.sub_items, level + 1, chapters);
+    }
+}
+
+
+// ---------------------------------------------------------------------------------
+//      Tests
+//
+
+#[test]
+fn test_chapters_json() {
+    use parse;
+
+    let items = parse::summary::parse_summary("# Summary\n\n[Intro](./README.md)\n\n- [CLI](cli/README.md)\n    \
+                                               - [init](./cli/init.md)\n- [Draft]()\n\n---\n\n[Credits](credits.md)")
+        .unwrap();
+    let json: Value = serde_json::from_str(&chapters_json("Book", &items).unwrap()).unwrap();
+
+    assert_eq!(json.lookup("title").and_then(|t| t.as_str()), Some("Book"));
+    let chapters = json.lookup("chapters").and_then(|c| c.as_array()).unwrap();
+    let fields: Vec<String> = chapters.iter()
+        .map(|c| {
+            let field = |key: &str| c.lookup(key).map_or("-".to_owned(), |v| v.as_str().unwrap_or("-").to_owned());
+            format!("{} {} {} {} {}",
+                    field("title"),
+                    field("source"),
+                    field("path"),
+                    field("section"),
+                    c.lookup("level").and_then(|l| l.as_u64()).unwrap())
+        })
+        .collect();
+    assert_eq!(fields,
+               vec!["Intro README.md README.html - 0",
+                    "CLI cli/README.md cli/README.html 1. 0",
+                    "init cli/init.md cli/init.html 1.1. 1",
+                    "Draft - - 2. 0",
+                    "Credits credits.md credits.html - 0"]);
+}
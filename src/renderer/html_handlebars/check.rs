@@ -0,0 +1,178 @@
+use std::path::{Path, PathBuf};
+use std::collections::{HashMap, HashSet};
+
+use pulldown_cmark::{html, Event, Parser, Tag};
+
+use book::markdownconfig::MarkdownConfig;
+use renderer::Warning;
+use renderer::html_handlebars::hbs_renderer::{BOOK_INDEX, SOURCES};
+use renderer::html_handlebars::helpers::directives::replace_all_directives;
+use renderer::html_handlebars::helpers::extensions::heading_attributes;
+use renderer::html_handlebars::helpers::links::{is_asset, linked_chapter, normalize};
+use renderer::html_handlebars::{archive, changes, chapters, icons, manifest, offline, print, robots, search, sitemap,
+                                split, tags};
+use utils;
+
+
+// Files written at the root of the output by the renderer, the links to them are not reported
+const GENERATED: &'static [&'static str] = &["index.html",
+                                             print::PRINT,
+                                             search::SEARCH_PAGE,
+                                             chapters::CHAPTERS,
+                                             sitemap::SITEMAP,
+                                             manifest::MANIFEST,
+                                             archive::ARCHIVE,
+                                             changes::CHANGES,
+                                             robots::ROBOTS,
+                                             icons::FAVICON_PNG,
+                                             icons::FAVICON_ICO,
+                                             icons::FAVICON_SVG,
+                                             icons::APPLE_TOUCH_ICON,
+                                             offline::SERVICE_WORKER];
+
+/// The anchors of a chapter, the `id` of its elements and the anchors `book.js` gives to its headings
+pub fn chapter_anchors(markdown: &str, config: &MarkdownConfig) -> Vec<String> {
+    let mut events: Vec<Event> = Parser::new_ext(markdown, config.options()).collect();
+    if config.heading_attributes {
+        events = heading_attributes(events);
+    }
+
+    let mut html = String::new();
+    html::push_html(&mut html, events.into_iter());
+    split::anchors(&html)
+}
+
+/// Reports the links of a chapter to a markdown file that is not a chapter of `SUMMARY.md`, to a page that is
+/// neither in the source directory `src` nor written by the renderer, and to an anchor that is not in the chapter
+/// it points to. The links to the other files, like `guide.pdf`, are checked by `check_assets`. `anchors` has the
+/// anchors of every chapter, by their path without `./`, and `mount` is the directory of the embedded book
+/// containing the chapter, or an empty path.
+pub fn check_links(markdown: &str,
+                   config: &MarkdownConfig,
+                   chapter: &Path,
+                   mount: &Path,
+                   src: &Path,
+                   anchors: &HashMap<PathBuf, Vec<String>>,
+                   warnings: &mut Vec<Warning>) {
+    let chapters: HashSet<PathBuf> = anchors.keys().cloned().collect();
+    let current = normalize(chapter).unwrap_or_default();
+
+    for event in Parser::new_ext(markdown, config.options()) {
+        let dest = match event {
+            Event::Start(Tag::Link(dest, _)) => dest,
+            _ => continue,
+        };
+        if dest.is_empty() || dest.contains("://") || dest.starts_with("mailto:") || dest.starts_with('/') {
+            continue;
+        }
+
+        let (target, fragment) = match dest.find('#') {
+            Some(i) => (&dest[..i], &dest[i + 1..]),
+            None => (&dest[..], ""),
+        };
+        let target = target.split('?').next().unwrap_or_default();
+
+        let linked = if target.is_empty() {
+            current.clone()
+        } else if target.ends_with(".md") {
+            match linked_chapter(target, chapter, mount, &chapters) {
+                Some(path) => path,
+                None => {
+                    warnings.push(Warning::new(chapter,
+                                               format!("the link to `{}` does not point to a chapter of SUMMARY.md",
+                                                       dest)));
+                    continue;
+                },
+            }
+        } else if is_asset(target) {
+            // Reported with their line by `check_assets`
+            continue;
+        } else {
+            // The other links are relative to the root of the book, or of the embedded book
+            let page = normalize(&mount.join(target)).unwrap_or_default();
+            match chapters.iter().find(|c| c.with_extension("html") == page) {
+                Some(path) => path.clone(),
+                None => {
+                    if !src.join(target).exists() && !is_generated(&page) {
+                        warnings.push(Warning::new(chapter,
+                                                   format!("the link to `{}` does not point to a chapter or a \
+                                                            file of the book",
+                                                           dest)));
+                    }
+                    continue;
+                },
+            }
+        };
+
+        let found = anchors.get(&linked).is_some_and(|a| a.iter().any(|anchor| anchor == fragment));
+        if !fragment.is_empty() && !found {
+            warnings.push(Warning::new(chapter,
+                                       format!("the link to `{}` points to no heading or element of {}",
+                                               dest,
+                                               linked.display())));
+        }
+    }
+}
+
+/// Adds the files included by the `{{#include path}}` directives of `markdown`, the content of `file`, and by
+/// the files they include to `included`, with their canonical path
+pub fn included_files(markdown: &str, file: &Path, included: &mut HashSet<PathBuf>) {
+    let dir = file.parent().map(|dir| dir.to_owned()).unwrap_or_default();
+    let mut found = vec![];
+    replace_all_directives(markdown, "include", |argument| {
+        if let Ok(path) = dir.join(argument).canonicalize() {
+            found.push(path);
+        }
+        None
+    });
+
+    for path in found {
+        if included.insert(path.clone()) {
+            if let Ok(content) = utils::fs::file_to_string(&path) {
+                included_files(&content, &path, included);
+            }
+        }
+    }
+}
+
+/// Whether a page or a file of the root of the book is written by the renderer, like `print.html`
+pub fn is_generated(page: &Path) -> bool {
+    GENERATED.iter().any(|file| page == Path::new(file)) || page == Path::new(BOOK_INDEX).with_extension("html") ||
+    page.starts_with(tags::TAGS_DIR) || page.starts_with(SOURCES)
+}
+
+
+// ---------------------------------------------------------------------------------
+//      Tests
+//
+
+#[test]
+fn test_check_links() {
+    let config = MarkdownConfig::new();
+    let mut anchors = HashMap::new();
+    anchors.insert(PathBuf::from("README.md"),
+                   chapter_anchors("# Introduction\n\n<p id=\"goals\">Goals</p>", &config));
+    anchors.insert(PathBuf::from("cli/init.md"), chapter_anchors("# The init command\n\n## --theme", &config));
+    assert_eq!(anchors[Path::new("cli/init.md")], vec!["The-init-command", "--theme"]);
+
+    let markdown = "[ok](../README.md#goals) [top](#The-init-command) [page](cli/init.html#--theme) \
+                    [print](print.html) [web](https://example.com#nothing)\n\n\
+                    [missing](build.md) [heading](#Options) [file](downloads/book.pdf) [page](downloads/) \
+                    [anchor](../README.md#Usage)";
+    let mut warnings = vec![];
+    check_links(markdown,
+                &config,
+                Path::new("cli/init.md"),
+                Path::new(""),
+                Path::new("/nonexistent"),
+                &anchors,
+                &mut warnings);
+
+    let chapter = Path::new("cli/init.md");
+    assert_eq!(warnings,
+               vec![Warning::new(chapter, "the link to `build.md` does not point to a chapter of SUMMARY.md"),
+                    Warning::new(chapter, "the link to `#Options` points to no heading or element of cli/init.md"),
+                    Warning::new(chapter, "the link to `downloads/` does not point to a chapter or a file of the book"),
+                    Warning::new(chapter, "the link to `../README.md#Usage` points to no heading or element of \
+                                           README.md")]);
+}
@@ -0,0 +1,207 @@
+use std::borrow::Cow;
+use std::cmp::Reverse;
+
+use pulldown_cmark::{Parser, Event, Tag};
+
+use utils;
+use super::search::heading_anchor;
+
+
+/// Terms defined in `glossary.md`. Every level 2 heading of the file is a term, the text below it
+/// is its definition:
+///
+/// ```markdown
+/// ## Crate
+///
+/// A compilation unit in Rust.
+/// ```
+pub struct Glossary {
+    terms: Vec<Term>,
+}
+
+struct Term {
+    name: String,
+    definition: String,
+}
+
+impl Glossary {
+    pub fn parse(markdown: &str) -> Self {
+        let mut terms = vec![];
+        let mut term: Option<Term> = None;
+        let mut in_heading = false;
+
+        for event in Parser::new_ext(markdown, utils::markdown_options()) {
+            match event {
+                Event::Start(Tag::Header(level)) => {
+                    terms.extend(term.take());
+                    if level == 2 {
+                        term = Some(Term { name: String::new(), definition: String::new() });
+                        in_heading = true;
+                    }
+                },
+                Event::End(Tag::Header(_)) => in_heading = false,
+                Event::Text(text) | Event::Html(text) | Event::InlineHtml(text) => {
+                    if let Some(ref mut term) = term {
+                        if in_heading {
+                            term.name.push_str(&text);
+                        } else {
+                            term.definition.push_str(&text);
+                        }
+                    }
+                },
+                Event::End(Tag::Paragraph) | Event::SoftBreak | Event::HardBreak => {
+                    if let Some(ref mut term) = term {
+                        term.definition.push(' ');
+                    }
+                },
+                _ => {},
+            }
+        }
+        terms.extend(term);
+
+        let mut terms: Vec<Term> = terms.into_iter()
+            .map(|t| {
+                Term {
+                    name: t.name.trim().to_owned(),
+                    definition: t.definition.split_whitespace().collect::<Vec<_>>().join(" "),
+                }
+            })
+            .filter(|t| !t.name.is_empty())
+            .collect();
+
+        // Longer terms first, so that "borrow checker" wins over "borrow"
+        terms.sort_by_key(|t| Reverse(t.name.len()));
+
+        Glossary { terms: terms }
+    }
+
+    /// Links the first occurrence of every term in the text of a chapter to its definition.
+    /// Occurrences in headings, links, images and code are left alone.
+    pub fn link_terms<'a, I>(&self, events: I) -> Vec<Event<'a>>
+        where I: Iterator<Item = Event<'a>>
+    {
+        let lowercase_terms: Vec<String> = self.terms.iter().map(|t| t.name.to_ascii_lowercase()).collect();
+        let mut linked = vec![false; self.terms.len()];
+        let mut skip = 0;
+        let mut linked_events = vec![];
+
+        for event in events {
+            match event {
+                Event::Start(Tag::Header(_)) |
+                Event::Start(Tag::Link(..)) |
+                Event::Start(Tag::Image(..)) |
+                Event::Start(Tag::CodeBlock(_)) |
+                Event::Start(Tag::Code) => skip += 1,
+                Event::End(Tag::Header(_)) |
+                Event::End(Tag::Link(..)) |
+                Event::End(Tag::Image(..)) |
+                Event::End(Tag::CodeBlock(_)) |
+                Event::End(Tag::Code) => skip -= 1,
+                Event::Text(ref text) if skip == 0 => {
+                    self.link_text(text, &lowercase_terms, &mut linked, &mut linked_events);
+                    continue;
+                },
+                _ => {},
+            }
+
+            linked_events.push(event);
+        }
+
+        linked_events
+    }
+
+    fn link_text<'a>(&self, text: &str, lowercase_terms: &[String], linked: &mut [bool], events: &mut Vec<Event<'a>>) {
+        let mut rest = text;
+
+        loop {
+            // ASCII lowercase keeps the byte offsets of the original text
+            let lowercase = rest.to_ascii_lowercase();
+            let next = lowercase_terms.iter()
+                .enumerate()
+                .filter(|&(i, _)| !linked[i])
+                .filter_map(|(i, term)| find_word(&lowercase, term).map(|pos| (pos, i)))
+                .min();
+
+            let (pos, i) = match next {
+                Some(next) => next,
+                None => break,
+            };
+
+            let end = pos + lowercase_terms[i].len();
+            if pos > 0 {
+                events.push(Event::Text(Cow::Owned(rest[..pos].to_owned())));
+            }
+            events.push(Event::InlineHtml(Cow::Owned(format!(
+                "<a class=\"glossary-term\" href=\"glossary.html#{}\">{}<span class=\"glossary-definition\" \
+                 role=\"tooltip\">{}</span></a>",
+                heading_anchor(&self.terms[i].name),
+                utils::escape_html(&rest[pos..end]),
+                utils::escape_html(&self.terms[i].definition)))));
+
+            linked[i] = true;
+            rest = &rest[end..];
+        }
+
+        if !rest.is_empty() {
+            events.push(Event::Text(Cow::Owned(rest.to_owned())));
+        }
+    }
+}
+
+// Position of `word` in `s` when it is not part of a longer word
+fn find_word(s: &str, word: &str) -> Option<usize> {
+    let mut start = 0;
+
+    while let Some(i) = s[start..].find(word) {
+        let i = start + i;
+        let end = i + word.len();
+
+        let starts_word = !s[..i].chars().next_back().map(char::is_alphanumeric).unwrap_or(false);
+        let ends_word = !s[end..].chars().next().map(char::is_alphanumeric).unwrap_or(false);
+        if starts_word && ends_word {
+            return Some(i);
+        }
+
+        start = i + s[i..].chars().next().map_or(1, |c| c.len_utf8());
+    }
+
+    None
+}
+
+// ---------------------------------------------------------------------------------
+//      Tests
+//
+
+#[cfg(test)]
+fn render(glossary: &Glossary, markdown: &str) -> String {
+    let mut s = String::new();
+    ::pulldown_cmark::html::push_html(&mut s, glossary.link_terms(Parser::new(markdown)).into_iter());
+    s
+}
+
+#[test]
+fn test_glossary_parse() {
+    let glossary = Glossary::parse("# Glossary\n\n## Crate\n\nA compilation\nunit.\n\n## Borrow checker\n\nChecks \
+                                    borrows.\n\n# Other\n\nNot a definition.");
+    let terms: Vec<(&str, &str)> = glossary.terms.iter().map(|t| (&t.name[..], &t.definition[..])).collect();
+
+    assert_eq!(terms, vec![("Borrow checker", "Checks borrows."), ("Crate", "A compilation unit.")]);
+}
+
+#[test]
+fn test_glossary_links_first_occurrence() {
+    let glossary = Glossary::parse("## Crate\n\nA <compilation> unit.");
+
+    assert_eq!(render(&glossary, "Every crate is a crate, not a crates."),
+               "<p>Every <a class=\"glossary-term\" href=\"glossary.html#Crate\">crate<span \
+                class=\"glossary-definition\" role=\"tooltip\">A &lt;compilation&gt; unit.</span></a> is a \
+                crate, not a crates.</p>\n");
+}
+
+#[test]
+fn test_glossary_skips_code_and_headings() {
+    let glossary = Glossary::parse("## Crate\n\nA compilation unit.");
+
+    assert_eq!(render(&glossary, "# Crate\n\n`crate` [crate](x.html)"),
+               "<h1>Crate</h1>\n<p><code>crate</code> <a href=\"x.html\">crate</a></p>\n");
+}
@@ -1,85 +1,579 @@
 use renderer::html_handlebars::helpers;
-use renderer::Renderer;
+use renderer::html_handlebars::helpers::crossref::CrossRefs;
+use renderer::html_handlebars::helpers::figures::Figures;
+use renderer::html_handlebars::helpers::headings::HeadingNumbers;
+use renderer::html_handlebars::helpers::math::Katex;
+use renderer::html_handlebars::helpers::run::CodeRunner;
+use renderer::html_handlebars::helpers::quiz::Quizzes;
+use renderer::html_handlebars::search::{self, SearchIndex};
+use renderer::html_handlebars::glossary::Glossary;
+use renderer::html_handlebars::bookindex::BookIndex;
+use renderer::html_handlebars::offline;
+use renderer::html_handlebars::manifest;
+use renderer::html_handlebars::icons;
+use renderer::html_handlebars::chapters;
+use renderer::html_handlebars::archive;
+use renderer::html_handlebars::print::{self, PrintPage};
+use renderer::html_handlebars::highlight::{self, Bundle};
+use renderer::html_handlebars::tags::{self, Tags};
+use renderer::html_handlebars::atom;
+use renderer::html_handlebars::changes;
+use renderer::html_handlebars::style;
+use renderer::html_handlebars::redirects;
+use renderer::html_handlebars::robots;
+use renderer::html_handlebars::sitemap;
+use renderer::html_handlebars::check;
+use renderer::html_handlebars::split;
+use renderer::html_handlebars::structured_data::{self, Article};
+use renderer::html_handlebars::templates::Templates;
+use renderer::{Renderer, Warning};
 use book::MDBook;
+use book::timings::Progress;
 use book::bookitem::BookItem;
+use book::frontmatter::FrontMatter;
+use book::blog::{self, BlogConfig, Post};
+use book::markdownconfig::MarkdownConfig;
+use book::subbook::SubBook;
+use book::htmlconfig::{ChangesConfig, CodeConfig, Layout, MathMode, Permalink, PermalinkVisibility};
+use book::bookconfig::{BookConfig, toml_value_to_json_value};
+use book::plugins::{self, PluginChapter, PluginKind};
 use {utils, theme};
 
-use std::path::{Path, PathBuf};
+use std::path::{Component, Path, PathBuf};
 use std::fs::{self, File};
 use std::error::Error;
 use std::io::{self, Read};
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::time::{Instant, SystemTime};
 
-use handlebars::Handlebars;
+use handlebars::{Handlebars, HelperDef, RenderError, RenderContext, Helper, Context};
+use pulldown_cmark::{Parser, Event, html};
 
 use serde_json;
 use serde_json::value::ToJson;
 
+use sha2::{Digest, Sha384};
+use base64;
 
-pub struct HtmlHandlebars;
+
+// Name of this renderer in `book.toml` and in the `{{#if output.html}}` blocks of the chapters
+const RENDERER: &'static str = "html";
+
+// Path of the glossary, relative to the source directory
+const GLOSSARY: &'static str = "glossary.md";
+
+// Page generated for the back-of-book index
+pub const BOOK_INDEX: &'static str = "book-index.md";
+
+// Directory of the copies of the markdown files of the chapters
+pub const SOURCES: &'static str = "_sources";
+
+// Maximum length of the description of a page taken from its first paragraph, what search engines show
+const DESCRIPTION_LENGTH: usize = 160;
+
+pub struct HtmlHandlebars {
+    helpers: Vec<(String, Arc<HelperDef>)>,
+    // The theme of the last build, `mdbook serve` and `mdbook watch` only load it again when it changed
+    loaded: RefCell<Option<Rc<LoadedTheme>>>,
+}
+
+// The theme with its templates and helpers registered
+struct LoadedTheme {
+    key: ThemeKey,
+    theme: theme::Theme,
+    templates: Templates,
+    handlebars: Handlebars,
+    // The languages of the highlight.js bundle, `None` when it is not a bundle
+    highlight_languages: Option<HashSet<String>>,
+    // The warnings of the loading, reported by every build
+    warnings: Vec<Warning>,
+}
+
+// What a theme is loaded from, the files of the theme directory are compared with their size and modification time
+#[derive(PartialEq)]
+struct ThemeKey {
+    path: PathBuf,
+    code: CodeConfig,
+    files: Vec<(String, u64, Option<SystemTime>)>,
+}
 
 impl HtmlHandlebars {
     pub fn new() -> Self {
-        HtmlHandlebars
+        HtmlHandlebars {
+            helpers: Vec::new(),
+            loaded: RefCell::new(None),
+        }
     }
-}
 
-impl Renderer for HtmlHandlebars {
-    fn render(&self, book: &MDBook) -> Result<(), Box<Error>> {
-        debug!("[fn]: render");
+    /// Registers a handlebars helper for the templates of the theme, it replaces the helper of the theme or
+    /// the built-in helper with the same name.
+    ///
+    /// ```no_run
+    /// # extern crate mdbook;
+    /// # extern crate handlebars;
+    /// use mdbook::renderer::HtmlHandlebars;
+    /// use handlebars::{Handlebars, RenderError, RenderContext, Helper, Context};
+    ///
+    /// fn year(_: &Context, _: &Helper, _: &Handlebars, rc: &mut RenderContext) -> Result<(), RenderError> {
+    ///     try!(rc.writer.write_all(b"2017"));
+    ///     Ok(())
+    /// }
+    ///
+    /// # fn main() {
+    /// let renderer = HtmlHandlebars::new().with_helper("year", year);
+    /// # }
+    /// ```
+    pub fn with_helper<H: HelperDef + 'static>(mut self, name: &str, helper: H) -> Self {
+        self.helpers.push((name.to_owned(), Arc::new(helper)));
+        self
+    }
+
+    // The theme of the book, the one of the previous build when the theme directory and the code options are the same
+    fn load_theme(&self, book: &MDBook) -> Result<Rc<LoadedTheme>, Box<Error>> {
+        let key = ThemeKey::new(book);
+        if let Some(ref loaded) = *self.loaded.borrow() {
+            if loaded.key == key {
+                debug!("[*]: The theme did not change");
+                return Ok(loaded.clone());
+            }
+        }
+
         let mut handlebars = Handlebars::new();
+        let mut warnings = vec![];
 
         // Load theme
-        let theme = theme::Theme::new(book.get_theme_path());
+        let mut theme = theme::Theme::new(book.get_theme_path());
+        let code_config = &book.get_config().html_config.code;
+        if let Some(ref languages) = code_config.highlight_languages {
+            trim_highlight_js(&mut theme, languages, &mut warnings);
+        }
+        for path in &code_config.additional_languages {
+            try!(add_highlight_language(&mut theme, &book.get_theme_path().join(path)));
+        }
+        // The code blocks of the other languages are rendered as plain text
+        let highlight_languages = ::std::str::from_utf8(&theme.highlight_js)
+            .ok()
+            .and_then(Bundle::parse)
+            .map(|bundle| bundle.language_names());
 
-        // Register template
+        // Register templates, chapters can select another one than `index` in their front matter
         debug!("[*]: Register handlebars template");
-        try!(handlebars.register_template_string("index", try!(String::from_utf8(theme.index))));
+        let templates = try!(Templates::new(&theme));
+        try!(templates.register(&mut handlebars));
 
         // Register helpers
         debug!("[*]: Register handlebars helpers");
+        self.register_helpers(&mut handlebars, &theme);
+
+        let loaded = Rc::new(LoadedTheme {
+            key: key,
+            theme: theme,
+            templates: templates,
+            handlebars: handlebars,
+            highlight_languages: highlight_languages,
+            warnings: warnings,
+        });
+        *self.loaded.borrow_mut() = Some(loaded.clone());
+        Ok(loaded)
+    }
+
+    // The built-in helpers, then the helpers of the theme and the ones of `with_helper`, replacing them
+    fn register_helpers(&self, handlebars: &mut Handlebars, theme: &theme::Theme) {
         handlebars.register_helper("toc", Box::new(helpers::toc::RenderToc));
+        handlebars.register_helper("navbar", Box::new(helpers::toc::RenderNavbar));
         handlebars.register_helper("previous", Box::new(helpers::navigation::previous));
         handlebars.register_helper("next", Box::new(helpers::navigation::next));
+        for name in theme.helpers.keys() {
+            let template = format!("helpers/{}", name);
+            handlebars.register_helper(name, Box::new(helpers::template::TemplateHelper::new(&template)));
+        }
+        for (name, helper) in &self.helpers {
+            handlebars.register_helper(name, Box::new(SharedHelper(helper.clone())));
+        }
+    }
+}
+
+/// Helper registered with `with_helper`, shared between the builds of the renderer
+struct SharedHelper(Arc<HelperDef>);
+
+impl HelperDef for SharedHelper {
+    fn call(&self, c: &Context, h: &Helper, r: &Handlebars, rc: &mut RenderContext) -> Result<(), RenderError> {
+        self.0.call(c, h, r, rc)
+    }
+}
+
+impl Renderer for HtmlHandlebars {
+    fn render(&self, book: &MDBook) -> Result<Vec<Warning>, Box<Error>> {
+        debug!("[fn]: render");
+        // Time of the phases of the build, the time between the other phases is rendering
+        let timings = book.get_timings();
+        let mut clock = Instant::now();
+
+        let loaded = try!(self.load_theme(book));
+        let theme = &loaded.theme;
+        let templates = &loaded.templates;
+        let handlebars = &loaded.handlebars;
+        let highlight_languages = &loaded.highlight_languages;
+        let mut warnings = loaded.warnings.clone();
 
         let mut data = try!(make_data(book));
 
-        // Print version
-        let mut print_content: String = String::new();
+        // Subresource integrity hashes for the static files referenced by the template
+        if book.get_config().html_config.subresource_integrity {
+            data.insert("integrity".to_owned(), integrity_hashes(&theme).to_json());
+        }
+
+        // The index is not needed when the search is handled by DocSearch
+        let search_config = &book.get_config().html_config.search;
+        let build_search_index = search_config.enable && search_config.docsearch.is_none();
+        let mut search_index = SearchIndex::new();
+        let mut book_index = BookIndex::new();
+        let mut tags = Tags::new();
+        let mut total_word_count = 0;
+
+        // Former locations of the chapters, from the `aliases` of their front matter: page, chapter, title
+        let mut aliases = vec![];
+
+        // `{{ book.title }}`, `{{ book.author }}` and `{{ book.description }}` can be overridden in `[variables]`
+        let mut variables = BTreeMap::new();
+        variables.insert("title".to_owned(), book.get_title().to_owned());
+        variables.insert("author".to_owned(), book.get_author().to_owned());
+        variables.insert("description".to_owned(), book.get_description().to_owned());
+        variables.extend(book.get_config().variables.clone());
+
+        let doc_crates = book.get_config().html_config.doc_crates
+            .iter()
+            .map(|(name, root)| (name.clone(), book.get_root().join(root)))
+            .collect();
+        let mut runner = Some(CodeRunner::new(&book.get_config().html_config.run))
+            .filter(|_| book.get_config().html_config.run.enable);
+        let mut quizzes = Some(Quizzes::new()).filter(|_| book.get_config().html_config.quizzes);
+        let math_config = &book.get_config().html_config.math;
+        let mut katex = Some(Katex::new(math_config)).filter(|_| math_config.mode == MathMode::Katex);
+        let mut chapter_count = 0;
+
+        // Script transforming the chapters before anything else
+        let script_path = book.get_root().join(helpers::script::SCRIPT);
+        let script = if script_path.exists() { Some(try!(helpers::script::Script::load(&script_path))) } else { None };
+
+        // Paths of the chapters, used to rewrite the links between them
+        let chapters = book.iter()
+            .filter_map(|item| match *item {
+                BookItem::Chapter(_, ref ch) |
+                BookItem::Affix(ref ch) if ch.path != PathBuf::new() => Some(ch.path.clone()),
+                _ => None,
+            })
+            .collect::<HashSet<_>>();
+
+        // Previous and next links of the pages, in the order of SUMMARY.md
+        let links = book.iter()
+            .filter_map(|item| match *item {
+                BookItem::Chapter(_, ref ch) |
+                BookItem::Affix(ref ch) if ch.path != PathBuf::new() => {
+                    Some(helpers::navigation::chapter_link(&ch.name, &ch.path))
+                },
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        let mut position: usize = 0;
+
+        let (crossrefs, figures) = try!(collect_references(book, &mut warnings));
+        let glossary = if book.get_config().html_config.glossary {
+            try!(load_glossary(book, &chapters, &mut warnings))
+        } else {
+            None
+        };
+
+        // The blog chapter lists the posts, the feed has the html of the newest ones with their authors
+        let blog_config = book.get_config().blog_config.as_ref().filter(|_| !book.get_blog_posts().is_empty());
+        let mut post_contents = HashMap::new();
+        if let Some(blog_config) = blog_config {
+            if book.get_config().html_config.site_url.is_some() {
+                data.insert("blog_feed".to_owned(), blog_config.feed_path().to_string_lossy().to_json());
+                data.insert("blog_title".to_owned(), blog_title(book, blog_config).to_json());
+            }
+        }
+        let html_config = &book.get_config().html_config;
+        if let (Some(changes_config), Some(_)) = (html_config.changes.as_ref(), html_config.site_url.as_ref()) {
+            data.insert("changes_feed".to_owned(), changes::CHANGES.to_json());
+            data.insert("changes_title".to_owned(), changes_title(book, changes_config).to_json());
+        }
+
+        // Pages already written, a chapter overwriting one of them is reported
+        let mut pages = HashSet::new();
+        // Pages listed in the sitemap, the ones the search engines can index
+        let mut sitemap_pages = vec![];
 
         // Check if dest directory exists
         debug!("[*]: Check if destination directory exists");
-        if let Err(_) = fs::create_dir_all(book.get_dest()) {
+        if !book.is_dry_run() && fs::create_dir_all(book.get_dest()).is_err() {
             return Err(Box::new(io::Error::new(io::ErrorKind::Other,
                                                "Unexpected error when constructing destination path")));
         }
 
+        // Print version, the chapters are written to it as they are rendered
+        let mut print_page = if book.get_config().html_config.print {
+            data.insert("path".to_owned(), "print.md".to_json());
+            data.insert("chapter_title".to_owned(), "Print".to_json());
+            data.insert("content".to_owned(), print::CONTENT_MARKER.to_json());
+            data.insert("path_to_root".to_owned(), utils::fs::path_to_root(Path::new("print.md")).to_json());
+            insert_canonical(&mut data, book, Path::new(print::PRINT));
+            let rendered = try!(templates.render(&handlebars, "index", &data));
+
+            let mut print_page = if book.is_dry_run() {
+                book.add_dry_run_file(print::PRINT);
+                PrintPage::discarded()
+            } else {
+                try!(PrintPage::create(book.get_dest(), &rendered))
+            };
+            try!(print_page.push(&title_page_html(book.get_config())));
+            if let Some(ref colophon) = book.get_config().colophon {
+                match utils::fs::file_to_string(&book.get_src().join(colophon)) {
+                    Ok(markdown) => {
+                        try!(print_page.push(&colophon_html(&markdown, &book.get_config().markdown_config)))
+                    },
+                    Err(e) => {
+                        warnings.push(Warning::new(colophon, format!("the colophon can not be read: {}", e)));
+                    },
+                }
+            }
+            Some(print_page)
+        } else {
+            None
+        };
+
         // Render a file for every entry in the book
         let mut index = true;
+        let mut progress = Progress::new(chapters.len());
         for item in book.iter() {
 
+            let section = match *item {
+                BookItem::Chapter(ref s, _) => Some(&s[..]),
+                _ => None,
+            };
+
             match *item {
                 BookItem::Chapter(_, ref ch) |
                 BookItem::Affix(ref ch) => {
                     if ch.path != PathBuf::new() {
+                        progress.chapter(&ch.path.to_string_lossy());
+                        timings.lap("render", &mut clock);
 
-                        let path = book.get_src().join(&ch.path);
+                        let (chapter_src, source_path) = book.get_chapter_source(&ch.path);
+                        let path = chapter_src.join(source_path);
+
+                        debug!("[*]: Reading file: {:?}", path);
+                        let (content, decoding) = try!(book.read_chapter(&ch.path));
+                        if let Some(decoding) = decoding {
+                            warnings.push(Warning::new(&ch.path, decoding));
+                        }
 
-                        debug!("[*]: Opening file: {:?}", path);
-                        let mut f = try!(File::open(&path));
-                        let mut content: String = String::new();
+                        let (front_matter, markdown) = FrontMatter::split(&content).map_err(|e| {
+                            io::Error::new(io::ErrorKind::Other, format!("{}: {}", path.display(), e))
+                        })?;
+                        timings.lap("parse", &mut clock);
 
-                        debug!("[*]: Reading file");
-                        try!(f.read_to_string(&mut content));
+                        // An `.html` chapter is wrapped in the template as it is written, it has no markdown
+                        let raw_html = helpers::rawhtml::is_html_chapter(&ch.path);
+
+                        // Authors and date, from the front matter or the defaults of the config
+                        let html_config = &book.get_config().html_config;
+
+                        // The markdown of the chapter as it is written, for the readers
+                        if html_config.sources && !raw_html {
+                            let source = Path::new(SOURCES)
+                                .join(ch.path.components().filter(|c| *c != Component::CurDir).collect::<PathBuf>());
+                            try!(book.write_file(&source, content.as_bytes()));
+                            data.insert("source".to_owned(), utils::fs::path_to_url(&source).to_json());
+                        } else {
+                            data.remove("source");
+                        }
+
+                        let authors = front_matter.get_str_list("authors")
+                            .map(|a| a.iter().map(|s| s.to_string()).collect())
+                            .unwrap_or_else(|| html_config.chapter_authors.clone());
+                        let date = front_matter.get("date")
+                            .and_then(|v| v.as_str().or_else(|| v.as_datetime()))
+                            .map(|d| d.to_owned())
+                            .or_else(|| html_config.chapter_date.clone());
+                        let chapter_tags: Vec<&str> = front_matter.get_str_list("tags")
+                            .unwrap_or_default()
+                            .into_iter()
+                            .map(|tag| tag.trim())
+                            .filter(|tag| !tag.is_empty())
+                            .collect();
+                        tags.add_chapter(&ch.path, &ch.name, &chapter_tags);
+                        for alias in front_matter.get_str_list("aliases").unwrap_or_default() {
+                            match redirects::alias_page(alias) {
+                                Some(page) => aliases.push((page, ch.path.clone(), ch.name.clone())),
+                                None => {
+                                    warnings.push(Warning::new(&ch.path,
+                                                               format!("the alias `{}` is outside of the book",
+                                                                       alias)))
+                                },
+                            }
+                        }
+                        let template = front_matter.get_str("template").unwrap_or("index");
+                        if handlebars.get_template(template).is_none() {
+                            return Err(Box::new(io::Error::new(io::ErrorKind::Other,
+                                                               format!("{}: unknown template `{}`, there is no \
+                                                                        `{}.hbs` in the theme",
+                                                                       path.display(),
+                                                                       template,
+                                                                       template))));
+                        }
+
+                        // The file as it is written, for the lines of the warnings
+                        let source = &content;
+
+                        let mut content = if raw_html {
+                            markdown.to_owned()
+                        } else {
+                            // Snippets of the other files, before anything else sees the content
+                            let markdown = helpers::include::render_includes(markdown,
+                                                                             &path,
+                                                                             chapter_src,
+                                                                             &book.get_config().includes,
+                                                                             &ch.path,
+                                                                             &mut warnings);
+                            let mut markdown = match script {
+                                Some(ref script) => {
+                                    try!(script.run(&markdown, &ch.name, &ch.path, section, &front_matter))
+                                },
+                                None => markdown,
+                            };
+                            let preprocessors = book.get_config()
+                                .plugins
+                                .iter()
+                                .filter(|p| p.kind == PluginKind::Preprocessor);
+                            for plugin in preprocessors {
+                                let chapter = PluginChapter {
+                                    name: &ch.name,
+                                    path: &ch.path,
+                                    section: section,
+                                    content: &markdown,
+                                };
+                                markdown = try!(plugins::preprocess(plugin, book.get_config(), RENDERER, &chapter));
+                            }
+
+                            // Content of the other renderers is dropped before anything else
+                            let mut content = helpers::conditionals::render_conditionals(&markdown,
+                                                                                         RENDERER,
+                                                                                         &ch.path,
+                                                                                         &mut warnings);
+                            content = helpers::variables::replace_variables(&content,
+                                                                            &variables,
+                                                                            &ch.path,
+                                                                            &mut warnings);
+                            content = crossrefs.render(&content, &ch.path, &mut warnings);
+                            content = figures.render(&content, &ch.path, &mut warnings);
+                            content = helpers::details::render_details(&content, &ch.path, &mut warnings);
+                            content = helpers::embeds::render_embeds(&content, &ch.path, &mut warnings);
+                            content = book_index.add_chapter(&ch.path, &ch.name, &content, &mut warnings);
+                            content
+                        };
+                        helpers::links::check_assets(&content, source, chapter_src, &ch.path, &mut warnings);
+
+                        // The text of an html chapter as markdown, for the search index, the word count and the
+                        // description
+                        let html_text = if raw_html {
+                            helpers::rawhtml::text_markdown(&content)
+                        } else {
+                            String::new()
+                        };
+                        let text = if raw_html { &html_text } else { &content };
+
+                        if build_search_index && front_matter.get_bool("search") != Some(false) {
+                            search_index.add_chapter(&ch.path, &ch.name, text);
+                        }
+
+                        let word_count = utils::word_count(text);
+                        total_word_count += word_count;
+                        chapter_count += 1;
+
+                        // Items from the source of the crates, the paths of the crates are relative to the root
+                        if !raw_html && !book.get_config().html_config.doc_crates.is_empty() {
+                            content = helpers::docitems::render_doc_items(&content, &doc_crates, &ch.path, &mut warnings);
+                        }
 
                         // Parse for playpen links
-                        if let Some(p) = path.parent() {
-                            content = helpers::playpen::render_playpen(&content, p);
+                        if let Some(p) = path.parent().filter(|_| !raw_html) {
+                            content = helpers::playpen::render_playpen(&content, p, &ch.path, &mut warnings);
                         }
+                        timings.lap("preprocess", &mut clock);
+
+                        // The description of the page, from the markdown after the preprocessing
+                        let description = front_matter.get_str("description")
+                            .map(|d| d.to_owned())
+                            .or_else(|| utils::first_paragraph(if raw_html { &html_text } else { &content },
+                                                               DESCRIPTION_LENGTH))
+                            .unwrap_or_else(|| book.get_description().to_owned());
 
                         // Render markdown using the pulldown-cmark crate
-                        content = utils::render_markdown(&content);
-                        print_content.push_str(&content);
+                        if !raw_html {
+                            let chapter_glossary = glossary.as_ref().filter(|_| ch.path != Path::new(GLOSSARY));
+                            let numbered_section = section.filter(|_| book.get_config().html_config.numbered_headings);
+                            let markdown_config = &book.get_config().markdown_config;
+                            let mount = book.get_sub_book(&ch.path).map_or(Path::new(""), |b| &b.dir);
+                            let events = parse_markdown(&content,
+                                                        &ch.path,
+                                                        mount,
+                                                        &chapters,
+                                                        chapter_glossary,
+                                                        markdown_config,
+                                                        &mut warnings);
+                            let events = match runner {
+                                Some(ref mut runner) => runner.run_blocks(events, &ch.path, &mut warnings),
+                                None => events,
+                            };
+                            let events = match quizzes {
+                                Some(ref mut quizzes) => quizzes.render_blocks(events, &ch.path, &mut warnings),
+                                None => events,
+                            };
+                            let events = helpers::codeblocks::render_titles(events);
+                            let events = match highlight_languages {
+                                Some(ref languages) => {
+                                    highlight::plain_unknown_languages(events, languages, &ch.path, &mut warnings)
+                                },
+                                None => events,
+                            };
+                            timings.lap("parse", &mut clock);
+                            content = render_events(events, numbered_section, markdown_config);
+                        }
+                        if front_matter.get_bool("sanitize").unwrap_or(book.get_config().html_config.sanitize) {
+                            content = helpers::sanitize::sanitize(&content);
+                        }
+                        if !raw_html {
+                            if let Some(ref mut katex) = katex {
+                                content = katex.render_math(&content, &ch.path, &mut warnings);
+                            }
+                            if book.get_config().html_config.code.line_anchors {
+                                content = helpers::codeblocks::line_anchors(&content);
+                            }
+                        }
+                        let html_config = &book.get_config().html_config;
+                        let site_url = html_config.site_url.as_ref().map(|u| &u[..]);
+                        content = helpers::externals::mark_external_links(&content,
+                                                                          &html_config.external_links,
+                                                                          site_url);
+                        if let Some(blog_config) = blog_config {
+                            let posts = book.get_blog_posts();
+                            if blog::same_chapter(&ch.path, &blog_config.chapter) {
+                                content.push_str(&blog_listing_html(posts));
+                            } else if posts.iter().any(|p| p.path == ch.path) {
+                                post_contents.insert(ch.path.clone(), (content.clone(), authors.clone()));
+                            }
+                        }
+                        if let Some(ref mut print_page) = print_page {
+                            // Unless `render` leaves the chapter out of it
+                            if front_matter.renders("print") {
+                                try!(print_page.push(&chapter_meta_html(&authors, date.as_ref().map(|d| &d[..]))));
+                                try!(print_page.push(&content));
+                            }
+                        }
 
                         // Update the context with data for this file
                         let path = ch.path.to_str().ok_or(io::Error::new(io::ErrorKind::Other,
@@ -87,63 +581,269 @@ impl Renderer for HtmlHandlebars {
                         data.insert("path".to_owned(), path.to_json());
                         data.insert("content".to_owned(), content.to_json());
                         data.insert("chapter_title".to_owned(), ch.name.to_json());
+                        data.insert("body_classes".to_owned(), body_classes(&ch.path, &front_matter).to_json());
+                        data.insert("description".to_owned(), description.to_json());
+                        let noindex = front_matter.get_bool("noindex").unwrap_or(html_config.noindex);
+                        data.insert("noindex".to_owned(), noindex.to_json());
+                        if html_config.structured_data {
+                            let article = Article {
+                                title: &ch.name,
+                                description: &description,
+                                authors: &authors,
+                                date: date.as_ref().map(|d| &d[..]),
+                                position: position + 1,
+                                path: &ch.path,
+                            };
+                            let json_ld = structured_data::chapter_json_ld(book.get_config(), &article);
+                            data.insert("json_ld".to_owned(), json_ld.to_json());
+                        }
+                        match section {
+                            Some(section) => data.insert("chapter_section".to_owned(), section.to_json()),
+                            None => data.remove("chapter_section"),
+                        };
+                        data.insert("word_count".to_owned(), word_count.to_json());
+                        data.insert("reading_time".to_owned(), utils::reading_time(word_count).to_json());
+                        let has_meta = !authors.is_empty() || date.is_some() || !chapter_tags.is_empty();
+                        data.insert("chapter_meta".to_owned(), has_meta.to_json());
+                        if authors.is_empty() {
+                            data.remove("chapter_authors");
+                        } else {
+                            data.insert("chapter_authors".to_owned(), authors.join(", ").to_json());
+                        }
+                        match date {
+                            Some(ref date) => data.insert("chapter_date".to_owned(), date.to_json()),
+                            None => data.remove("chapter_date"),
+                        };
+                        if chapter_tags.is_empty() {
+                            data.remove("chapter_tags");
+                        } else {
+                            data.insert("chapter_tags".to_owned(), tag_links(&chapter_tags).to_json());
+                        }
                         data.insert("path_to_root".to_owned(), utils::fs::path_to_root(&ch.path).to_json());
+                        match position.checked_sub(1).and_then(|p| links.get(p)) {
+                            Some(previous) => data.insert("previous".to_owned(), previous.to_json()),
+                            None => data.remove("previous"),
+                        };
+                        match links.get(position + 1) {
+                            Some(next) => data.insert("next".to_owned(), next.to_json()),
+                            None => data.remove("next"),
+                        };
+                        position += 1;
 
-                        // Render the handlebars template with the data
-                        debug!("[*]: Render template");
-                        let rendered = try!(handlebars.render("index", &data));
-
-                        // Write to file
-                        let filename = Path::new(&ch.path).with_extension("html");
-                        info!("[*] Creating {:?} ✓", filename.display());
-                        try!(book.write_file(filename, &rendered.into_bytes()));
+                        // The long chapters are split in several pages, with the links to the other pages after
+                        // their content
+                        let split_config = &html_config.split;
+                        let parts = if front_matter.get_bool("split").unwrap_or(split_config.enable) &&
+                                       word_count >= split_config.min_words {
+                            split::split_chapter(&content, &ch.path, split_config.level)
+                        } else {
+                            vec![]
+                        };
 
-                        // Create an index.html from the first element in SUMMARY.md
-                        if index {
-                            debug!("[*]: index.html");
-
-                            let mut content = String::new();
-                            let _source = try!(File::open(book.get_dest().join(&ch.path.with_extension("html"))))
-                                .read_to_string(&mut content);
+                        // Render the handlebars template with the data, for every page of the chapter
+                        debug!("[*]: Render template");
+                        let chapter_pages: Vec<(PathBuf, String, String)> = if parts.len() > 1 {
+                            parts.iter()
+                                .enumerate()
+                                .map(|(i, part)| {
+                                    let title = match part.title {
+                                        Some(ref title) => format!("{}: {}", ch.name, title),
+                                        None => ch.name.clone(),
+                                    };
+                                    let html = format!("{}{}", part.html, split::pages_html(&parts, i, &ch.name));
+                                    (part.page.clone(), title, html)
+                                })
+                                .collect()
+                        } else {
+                            vec![(ch.path.with_extension("html"), ch.name.clone(), content.clone())]
+                        };
+                        for (i, (filename, title, html)) in chapter_pages.into_iter().enumerate() {
+                            data.insert("chapter_title".to_owned(), title.to_json());
+                            data.insert("content".to_owned(), html.to_json());
+                            insert_canonical(&mut data, book, &filename);
+                            let rendered = try!(templates.render(&handlebars, template, &data));
 
-                            // This could cause a problem when someone displays code containing <base href=...>
-                            // on the front page, however this case should be very very rare...
-                            content = content.lines()
-                                .filter(|line| !line.contains("<base href="))
-                                .collect::<Vec<&str>>()
-                                .join("\n");
+                            // Write to file
+                            if !pages.insert(filename.clone()) {
+                                warnings.push(Warning::new(&ch.path,
+                                                           format!("overwrites {}, written for another chapter",
+                                                                   filename.display())));
+                            }
+                            if !noindex {
+                                sitemap_pages.push(filename.clone());
+                            }
+                            info!("[*] Creating {:?} ✓", filename.display());
+                            try!(book.write_file(&filename, &rendered.into_bytes()));
 
-                            try!(book.write_file("index.html", content.as_bytes()));
+                            // The first page of the first chapter of SUMMARY.md is also index.html, at the root
+                            if index && i == 0 {
+                                debug!("[*]: index.html");
+                                data.insert("path_to_root".to_owned(),
+                                            utils::fs::path_to_root(Path::new("index.md")).to_json());
+                                let rendered = try!(templates.render(&handlebars, template, &data));
+                                data.insert("path_to_root".to_owned(), utils::fs::path_to_root(&ch.path).to_json());
 
-                            info!("[*] Creating index.html from {:?} ✓",
-                                  book.get_dest().join(&ch.path.with_extension("html")));
-                            index = false;
+                                pages.insert(PathBuf::from("index.html"));
+                                try!(book.write_file("index.html", &rendered.into_bytes()));
+                                info!("[*] Creating index.html from {:?} ✓", filename.display());
+                            }
                         }
+                        index = false;
                     }
                 },
                 _ => {},
             }
         }
 
+        progress.finish();
+
         // Print version
+        if let Some(print_page) = print_page {
+            try!(print_page.finish());
+            info!("[*] Creating print.html ✓");
+        }
 
-        // Update the context with data for this file
-        data.insert("path".to_owned(), "print.md".to_json());
-        data.insert("content".to_owned(), print_content.to_json());
-        data.insert("path_to_root".to_owned(), utils::fs::path_to_root(Path::new("print.md")).to_json());
+        // The other pages do not have the data of the last chapter
+        let chapter_keys = ["chapter_meta", "chapter_authors", "chapter_date", "chapter_tags", "word_count",
+                            "reading_time", "previous", "next", "body_classes", "chapter_section", "source",
+                            "canonical"];
+        for key in &chapter_keys {
+            data.remove(*key);
+        }
+        data.insert("noindex".to_owned(), book.get_config().html_config.noindex.to_json());
+        data.insert("description".to_owned(), book.get_description().to_json());
+        if book.get_config().html_config.structured_data {
+            data.insert("json_ld".to_owned(), structured_data::book_json_ld(book.get_config()).to_json());
+        }
+
+        // Back-of-book index, only when the chapters contain `{{#index}}` markers
+        if !book_index.is_empty() {
+            data.insert("path".to_owned(), BOOK_INDEX.to_json());
+            data.insert("content".to_owned(), book_index.to_html().to_json());
+            data.insert("chapter_title".to_owned(), "Index".to_json());
+            data.insert("path_to_root".to_owned(), utils::fs::path_to_root(Path::new(BOOK_INDEX)).to_json());
+            insert_canonical(&mut data, book, &Path::new(BOOK_INDEX).with_extension("html"));
+
+            let rendered = try!(templates.render(&handlebars, "index", &data));
+            try!(book.write_file(Path::new(BOOK_INDEX).with_extension("html"), &rendered.into_bytes()));
+            sitemap_pages.push(Path::new(BOOK_INDEX).with_extension("html"));
+            info!("[*] Creating book-index.html ✓");
+        }
+
+        // A page by tag of the front matter of the chapters, and the list of all the tags
+        if !tags.is_empty() {
+            let index_page = (Path::new(tags::TAGS_DIR).join("index.md"), "Tags".to_owned(), tags.index_html());
+            for (path, title, html) in tags.pages().into_iter().chain(Some(index_page)) {
+                data.insert("path".to_owned(), path.to_string_lossy().to_json());
+                data.insert("content".to_owned(), html.to_json());
+                data.insert("chapter_title".to_owned(), title.to_json());
+                data.insert("path_to_root".to_owned(), utils::fs::path_to_root(&path).to_json());
 
-        // Render the handlebars template with the data
-        debug!("[*]: Render template");
-        let rendered = try!(handlebars.render("index", &data));
-        try!(book.write_file(Path::new("print").with_extension("html"), &rendered.into_bytes()));
-        info!("[*] Creating print.html ✓");
+                let filename = path.with_extension("html");
+                insert_canonical(&mut data, book, &filename);
+                let rendered = try!(templates.render(&handlebars, "index", &data));
+                try!(book.write_file(&filename, &rendered.into_bytes()));
+                info!("[*] Creating {:?} ✓", filename.display());
+                sitemap_pages.push(filename);
+            }
+        }
+
+        // Page of the search results, not indexed by the search engines
+        if build_search_index && search_config.page {
+            data.insert("path".to_owned(), search::SEARCH_PAGE.to_json());
+            data.insert("content".to_owned(), search::search_page_html(search_config.page_size).to_json());
+            data.insert("chapter_title".to_owned(), "Search".to_json());
+            data.insert("path_to_root".to_owned(), "".to_json());
+            data.insert("noindex".to_owned(), true.to_json());
+            data.remove("canonical");
+
+            let rendered = try!(templates.render(&handlebars, "index", &data));
+            try!(book.write_file(search::SEARCH_PAGE, &rendered.into_bytes()));
+            info!("[*] Creating search.html ✓");
+        }
+
+        // Redirects from the former locations of the chapters, the pages of the book are not replaced
+        for (page, chapter, title) in aliases {
+            if pages.contains(&page) || book.get_src().join(&page).exists() {
+                warnings.push(Warning::new(&chapter,
+                                           format!("the alias {} is a page of the book, no redirect is written",
+                                                   page.display())));
+                continue;
+            }
+            let html = redirects::redirect_html(&page, &chapter.with_extension("html"), &title);
+            try!(book.write_file(&page, html.as_bytes()));
+            pages.insert(page);
+        }
+
+        // Atom feed of the blog, its links have to be absolute
+        if let Some(blog_config) = blog_config {
+            match book.get_config().html_config.site_url {
+                Some(ref site_url) => {
+                    let feed = blog_feed(book, blog_config, site_url, &post_contents, &mut warnings);
+                    try!(book.write_file(blog_config.feed_path(), feed.to_xml().as_bytes()));
+                    info!("[*] Creating {:?} ✓", blog_config.feed_path().display());
+                },
+                None => {
+                    warnings.push(Warning::new(&blog_config.chapter,
+                                               "the feed of the blog needs the `site-url` of the book, it is not \
+                                                written"))
+                },
+            }
+        }
+
+        // Atom feed of the chapters changed by the recent commits
+        if let Some(ref changes_config) = html_config.changes {
+            match html_config.site_url {
+                Some(ref site_url) => {
+                    match changes_feed(book, changes_config, site_url) {
+                        Ok(feed) => {
+                            try!(book.write_file(changes::CHANGES, feed.to_xml().as_bytes()));
+                            info!("[*] Creating {:?} ✓", changes::CHANGES);
+                        },
+                        Err(e) => {
+                            warnings.push(Warning::global(format!("the feed of the changes is not written, it needs \
+                                                                   the history of git: {}",
+                                                                  e)))
+                        },
+                    }
+                },
+                None => {
+                    warnings.push(Warning::global("the feed of the changes needs the `site-url` of the book, it is \
+                                                   not written"))
+                },
+            }
+        }
+
+        // Search index
+        if build_search_index {
+            debug!("[*]: Write search index");
+            try!(book.write_file("searchindex.js", search_index.to_javascript(search_config)?.as_bytes()));
+        }
 
         // Copy static files (js, css, images, ...)
 
         debug!("[*] Copy static files");
+        timings.lap("render", &mut clock);
         try!(book.write_file("book.js", &theme.js));
         try!(book.write_file("book.css", &theme.css));
-        try!(book.write_file("favicon.png", &theme.favicon));
+        if !book.get_config().html_config.style.variables.is_empty() {
+            try!(book.write_file(style::VARIABLES_CSS,
+                                 style::variables_css(&book.get_config().html_config.style).as_bytes()));
+        }
+        for (file, content) in icons::icon_files(book.get_root(),
+                                                 &book.get_config().html_config,
+                                                 &theme.favicon,
+                                                 &mut warnings) {
+            try!(book.write_file(file, &content));
+        }
+        if let Some(ref logo) = book.get_config().html_config.logo {
+            let mut content = vec![];
+            let read = File::open(book.get_root().join(logo)).and_then(|mut f| f.read_to_end(&mut content));
+            match (logo.file_name(), read) {
+                (Some(name), Ok(_)) => try!(book.write_file(name, &content)),
+                _ => warnings.push(Warning::global(format!("the logo `{}` can not be read", logo.display()))),
+            }
+        }
         try!(book.write_file("jquery.js", &theme.jquery));
         try!(book.write_file("highlight.css", &theme.highlight_css));
         try!(book.write_file("tomorrow-night.css", &theme.tomorrow_night_css));
@@ -156,11 +856,702 @@ impl Renderer for HtmlHandlebars {
         try!(book.write_file("_FontAwesome/fonts/fontawesome-webfont.woff2", theme::FONT_AWESOME_WOFF2));
         try!(book.write_file("_FontAwesome/fonts/FontAwesome.ttf", theme::FONT_AWESOME_TTF));
 
-        // Copy all remaining files
-        try!(utils::fs::copy_files_except_ext(book.get_src(), book.get_dest(), true, &["md"]));
+        // Copy all remaining files, but the chapters written in html
+        let html_config = &book.get_config().html_config;
+        let html_chapters: Vec<&Path> = book.iter()
+            .filter_map(|item| match *item {
+                BookItem::Chapter(_, ref ch) |
+                BookItem::Affix(ref ch) if helpers::rawhtml::is_html_chapter(&ch.path) => Some(ch.path.as_path()),
+                _ => None,
+            })
+            .collect();
+        let ignore = book.get_ignore_patterns().with_files(&html_chapters);
+        // The paths of the chapters of an embedded book are relative to its source directory
+        let sub_book_ignore = |sub_book: &SubBook| {
+            let chapters: Vec<&Path> = html_chapters.iter()
+                .filter_map(|ch| ch.strip_prefix(&sub_book.dir).ok())
+                .collect();
+            sub_book.ignore.clone().with_files(&chapters)
+        };
+        if book.is_dry_run() {
+            for file in try!(utils::fs::files_except_ext(book.get_src(), book.get_dest(), &["md"], &ignore)) {
+                book.add_dry_run_file(file);
+            }
+            for sub_book in book.get_sub_books() {
+                let ignore = sub_book_ignore(sub_book);
+                for file in try!(utils::fs::files_except_ext(&sub_book.src, book.get_dest(), &["md"], &ignore)) {
+                    book.add_dry_run_file(sub_book.dir.join(file));
+                }
+            }
+        } else {
+            try!(utils::fs::copy_files_except_ext(book.get_src(),
+                                                  book.get_dest(),
+                                                  true,
+                                                  &["md"],
+                                                  html_config.preserve_symlinks,
+                                                  &ignore));
+            for sub_book in book.get_sub_books() {
+                let dest = book.get_dest().join(&sub_book.dir);
+                try!(fs::create_dir_all(&dest));
+                try!(utils::fs::copy_files_except_ext(&sub_book.src,
+                                                      &dest,
+                                                      true,
+                                                      &["md"],
+                                                      html_config.preserve_symlinks,
+                                                      &sub_book_ignore(sub_book)));
+            }
+        }
+        timings.lap("copy", &mut clock);
+
+        // The pages for the search engines, with their address at the `site-url`
+        let sitemap_url = html_config.site_url
+            .as_ref()
+            .map(|site_url| sitemap::page_url(site_url, Path::new(sitemap::SITEMAP)));
+        if let Some(ref site_url) = html_config.site_url {
+            try!(book.write_file(sitemap::SITEMAP, sitemap::sitemap_xml(site_url, &sitemap_pages).as_bytes()));
+        }
+        if let Some(ref robots_config) = html_config.robots {
+            let robots_txt = robots::robots_txt(robots_config, sitemap_url.as_ref().map(|u| &u[..]));
+            try!(book.write_file(robots::ROBOTS, robots_txt.as_bytes()));
+        }
+
+        // GitHub Pages, without `.nojekyll` the directories starting with `_` are not published
+        if html_config.site_url.is_some() || html_config.cname.is_some() {
+            try!(book.write_file(".nojekyll", b""));
+        }
+        if let Some(cname) = html_config.cname() {
+            try!(book.write_file("CNAME", format!("{}\n", cname).as_bytes()));
+        }
 
+        // The structure of the book for the other tools
+        let chapter_list = try!(chapters::chapters_json(book.get_title(), &book.content));
+        try!(book.write_file(chapters::CHAPTERS, chapter_list.as_bytes()));
+
+        // The icons of the manifest are read from the output, which a dry run does not have
+        if html_config.manifest.enable && book.is_dry_run() {
+            book.add_dry_run_file(manifest::MANIFEST);
+        } else if html_config.manifest.enable {
+            let manifest = try!(manifest::web_app_manifest(book.get_title(),
+                                                           book.get_description(),
+                                                           &html_config.manifest,
+                                                           book.get_dest(),
+                                                           &mut warnings));
+            try!(book.write_file(manifest::MANIFEST, manifest.as_bytes()));
+        }
+
+        // Last, it lists every other file, a dry run has no files to list
+        if html_config.offline {
+            if book.is_dry_run() {
+                book.add_dry_run_file(offline::SERVICE_WORKER);
+            } else {
+                try!(book.write_file(offline::SERVICE_WORKER,
+                                     try!(offline::service_worker(book.get_dest())).as_bytes()));
+            }
+        }
+        if html_config.archive {
+            if book.is_dry_run() {
+                book.add_dry_run_file(archive::ARCHIVE);
+            } else {
+                try!(archive::write_archive(book.get_dest()));
+            }
+        }
+
+        book.set_word_count(chapter_count, total_word_count);
+
+        timings.lap("render", &mut clock);
+        Ok(warnings)
+    }
+
+    fn check_theme(&self, book: &MDBook) -> Result<(), Box<Error>> {
+        debug!("[fn]: check_theme");
+        let mut handlebars = Handlebars::new();
+
+        let theme = theme::Theme::new(book.get_theme_path());
+        let templates = try!(Templates::new(&theme));
+        try!(templates.register(&mut handlebars));
+        self.register_helpers(&mut handlebars, &theme);
+
+        // The pages are rendered with the data of the first chapter, linked to itself as previous and next
+        // chapter so that the navigation is rendered too
+        let mut data = try!(make_data(book));
+        if book.get_config().html_config.subresource_integrity {
+            data.insert("integrity".to_owned(), integrity_hashes(&theme).to_json());
+        }
+        let first = book.iter()
+            .filter_map(|item| match *item {
+                BookItem::Chapter(_, ref ch) |
+                BookItem::Affix(ref ch) if ch.path != PathBuf::new() => Some(ch),
+                _ => None,
+            })
+            .next();
+        if let Some(ch) = first {
+            let link = helpers::navigation::chapter_link(&ch.name, &ch.path);
+            data.insert("path".to_owned(), ch.path.to_string_lossy().to_json());
+            data.insert("chapter_title".to_owned(), ch.name.to_json());
+            data.insert("path_to_root".to_owned(), utils::fs::path_to_root(&ch.path).to_json());
+            data.insert("previous".to_owned(), link.to_json());
+            data.insert("next".to_owned(), link.to_json());
+        }
+        data.insert("content".to_owned(), "".to_json());
+
+        for name in templates.pages() {
+            debug!("[*]: Checking {}.hbs", name);
+            try!(templates.render(&handlebars, name, &data));
+        }
         Ok(())
     }
+
+    fn check(&self, book: &MDBook) -> Result<Vec<Warning>, Box<Error>> {
+        debug!("[fn]: check");
+        let config = book.get_config();
+        let mut warnings = vec![];
+
+        // Every chapter is read first, the links need the anchors of all of them
+        let mut contents = vec![];
+        let mut anchors = HashMap::new();
+        let mut included = HashSet::new();
+        for item in book.iter() {
+            let ch = match *item {
+                BookItem::Chapter(_, ref ch) |
+                BookItem::Affix(ref ch) if ch.path != PathBuf::new() => ch,
+                _ => continue,
+            };
+
+            // The missing files are reported by the book
+            let (chapter_src, source_path) = book.get_chapter_source(&ch.path);
+            let path = chapter_src.join(source_path);
+            if !path.is_file() {
+                continue;
+            }
+            let (content, decoding) = try!(book.read_chapter(&ch.path));
+            if let Some(decoding) = decoding {
+                warnings.push(Warning::new(&ch.path, decoding));
+            }
+            let (_, markdown) = FrontMatter::split(&content).map_err(|e| {
+                io::Error::new(io::ErrorKind::Other, format!("{}: {}", path.display(), e))
+            })?;
+
+            check::included_files(markdown, &path, &mut included);
+            let markdown = helpers::include::render_includes(markdown,
+                                                             &path,
+                                                             chapter_src,
+                                                             &config.includes,
+                                                             &ch.path,
+                                                             &mut warnings);
+            let chapter = ch.path.components().filter(|c| *c != Component::CurDir).collect::<PathBuf>();
+            anchors.insert(chapter, check::chapter_anchors(&markdown, &config.markdown_config));
+            contents.push((ch.path.clone(), markdown, content));
+        }
+
+        for (path, markdown, source) in &contents {
+            let (chapter_src, _) = book.get_chapter_source(path);
+            let mount = book.get_sub_book(path).map_or(Path::new(""), |b| &b.dir);
+            check::check_links(markdown,
+                               &config.markdown_config,
+                               path,
+                               mount,
+                               chapter_src,
+                               &anchors,
+                               &mut warnings);
+            helpers::links::check_assets(markdown, source, chapter_src, path, &mut warnings);
+        }
+
+        // The markdown files of the source directories that the book does not use
+        let ignore = book.get_ignore_patterns();
+        let mut sources = vec![(book.get_src(), Path::new(""), &ignore)];
+        sources.extend(book.get_sub_books().iter().map(|b| (b.src.as_path(), b.dir.as_path(), &b.ignore)));
+        for &(src, dir, ignore) in &sources {
+            for file in try!(utils::fs::list_files(src)) {
+                let full = src.join(&file);
+                let path = dir.join(&file);
+                let used = anchors.contains_key(&path) || file == "SUMMARY.md" ||
+                           (dir == Path::new("") && config.colophon.as_ref().is_some_and(|c| *c == Path::new(&file))) ||
+                           full.canonicalize().is_ok_and(|f| included.contains(&f));
+                let elsewhere = config.exclude_chapters.iter().any(|e| path.starts_with(e)) ||
+                                sources.iter().any(|&(other, _, _)| other != src && full.starts_with(other));
+                if file.ends_with(".md") && !used && !elsewhere && !ignore.is_ignored(&full) {
+                    warnings.push(Warning::new(&path, "is not a chapter of SUMMARY.md nor included by one"));
+                }
+            }
+        }
+
+        Ok(warnings)
+    }
+
+    fn pages(&self, book: &MDBook) -> Result<Vec<(PathBuf, String)>, Box<Error>> {
+        let html_config = &book.get_config().html_config;
+        let chapters: Vec<_> = book.iter()
+            .filter_map(|item| match *item {
+                BookItem::Chapter(_, ref ch) |
+                BookItem::Affix(ref ch) if ch.path != PathBuf::new() => Some(ch),
+                _ => None,
+            })
+            .collect();
+        let page = |path: &Path| path.components().filter(|c| *c != Component::CurDir).collect::<PathBuf>();
+        let chapter_pages: HashSet<PathBuf> = chapters.iter().map(|ch| page(&ch.path).with_extension("html")).collect();
+
+        let mut pages = vec![];
+        if let Some(first) = chapters.first() {
+            if page(&first.path).with_extension("html") != Path::new("index.html") {
+                pages.push((PathBuf::from("index.html"), format!("the copy of \"{}\"", first.name)));
+            }
+        }
+        if html_config.print {
+            pages.push((PathBuf::from(print::PRINT), "the print version".to_owned()));
+        }
+        let search_config = &html_config.search;
+        if search_config.enable && search_config.docsearch.is_none() && search_config.page {
+            pages.push((PathBuf::from(search::SEARCH_PAGE), "the page of the search results".to_owned()));
+        }
+
+        // The index of the book, the tags and the aliases come from the chapters
+        let mut book_index = false;
+        let mut tags = Tags::new();
+        let mut aliases = vec![];
+        for ch in &chapters {
+            if !book.get_chapter_file(&ch.path).is_file() {
+                continue;
+            }
+            let (content, _) = try!(book.read_chapter(&ch.path));
+            let (front_matter, markdown) = try!(FrontMatter::split(&content));
+            book_index = book_index || !helpers::directives::find_directives(markdown, "index").is_empty();
+            tags.add_chapter(&ch.path, &ch.name, &front_matter.get_str_list("tags").unwrap_or_default());
+            // The aliases that are a page of the book or a static file are not written
+            for alias in front_matter.get_str_list("aliases").unwrap_or_default() {
+                if let Some(page) = redirects::alias_page(alias) {
+                    if !chapter_pages.contains(&page) && !book.get_src().join(&page).exists() {
+                        aliases.push((page, format!("the redirect of the alias `{}` of \"{}\"", alias, ch.name)));
+                    }
+                }
+            }
+        }
+        if book_index {
+            pages.push((Path::new(BOOK_INDEX).with_extension("html"), "the index of the book".to_owned()));
+        }
+        if !tags.is_empty() {
+            for (path, name, _) in tags.pages() {
+                pages.push((path.with_extension("html"), format!("the page of the tag `{}`", name)));
+            }
+            pages.push((Path::new(tags::TAGS_DIR).join("index.html"), "the list of the tags".to_owned()));
+        }
+        pages.extend(aliases);
+        Ok(pages)
+    }
+}
+
+/// Keeps only the `languages` in the highlight.js bundle of the theme, a file that is not a bundle is left as it is
+fn trim_highlight_js(theme: &mut theme::Theme, languages: &[String], warnings: &mut Vec<Warning>) {
+    let trimmed = match ::std::str::from_utf8(&theme.highlight_js).ok().and_then(Bundle::parse) {
+        Some(bundle) => {
+            let (trimmed, unknown) = bundle.select(languages);
+            for language in unknown {
+                warnings.push(Warning::global(format!("highlight.js has no language `{}`", language)));
+            }
+            trimmed
+        },
+        None => {
+            warnings.push(Warning::global("highlight.js of the theme is not a bundle of languages, it is not trimmed"));
+            return;
+        },
+    };
+    theme.highlight_js = trimmed.into_bytes();
+}
+
+/// Appends the definition of a language, a file calling `hljs.registerLanguage`, to the highlight.js of the theme
+fn add_highlight_language(theme: &mut theme::Theme, path: &Path) -> Result<(), Box<Error>> {
+    let mut definition = String::new();
+    try!(File::open(path).and_then(|mut f| f.read_to_string(&mut definition)).map_err(|e| {
+        io::Error::new(io::ErrorKind::Other,
+                       format!("Could not read the highlight.js language {}: {}", path.display(), e))
+    }));
+    if !definition.contains("hljs.registerLanguage(") {
+        return Err(Box::new(io::Error::new(io::ErrorKind::Other,
+                                           format!("{} does not register a highlight.js language with \
+                                                    `hljs.registerLanguage`",
+                                                   path.display()))));
+    }
+
+    theme.highlight_js.push(b'\n');
+    theme.highlight_js.extend_from_slice(definition.trim().as_bytes());
+    theme.highlight_js.push(b'\n');
+    Ok(())
+}
+
+/// Computes the `integrity` attribute values for the theme files that `index.hbs` links to.
+/// The keys are the file names with `.` and `-` replaced by `_`, e.g. `book_css`.
+fn integrity_hashes(theme: &theme::Theme) -> BTreeMap<String, String> {
+    let mut hashes = BTreeMap::new();
+    hashes.insert("book_css".to_owned(), integrity_hash(&theme.css));
+    hashes.insert("book_js".to_owned(), integrity_hash(&theme.js));
+    hashes.insert("highlight_css".to_owned(), integrity_hash(&theme.highlight_css));
+    hashes.insert("tomorrow_night_css".to_owned(), integrity_hash(&theme.tomorrow_night_css));
+    hashes.insert("highlight_js".to_owned(), integrity_hash(&theme.highlight_js));
+    hashes
+}
+
+fn integrity_hash(content: &[u8]) -> String {
+    format!("sha384-{}", base64::encode(&Sha384::digest(content)))
+}
+
+
+impl ThemeKey {
+    fn new(book: &MDBook) -> Self {
+        let path = book.get_theme_path();
+        // The files of the themes it extends too
+        let files = theme::theme_dirs(path)
+            .iter()
+            .flat_map(|dir| {
+                utils::fs::list_files(dir)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(move |file| {
+                        let metadata = fs::metadata(dir.join(&file)).ok();
+                        let len = metadata.as_ref().map_or(0, |m| m.len());
+                        let modified = metadata.and_then(|m| m.modified().ok());
+                        (dir.join(file).display().to_string(), len, modified)
+                    })
+            })
+            .collect();
+
+        ThemeKey {
+            path: path.to_owned(),
+            code: book.get_config().html_config.code.clone(),
+            files: files,
+        }
+    }
+}
+
+// Byline of a chapter in the print view, the pages get it from the template
+// Title page at the top of the print version, on its own page when printed
+fn title_page_html(config: &BookConfig) -> String {
+    if config.title.is_empty() {
+        return String::new();
+    }
+
+    let mut html = String::from("<section class=\"title-page\">\n");
+    if let Some(ref cover) = config.cover {
+        let cover = utils::fs::path_to_url(cover);
+        html.push_str(&format!("<img class=\"cover\" src=\"{}\" alt=\"Cover\">\n", utils::escape_html(&cover)));
+    }
+    html.push_str(&format!("<h1 class=\"title\">{}</h1>\n", utils::escape_html(&config.title)));
+    if let Some(ref subtitle) = config.subtitle {
+        html.push_str(&format!("<p class=\"subtitle\">{}</p>\n", utils::escape_html(subtitle)));
+    }
+    let authors = config.get_authors();
+    if !authors.is_empty() {
+        html.push_str(&format!("<p class=\"authors\">{}</p>\n", utils::escape_html(&authors.join(", "))));
+    }
+    if let Some(ref publisher) = config.publisher {
+        html.push_str(&format!("<p class=\"publisher\">{}</p>\n", utils::escape_html(publisher)));
+    }
+    if let Some(ref isbn) = config.isbn {
+        html.push_str(&format!("<p class=\"isbn\">ISBN {}</p>\n", utils::escape_html(isbn)));
+    }
+    match (config.version.as_ref(), config.date.as_ref()) {
+        (Some(version), Some(date)) => {
+            html.push_str(&format!("<p class=\"edition\">Version {}, {}</p>\n",
+                                   utils::escape_html(version),
+                                   utils::escape_html(date.get(..10).unwrap_or(date))))
+        },
+        (Some(version), None) => {
+            html.push_str(&format!("<p class=\"edition\">Version {}</p>\n", utils::escape_html(version)))
+        },
+        (None, Some(date)) => {
+            let date = date.get(..10).unwrap_or(date);
+            html.push_str(&format!("<p class=\"edition\">{}</p>\n", utils::escape_html(date)))
+        },
+        (None, None) => {},
+    }
+    html.push_str("</section>\n");
+    html
+}
+
+// The colophon after the title page of the print version, on its own page too
+fn colophon_html(markdown: &str, config: &MarkdownConfig) -> String {
+    let mut s = String::from("<section class=\"colophon\">\n");
+    html::push_html(&mut s, Parser::new_ext(markdown, config.options()));
+    s.push_str("</section>\n");
+    s
+}
+
+// The title of the feed, the name of the blog chapter by default
+fn blog_title(book: &MDBook, blog_config: &BlogConfig) -> String {
+    if let Some(ref title) = blog_config.title {
+        return title.clone();
+    }
+    book.iter()
+        .filter_map(|item| match *item {
+            BookItem::Chapter(_, ref ch) if blog::same_chapter(&ch.path, &blog_config.chapter) => Some(ch.name.clone()),
+            _ => None,
+        })
+        .next()
+        .unwrap_or_else(|| book.get_title().to_owned())
+}
+
+// The posts with their date, added after the content of the blog chapter
+fn blog_listing_html(posts: &[Post]) -> String {
+    let mut html = String::from("<ul class=\"blog-posts\">\n");
+    for post in posts {
+        let link = utils::fs::path_to_link(&post.path);
+        html.push_str(&format!("<li><a href=\"{}\">{}</a>", link, utils::escape_html(&post.name)));
+        if let Some(ref date) = post.date {
+            let day = date.get(..10).unwrap_or(date);
+            html.push_str(&format!(" <time datetime=\"{}\">{}</time>",
+                                   utils::escape_html(date),
+                                   utils::escape_html(day)));
+        }
+        html.push_str("</li>\n");
+    }
+    html.push_str("</ul>\n");
+    html
+}
+
+// The feed of the newest posts, the posts without a date are left out
+fn blog_feed(book: &MDBook,
+             blog_config: &BlogConfig,
+             site_url: &str,
+             post_contents: &HashMap<PathBuf, (String, Vec<String>)>,
+             warnings: &mut Vec<Warning>)
+             -> atom::Feed {
+    let base = format!("{}/", site_url.trim_end_matches('/'));
+    let url_of = |path: &Path| sitemap::page_url(site_url, &path.with_extension("html"));
+
+    let mut entries = vec![];
+    for post in book.get_blog_posts() {
+        let date = match post.date {
+            Some(ref date) => date,
+            None => {
+                warnings.push(Warning::new(&post.path, "the post has no `date`, it is left out of the feed"));
+                continue;
+            },
+        };
+        if entries.len() == blog_config.feed_entries {
+            continue;
+        }
+        let (content, authors) = post_contents.get(&post.path).cloned().unwrap_or_default();
+        entries.push(atom::Entry {
+            title: post.name.clone(),
+            link: url_of(&post.path),
+            updated: blog::rfc3339_date(date),
+            authors: authors,
+            content: content,
+            base: base.clone(),
+        });
+    }
+
+    atom::Feed {
+        title: blog_title(book, blog_config),
+        link: url_of(&blog_config.chapter),
+        url: sitemap::page_url(site_url, &blog_config.feed_path()),
+        author: book.get_author().to_owned(),
+        entries: entries,
+    }
+}
+
+// The title of the feed of the changes, from the title of the book by default
+fn changes_title(book: &MDBook, changes_config: &ChangesConfig) -> String {
+    changes_config.title.clone().unwrap_or_else(|| format!("{}: recent changes", book.get_title()))
+}
+
+// The feed of the chapters changed by the recent commits, the last changed first
+fn changes_feed(book: &MDBook, changes_config: &ChangesConfig, site_url: &str) -> Result<atom::Feed, String> {
+    let recent = try!(changes::recent_changes(book.get_src(), changes_config.commits));
+    let chapters: HashMap<PathBuf, &str> = book.iter()
+        .filter_map(|item| match *item {
+            BookItem::Chapter(_, ref ch) |
+            BookItem::Affix(ref ch) if ch.path != PathBuf::new() => {
+                Some((ch.path.components().filter(|c| *c != Component::CurDir).collect(), &ch.name[..]))
+            },
+            _ => None,
+        })
+        .collect();
+
+    let base = format!("{}/", site_url.trim_end_matches('/'));
+    let entries = recent.iter()
+        .filter_map(|change| chapters.get(&change.path).map(|name| (change, name)))
+        .take(changes_config.entries)
+        .map(|(change, name)| {
+            atom::Entry {
+                title: name.to_string(),
+                link: sitemap::page_url(site_url, &change.path.with_extension("html")),
+                updated: change.date.clone(),
+                authors: change.authors.clone(),
+                content: changes::change_html(change),
+                base: base.clone(),
+            }
+        })
+        .collect();
+
+    Ok(atom::Feed {
+        title: changes_title(book, changes_config),
+        link: sitemap::page_url(site_url, Path::new("index.html")),
+        url: sitemap::page_url(site_url, Path::new(changes::CHANGES)),
+        author: book.get_author().to_owned(),
+        entries: entries,
+    })
+}
+
+// Names of the tags of a chapter with the links to their pages, for the header of the chapter
+fn tag_links(chapter_tags: &[&str]) -> Vec<BTreeMap<String, String>> {
+    chapter_tags.iter()
+        .map(|tag| {
+            let mut link = BTreeMap::new();
+            link.insert("name".to_owned(), tag.to_string());
+            link.insert("link".to_owned(), tags::tag_link(tag));
+            link
+        })
+        .collect()
+}
+
+fn chapter_meta_html(authors: &[String], date: Option<&str>) -> String {
+    if authors.is_empty() && date.is_none() {
+        return String::new();
+    }
+
+    let mut html = String::from("<div class=\"chapter-meta\">");
+    if !authors.is_empty() {
+        html.push_str(&format!("<span class=\"chapter-authors\">{}</span>", utils::escape_html(&authors.join(", "))));
+    }
+    if let Some(date) = date {
+        html.push_str(&format!("<time class=\"chapter-date\" datetime=\"{0}\">{0}</time>", utils::escape_html(date)));
+    }
+    html.push_str("</div>\n");
+    html
+}
+
+// Renders the markdown of a chapter, with the links to other chapters rewritten, the glossary
+// terms linked to their definition, the `run` code blocks run and, when `section` is given,
+// numbered headings
+fn parse_markdown<'a>(text: &'a str,
+                      chapter: &Path,
+                      mount: &Path,
+                      chapters: &HashSet<PathBuf>,
+                      glossary: Option<&Glossary>,
+                      config: &MarkdownConfig,
+                      warnings: &mut Vec<Warning>)
+                      -> Vec<Event<'a>> {
+    let events = Parser::new_ext(text, config.options())
+        .map(|event| helpers::links::rewrite_event(event, chapter, mount, chapters, warnings));
+
+    match glossary {
+        Some(glossary) => glossary.link_terms(events),
+        None => events.collect(),
+    }
+}
+
+// Numbers the headings with the `section` of the chapter and applies the extensions pulldown-cmark
+// does not have before writing the html
+fn render_events(mut events: Vec<Event>, section: Option<&str>, config: &MarkdownConfig) -> String {
+    let mut s = String::new();
+
+    if let Some(section) = section {
+        let mut numbers = HeadingNumbers::new(section);
+        events = events.into_iter().map(|event| numbers.number_event(event)).collect();
+    }
+
+    if config.heading_attributes {
+        events = helpers::extensions::heading_attributes(events);
+    }
+    if config.strikethrough {
+        events = helpers::extensions::strikethrough(events);
+    }
+    if config.autolinks {
+        events = helpers::extensions::autolinks(events);
+    }
+    if config.smart_punctuation {
+        events = helpers::extensions::smart_punctuation(events);
+    }
+    if config.emoji {
+        events = helpers::extensions::emoji(events);
+    }
+    if config.image_captions {
+        events = helpers::extensions::image_captions(events);
+    }
+
+    html::push_html(&mut s, events.into_iter());
+    s
+}
+
+/// Classes of the `<body>` of a chapter, `chapter-` followed by its path, e.g. `chapter-format-config` for
+/// `format/config.md`, and the `classes` of its front matter
+fn body_classes(path: &Path, front_matter: &FrontMatter) -> String {
+    let name = path.with_extension("")
+        .to_string_lossy()
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c.to_ascii_lowercase() } else { '-' })
+        .collect::<String>();
+    let mut classes = vec![format!("chapter-{}", name)];
+
+    if let Some(extra) = front_matter.get_str_list("classes") {
+        classes.extend(extra.iter().flat_map(|c| c.split_whitespace()).map(|c| c.to_owned()));
+    }
+    classes.join(" ")
+}
+
+fn load_glossary(book: &MDBook,
+                 chapters: &HashSet<PathBuf>,
+                 warnings: &mut Vec<Warning>)
+                 -> Result<Option<Glossary>, Box<Error>> {
+    let path = book.get_src().join(GLOSSARY);
+    if !path.is_file() {
+        warnings.push(Warning::new(Path::new(GLOSSARY), "the glossary is enabled but the file does not exist"));
+        return Ok(None);
+    }
+
+    if !chapters.contains(Path::new(GLOSSARY)) {
+        warnings.push(Warning::new(Path::new(GLOSSARY),
+                                   "is not in SUMMARY.md, the links to the glossary will be broken"));
+    }
+
+    // A file that is not valid UTF-8 is reported when the chapter is rendered
+    let (content, _) = try!(book.read_chapter(Path::new(GLOSSARY)));
+    let (_, markdown) = try!(FrontMatter::split(&content));
+
+    Ok(Some(Glossary::parse(markdown)))
+}
+
+// Reads every chapter beforehand to know under which ids they can be referenced, and the
+// numbers of their figures and tables
+fn collect_references(book: &MDBook, warnings: &mut Vec<Warning>) -> Result<(CrossRefs, Figures), Box<Error>> {
+    let mut crossrefs = CrossRefs::new();
+    let mut figures = Figures::new();
+
+    for item in book.iter() {
+        let (section, ch) = match *item {
+            BookItem::Chapter(ref s, ref ch) => (Some(&s[..]), ch),
+            BookItem::Affix(ref ch) => (None, ch),
+            BookItem::Spacer => continue,
+        };
+
+        if ch.path == PathBuf::new() {
+            continue;
+        }
+
+        let (chapter_src, source_path) = book.get_chapter_source(&ch.path);
+        let path = chapter_src.join(source_path);
+        let (content, _) = try!(book.read_chapter(&ch.path));
+
+        // Invalid front matter, invalid UTF-8 and missing includes are reported when the chapter is rendered
+        let (front_matter, markdown) = FrontMatter::split(&content).unwrap_or_else(|_| (FrontMatter::new(), &content));
+        let markdown = helpers::include::render_includes(markdown,
+                                                         &path,
+                                                         chapter_src,
+                                                         &book.get_config().includes,
+                                                         &ch.path,
+                                                         &mut vec![]);
+        crossrefs.add_chapter(&ch.path, section, &ch.name, front_matter.get_str("id"));
+        figures.add_chapter(&ch.path, section, &helpers::conditionals::select_content(&markdown, RENDERER).0, warnings);
+    }
+
+    Ok((crossrefs, figures))
+}
+
+// The address of the page in its `<link rel="canonical">`, only when the book has a `site-url`. `page` is the html
+// file relative to the root of the book.
+fn insert_canonical(data: &mut serde_json::Map<String, serde_json::Value>, book: &MDBook, page: &Path) {
+    if let Some(ref site_url) = book.get_config().html_config.site_url {
+        data.insert("canonical".to_owned(), sitemap::page_url(site_url, page).to_json());
+    }
 }
 
 fn make_data(book: &MDBook) -> Result<serde_json::Map<String, serde_json::Value>, Box<Error>> {
@@ -170,42 +1561,186 @@ fn make_data(book: &MDBook) -> Result<serde_json::Map<String, serde_json::Value>
     data.insert("language".to_owned(), "en".to_json());
     data.insert("title".to_owned(), book.get_title().to_json());
     data.insert("description".to_owned(), book.get_description().to_json());
-    data.insert("favicon".to_owned(), "favicon.png".to_json());
+
+    // Title page metadata
+    let config = book.get_config();
+    if let Some(ref subtitle) = config.subtitle {
+        data.insert("subtitle".to_owned(), subtitle.to_json());
+    }
+    let authors = config.get_authors();
+    if !authors.is_empty() {
+        data.insert("authors".to_owned(), authors.to_json());
+    }
+    if let Some(ref publisher) = config.publisher {
+        data.insert("publisher".to_owned(), publisher.to_json());
+    }
+    if let Some(ref isbn) = config.isbn {
+        data.insert("isbn".to_owned(), isbn.to_json());
+    }
+    if let Some(ref cover) = config.cover {
+        data.insert("cover".to_owned(), utils::fs::path_to_url(cover).to_json());
+    }
+    // Header of the sidebar, the logo is copied at the root of the book
+    let html_config = &book.get_config().html_config;
+    // Icons of the pages, at the root of the book
+    data.insert("favicon".to_owned(), icons::FAVICON_PNG.to_json());
+    if let Some(ref favicon) = html_config.favicon {
+        data.insert("favicon_ico".to_owned(), icons::FAVICON_ICO.to_json());
+        if icons::is_svg(favicon) {
+            data.insert("favicon_svg".to_owned(), icons::FAVICON_SVG.to_json());
+        }
+    }
+    if icons::has_apple_touch_icon(html_config) {
+        data.insert("apple_touch_icon".to_owned(), icons::APPLE_TOUCH_ICON.to_json());
+    }
+
+    if let Some(logo) = html_config.logo.as_ref().and_then(|logo| logo.file_name()) {
+        data.insert("sidebar_logo".to_owned(), logo.to_string_lossy().to_json());
+    }
+    if let Some(ref title) = html_config.sidebar_title {
+        data.insert("sidebar_title".to_owned(), title.to_json());
+    }
+    if html_config.logo.is_some() || html_config.sidebar_title.is_some() {
+        data.insert("sidebar_header".to_owned(), true.to_json());
+    }
+    if html_config.layout == Layout::Navbar {
+        data.insert("navbar".to_owned(), true.to_json());
+    }
+    let permalink = match html_config.headings.permalink {
+        Permalink::Heading => "heading",
+        Permalink::Icon => "icon",
+    };
+    let visibility = match html_config.headings.visibility {
+        PermalinkVisibility::Hover => "hover",
+        PermalinkVisibility::Always => "always",
+    };
+    data.insert("heading_permalink".to_owned(), permalink.to_json());
+    data.insert("heading_permalink_visibility".to_owned(), visibility.to_json());
+    data.insert("heading_min_level".to_owned(), html_config.headings.min_level.to_json());
+    match html_config.math.mode {
+        MathMode::MathJax => data.insert("mathjax".to_owned(), true.to_json()),
+        MathMode::Katex => data.insert("katex_stylesheet".to_owned(), html_config.math.katex_stylesheet.to_json()),
+    };
+    data.insert("noindex".to_owned(), html_config.noindex.to_json());
+    if html_config.structured_data {
+        data.insert("json_ld".to_owned(), structured_data::book_json_ld(config).to_json());
+    }
+    data.insert("progress_bar".to_owned(), html_config.progress_bar.to_json());
+    data.insert("back_to_top".to_owned(), html_config.back_to_top.to_json());
+    if !html_config.style.variables.is_empty() {
+        data.insert("style_variables".to_owned(), style::VARIABLES_CSS.to_json());
+    }
+    if let Some(toc_depth) = book.get_config().html_config.toc_depth {
+        data.insert("toc_depth".to_owned(), toc_depth.to_json());
+    }
+
+    let search_config = &book.get_config().html_config.search;
+    data.insert("search_enabled".to_owned(), search_config.enable.to_json());
+    if search_config.enable && search_config.docsearch.is_none() && search_config.page {
+        data.insert("search_page".to_owned(), true.to_json());
+    }
+    // Not with `mdbook serve`, the cached pages would hide the changes
+    let offline = book.get_config().html_config.offline && book.get_livereload().is_none();
+    data.insert("offline".to_owned(), offline.to_json());
+    data.insert("print".to_owned(), book.get_config().html_config.print.to_json());
+    if book.get_config().html_config.archive {
+        data.insert("archive".to_owned(), archive::ARCHIVE.to_json());
+    }
+    if book.get_config().html_config.manifest.enable {
+        data.insert("manifest".to_owned(), manifest::MANIFEST.to_json());
+        data.insert("theme_color".to_owned(), book.get_config().html_config.manifest.theme_color.to_json());
+    }
+    if let Some(ref docsearch) = search_config.docsearch {
+        let mut options = BTreeMap::new();
+        if let Some(ref app_id) = docsearch.app_id {
+            options.insert("appId".to_owned(), app_id.to_json());
+        }
+        options.insert("apiKey".to_owned(), docsearch.api_key.to_json());
+        options.insert("indexName".to_owned(), docsearch.index_name.to_json());
+        options.insert("inputSelector".to_owned(), "#searchbar".to_json());
+        data.insert("docsearch".to_owned(), try!(serde_json::to_string(&options)).to_json());
+    }
     if let Some(livereload) = book.get_livereload() {
         data.insert("livereload".to_owned(), livereload.to_json());
     }
 
-    let mut chapters = vec![];
+    // Version picker of the books built by `mdbook versions`
+    if let Some(picker) = book.get_version_picker() {
+        let versions = picker.versions
+            .iter()
+            .map(|version| {
+                let mut v = BTreeMap::new();
+                v.insert("name".to_owned(), version.to_json());
+                v.insert("current".to_owned(), (*version == picker.current).to_json());
+                v.insert("latest".to_owned(), (*version == picker.latest).to_json());
+                v
+            })
+            .collect::<Vec<_>>();
+        data.insert("versions".to_owned(), versions.to_json());
+    }
 
+    let mut chapters = vec![];
+    let mut numbered_chapters = false;
     for item in book.iter() {
-        // Create the data to inject in the template
-        let mut chapter = BTreeMap::new();
-
-        match *item {
-            BookItem::Affix(ref ch) => {
-                chapter.insert("name".to_owned(), ch.name.to_json());
-                let path = ch.path.to_str().ok_or(io::Error::new(io::ErrorKind::Other,
-                                                                 "Could not convert path to str"))?;
-                chapter.insert("path".to_owned(), path.to_json());
-            },
-            BookItem::Chapter(ref s, ref ch) => {
-                chapter.insert("section".to_owned(), s.to_json());
-                chapter.insert("name".to_owned(), ch.name.to_json());
-                let path = ch.path.to_str().ok_or(io::Error::new(io::ErrorKind::Other,
-                                                                 "Could not convert path to str"))?;
-                chapter.insert("path".to_owned(), path.to_json());
-            },
-            BookItem::Spacer => {
-                chapter.insert("spacer".to_owned(), "_spacer_".to_json());
-            },
+        chapters.push(try!(chapter_json(item, &mut numbered_chapters)));
+    }
 
-        }
+    // The same chapters, with their sub-chapters nested in `sub_items`
+    let chapter_tree = try!(chapter_tree_json(&book.content, &mut false));
 
-        chapters.push(chapter);
-    }
+    // Every value of book.toml, e.g. `config.output.html.site-url`
+    let values = book.get_config().values.iter().map(|(k, v)| (k.clone(), toml_value_to_json_value(v))).collect();
 
     data.insert("chapters".to_owned(), chapters.to_json());
+    data.insert("chapter_tree".to_owned(), serde_json::Value::Array(chapter_tree));
+    data.insert("config".to_owned(), serde_json::Value::Object(values));
 
     debug!("[*]: JSON constructed");
     Ok(data)
 }
+
+// The data of a chapter in the template, the affixes after the first numbered chapter are suffix chapters
+fn chapter_json(item: &BookItem,
+                numbered_chapters: &mut bool)
+                -> Result<BTreeMap<String, serde_json::Value>, Box<Error>> {
+    let mut chapter = BTreeMap::new();
+
+    match *item {
+        BookItem::Affix(ref ch) => {
+            let kind = if *numbered_chapters { "suffix" } else { "prefix" };
+            chapter.insert("kind".to_owned(), kind.to_json());
+            chapter.insert("name".to_owned(), ch.name.to_json());
+            let path = ch.path.to_str().ok_or(io::Error::new(io::ErrorKind::Other,
+                                                             "Could not convert path to str"))?;
+            chapter.insert("path".to_owned(), path.to_json());
+        },
+        BookItem::Chapter(ref s, ref ch) => {
+            *numbered_chapters = true;
+            let kind = if s.starts_with(char::is_alphabetic) { "appendix" } else { "numbered" };
+            chapter.insert("kind".to_owned(), kind.to_json());
+            chapter.insert("section".to_owned(), s.to_json());
+            chapter.insert("name".to_owned(), ch.name.to_json());
+            let path = ch.path.to_str().ok_or(io::Error::new(io::ErrorKind::Other,
+                                                             "Could not convert path to str"))?;
+            chapter.insert("path".to_owned(), path.to_json());
+        },
+        BookItem::Spacer => {
+            chapter.insert("spacer".to_owned(), "_spacer_".to_json());
+        },
+    }
+
+    Ok(chapter)
+}
+
+fn chapter_tree_json(items: &[BookItem], numbered_chapters: &mut bool) -> Result<Vec<serde_json::Value>, Box<Error>> {
+    let mut tree = vec![];
+    for item in items {
+        let mut chapter = try!(chapter_json(item, numbered_chapters));
+        if let BookItem::Chapter(_, ref ch) | BookItem::Affix(ref ch) = *item {
+            let sub_items = try!(chapter_tree_json(&ch.sub_items, numbered_chapters));
+            chapter.insert("sub_items".to_owned(), serde_json::Value::Array(sub_items));
+        }
+        tree.push(serde_json::Value::Object(chapter.into_iter().collect()));
+    }
+    Ok(tree)
+}
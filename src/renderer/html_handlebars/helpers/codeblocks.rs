@@ -0,0 +1,119 @@
+use std::borrow::Cow;
+
+use pulldown_cmark::{Event, Tag};
+
+use utils;
+
+
+/// Renders the `title` attribute of the code blocks, like ```` ```rust,title="src/main.rs" ````, as a bar
+/// above the block. The attribute is removed from the info string so that the block keeps its language.
+pub fn render_titles<'a>(events: Vec<Event<'a>>) -> Vec<Event<'a>> {
+    let mut result = Vec::with_capacity(events.len());
+
+    for event in events {
+        match event {
+            Event::Start(Tag::CodeBlock(info)) => {
+                match split_title(&info) {
+                    Some((info, title)) => {
+                        result.push(Event::Html(Cow::Owned(format!("<div class=\"code-title\">{}</div>\n",
+                                                                   utils::escape_html(&title)))));
+                        result.push(Event::Start(Tag::CodeBlock(Cow::Owned(info))));
+                    },
+                    None => result.push(Event::Start(Tag::CodeBlock(info))),
+                }
+            },
+            event => result.push(event),
+        }
+    }
+
+    result
+}
+
+/// Gives the code blocks of the html of a chapter the ids `code-1`, `code-2`... in their order, and the
+/// `line-anchors` class with which `book.js` gives an id to each of their lines once they are highlighted,
+/// e.g. `code-3.L42` for the line 42 of the third block
+pub fn line_anchors(html: &str) -> String {
+    let mut result = String::with_capacity(html.len());
+    let mut rest = html;
+    let mut count = 0;
+    while let Some(start) = rest.find("<pre><code") {
+        count += 1;
+        result.push_str(&rest[..start]);
+        result.push_str(&format!("<pre id=\"code-{}\" class=\"line-anchors\"><code", count));
+        rest = &rest[start + "<pre><code".len()..];
+    }
+    result.push_str(rest);
+    result
+}
+
+// The info string without its `title` attribute, and the title. The title is quoted when it has commas.
+fn split_title(info: &str) -> Option<(String, String)> {
+    let start = info.split(',')
+        .scan(0, |offset, token| {
+            let start = *offset;
+            *offset += token.len() + 1;
+            Some((start, token))
+        })
+        .find(|&(_, token)| token.trim_start().starts_with("title="))
+        .map(|(start, token)| start + token.len() - token.trim_start().len())?;
+
+    let value = &info[start + "title=".len()..];
+    let (title, len) = match value.strip_prefix('"') {
+        Some(quoted) => {
+            let end = quoted.find('"').unwrap_or(quoted.len());
+            (&quoted[..end], (end + 2).min(value.len()))
+        },
+        None => {
+            let end = value.find(',').unwrap_or(value.len());
+            (&value[..end], end)
+        },
+    };
+
+    // The other attributes, without the comma before the title or, for the first one, after it
+    let before = info[..start].trim_end().trim_end_matches(',');
+    let after = &info[start + "title=".len() + len..];
+    let after = if before.is_empty() { after.trim_start_matches(',') } else { after };
+
+    Some((format!("{}{}", before, after), title.to_owned()))
+}
+
+
+// ---------------------------------------------------------------------------------------------------------------
+//      Tests
+//
+
+#[test]
+fn test_split_title() {
+    assert_eq!(split_title("rust,title=\"src/main.rs\""),
+               Some(("rust".to_owned(), "src/main.rs".to_owned())));
+    assert_eq!(split_title("rust,title=main.rs,ignore"),
+               Some(("rust,ignore".to_owned(), "main.rs".to_owned())));
+    assert_eq!(split_title("toml, title=\"Cargo.toml, the manifest\", run"),
+               Some(("toml, run".to_owned(), "Cargo.toml, the manifest".to_owned())));
+    assert_eq!(split_title("title=\"notes.txt\""), Some(("".to_owned(), "notes.txt".to_owned())));
+    assert_eq!(split_title("rust,ignore"), None);
+    assert_eq!(split_title("subtitle=x"), None);
+}
+
+#[test]
+fn test_render_titles() {
+    use pulldown_cmark::{Parser, html};
+
+    let markdown = "```rust,title=\"src/<main>.rs\"\nfn main() {}\n```\n\n```rust\n```";
+    let mut s = String::new();
+    html::push_html(&mut s, render_titles(Parser::new(markdown).collect()).into_iter());
+    assert_eq!(s,
+               "<div class=\"code-title\">src/&lt;main&gt;.rs</div>\n\
+                <pre><code class=\"language-rust\">fn main() {}\n</code></pre>\n\
+                <pre><code class=\"language-rust\"></code></pre>\n");
+}
+
+#[test]
+fn test_line_anchors() {
+    let html = "<pre><code class=\"language-rust\">fn main() {}\n</code></pre>\n\
+                <pre class=\"run-output\"><code>ok</code></pre>\n<pre><code>$ ls\n</code></pre>\n";
+    assert_eq!(line_anchors(html),
+               "<pre id=\"code-1\" class=\"line-anchors\"><code class=\"language-rust\">fn main() {}\n</code></pre>\n\
+                <pre class=\"run-output\"><code>ok</code></pre>\n\
+                <pre id=\"code-2\" class=\"line-anchors\"><code>$ ls\n</code></pre>\n");
+}
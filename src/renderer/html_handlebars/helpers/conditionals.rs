@@ -0,0 +1,105 @@
+use std::path::Path;
+
+use renderer::Warning;
+
+
+/// Keeps the content of the `{{#if output.name}} ... {{else}} ... {{/if}}` blocks of a chapter that
+/// applies to the renderer `renderer`, e.g. `html`. Blocks can be nested, the `{{else}}` part is
+/// optional, and a backslash in front of a tag, `\{{#if output.html}}`, escapes it.
+///
+/// Handlebars `{{#if}}` blocks on anything else than `output.name` are left untouched, so that
+/// templates can still be shown in the book.
+pub fn render_conditionals(s: &str, renderer: &str, chapter: &Path, warnings: &mut Vec<Warning>) -> String {
+    let (shown, closed) = select_content(s, renderer);
+    if !closed {
+        warnings.push(Warning::new(chapter, "`{{#if output...}}` without `{{/if}}`"));
+    }
+    shown
+}
+
+/// Same as `render_conditionals`, without warning, and also returns whether every block was closed
+pub fn select_content(s: &str, renderer: &str) -> (String, bool) {
+    // For every open block `true` if it is one of ours and if the current part of it is shown,
+    // `None` for handlebars blocks
+    let mut blocks: Vec<Option<bool>> = vec![];
+    let mut shown = String::with_capacity(s.len());
+    let mut rest = s;
+
+    while let Some(start) = rest.find("{{") {
+        let (before, tag) = rest.split_at(start);
+        let end = match tag.find("}}") {
+            Some(end) => end + "}}".len(),
+            None => break,
+        };
+        let inner = tag[2..end - 2].trim();
+
+        let visible = blocks.iter().all(|b| b.unwrap_or(true));
+        let mut push = |s: &str| if visible { shown.push_str(s) };
+        rest = &tag[end..];
+
+        let condition = inner.strip_prefix("#if ").map(|c| c.trim()).and_then(|c| c.strip_prefix("output."));
+        if condition.is_none() && inner != "else" && inner != "/if" {
+            push(before);
+            push(&tag[..end]);
+            if inner.starts_with("#if ") {
+                blocks.push(None);
+            }
+            continue;
+        }
+
+        if let Some(before) = before.strip_suffix('\\') {
+            push(before);
+            push(&tag[..end]);
+            continue;
+        }
+        push(before);
+
+        match (condition, inner, blocks.last().cloned()) {
+            (Some(name), _, _) => blocks.push(Some(name == renderer)),
+            (None, "else", Some(Some(shown))) => *blocks.last_mut().unwrap() = Some(!shown),
+            (None, "/if", Some(Some(_))) => {
+                blocks.pop();
+            },
+            // Handlebars blocks
+            (None, "/if", Some(None)) => {
+                blocks.pop();
+                push(&tag[..end]);
+            },
+            (None, _, _) => push(&tag[..end]),
+        }
+    }
+
+    if blocks.iter().all(|b| b.unwrap_or(true)) {
+        shown.push_str(rest);
+    }
+    (shown, blocks.iter().all(|b| b.is_none()))
+}
+
+
+// ---------------------------------------------------------------------------------
+//      Tests
+//
+
+#[test]
+fn test_render_conditionals() {
+    let s = "a {{#if output.html}}html{{else}}other{{/if}} b {{#if output.epub}}epub{{/if}} \
+             {{#if output.html}}1 {{#if output.pdf}}pdf{{else}}2{{/if}}{{/if}}";
+
+    let mut warnings = vec![];
+    assert_eq!(render_conditionals(s, "html", Path::new("a.md"), &mut warnings), "a html b  1 2");
+    assert_eq!(render_conditionals(s, "epub", Path::new("a.md"), &mut warnings), "a other b epub ");
+    assert_eq!(select_content("{{#if output.html}}a", "html"), ("a".to_owned(), false));
+    assert!(warnings.is_empty());
+
+    assert_eq!(render_conditionals("{{#if output.html}}a", "html", Path::new("a.md"), &mut warnings), "a");
+    assert_eq!(warnings, vec![Warning::new(Path::new("a.md"), "`{{#if output...}}` without `{{/if}}`")]);
+}
+
+#[test]
+fn test_render_conditionals_keeps_handlebars() {
+    let s = "{{#if output.epub}}x{{/if}}<script{{#if integrity}} integrity=\"{{integrity}}\"{{else}}{{/if}}> \
+             \\{{#if output.epub}}{{title}}\\{{/if}}";
+
+    assert_eq!(render_conditionals(s, "html", Path::new("a.md"), &mut vec![]),
+               "<script{{#if integrity}} integrity=\"{{integrity}}\"{{else}}{{/if}}> {{#if output.epub}}{{title}}{{/if}}");
+}
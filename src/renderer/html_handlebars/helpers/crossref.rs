@@ -0,0 +1,177 @@
+use std::path::Path;
+use std::collections::HashMap;
+
+use utils;
+use renderer::Warning;
+use super::directives::replace_outside_code;
+
+
+/// Resolves the cross-references between chapters. A chapter can be referenced by its id with
+/// `{{#ref cli/init}}` or by its title with `[[init]]`, both are replaced by a markdown link
+/// showing the number and the name of the chapter.
+///
+/// The id of a chapter is the `id` key of its front matter, or its path without the `.md`
+/// extension. A backslash in front of a reference, `\{{#ref ...}}` or `\[[...]]`, escapes it, and
+/// the references in code are left as they are.
+pub struct CrossRefs {
+    by_id: HashMap<String, Target>,
+    by_title: HashMap<String, Target>,
+}
+
+#[derive(Clone)]
+struct Target {
+    text: String,
+    link: String,
+}
+
+impl CrossRefs {
+    pub fn new() -> Self {
+        CrossRefs {
+            by_id: HashMap::new(),
+            by_title: HashMap::new(),
+        }
+    }
+
+    /// Registers a chapter, `path` is relative to the source directory and `section` is the
+    /// number of the chapter, e.g. `"2.1."`
+    pub fn add_chapter(&mut self, path: &Path, section: Option<&str>, name: &str, id: Option<&str>) {
+        let link = utils::fs::path_to_link(path);
+
+        let target = Target {
+            text: match section {
+                Some(section) => format!("{} {}", section, name),
+                None => name.to_owned(),
+            },
+            link: link,
+        };
+
+        let path_id = utils::fs::path_to_url(&path.with_extension(""));
+        if let Some(id) = id {
+            self.by_id.insert(id.to_owned(), target.clone());
+        }
+        self.by_id.entry(path_id).or_insert_with(|| target.clone());
+        self.by_title.entry(name.to_owned()).or_insert(target);
+    }
+
+    /// Replaces the references in the markdown of `chapter`
+    pub fn render(&self, s: &str, chapter: &Path, warnings: &mut Vec<Warning>) -> String {
+        replace_outside_code(s, |text| self.render_text(text, chapter, warnings))
+    }
+
+    fn render_text(&self, s: &str, chapter: &Path, warnings: &mut Vec<Warning>) -> String {
+        let mut replaced = String::with_capacity(s.len());
+        let mut rest = s;
+
+        while let Some((start, open, close)) = find_reference(rest) {
+            let (before, reference) = rest.split_at(start);
+
+            if let Some(before) = before.strip_suffix('\\') {
+                replaced.push_str(before);
+                replaced.push_str(open);
+                rest = &reference[open.len()..];
+                continue;
+            }
+
+            replaced.push_str(before);
+
+            // `{{#ref id}}`, but not `{{#refs}}`
+            let arguments = &reference[open.len()..];
+            let is_reference = open == "[[" || arguments.starts_with("}}") ||
+                               arguments.starts_with(char::is_whitespace);
+
+            let end = match arguments.find(close) {
+                Some(end) if is_reference && !arguments[..end].contains('\n') => open.len() + end,
+                _ => {
+                    replaced.push_str(open);
+                    rest = &reference[open.len()..];
+                    continue;
+                },
+            };
+
+            let key = reference[open.len()..end].trim();
+            let target = if open == "[[" { self.by_title.get(key) } else { self.by_id.get(key) };
+
+            match target {
+                Some(target) => replaced.push_str(&format!("[{}]({})", target.text, target.link)),
+                None => {
+                    // `[[...]]` also appears in text, only unknown ids are worth a warning
+                    if open != "[[" {
+                        warnings.push(Warning::new(chapter, format!("no chapter with the id `{}` for {{{{#ref}}}}", key)));
+                    }
+                    replaced.push_str(&reference[..end + close.len()]);
+                },
+            }
+
+            rest = &reference[end + close.len()..];
+        }
+
+        replaced.push_str(rest);
+        replaced
+    }
+}
+
+// Position of the next reference with its opening and closing delimiters
+fn find_reference(s: &str) -> Option<(usize, &'static str, &'static str)> {
+    let by_id = s.find("{{#ref").map(|i| (i, "{{#ref", "}}"));
+    let by_title = s.find("[[").map(|i| (i, "[[", "]]"));
+
+    match (by_id, by_title) {
+        (Some(a), Some(b)) => Some(if a.0 < b.0 { a } else { b }),
+        (a, b) => a.or(b),
+    }
+}
+
+
+// ---------------------------------------------------------------------------------
+//      Tests
+//
+
+#[cfg(test)]
+fn test_refs() -> CrossRefs {
+    let mut refs = CrossRefs::new();
+    refs.add_chapter(Path::new("cli/init.md"), Some("2.1."), "init", None);
+    refs.add_chapter(Path::new("format/config.md"), Some("3.2."), "Configuration", Some("config"));
+    refs.add_chapter(Path::new("misc/contributors.md"), None, "Contributors", None);
+    refs
+}
+
+#[test]
+fn test_crossref_by_id() {
+    let refs = test_refs();
+    let s = "See {{#ref cli/init}} and {{#ref config}}.";
+
+    assert_eq!(refs.render(s, Path::new("README.md"), &mut vec![]),
+               "See [2.1. init](cli/init.html) and [3.2. Configuration](format/config.html).");
+}
+
+#[test]
+fn test_crossref_by_title() {
+    let refs = test_refs();
+
+    assert_eq!(refs.render("Thanks to the [[Contributors]]!", Path::new("README.md"), &mut vec![]),
+               "Thanks to the [Contributors](misc/contributors.html)!");
+}
+
+#[test]
+fn test_crossref_unknown_and_escaped() {
+    let refs = test_refs();
+    let s = "let a = [[1, 2], [3, 4]]; {{#ref missing}} \\{{#ref config}} \\[[init]] {{#refs}} {{#reference config}}";
+
+    let mut warnings = vec![];
+    assert_eq!(refs.render(s, Path::new("README.md"), &mut warnings),
+               "let a = [[1, 2], [3, 4]]; {{#ref missing}} {{#ref config}} [[init]] {{#refs}} {{#reference config}}");
+    assert_eq!(warnings,
+               vec![Warning::new(Path::new("README.md"), "no chapter with the id `missing` for {{#ref}}")]);
+}
+
+#[test]
+fn test_crossref_in_code() {
+    let mut refs = test_refs();
+    refs.add_chapter(Path::new("sub.md"), Some("4."), "Sub", None);
+    let code = "```toml\n[[Sub]]\nname = \"a\"\n```\n\nWrite `{{#ref missing}}` or `[[Sub]]`.";
+
+    let mut warnings = vec![];
+    assert_eq!(refs.render(&format!("See [[Sub]].\n\n{}", code), Path::new("README.md"), &mut warnings),
+               format!("See [4. Sub](sub.html).\n\n{}", code));
+    assert!(warnings.is_empty());
+}
@@ -0,0 +1,133 @@
+use std::path::Path;
+
+use utils;
+use renderer::Warning;
+use super::directives::{replace_directives, replace_outside_code};
+
+
+const CLOSE: &'static str = "{{/details}}";
+
+// pulldown-cmark does not know the `details` tag, the blocks start with a `div` so that they are html blocks
+const OPEN_HTML: &'static str = "<div class=\"collapsible\"><details>";
+const CLOSE_HTML: &'static str = "</div></details></div>";
+
+
+/// Replaces the `{{#details Summary text}} ... {{/details}}` blocks of a chapter by collapsible
+/// `<details>` elements, closed until the reader clicks on their summary. The content of a block
+/// is markdown and blocks can be nested. A backslash in front of a tag, `\{{/details}}`, escapes it,
+/// and the tags in code are left as they are.
+pub fn render_details(s: &str, chapter: &Path, warnings: &mut Vec<Warning>) -> String {
+    let opened = replace_directives(s, "details", |summary| {
+        let summary = if summary.is_empty() { "Details" } else { summary };
+        Some(format!("\n\n{}<summary>{}</summary><div class=\"collapsible-content\">\n\n",
+                     OPEN_HTML,
+                     utils::escape_html(summary)))
+    });
+
+    let mut depth = 0;
+    let mut rendered = replace_outside_code(&opened, |text| close_blocks(text, &mut depth, chapter, warnings));
+
+    // The blocks left open are closed at the end of the chapter
+    if depth > 0 {
+        warnings.push(Warning::new(chapter, "`{{#details}}` without `{{/details}}`"));
+        for _ in 0..depth {
+            rendered.push_str("\n\n");
+            rendered.push_str(CLOSE_HTML);
+            rendered.push('\n');
+        }
+    }
+
+    rendered
+}
+
+// Closes the blocks opened before `{{/details}}`, `depth` is the number of blocks left open
+fn close_blocks(s: &str, depth: &mut usize, chapter: &Path, warnings: &mut Vec<Warning>) -> String {
+    let mut rendered = String::with_capacity(s.len());
+    let mut rest = s;
+
+    loop {
+        let open = rest.find(OPEN_HTML);
+        let close = rest.find(CLOSE);
+        let (start, is_open) = match (open, close) {
+            (Some(open), Some(close)) if open < close => (open, true),
+            (_, Some(close)) => (close, false),
+            (Some(open), None) => (open, true),
+            (None, None) => break,
+        };
+        let (before, tag) = rest.split_at(start);
+
+        if is_open {
+            *depth += 1;
+            rendered.push_str(before);
+            rendered.push_str(OPEN_HTML);
+            rest = &tag[OPEN_HTML.len()..];
+            continue;
+        }
+
+        rest = &tag[CLOSE.len()..];
+        match before.strip_suffix('\\') {
+            Some(before) => {
+                rendered.push_str(before);
+                rendered.push_str(CLOSE);
+            },
+            None if *depth == 0 => {
+                warnings.push(Warning::new(chapter, "`{{/details}}` without `{{#details}}`"));
+                rendered.push_str(before);
+                rendered.push_str(CLOSE);
+            },
+            None => {
+                *depth -= 1;
+                rendered.push_str(before);
+                rendered.push_str("\n\n");
+                rendered.push_str(CLOSE_HTML);
+                rendered.push_str("\n\n");
+            },
+        }
+    }
+    rendered.push_str(rest);
+    rendered
+}
+
+
+// ---------------------------------------------------------------------------------
+//      Tests
+//
+
+#[test]
+fn test_render_details() {
+    let mut warnings = vec![];
+    let s = "Try it.\n\n{{#details The <answer>}}\nUse `fold`.\n{{/details}}\n\\{{#details no}} \\{{/details}}";
+    let rendered = render_details(s, Path::new("intro.md"), &mut warnings);
+
+    assert_eq!(rendered,
+               "Try it.\n\n\n\n<div class=\"collapsible\"><details><summary>The &lt;answer&gt;</summary>\
+                <div class=\"collapsible-content\">\n\n\nUse `fold`.\n\n\n</div></details></div>\n\n\n\
+                {{#details no}} {{/details}}");
+    assert!(warnings.is_empty());
+
+    let mut html = String::new();
+    ::pulldown_cmark::html::push_html(&mut html, ::pulldown_cmark::Parser::new(&rendered));
+    assert_eq!(html,
+               "<p>Try it.</p>\n<div class=\"collapsible\"><details><summary>The &lt;answer&gt;</summary>\
+                <div class=\"collapsible-content\">\n<p>Use <code>fold</code>.</p>\n</div></details></div>\n\
+                <p>{{#details no}} {{/details}}</p>\n");
+}
+
+#[test]
+fn test_render_nested_details() {
+    let mut warnings = vec![];
+    let s = "{{#details}}\n{{#details Hint}}\na\n{{/details}}\n";
+    let rendered = render_details(s, Path::new("a.md"), &mut warnings);
+
+    assert_eq!(rendered.matches("<details>").count(), 2);
+    assert_eq!(rendered.matches("</details>").count(), 2);
+    assert!(rendered.contains("<summary>Details</summary>"));
+    assert_eq!(warnings.len(), 1);
+
+    render_details("a {{/details}}", Path::new("a.md"), &mut warnings);
+    assert_eq!(warnings.len(), 2);
+
+    let s = "Write `{{#details}}` and `{{/details}}`:\n\n```\n{{#details Hint}}\n{{/details}}\n```";
+    assert_eq!(render_details(s, Path::new("a.md"), &mut warnings), s);
+    assert_eq!(warnings.len(), 2);
+}
@@ -0,0 +1,230 @@
+/// Replaces the `{{#name arguments}}` directives of a chapter by the result of `f`, which gets the
+/// trimmed arguments. Directives for which `f` returns `None` are left as they are, and a
+/// backslash in front of a directive, `\{{#name ...}}`, escapes it. The directives in code are
+/// left as they are too.
+pub fn replace_directives<F>(s: &str, name: &str, mut f: F) -> String
+    where F: FnMut(&str) -> Option<String>
+{
+    replace_outside_code(s, |text| replace_all_directives(text, name, &mut f))
+}
+
+/// Like `replace_directives`, but also replaces the directives in code, for `{{#include}}` whose
+/// files are often the code of a block
+pub fn replace_all_directives<F>(s: &str, name: &str, mut f: F) -> String
+    where F: FnMut(&str) -> Option<String>
+{
+    let open = format!("{{{{#{}", name);
+    let mut replaced = String::with_capacity(s.len());
+    let mut rest = s;
+
+    while let Some(start) = rest.find(&open) {
+        let (before, directive) = rest.split_at(start);
+        let arguments = &directive[open.len()..];
+
+        // `{{#name}}` or `{{#name arguments}}`, but not `{{#names}}`
+        let is_directive = arguments.starts_with("}}") || arguments.starts_with(char::is_whitespace);
+
+        if let Some(before) = before.strip_suffix('\\') {
+            replaced.push_str(before);
+            replaced.push_str(&open);
+            rest = arguments;
+            continue;
+        }
+
+        replaced.push_str(before);
+
+        let end = match arguments.find("}}") {
+            Some(end) if is_directive && !arguments[..end].contains('\n') => end,
+            _ => {
+                replaced.push_str(&open);
+                rest = arguments;
+                continue;
+            },
+        };
+
+        match f(arguments[..end].trim()) {
+            Some(replacement) => replaced.push_str(&replacement),
+            None => replaced.push_str(&directive[..open.len() + end + "}}".len()]),
+        }
+
+        rest = &arguments[end + "}}".len()..];
+    }
+
+    replaced.push_str(rest);
+    replaced
+}
+
+/// Arguments of every directive `name` that is not escaped, in the order they appear
+pub fn find_directives(s: &str, name: &str) -> Vec<String> {
+    let mut found = vec![];
+    replace_directives(s, name, |arguments| {
+        found.push(arguments.to_owned());
+        None
+    });
+    found
+}
+
+/// Replaces the parts of the markdown that are not code by the result of `f`, the code blocks and
+/// the inline code are left as they are
+pub fn replace_outside_code<F>(s: &str, mut f: F) -> String
+    where F: FnMut(&str) -> String
+{
+    let mut replaced = String::with_capacity(s.len());
+    let mut start = 0;
+
+    for (code_start, code_end) in code_ranges(s) {
+        replaced.push_str(&f(&s[start..code_start]));
+        replaced.push_str(&s[code_start..code_end]);
+        start = code_end;
+    }
+
+    replaced.push_str(&f(&s[start..]));
+    replaced
+}
+
+// The code blocks, fenced or indented, and the inline code of the markdown, in order. The indented
+// lines of the lists are not code.
+fn code_ranges(s: &str) -> Vec<(usize, usize)> {
+    let mut ranges = vec![];
+    // Start of the text after the last code block, where the inline code is searched
+    let mut text_start = 0;
+    let mut block_start = 0;
+    let mut fence: Option<(char, usize)> = None;
+    let mut indented = false;
+    let mut in_list = false;
+    let mut blank_before = true;
+    let mut line_start = 0;
+
+    for line in s.split_inclusive('\n') {
+        let line_end = line_start + line.len();
+        let trimmed = line.trim_start();
+        let indent = line[..line.len() - trimmed.len()].chars().map(|c| if c == '\t' { 4 } else { 1 }).sum::<usize>();
+        let blank = trimmed.trim_end().is_empty();
+        let after_blank = blank_before;
+        blank_before = blank;
+
+        if let Some((c, len)) = fence {
+            if trimmed.trim_end().len() >= len && trimmed.trim_end().chars().all(|t| t == c) {
+                fence = None;
+                ranges.push((block_start, line_end));
+                text_start = line_end;
+            }
+        } else if indented && (blank || indent >= 4) {
+            // The code goes on
+        } else {
+            if indented {
+                indented = false;
+                ranges.push((block_start, line_start));
+                text_start = line_start;
+            }
+
+            let fence_len = trimmed.len() - trimmed.trim_start_matches(['`', '~']).len();
+            let fence_char = trimmed.chars().next().unwrap_or(' ');
+            if fence_len >= 3 && trimmed[..fence_len].chars().all(|c| c == fence_char) {
+                push_inline_code(s, text_start, line_start, &mut ranges);
+                fence = Some((fence_char, fence_len));
+                block_start = line_start;
+            } else if !blank && indent >= 4 && after_blank && !in_list {
+                push_inline_code(s, text_start, line_start, &mut ranges);
+                indented = true;
+                block_start = line_start;
+            } else if !blank && indent < 4 && is_list_item(trimmed) {
+                in_list = true;
+            } else if !blank && indent == 0 && after_blank {
+                in_list = false;
+            }
+        }
+
+        line_start = line_end;
+    }
+
+    if fence.is_some() || indented {
+        ranges.push((block_start, s.len()));
+    } else {
+        push_inline_code(s, text_start, s.len(), &mut ranges);
+    }
+    ranges
+}
+
+// Adds the inline code between `start` and `end`, a run of backticks up to the next run of the
+// same length in the paragraph
+fn push_inline_code(s: &str, start: usize, end: usize, ranges: &mut Vec<(usize, usize)>) {
+    let text = &s[start..end];
+    let bytes = text.as_bytes();
+    let backticks = |i: usize| bytes[i..].iter().take_while(|&&b| b == b'`').count();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 2,
+            b'`' => {
+                let run = backticks(i);
+                let paragraph_end = text[i..].find("\n\n").map_or(text.len(), |end| i + end);
+                let mut j = i + run;
+                let mut close = None;
+                while let Some(next) = text[j..paragraph_end].find('`') {
+                    let next = j + next;
+                    let len = backticks(next);
+                    if len == run {
+                        close = Some(next + len);
+                        break;
+                    }
+                    j = next + len;
+                }
+                match close {
+                    Some(close) => {
+                        ranges.push((start + i, start + close));
+                        i = close;
+                    },
+                    None => i += run,
+                }
+            },
+            _ => i += 1,
+        }
+    }
+}
+
+// `- item`, `* item`, `+ item`, `1. item` or `1) item`
+fn is_list_item(line: &str) -> bool {
+    let marker = line.trim_start_matches(|c: char| c.is_ascii_digit());
+    let rest = if marker.len() < line.len() {
+        marker.strip_prefix('.').or_else(|| marker.strip_prefix(')'))
+    } else {
+        line.strip_prefix('-').or_else(|| line.strip_prefix('*')).or_else(|| line.strip_prefix('+'))
+    };
+    rest.is_some_and(|rest| rest.is_empty() || rest.starts_with(char::is_whitespace))
+}
+
+
+// ---------------------------------------------------------------------------------
+//      Tests
+//
+
+#[test]
+fn test_replace_directives() {
+    let s = "a {{#fig one}} b \\{{#fig two}} {{#figure three}} {{#fig}} {{#fig\nfour}}";
+    let replaced = replace_directives(s, "fig", |args| Some(format!("[{}]", args)));
+
+    assert_eq!(replaced, "a [one] b {{#fig two}} {{#figure three}} [] {{#fig\nfour}}");
+
+    let s = "{{#fig one}} `{{#fig two}}`\n\n```\n{{#fig three}}\n```";
+    assert_eq!(replace_directives(s, "fig", |args| Some(format!("[{}]", args))),
+               "[one] `{{#fig two}}`\n\n```\n{{#fig three}}\n```");
+    assert_eq!(replace_all_directives(s, "fig", |args| Some(format!("[{}]", args))),
+               "[one] `[two]`\n\n```\n[three]\n```");
+}
+
+#[test]
+fn test_find_directives() {
+    assert_eq!(find_directives("{{#table a B}} \\{{#table c}} {{#table d}}", "table"),
+               vec!["a B".to_owned(), "d".to_owned()]);
+}
+
+#[test]
+fn test_replace_outside_code() {
+    let s = "a `b` c ``d ` e`` f\n\n```toml\n[[g]]\n```\n\n    h\n\ni\n\n- j\n\n    k\n\n~~~\nl";
+    let replaced = replace_outside_code(s, |text| text.to_uppercase());
+
+    assert_eq!(replaced, "A `b` C ``d ` e`` F\n\n```toml\n[[g]]\n```\n\n    h\n\nI\n\n- J\n\n    K\n\n~~~\nl");
+    assert_eq!(replace_outside_code("a ` b", |text| text.to_uppercase()), "A ` B");
+}
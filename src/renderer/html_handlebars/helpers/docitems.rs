@@ -0,0 +1,259 @@
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::collections::BTreeMap;
+
+use super::directives::replace_directives;
+use renderer::Warning;
+
+
+/// Replaces the `{{#doc my_crate::module::item}}` directives by the source of the item, read from
+/// the crates configured in `[output.html.doc-crates]`. With `{{#doc my_crate::module::item docs}}`
+/// the doc comment of the item is put above its source.
+///
+/// Modules are looked up like rustc does, in `module.rs`, `module/mod.rs` or an inline
+/// `mod module { ... }`. Items are found by name, the first item with that name in the module is
+/// used.
+pub fn render_doc_items(s: &str,
+                        crates: &BTreeMap<String, PathBuf>,
+                        chapter: &Path,
+                        warnings: &mut Vec<Warning>)
+                        -> String {
+    replace_directives(s, "doc", |arguments| {
+        let mut arguments = arguments.split_whitespace();
+        let item_path = arguments.next().unwrap_or_default();
+        let with_docs = arguments.any(|a| a == "docs");
+
+        match extract_item(item_path, crates) {
+            Ok((docs, code)) => {
+                let mut markdown = String::new();
+                if with_docs && !docs.is_empty() {
+                    markdown.push_str(&docs);
+                    markdown.push_str("\n\n");
+                }
+                markdown.push_str(&format!("```rust,ignore\n{}\n```", code));
+                Some(markdown)
+            },
+            Err(e) => {
+                warnings.push(Warning::new(chapter, format!("{{{{#doc {}}}}}: {}", item_path, e)));
+                None
+            },
+        }
+    })
+}
+
+// The doc comment and the source of an item
+fn extract_item(item_path: &str, crates: &BTreeMap<String, PathBuf>) -> Result<(String, String), String> {
+    let segments: Vec<&str> = item_path.split("::").collect();
+    if segments.len() < 2 {
+        return Err("the path should start with the name of a crate, e.g. `my_crate::item`".to_owned());
+    }
+
+    let root = try!(crates.get(segments[0])
+        .ok_or_else(|| format!("the crate `{}` is not in [output.html.doc-crates]", segments[0])));
+
+    let mut file = root.clone();
+    let mut source = try!(read_file(&file));
+
+    // Modules
+    for (i, module) in segments[1..segments.len() - 1].iter().enumerate() {
+        let dir = module_dir(&file, i == 0);
+        let candidates = [dir.join(format!("{}.rs", module)), dir.join(module).join("mod.rs")];
+
+        if let Some(path) = candidates.iter().find(|p| p.is_file()) {
+            source = try!(read_file(path));
+            file = path.clone();
+        } else if let Some(item) = find_item(&source, module) {
+            // Inline module, its items are searched in its body
+            let body = item.code;
+            let start = body.find('{').map_or(0, |i| i + 1);
+            let end = body.rfind('}').unwrap_or(body.len());
+            source = body[start..end].to_owned();
+            file = dir.join(module).join("mod.rs");
+        } else {
+            return Err(format!("the module `{}` was not found", module));
+        }
+    }
+
+    let name = segments[segments.len() - 1];
+    match find_item(&source, name) {
+        Some(item) => Ok((item.docs, item.code)),
+        None => Err(format!("no item named `{}` in {}", name, file.display())),
+    }
+}
+
+// Directory of the sub-modules of the module in `file`
+fn module_dir(file: &Path, crate_root: bool) -> PathBuf {
+    let parent = file.parent().unwrap_or(Path::new("")).to_owned();
+    let stem = file.file_stem().and_then(|s| s.to_str()).unwrap_or_default();
+
+    if crate_root || stem == "mod" {
+        parent
+    } else {
+        parent.join(stem)
+    }
+}
+
+fn read_file(path: &Path) -> Result<String, String> {
+    let mut content = String::new();
+    File::open(path)
+        .and_then(|mut f| f.read_to_string(&mut content))
+        .map_err(|e| format!("could not read {}: {}", path.display(), e))?;
+    Ok(content)
+}
+
+struct Item {
+    docs: String,
+    code: String,
+}
+
+const KEYWORDS: [&'static str; 10] = ["fn", "struct", "enum", "trait", "type", "const", "static", "union", "mod",
+                                      "macro_rules!"];
+const MODIFIERS: [&'static str; 7] = ["pub", "unsafe", "async", "const", "extern", "\"C\"", "default"];
+
+// Finds the first item named `name`, with its attributes and without its doc comment
+fn find_item(source: &str, name: &str) -> Option<Item> {
+    let lines: Vec<&str> = source.lines().collect();
+    let start = lines.iter().position(|line| declares(line, name))?;
+
+    // Attributes and doc comment above the item
+    let mut first = start;
+    let mut docs = vec![];
+    while first > 0 {
+        let line = lines[first - 1].trim();
+        if let Some(doc) = line.strip_prefix("///") {
+            docs.insert(0, doc.strip_prefix(' ').unwrap_or(doc));
+        } else if !line.starts_with("#[") {
+            break;
+        }
+        first -= 1;
+    }
+
+    let attributes: Vec<&str> = lines[first..start].iter().filter(|l| !l.trim().starts_with("///")).cloned().collect();
+    let end = start + item_length(&lines[start..].join("\n"));
+
+    let indent = lines[start].len() - lines[start].trim_start().len();
+    let code = attributes.iter()
+        .chain(lines[start..end].iter())
+        .map(|line| if line.len() >= indent && line[..indent].trim().is_empty() { &line[indent..] } else { line.trim_start() })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    Some(Item {
+        docs: docs.join("\n"),
+        code: code,
+    })
+}
+
+// Whether the line declares an item named `name`, e.g. `pub(crate) unsafe fn name<T>(...)`
+fn declares(line: &str, name: &str) -> bool {
+    let line = line.trim_start();
+    // `pub(crate)`, `pub(super)`, ...
+    let line = if line.starts_with("pub(") { line.find(')').map_or(line, |i| &line[i + 1..]) } else { line };
+    let mut tokens = line.split(|c: char| c.is_whitespace() || "(<{:;=".contains(c)).filter(|t| !t.is_empty());
+
+    while let Some(token) = tokens.next() {
+        if KEYWORDS.contains(&token) {
+            // `const fn`, `const NAME`
+            let next = tokens.next();
+            if token == "const" && next == Some("fn") {
+                return tokens.next() == Some(name);
+            }
+            return next == Some(name);
+        }
+        if !MODIFIERS.contains(&token) {
+            return false;
+        }
+    }
+
+    false
+}
+
+// Number of lines of the item starting at the beginning of `s`, up to its closing brace or to the
+// `;` ending it
+fn item_length(s: &str) -> usize {
+    let chars: Vec<char> = s.chars().collect();
+    let mut depth = 0;
+    let mut lines = 1;
+    let mut i = 0;
+
+    while i < chars.len() {
+        match chars[i] {
+            '\n' => lines += 1,
+            '/' if chars.get(i + 1) == Some(&'/') => {
+                while i < chars.len() && chars[i] != '\n' {
+                    i += 1;
+                }
+                continue;
+            },
+            '"' => {
+                i += 1;
+                while i < chars.len() && chars[i] != '"' {
+                    if chars[i] == '\\' {
+                        i += 1;
+                    } else if chars[i] == '\n' {
+                        lines += 1;
+                    }
+                    i += 1;
+                }
+            },
+            // '{' and '}' as char literals
+            '\'' if chars.get(i + 2) == Some(&'\'') => i += 2,
+            '{' | '(' | '[' => depth += 1,
+            '}' | ')' | ']' => {
+                depth -= 1;
+                if depth == 0 && chars[i] == '}' {
+                    return lines;
+                }
+            },
+            ';' if depth == 0 => return lines,
+            _ => {},
+        }
+        i += 1;
+    }
+
+    lines
+}
+
+
+// ---------------------------------------------------------------------------------
+//      Tests
+//
+
+#[test]
+fn test_find_item() {
+    let source = "use std::io;\n\n\
+                  /// Adds one.\n\
+                  ///\n\
+                  /// Really.\n\
+                  #[inline]\n\
+                  pub fn add_one(x: i32) -> i32 {\n    \
+                      if x == 0 { '}'; }\n    \
+                      x + 1 // }\n\
+                  }\n\n\
+                  pub const MAX: u32 = 10;\n";
+
+    let item = find_item(source, "add_one").unwrap();
+    assert_eq!(item.docs, "Adds one.\n\nReally.");
+    assert_eq!(item.code,
+               "#[inline]\npub fn add_one(x: i32) -> i32 {\n    if x == 0 { '}'; }\n    x + 1 // }\n}");
+
+    assert_eq!(find_item(source, "MAX").unwrap().code, "pub const MAX: u32 = 10;");
+    assert!(find_item(source, "add").is_none());
+}
+
+#[test]
+fn test_find_item_in_impl() {
+    let source = "impl Foo {\n    pub(crate) const fn new() -> Self {\n        Foo\n    }\n}\n";
+
+    assert_eq!(find_item(source, "new").unwrap().code, "pub(crate) const fn new() -> Self {\n    Foo\n}");
+}
+
+#[test]
+fn test_declares() {
+    assert!(declares("pub struct Book<'a> {", "Book"));
+    assert!(declares("    unsafe extern \"C\" fn callback()", "callback"));
+    assert!(declares("macro_rules! try_opt {", "try_opt"));
+    assert!(!declares("let fn_name = 5;", "fn_name"));
+    assert!(!declares("// fn commented()", "commented"));
+}
@@ -0,0 +1,144 @@
+use std::path::Path;
+
+use utils;
+use renderer::Warning;
+use super::directives::replace_directives;
+
+
+// A video or a page loaded in an iframe when the reader clicks on its placeholder
+struct Embed {
+    // Address of the iframe
+    src: String,
+    // Page of the video or the page itself, linked by the placeholder
+    link: String,
+    // Site loading the content, named on the placeholder
+    site: String,
+    title: String,
+    icon: &'static str,
+}
+
+/// Replaces the `{{#video youtube:ID}}`, `{{#video vimeo:ID}}` and `{{#embed https://...}}` directives of a
+/// chapter by responsive embeds, that the reader loads by clicking on them. Until then nothing is requested
+/// from the other site, the videos of YouTube are loaded from `youtube-nocookie.com` and the ones of Vimeo
+/// without tracking. The placeholder is a link to the video or the page, so that it still works without
+/// `book.js`, printed or sanitized. A title can follow the address, e.g. `{{#video youtube:ID The borrow
+/// checker}}`.
+pub fn render_embeds(s: &str, chapter: &Path, warnings: &mut Vec<Warning>) -> String {
+    let s = replace_directives(s, "video", |arguments| {
+        let (video, title) = split_title(arguments);
+        match video_embed(video, title) {
+            Some(embed) => Some(embed_html(&embed)),
+            None => {
+                warnings.push(Warning::new(chapter,
+                                           format!("unknown video `{}`, expected `youtube:ID` or `vimeo:ID`",
+                                                   video)));
+                None
+            },
+        }
+    });
+    replace_directives(&s, "embed", |arguments| {
+        let (url, title) = split_title(arguments);
+        let site = match url.strip_prefix("https://").or_else(|| url.strip_prefix("http://")) {
+            Some(rest) => rest.split(['/', '?', '#']).next().unwrap_or_default(),
+            None => "",
+        };
+        if site.is_empty() {
+            warnings.push(Warning::new(chapter, format!("the embed `{}` is not an http or https address", url)));
+            return None;
+        }
+        Some(embed_html(&Embed {
+            src: url.to_owned(),
+            link: url.to_owned(),
+            site: site.to_owned(),
+            title: title.unwrap_or(site).to_owned(),
+            icon: "fa-external-link-square",
+        }))
+    })
+}
+
+// The address and the title after it
+fn split_title(arguments: &str) -> (&str, Option<&str>) {
+    match arguments.find(char::is_whitespace) {
+        Some(i) => (&arguments[..i], Some(arguments[i..].trim())),
+        None => (arguments, None),
+    }
+}
+
+fn video_embed(video: &str, title: Option<&str>) -> Option<Embed> {
+    let (provider, id) = video.split_once(':')?;
+    let (src, link, site) = match provider {
+        "youtube" if !id.is_empty() && id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') => {
+            (format!("https://www.youtube-nocookie.com/embed/{}?autoplay=1", id),
+             format!("https://www.youtube.com/watch?v={}", id),
+             "YouTube")
+        },
+        "vimeo" if !id.is_empty() && id.chars().all(|c| c.is_ascii_digit()) => {
+            (format!("https://player.vimeo.com/video/{}?autoplay=1&dnt=1", id),
+             format!("https://vimeo.com/{}", id),
+             "Vimeo")
+        },
+        _ => return None,
+    };
+    Some(Embed {
+        src: src,
+        link: link,
+        site: site.to_owned(),
+        title: title.map_or_else(|| format!("{} video", site), |title| title.to_owned()),
+        icon: "fa-play-circle",
+    })
+}
+
+// The placeholder of the embed, an html block replaced by the iframe by `book.js`
+fn embed_html(embed: &Embed) -> String {
+    format!("\n\n<div class=\"embed\" data-embed=\"{}\" title=\"{}\"><a class=\"embed-placeholder\" href=\"{}\"><i \
+             class=\"fa {}\" aria-hidden=\"true\"></i><span class=\"embed-title\">{}</span><span \
+             class=\"embed-notice\">Loaded from {} when clicked</span></a></div>\n\n",
+            utils::escape_html(&embed.src),
+            utils::escape_html(&embed.title),
+            utils::escape_html(&embed.link),
+            embed.icon,
+            utils::escape_html(&embed.title),
+            utils::escape_html(&embed.site))
+}
+
+
+// ---------------------------------------------------------------------------------
+//      Tests
+//
+
+#[test]
+fn test_render_embeds() {
+    let chapter = Path::new("intro.md");
+    let mut warnings = vec![];
+    let rendered = render_embeds("{{#video youtube:aBc-12_x The borrow checker}}\n\n{{#video vimeo:7600}}\n\n\
+                                  {{#embed https://play.rust-lang.org/?code=1&edition=2015}}\n\n\
+                                  {{#video dailymotion:x7}} {{#embed javascript:alert(1)}} \\{{#video youtube:a}}",
+                                 chapter,
+                                 &mut warnings);
+
+    assert!(rendered.contains("<div class=\"embed\" \
+                               data-embed=\"https://www.youtube-nocookie.com/embed/aBc-12_x?autoplay=1\" \
+                               title=\"The borrow checker\"><a class=\"embed-placeholder\" \
+                               href=\"https://www.youtube.com/watch?v=aBc-12_x\"><i class=\"fa fa-play-circle\" \
+                               aria-hidden=\"true\"></i><span class=\"embed-title\">The borrow checker</span><span \
+                               class=\"embed-notice\">Loaded from YouTube when clicked</span></a></div>"));
+    assert!(rendered.contains("data-embed=\"https://player.vimeo.com/video/7600?autoplay=1&amp;dnt=1\" \
+                               title=\"Vimeo video\""));
+    assert!(rendered.contains("data-embed=\"https://play.rust-lang.org/?code=1&amp;edition=2015\" \
+                               title=\"play.rust-lang.org\""));
+    assert!(rendered.ends_with("{{#video dailymotion:x7}} {{#embed javascript:alert(1)}} {{#video youtube:a}}"));
+    assert_eq!(warnings,
+               vec![Warning::new(chapter, "unknown video `dailymotion:x7`, expected `youtube:ID` or `vimeo:ID`"),
+                    Warning::new(chapter, "the embed `javascript:alert(1)` is not an http or https address")]);
+}
+
+#[test]
+fn test_sanitize_embeds() {
+    use super::sanitize::sanitize;
+
+    let mut warnings = vec![];
+    let html = render_embeds("{{#video youtube:aBc-12_x}}", Path::new("intro.md"), &mut warnings);
+    assert_eq!(sanitize(&html), html);
+    assert_eq!(sanitize("<div class=\"embed\" data-embed=\"javascript:alert(1)\"></div>"),
+               "<div class=\"embed\"></div>");
+}
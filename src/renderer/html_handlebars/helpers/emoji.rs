@@ -0,0 +1,1687 @@
+// The emoji of the `[markdown]` `emoji` shortcodes, sorted by name to be searched with a binary search.
+//
+// The names are the ones GitHub uses for the common emoji, and the Unicode names of the others in lower case
+// with `_` between the words, e.g. `:crab:`, `:+1:`, `:tada:` or `:face_with_monocle:`.
+
+pub const EMOJI: &'static [(&'static str, &'static str)] = &[
+    ("+1", "\u{1f44d}"),
+    ("-1", "\u{1f44e}"),
+    ("100", "\u{1f4af}"),
+    ("1234", "\u{1f522}"),
+    ("1st_place_medal", "\u{1f947}"),
+    ("2nd_place_medal", "\u{1f948}"),
+    ("3rd_place_medal", "\u{1f949}"),
+    ("abacus", "\u{1f9ee}"),
+    ("abc", "\u{1f524}"),
+    ("accordion", "\u{1fa97}"),
+    ("adhesive_bandage", "\u{1fa79}"),
+    ("admission_tickets", "\u{1f39f}\u{fe0f}"),
+    ("adult", "\u{1f9d1}"),
+    ("aerial_tramway", "\u{1f6a1}"),
+    ("airplane", "\u{2708}\u{fe0f}"),
+    ("airplane_arriving", "\u{1f6ec}"),
+    ("airplane_departure", "\u{1f6eb}"),
+    ("alarm_clock", "\u{23f0}"),
+    ("alien", "\u{1f47d}"),
+    ("alien_monster", "\u{1f47e}"),
+    ("ambulance", "\u{1f691}"),
+    ("american_football", "\u{1f3c8}"),
+    ("amphora", "\u{1f3fa}"),
+    ("anatomical_heart", "\u{1fac0}"),
+    ("anchor", "\u{2693}"),
+    ("angel", "\u{1f47c}"),
+    ("anger", "\u{1f4a2}"),
+    ("angry", "\u{1f620}"),
+    ("angry_face", "\u{1f620}"),
+    ("anguished", "\u{1f627}"),
+    ("anguished_face", "\u{1f627}"),
+    ("ant", "\u{1f41c}"),
+    ("antenna_with_bars", "\u{1f4f6}"),
+    ("anticlockwise_downwards_and_upwards_open_circle_arrows", "\u{1f504}"),
+    ("apple", "\u{1f34e}"),
+    ("arrow_backward", "\u{25c0}\u{fe0f}"),
+    ("arrow_down", "\u{2b07}\u{fe0f}"),
+    ("arrow_forward", "\u{25b6}\u{fe0f}"),
+    ("arrow_left", "\u{2b05}\u{fe0f}"),
+    ("arrow_right", "\u{27a1}\u{fe0f}"),
+    ("arrow_right_hook", "\u{21aa}\u{fe0f}"),
+    ("arrow_up", "\u{2b06}\u{fe0f}"),
+    ("art", "\u{1f3a8}"),
+    ("articulated_lorry", "\u{1f69b}"),
+    ("artist_palette", "\u{1f3a8}"),
+    ("astonished", "\u{1f632}"),
+    ("astonished_face", "\u{1f632}"),
+    ("athletic_shoe", "\u{1f45f}"),
+    ("aubergine", "\u{1f346}"),
+    ("auto_rickshaw", "\u{1f6fa}"),
+    ("automated_teller_machine", "\u{1f3e7}"),
+    ("automobile", "\u{1f697}"),
+    ("avocado", "\u{1f951}"),
+    ("axe", "\u{1fa93}"),
+    ("baby", "\u{1f476}"),
+    ("baby_angel", "\u{1f47c}"),
+    ("baby_bottle", "\u{1f37c}"),
+    ("baby_chick", "\u{1f424}"),
+    ("back", "\u{1f519}"),
+    ("back_of_envelope", "\u{1f582}"),
+    ("back_with_leftwards_arrow_above", "\u{1f519}"),
+    ("bacon", "\u{1f953}"),
+    ("bactrian_camel", "\u{1f42b}"),
+    ("badger", "\u{1f9a1}"),
+    ("badminton_racquet_and_shuttlecock", "\u{1f3f8}"),
+    ("bagel", "\u{1f96f}"),
+    ("baggage_claim", "\u{1f6c4}"),
+    ("baguette_bread", "\u{1f956}"),
+    ("ball_of_yarn", "\u{1f9f6}"),
+    ("ballet_shoes", "\u{1fa70}"),
+    ("balloon", "\u{1f388}"),
+    ("ballot_bold_script_x", "\u{1f5f6}"),
+    ("ballot_box_with_ballot", "\u{1f5f3}\u{fe0f}"),
+    ("ballot_box_with_bold_check", "\u{1f5f9}"),
+    ("ballot_box_with_bold_script_x", "\u{1f5f7}"),
+    ("ballot_box_with_script_x", "\u{1f5f5}"),
+    ("ballot_script_x", "\u{1f5f4}"),
+    ("banana", "\u{1f34c}"),
+    ("bangbang", "\u{203c}\u{fe0f}"),
+    ("banjo", "\u{1fa95}"),
+    ("bank", "\u{1f3e6}"),
+    ("banknote_with_dollar", "\u{1f4b5}"),
+    ("banknote_with_euro", "\u{1f4b6}"),
+    ("banknote_with_pound", "\u{1f4b7}"),
+    ("banknote_with_yen", "\u{1f4b4}"),
+    ("bar_chart", "\u{1f4ca}"),
+    ("bar_of_soap", "\u{1f9fc}"),
+    ("barber_pole", "\u{1f488}"),
+    ("basket", "\u{1f9fa}"),
+    ("basketball_and_hoop", "\u{1f3c0}"),
+    ("bat", "\u{1f987}"),
+    ("bath", "\u{1f6c0}"),
+    ("bathtub", "\u{1f6c1}"),
+    ("battery", "\u{1f50b}"),
+    ("beach_with_umbrella", "\u{1f3d6}\u{fe0f}"),
+    ("beamed_ascending_musical_notes", "\u{1f39c}\u{fe0f}"),
+    ("beamed_descending_musical_notes", "\u{1f39d}\u{fe0f}"),
+    ("beans", "\u{1fad8}"),
+    ("bear", "\u{1f43b}"),
+    ("bear_face", "\u{1f43b}"),
+    ("bearded_person", "\u{1f9d4}"),
+    ("beating_heart", "\u{1f493}"),
+    ("beaver", "\u{1f9ab}"),
+    ("bed", "\u{1f6cf}\u{fe0f}"),
+    ("bee", "\u{1f41d}"),
+    ("beer", "\u{1f37a}"),
+    ("beer_mug", "\u{1f37a}"),
+    ("beers", "\u{1f37b}"),
+    ("beetle", "\u{1f41e}"),
+    ("bell", "\u{1f514}"),
+    ("bell_pepper", "\u{1fad1}"),
+    ("bell_with_cancellation_stroke", "\u{1f515}"),
+    ("bellhop_bell", "\u{1f6ce}\u{fe0f}"),
+    ("bento_box", "\u{1f371}"),
+    ("beverage_box", "\u{1f9c3}"),
+    ("bicycle", "\u{1f6b2}"),
+    ("bicyclist", "\u{1f6b4}"),
+    ("bike", "\u{1f6b2}"),
+    ("bikini", "\u{1f459}"),
+    ("billed_cap", "\u{1f9e2}"),
+    ("billiards", "\u{1f3b1}"),
+    ("bird", "\u{1f426}"),
+    ("birthday", "\u{1f382}"),
+    ("birthday_cake", "\u{1f382}"),
+    ("bison", "\u{1f9ac}"),
+    ("biting_lip", "\u{1fae6}"),
+    ("black_chess_bishop_rotated_ninety_degrees", "\u{1fa12}"),
+    ("black_chess_bishop_rotated_two_hundred_seventy_degrees", "\u{1fa3c}"),
+    ("black_chess_equihopper", "\u{1fa49}"),
+    ("black_chess_equihopper_rotated_ninety_degrees", "\u{1fa4c}"),
+    ("black_chess_king_rotated_ninety_degrees", "\u{1fa0f}"),
+    ("black_chess_king_rotated_two_hundred_seventy_degrees", "\u{1fa39}"),
+    ("black_chess_knight_bishop", "\u{1fa53}"),
+    ("black_chess_knight_queen", "\u{1fa51}"),
+    ("black_chess_knight_rook", "\u{1fa52}"),
+    ("black_chess_knight_rotated_forty_five_degrees", "\u{1fa07}"),
+    ("black_chess_knight_rotated_ninety_degrees", "\u{1fa13}"),
+    ("black_chess_knight_rotated_one_hundred_thirty_five_degrees", "\u{1fa1c}"),
+    ("black_chess_knight_rotated_three_hundred_fifteen_degrees", "\u{1fa46}"),
+    ("black_chess_knight_rotated_two_hundred_seventy_degrees", "\u{1fa3d}"),
+    ("black_chess_knight_rotated_two_hundred_twenty_five_degrees", "\u{1fa31}"),
+    ("black_chess_pawn_rotated_ninety_degrees", "\u{1fa14}"),
+    ("black_chess_pawn_rotated_two_hundred_seventy_degrees", "\u{1fa3e}"),
+    ("black_chess_queen_rotated_ninety_degrees", "\u{1fa10}"),
+    ("black_chess_queen_rotated_two_hundred_seventy_degrees", "\u{1fa3a}"),
+    ("black_chess_rook_rotated_ninety_degrees", "\u{1fa11}"),
+    ("black_chess_rook_rotated_two_hundred_seventy_degrees", "\u{1fa3b}"),
+    ("black_chess_turned_bishop", "\u{1fa27}"),
+    ("black_chess_turned_king", "\u{1fa24}"),
+    ("black_chess_turned_knight", "\u{1fa28}"),
+    ("black_chess_turned_pawn", "\u{1fa29}"),
+    ("black_chess_turned_queen", "\u{1fa25}"),
+    ("black_chess_turned_rook", "\u{1fa26}"),
+    ("black_circle", "\u{26ab}"),
+    ("black_down_pointing_backhand_index", "\u{1f5a3}"),
+    ("black_droplet", "\u{1f322}\u{fe0f}"),
+    ("black_flag", "\u{1f3f4}"),
+    ("black_folder", "\u{1f5bf}"),
+    ("black_hard_shell_floppy_disk", "\u{1f5aa}"),
+    ("black_heart", "\u{1f5a4}"),
+    ("black_left_pointing_backhand_index", "\u{1f59c}"),
+    ("black_pennant", "\u{1f3f2}"),
+    ("black_pushpin", "\u{1f588}"),
+    ("black_right_pointing_backhand_index", "\u{1f59d}"),
+    ("black_rosette", "\u{1f3f6}"),
+    ("black_skull_and_crossbones", "\u{1f571}"),
+    ("black_square_button", "\u{1f532}"),
+    ("black_touchtone_telephone", "\u{1f57f}"),
+    ("black_up_pointing_backhand_index", "\u{1f5a2}"),
+    ("blossom", "\u{1f33c}"),
+    ("blowfish", "\u{1f421}"),
+    ("blue_book", "\u{1f4d8}"),
+    ("blue_circle", "\u{1f535}"),
+    ("blue_heart", "\u{1f499}"),
+    ("blueberries", "\u{1fad0}"),
+    ("blush", "\u{1f60a}"),
+    ("boar", "\u{1f417}"),
+    ("boat", "\u{26f5}"),
+    ("bomb", "\u{1f4a3}"),
+    ("bone", "\u{1f9b4}"),
+    ("book", "\u{1f4d6}"),
+    ("bookmark", "\u{1f516}"),
+    ("bookmark_tabs", "\u{1f4d1}"),
+    ("books", "\u{1f4da}"),
+    ("boom", "\u{1f4a5}"),
+    ("boomerang", "\u{1fa83}"),
+    ("bottle_with_popping_cork", "\u{1f37e}"),
+    ("bouquet", "\u{1f490}"),
+    ("bouquet_of_flowers", "\u{1f395}"),
+    ("bow_and_arrow", "\u{1f3f9}"),
+    ("bowl_of_hygieia", "\u{1f54f}"),
+    ("bowl_with_spoon", "\u{1f963}"),
+    ("bowling", "\u{1f3b3}"),
+    ("boxing_glove", "\u{1f94a}"),
+    ("boy", "\u{1f466}"),
+    ("boys", "\u{1f6c9}"),
+    ("brain", "\u{1f9e0}"),
+    ("bread", "\u{1f35e}"),
+    ("breast_feeding", "\u{1f931}"),
+    ("brick", "\u{1f9f1}"),
+    ("bride_with_veil", "\u{1f470}"),
+    ("bridge_at_night", "\u{1f309}"),
+    ("briefcase", "\u{1f4bc}"),
+    ("briefs", "\u{1fa72}"),
+    ("broccoli", "\u{1f966}"),
+    ("broken_heart", "\u{1f494}"),
+    ("broom", "\u{1f9f9}"),
+    ("brown_heart", "\u{1f90e}"),
+    ("bubble_tea", "\u{1f9cb}"),
+    ("bubbles", "\u{1fae7}"),
+    ("bucket", "\u{1faa3}"),
+    ("bug", "\u{1f41b}"),
+    ("building_construction", "\u{1f3d7}\u{fe0f}"),
+    ("bulb", "\u{1f4a1}"),
+    ("bullhorn", "\u{1f56b}"),
+    ("bullhorn_with_sound_waves", "\u{1f56c}"),
+    ("burrito", "\u{1f32f}"),
+    ("bus", "\u{1f68c}"),
+    ("bus_stop", "\u{1f68f}"),
+    ("bust_in_silhouette", "\u{1f464}"),
+    ("busts_in_silhouette", "\u{1f465}"),
+    ("butter", "\u{1f9c8}"),
+    ("butterfly", "\u{1f98b}"),
+    ("cactus", "\u{1f335}"),
+    ("cake", "\u{1f370}"),
+    ("calendar", "\u{1f4c6}"),
+    ("call_me_hand", "\u{1f919}"),
+    ("camel", "\u{1f42b}"),
+    ("camera", "\u{1f4f7}"),
+    ("camera_with_flash", "\u{1f4f8}"),
+    ("camping", "\u{1f3d5}\u{fe0f}"),
+    ("cancellation_x", "\u{1f5d9}"),
+    ("candle", "\u{1f56f}\u{fe0f}"),
+    ("candy", "\u{1f36c}"),
+    ("canned_food", "\u{1f96b}"),
+    ("canoe", "\u{1f6f6}"),
+    ("car", "\u{1f697}"),
+    ("card_file_box", "\u{1f5c3}\u{fe0f}"),
+    ("card_index", "\u{1f4c7}"),
+    ("card_index_dividers", "\u{1f5c2}\u{fe0f}"),
+    ("carousel_horse", "\u{1f3a0}"),
+    ("carp_streamer", "\u{1f38f}"),
+    ("carpentry_saw", "\u{1fa9a}"),
+    ("carrot", "\u{1f955}"),
+    ("cat", "\u{1f431}"),
+    ("cat2", "\u{1f408}"),
+    ("cat_face", "\u{1f431}"),
+    ("cat_face_with_tears_of_joy", "\u{1f639}"),
+    ("cat_face_with_wry_smile", "\u{1f63c}"),
+    ("cd", "\u{1f4bf}"),
+    ("celtic_cross", "\u{1f548}"),
+    ("chair", "\u{1fa91}"),
+    ("chart_with_downwards_trend", "\u{1f4c9}"),
+    ("chart_with_upwards_trend", "\u{1f4c8}"),
+    ("chart_with_upwards_trend_and_yen", "\u{1f4b9}"),
+    ("checkered_flag", "\u{1f3c1}"),
+    ("cheering_megaphone", "\u{1f4e3}"),
+    ("cheese_wedge", "\u{1f9c0}"),
+    ("chequered_flag", "\u{1f3c1}"),
+    ("cherries", "\u{1f352}"),
+    ("cherry_blossom", "\u{1f338}"),
+    ("chestnut", "\u{1f330}"),
+    ("chicken", "\u{1f414}"),
+    ("child", "\u{1f9d2}"),
+    ("children_crossing", "\u{1f6b8}"),
+    ("chipmunk", "\u{1f43f}\u{fe0f}"),
+    ("chocolate_bar", "\u{1f36b}"),
+    ("chopsticks", "\u{1f962}"),
+    ("christmas_tree", "\u{1f384}"),
+    ("cinema", "\u{1f3a6}"),
+    ("circled_cross_formee", "\u{1f902}"),
+    ("circled_cross_formee_with_four_dots", "\u{1f900}"),
+    ("circled_cross_formee_with_two_dots", "\u{1f901}"),
+    ("circled_cross_pommee", "\u{1f540}"),
+    ("circled_information_source", "\u{1f6c8}"),
+    ("circus_tent", "\u{1f3aa}"),
+    ("cityscape", "\u{1f3d9}\u{fe0f}"),
+    ("cityscape_at_dusk", "\u{1f306}"),
+    ("clamshell_mobile_phone", "\u{1f581}"),
+    ("clap", "\u{1f44f}"),
+    ("clapper_board", "\u{1f3ac}"),
+    ("clapping_hands", "\u{1f44f}"),
+    ("classical_building", "\u{1f3db}\u{fe0f}"),
+    ("clinking_beer_mugs", "\u{1f37b}"),
+    ("clinking_glasses", "\u{1f942}"),
+    ("clipboard", "\u{1f4cb}"),
+    ("clock_face_eight_oclock", "\u{1f557}"),
+    ("clock_face_eight_thirty", "\u{1f563}"),
+    ("clock_face_eleven_oclock", "\u{1f55a}"),
+    ("clock_face_eleven_thirty", "\u{1f566}"),
+    ("clock_face_five_oclock", "\u{1f554}"),
+    ("clock_face_five_thirty", "\u{1f560}"),
+    ("clock_face_four_oclock", "\u{1f553}"),
+    ("clock_face_four_thirty", "\u{1f55f}"),
+    ("clock_face_nine_oclock", "\u{1f558}"),
+    ("clock_face_nine_thirty", "\u{1f564}"),
+    ("clock_face_one_oclock", "\u{1f550}"),
+    ("clock_face_one_thirty", "\u{1f55c}"),
+    ("clock_face_seven_oclock", "\u{1f556}"),
+    ("clock_face_seven_thirty", "\u{1f562}"),
+    ("clock_face_six_oclock", "\u{1f555}"),
+    ("clock_face_six_thirty", "\u{1f561}"),
+    ("clock_face_ten_oclock", "\u{1f559}"),
+    ("clock_face_ten_thirty", "\u{1f565}"),
+    ("clock_face_three_oclock", "\u{1f552}"),
+    ("clock_face_three_thirty", "\u{1f55e}"),
+    ("clock_face_twelve_oclock", "\u{1f55b}"),
+    ("clock_face_twelve_thirty", "\u{1f567}"),
+    ("clock_face_two_oclock", "\u{1f551}"),
+    ("clock_face_two_thirty", "\u{1f55d}"),
+    ("clockwise_downwards_and_upwards_open_circle_arrows", "\u{1f503}"),
+    ("clockwise_right_and_left_semicircle_arrows", "\u{1f5d8}"),
+    ("clockwise_rightwards_and_leftwards_open_circle_arrows", "\u{1f501}"),
+    ("clockwise_rightwards_and_leftwards_open_circle_arrows_with_circled_one_overlay", "\u{1f502}"),
+    ("closed_book", "\u{1f4d5}"),
+    ("closed_lock_with_key", "\u{1f510}"),
+    ("closed_mailbox_with_lowered_flag", "\u{1f4ea}"),
+    ("closed_mailbox_with_raised_flag", "\u{1f4eb}"),
+    ("closed_umbrella", "\u{1f302}"),
+    ("cloud", "\u{2601}\u{fe0f}"),
+    ("cloud_with_lightning", "\u{1f329}\u{fe0f}"),
+    ("cloud_with_rain", "\u{1f327}\u{fe0f}"),
+    ("cloud_with_snow", "\u{1f328}\u{fe0f}"),
+    ("cloud_with_tornado", "\u{1f32a}\u{fe0f}"),
+    ("clown_face", "\u{1f921}"),
+    ("coat", "\u{1f9e5}"),
+    ("cockroach", "\u{1fab3}"),
+    ("cocktail_glass", "\u{1f378}"),
+    ("coconut", "\u{1f965}"),
+    ("coffee", "\u{2615}"),
+    ("coin", "\u{1fa99}"),
+    ("cold_sweat", "\u{1f630}"),
+    ("collision", "\u{1f4a5}"),
+    ("compass", "\u{1f9ed}"),
+    ("compression", "\u{1f5dc}\u{fe0f}"),
+    ("computer", "\u{1f4bb}"),
+    ("confetti_ball", "\u{1f38a}"),
+    ("confounded", "\u{1f616}"),
+    ("confounded_face", "\u{1f616}"),
+    ("confused", "\u{1f615}"),
+    ("confused_face", "\u{1f615}"),
+    ("construction", "\u{1f6a7}"),
+    ("construction_worker", "\u{1f477}"),
+    ("control_knobs", "\u{1f39b}\u{fe0f}"),
+    ("convenience_store", "\u{1f3ea}"),
+    ("cooked_rice", "\u{1f35a}"),
+    ("cookie", "\u{1f36a}"),
+    ("cooking", "\u{1f373}"),
+    ("cool", "\u{1f192}"),
+    ("copyright", "\u{a9}\u{fe0f}"),
+    ("coral", "\u{1fab8}"),
+    ("couch_and_lamp", "\u{1f6cb}\u{fe0f}"),
+    ("couple_with_heart", "\u{1f491}"),
+    ("cow", "\u{1f42e}"),
+    ("cow2", "\u{1f404}"),
+    ("cow_face", "\u{1f42e}"),
+    ("cowboy_hat_face", "\u{1f920}"),
+    ("crab", "\u{1f980}"),
+    ("credit_card", "\u{1f4b3}"),
+    ("crescent_moon", "\u{1f319}"),
+    ("cricket", "\u{1f997}"),
+    ("cricket_bat_and_ball", "\u{1f3cf}"),
+    ("crocodile", "\u{1f40a}"),
+    ("croissant", "\u{1f950}"),
+    ("cross_pommee", "\u{1f542}"),
+    ("cross_pommee_with_half_circle_below", "\u{1f541}"),
+    ("crossed_fingers", "\u{1f91e}"),
+    ("crossed_flags", "\u{1f38c}"),
+    ("crown", "\u{1f451}"),
+    ("crutch", "\u{1fa7c}"),
+    ("cry", "\u{1f622}"),
+    ("crying_cat_face", "\u{1f63f}"),
+    ("crying_face", "\u{1f622}"),
+    ("crystal_ball", "\u{1f52e}"),
+    ("cucumber", "\u{1f952}"),
+    ("cup_with_straw", "\u{1f964}"),
+    ("cupcake", "\u{1f9c1}"),
+    ("cupid", "\u{1f498}"),
+    ("curling_stone", "\u{1f94c}"),
+    ("currency_exchange", "\u{1f4b1}"),
+    ("curry_and_rice", "\u{1f35b}"),
+    ("custard", "\u{1f36e}"),
+    ("customs", "\u{1f6c3}"),
+    ("cut_of_meat", "\u{1f969}"),
+    ("cyclone", "\u{1f300}"),
+    ("dagger_knife", "\u{1f5e1}\u{fe0f}"),
+    ("dancer", "\u{1f483}"),
+    ("dango", "\u{1f361}"),
+    ("dark_sunglasses", "\u{1f576}\u{fe0f}"),
+    ("dart", "\u{1f3af}"),
+    ("dash", "\u{1f4a8}"),
+    ("date", "\u{1f4c5}"),
+    ("deaf_person", "\u{1f9cf}"),
+    ("deciduous_tree", "\u{1f333}"),
+    ("decrease_font_size", "\u{1f5db}"),
+    ("deer", "\u{1f98c}"),
+    ("delivery_truck", "\u{1f69a}"),
+    ("department_store", "\u{1f3ec}"),
+    ("derelict_house_building", "\u{1f3da}\u{fe0f}"),
+    ("desert", "\u{1f3dc}\u{fe0f}"),
+    ("desert_island", "\u{1f3dd}\u{fe0f}"),
+    ("desktop_computer", "\u{1f5a5}\u{fe0f}"),
+    ("desktop_window", "\u{1f5d4}"),
+    ("diamond_shape_with_a_dot_inside", "\u{1f4a0}"),
+    ("diesel_locomotive", "\u{1f6f2}"),
+    ("direct_hit", "\u{1f3af}"),
+    ("disappointed", "\u{1f61e}"),
+    ("disappointed_but_relieved_face", "\u{1f625}"),
+    ("disappointed_face", "\u{1f61e}"),
+    ("disappointed_relieved", "\u{1f625}"),
+    ("disguised_face", "\u{1f978}"),
+    ("diving_mask", "\u{1f93f}"),
+    ("diya_lamp", "\u{1fa94}"),
+    ("dizzy", "\u{1f4ab}"),
+    ("dizzy_face", "\u{1f635}"),
+    ("dna_double_helix", "\u{1f9ec}"),
+    ("do_not_litter", "\u{1f6af}"),
+    ("document", "\u{1f5ce}"),
+    ("document_with_picture", "\u{1f5bb}"),
+    ("document_with_text", "\u{1f5b9}"),
+    ("document_with_text_and_picture", "\u{1f5ba}"),
+    ("dodo", "\u{1f9a4}"),
+    ("dog", "\u{1f436}"),
+    ("dog2", "\u{1f415}"),
+    ("dog_face", "\u{1f436}"),
+    ("dollar", "\u{1f4b5}"),
+    ("dolphin", "\u{1f42c}"),
+    ("door", "\u{1f6aa}"),
+    ("dotted_line_face", "\u{1fae5}"),
+    ("doughnut", "\u{1f369}"),
+    ("dove_of_peace", "\u{1f54a}\u{fe0f}"),
+    ("down_pointing_red_triangle", "\u{1f53b}"),
+    ("down_pointing_small_red_triangle", "\u{1f53d}"),
+    ("downward_facing_hook", "\u{1f908}"),
+    ("downward_facing_hook_with_dot", "\u{1f90a}"),
+    ("downward_facing_notched_hook", "\u{1f909}"),
+    ("downward_facing_notched_hook_with_dot", "\u{1f90b}"),
+    ("dragon", "\u{1f409}"),
+    ("dragon_face", "\u{1f432}"),
+    ("dress", "\u{1f457}"),
+    ("dromedary_camel", "\u{1f42a}"),
+    ("drooling_face", "\u{1f924}"),
+    ("drop_of_blood", "\u{1fa78}"),
+    ("droplet", "\u{1f4a7}"),
+    ("drum_with_drumsticks", "\u{1f941}"),
+    ("duck", "\u{1f986}"),
+    ("dumpling", "\u{1f95f}"),
+    ("dvd", "\u{1f4c0}"),
+    ("e-mail", "\u{1f4e7}"),
+    ("e_mail", "\u{1f4e7}"),
+    ("eagle", "\u{1f985}"),
+    ("ear", "\u{1f442}"),
+    ("ear_of_maize", "\u{1f33d}"),
+    ("ear_of_rice", "\u{1f33e}"),
+    ("ear_with_hearing_aid", "\u{1f9bb}"),
+    ("earth_africa", "\u{1f30d}"),
+    ("earth_americas", "\u{1f30e}"),
+    ("earth_asia", "\u{1f30f}"),
+    ("earth_globe_americas", "\u{1f30e}"),
+    ("earth_globe_asia_australia", "\u{1f30f}"),
+    ("earth_globe_europe_africa", "\u{1f30d}"),
+    ("egg", "\u{1f95a}"),
+    ("electric_light_bulb", "\u{1f4a1}"),
+    ("electric_plug", "\u{1f50c}"),
+    ("electric_torch", "\u{1f526}"),
+    ("elephant", "\u{1f418}"),
+    ("elevator", "\u{1f6d7}"),
+    ("elf", "\u{1f9dd}"),
+    ("email", "\u{2709}\u{fe0f}"),
+    ("emoji_component_bald", "\u{1f9b2}"),
+    ("emoji_component_curly_hair", "\u{1f9b1}"),
+    ("emoji_component_red_hair", "\u{1f9b0}"),
+    ("emoji_component_white_hair", "\u{1f9b3}"),
+    ("empty_document", "\u{1f5cb}"),
+    ("empty_nest", "\u{1fab9}"),
+    ("empty_note", "\u{1f5c5}"),
+    ("empty_note_pad", "\u{1f5c7}"),
+    ("empty_note_page", "\u{1f5c6}"),
+    ("empty_page", "\u{1f5cc}"),
+    ("empty_pages", "\u{1f5cd}"),
+    ("end", "\u{1f51a}"),
+    ("end_with_leftwards_arrow_above", "\u{1f51a}"),
+    ("envelope", "\u{2709}\u{fe0f}"),
+    ("envelope_with_downwards_arrow_above", "\u{1f4e9}"),
+    ("envelope_with_lightning", "\u{1f584}"),
+    ("european_castle", "\u{1f3f0}"),
+    ("european_post_office", "\u{1f3e4}"),
+    ("evergreen_tree", "\u{1f332}"),
+    ("exclamation", "\u{2757}"),
+    ("exploding_head", "\u{1f92f}"),
+    ("expressionless", "\u{1f611}"),
+    ("expressionless_face", "\u{1f611}"),
+    ("extraterrestrial_alien", "\u{1f47d}"),
+    ("eye", "\u{1f441}\u{fe0f}"),
+    ("eyeglasses", "\u{1f453}"),
+    ("eyes", "\u{1f440}"),
+    ("face_holding_back_tears", "\u{1f979}"),
+    ("face_massage", "\u{1f486}"),
+    ("face_palm", "\u{1f926}"),
+    ("face_savouring_delicious_food", "\u{1f60b}"),
+    ("face_screaming_in_fear", "\u{1f631}"),
+    ("face_throwing_a_kiss", "\u{1f618}"),
+    ("face_with_cold_sweat", "\u{1f613}"),
+    ("face_with_cowboy_hat", "\u{1f920}"),
+    ("face_with_diagonal_mouth", "\u{1fae4}"),
+    ("face_with_finger_covering_closed_lips", "\u{1f92b}"),
+    ("face_with_head_bandage", "\u{1f915}"),
+    ("face_with_look_of_triumph", "\u{1f624}"),
+    ("face_with_medical_mask", "\u{1f637}"),
+    ("face_with_monocle", "\u{1f9d0}"),
+    ("face_with_no_good_gesture", "\u{1f645}"),
+    ("face_with_ok_gesture", "\u{1f646}"),
+    ("face_with_one_eyebrow_raised", "\u{1f928}"),
+    ("face_with_open_eyes_and_hand_over_mouth", "\u{1fae2}"),
+    ("face_with_open_mouth", "\u{1f62e}"),
+    ("face_with_open_mouth_and_cold_sweat", "\u{1f630}"),
+    ("face_with_open_mouth_vomiting", "\u{1f92e}"),
+    ("face_with_party_horn_and_party_hat", "\u{1f973}"),
+    ("face_with_peeking_eye", "\u{1fae3}"),
+    ("face_with_pleading_eyes", "\u{1f97a}"),
+    ("face_with_rolling_eyes", "\u{1f644}"),
+    ("face_with_stuck_out_tongue", "\u{1f61b}"),
+    ("face_with_stuck_out_tongue_and_tightly_closed_eyes", "\u{1f61d}"),
+    ("face_with_stuck_out_tongue_and_winking_eye", "\u{1f61c}"),
+    ("face_with_tears_of_joy", "\u{1f602}"),
+    ("face_with_thermometer", "\u{1f912}"),
+    ("face_with_uneven_eyes_and_wavy_mouth", "\u{1f974}"),
+    ("face_without_mouth", "\u{1f636}"),
+    ("facepunch", "\u{1f44a}"),
+    ("factory", "\u{1f3ed}"),
+    ("fairy", "\u{1f9da}"),
+    ("falafel", "\u{1f9c6}"),
+    ("fallen_leaf", "\u{1f342}"),
+    ("family", "\u{1f46a}"),
+    ("father_christmas", "\u{1f385}"),
+    ("fax_icon", "\u{1f5b7}"),
+    ("fax_machine", "\u{1f4e0}"),
+    ("fearful", "\u{1f628}"),
+    ("fearful_face", "\u{1f628}"),
+    ("feather", "\u{1fab6}"),
+    ("feet", "\u{1f43e}"),
+    ("fencer", "\u{1f93a}"),
+    ("ferris_wheel", "\u{1f3a1}"),
+    ("field_hockey_stick_and_ball", "\u{1f3d1}"),
+    ("file_cabinet", "\u{1f5c4}\u{fe0f}"),
+    ("file_folder", "\u{1f4c1}"),
+    ("film_frames", "\u{1f39e}\u{fe0f}"),
+    ("film_projector", "\u{1f4fd}\u{fe0f}"),
+    ("fire", "\u{1f525}"),
+    ("fire_engine", "\u{1f692}"),
+    ("fire_extinguisher", "\u{1f9ef}"),
+    ("firecracker", "\u{1f9e8}"),
+    ("firework_sparkler", "\u{1f387}"),
+    ("fireworks", "\u{1f386}"),
+    ("first_place_medal", "\u{1f947}"),
+    ("first_quarter_moon", "\u{1f313}"),
+    ("first_quarter_moon_with_face", "\u{1f31b}"),
+    ("fish", "\u{1f41f}"),
+    ("fish_cake_with_swirl_design", "\u{1f365}"),
+    ("fishing_pole_and_fish", "\u{1f3a3}"),
+    ("fist", "\u{270a}"),
+    ("fist_raised", "\u{270a}"),
+    ("fisted_hand", "\u{1f44a}"),
+    ("flamingo", "\u{1f9a9}"),
+    ("flat_shoe", "\u{1f97f}"),
+    ("flatbread", "\u{1fad3}"),
+    ("flexed_biceps", "\u{1f4aa}"),
+    ("flipper", "\u{1f42c}"),
+    ("floppy_disk", "\u{1f4be}"),
+    ("flower_playing_cards", "\u{1f3b4}"),
+    ("flushed", "\u{1f633}"),
+    ("flushed_face", "\u{1f633}"),
+    ("fly", "\u{1fab0}"),
+    ("flying_disc", "\u{1f94f}"),
+    ("flying_envelope", "\u{1f585}"),
+    ("flying_saucer", "\u{1f6f8}"),
+    ("fog", "\u{1f32b}\u{fe0f}"),
+    ("foggy", "\u{1f301}"),
+    ("folder", "\u{1f5c0}"),
+    ("fondue", "\u{1fad5}"),
+    ("foot", "\u{1f9b6}"),
+    ("footprints", "\u{1f463}"),
+    ("fork_and_knife", "\u{1f374}"),
+    ("fork_and_knife_with_plate", "\u{1f37d}\u{fe0f}"),
+    ("fortune_cookie", "\u{1f960}"),
+    ("four_leaf_clover", "\u{1f340}"),
+    ("fox_face", "\u{1f98a}"),
+    ("frame_with_an_x", "\u{1f5be}"),
+    ("frame_with_picture", "\u{1f5bc}\u{fe0f}"),
+    ("frame_with_tiles", "\u{1f5bd}"),
+    ("free", "\u{1f193}"),
+    ("freezing_face", "\u{1f976}"),
+    ("french_fries", "\u{1f35f}"),
+    ("fried_shrimp", "\u{1f364}"),
+    ("fries", "\u{1f35f}"),
+    ("frog", "\u{1f438}"),
+    ("frog_face", "\u{1f438}"),
+    ("front_facing_baby_chick", "\u{1f425}"),
+    ("frowning", "\u{1f626}"),
+    ("frowning_face_with_open_mouth", "\u{1f626}"),
+    ("full_moon", "\u{1f315}"),
+    ("full_moon_with_face", "\u{1f31d}"),
+    ("game_die", "\u{1f3b2}"),
+    ("garlic", "\u{1f9c4}"),
+    ("gear", "\u{2699}\u{fe0f}"),
+    ("gem", "\u{1f48e}"),
+    ("gem_stone", "\u{1f48e}"),
+    ("genie", "\u{1f9de}"),
+    ("ghost", "\u{1f47b}"),
+    ("gift", "\u{1f381}"),
+    ("gift_heart", "\u{1f49d}"),
+    ("giraffe", "\u{1f992}"),
+    ("giraffe_face", "\u{1f992}"),
+    ("girl", "\u{1f467}"),
+    ("girls", "\u{1f6ca}"),
+    ("glass_of_milk", "\u{1f95b}"),
+    ("globe_with_meridians", "\u{1f310}"),
+    ("gloves", "\u{1f9e4}"),
+    ("glowing_star", "\u{1f31f}"),
+    ("goal_net", "\u{1f945}"),
+    ("goat", "\u{1f410}"),
+    ("goggles", "\u{1f97d}"),
+    ("golfer", "\u{1f3cc}\u{fe0f}"),
+    ("gorilla", "\u{1f98d}"),
+    ("graduation_cap", "\u{1f393}"),
+    ("grapes", "\u{1f347}"),
+    ("green_apple", "\u{1f34f}"),
+    ("green_book", "\u{1f4d7}"),
+    ("green_heart", "\u{1f49a}"),
+    ("green_salad", "\u{1f957}"),
+    ("grey_exclamation", "\u{2755}"),
+    ("grey_question", "\u{2754}"),
+    ("grimacing", "\u{1f62c}"),
+    ("grimacing_face", "\u{1f62c}"),
+    ("grin", "\u{1f601}"),
+    ("grinning", "\u{1f600}"),
+    ("grinning_cat_face_with_smiling_eyes", "\u{1f638}"),
+    ("grinning_face", "\u{1f600}"),
+    ("grinning_face_with_one_large_and_one_small_eye", "\u{1f92a}"),
+    ("grinning_face_with_smiling_eyes", "\u{1f601}"),
+    ("grinning_face_with_star_eyes", "\u{1f929}"),
+    ("growing_heart", "\u{1f497}"),
+    ("guardsman", "\u{1f482}"),
+    ("guide_dog", "\u{1f9ae}"),
+    ("guitar", "\u{1f3b8}"),
+    ("haircut", "\u{1f487}"),
+    ("hamburger", "\u{1f354}"),
+    ("hammer", "\u{1f528}"),
+    ("hammer_and_wrench", "\u{1f6e0}\u{fe0f}"),
+    ("hamsa", "\u{1faac}"),
+    ("hamster", "\u{1f439}"),
+    ("hamster_face", "\u{1f439}"),
+    ("hand", "\u{270b}"),
+    ("hand_with_index_and_middle_fingers_crossed", "\u{1f91e}"),
+    ("hand_with_index_finger_and_thumb_crossed", "\u{1faf0}"),
+    ("handbag", "\u{1f45c}"),
+    ("handball", "\u{1f93e}"),
+    ("handshake", "\u{1f91d}"),
+    ("hankey", "\u{1f4a9}"),
+    ("happy_person_raising_one_hand", "\u{1f64b}"),
+    ("hard_disk", "\u{1f5b4}"),
+    ("hatching_chick", "\u{1f423}"),
+    ("headphone", "\u{1f3a7}"),
+    ("headphones", "\u{1f3a7}"),
+    ("headstone", "\u{1faa6}"),
+    ("hear_no_evil", "\u{1f649}"),
+    ("hear_no_evil_monkey", "\u{1f649}"),
+    ("heart", "\u{2764}\u{fe0f}"),
+    ("heart_decoration", "\u{1f49f}"),
+    ("heart_eyes", "\u{1f60d}"),
+    ("heart_eyes_cat", "\u{1f63b}"),
+    ("heart_hands", "\u{1faf6}"),
+    ("heart_with_arrow", "\u{1f498}"),
+    ("heart_with_ribbon", "\u{1f49d}"),
+    ("heart_with_tip_on_the_left", "\u{1f394}"),
+    ("heartbeat", "\u{1f493}"),
+    ("heartpulse", "\u{1f497}"),
+    ("heavy_check_mark", "\u{2714}\u{fe0f}"),
+    ("heavy_division_sign", "\u{2797}"),
+    ("heavy_dollar", "\u{1f4b2}"),
+    ("heavy_dollar_sign", "\u{1f4b2}"),
+    ("heavy_exclamation_mark", "\u{2757}"),
+    ("heavy_latin_cross", "\u{1f547}"),
+    ("heavy_minus_sign", "\u{2796}"),
+    ("heavy_multiplication_x", "\u{2716}\u{fe0f}"),
+    ("heavy_plus_sign", "\u{2795}"),
+    ("hedgehog", "\u{1f994}"),
+    ("helicopter", "\u{1f681}"),
+    ("herb", "\u{1f33f}"),
+    ("hibiscus", "\u{1f33a}"),
+    ("high_brightness", "\u{1f506}"),
+    ("high_heeled_shoe", "\u{1f460}"),
+    ("high_speed_train", "\u{1f684}"),
+    ("high_speed_train_with_bullet_nose", "\u{1f685}"),
+    ("hiking_boot", "\u{1f97e}"),
+    ("hindu_temple", "\u{1f6d5}"),
+    ("hippopotamus", "\u{1f99b}"),
+    ("hocho", "\u{1f52a}"),
+    ("hole", "\u{1f573}\u{fe0f}"),
+    ("honey_pot", "\u{1f36f}"),
+    ("honeybee", "\u{1f41d}"),
+    ("hook", "\u{1fa9d}"),
+    ("horizontal_traffic_light", "\u{1f6a5}"),
+    ("horse", "\u{1f434}"),
+    ("horse_face", "\u{1f434}"),
+    ("horse_racing", "\u{1f3c7}"),
+    ("hospital", "\u{1f3e5}"),
+    ("hot_dog", "\u{1f32d}"),
+    ("hot_pepper", "\u{1f336}\u{fe0f}"),
+    ("hotel", "\u{1f3e8}"),
+    ("hourglass", "\u{231b}"),
+    ("hourglass_done", "\u{231b}"),
+    ("hourglass_flowing_sand", "\u{23f3}"),
+    ("house", "\u{1f3e0}"),
+    ("house_building", "\u{1f3e0}"),
+    ("house_buildings", "\u{1f3d8}\u{fe0f}"),
+    ("house_with_garden", "\u{1f3e1}"),
+    ("hugging_face", "\u{1f917}"),
+    ("hugs", "\u{1f917}"),
+    ("hundred_points", "\u{1f4af}"),
+    ("hushed", "\u{1f62f}"),
+    ("hushed_face", "\u{1f62f}"),
+    ("hut", "\u{1f6d6}"),
+    ("i_love_you_hand", "\u{1f91f}"),
+    ("ice_cream", "\u{1f368}"),
+    ("ice_cube", "\u{1f9ca}"),
+    ("ice_hockey_stick_and_puck", "\u{1f3d2}"),
+    ("id", "\u{1f194}"),
+    ("identification_card", "\u{1faaa}"),
+    ("imp", "\u{1f47f}"),
+    ("inbox_tray", "\u{1f4e5}"),
+    ("incoming_envelope", "\u{1f4e8}"),
+    ("increase_font_size", "\u{1f5da}"),
+    ("index_pointing_at_the_viewer", "\u{1faf5}"),
+    ("information_desk_person", "\u{1f481}"),
+    ("information_source", "\u{2139}\u{fe0f}"),
+    ("innocent", "\u{1f607}"),
+    ("input_symbol_for_latin_capital_letters", "\u{1f520}"),
+    ("input_symbol_for_latin_letters", "\u{1f524}"),
+    ("input_symbol_for_latin_small_letters", "\u{1f521}"),
+    ("input_symbol_for_numbers", "\u{1f522}"),
+    ("input_symbol_for_symbols", "\u{1f523}"),
+    ("interrobang", "\u{2049}\u{fe0f}"),
+    ("iphone", "\u{1f4f1}"),
+    ("izakaya_lantern", "\u{1f3ee}"),
+    ("jack_o_lantern", "\u{1f383}"),
+    ("japanese_castle", "\u{1f3ef}"),
+    ("japanese_dolls", "\u{1f38e}"),
+    ("japanese_goblin", "\u{1f47a}"),
+    ("japanese_ogre", "\u{1f479}"),
+    ("japanese_post_office", "\u{1f3e3}"),
+    ("japanese_symbol_for_beginner", "\u{1f530}"),
+    ("jar", "\u{1fad9}"),
+    ("jeans", "\u{1f456}"),
+    ("jigsaw_puzzle_piece", "\u{1f9e9}"),
+    ("joy", "\u{1f602}"),
+    ("joy_cat", "\u{1f639}"),
+    ("joystick", "\u{1f579}\u{fe0f}"),
+    ("juggling", "\u{1f939}"),
+    ("kaaba", "\u{1f54b}"),
+    ("kangaroo", "\u{1f998}"),
+    ("key", "\u{1f511}"),
+    ("keyboard", "\u{2328}\u{fe0f}"),
+    ("keyboard_and_mouse", "\u{1f5a6}"),
+    ("keycap_ten", "\u{1f51f}"),
+    ("kimono", "\u{1f458}"),
+    ("kiss", "\u{1f48f}"),
+    ("kiss_mark", "\u{1f48b}"),
+    ("kissing", "\u{1f617}"),
+    ("kissing_cat", "\u{1f63d}"),
+    ("kissing_cat_face_with_closed_eyes", "\u{1f63d}"),
+    ("kissing_closed_eyes", "\u{1f61a}"),
+    ("kissing_face", "\u{1f617}"),
+    ("kissing_face_with_closed_eyes", "\u{1f61a}"),
+    ("kissing_face_with_smiling_eyes", "\u{1f619}"),
+    ("kissing_heart", "\u{1f618}"),
+    ("kissing_smiling_eyes", "\u{1f619}"),
+    ("kite", "\u{1fa81}"),
+    ("kiwifruit", "\u{1f95d}"),
+    ("kneeling_person", "\u{1f9ce}"),
+    ("knot", "\u{1faa2}"),
+    ("koala", "\u{1f428}"),
+    ("lab_coat", "\u{1f97c}"),
+    ("label", "\u{1f3f7}\u{fe0f}"),
+    ("lacrosse_stick_and_ball", "\u{1f94d}"),
+    ("ladder", "\u{1fa9c}"),
+    ("lady_beetle", "\u{1f41e}"),
+    ("large_blue_circle", "\u{1f535}"),
+    ("large_blue_diamond", "\u{1f537}"),
+    ("large_blue_square", "\u{1f7e6}"),
+    ("large_brown_circle", "\u{1f7e4}"),
+    ("large_brown_square", "\u{1f7eb}"),
+    ("large_green_circle", "\u{1f7e2}"),
+    ("large_green_square", "\u{1f7e9}"),
+    ("large_orange_circle", "\u{1f7e0}"),
+    ("large_orange_diamond", "\u{1f536}"),
+    ("large_orange_square", "\u{1f7e7}"),
+    ("large_purple_circle", "\u{1f7e3}"),
+    ("large_purple_square", "\u{1f7ea}"),
+    ("large_red_circle", "\u{1f534}"),
+    ("large_red_square", "\u{1f7e5}"),
+    ("large_yellow_circle", "\u{1f7e1}"),
+    ("large_yellow_square", "\u{1f7e8}"),
+    ("last_quarter_moon", "\u{1f317}"),
+    ("last_quarter_moon_with_face", "\u{1f31c}"),
+    ("laughing", "\u{1f606}"),
+    ("leaf_fluttering_in_wind", "\u{1f343}"),
+    ("leafy_green", "\u{1f96c}"),
+    ("leaves", "\u{1f343}"),
+    ("ledger", "\u{1f4d2}"),
+    ("left_anger_bubble", "\u{1f5ee}"),
+    ("left_facing_fist", "\u{1f91b}"),
+    ("left_half_circle", "\u{1f907}"),
+    ("left_half_circle_with_dot", "\u{1f906}"),
+    ("left_half_circle_with_four_dots", "\u{1f903}"),
+    ("left_half_circle_with_three_dots", "\u{1f904}"),
+    ("left_half_circle_with_two_dots", "\u{1f905}"),
+    ("left_hand_telephone_receiver", "\u{1f57b}"),
+    ("left_luggage", "\u{1f6c5}"),
+    ("left_pointing_magnifying_glass", "\u{1f50d}"),
+    ("left_speech_bubble", "\u{1f5e8}\u{fe0f}"),
+    ("left_thought_bubble", "\u{1f5ec}"),
+    ("left_writing_hand", "\u{1f58e}"),
+    ("leftwards_arrow_with_hook", "\u{21a9}\u{fe0f}"),
+    ("leftwards_hand", "\u{1faf2}"),
+    ("leg", "\u{1f9b5}"),
+    ("lemon", "\u{1f34b}"),
+    ("leopard", "\u{1f406}"),
+    ("level_slider", "\u{1f39a}\u{fe0f}"),
+    ("light_check_mark", "\u{1f5f8}"),
+    ("light_rail", "\u{1f688}"),
+    ("lightning_mood", "\u{1f5f2}"),
+    ("lightning_mood_bubble", "\u{1f5f1}"),
+    ("link", "\u{1f517}"),
+    ("linked_paperclips", "\u{1f587}\u{fe0f}"),
+    ("lion", "\u{1f981}"),
+    ("lion_face", "\u{1f981}"),
+    ("lips", "\u{1f5e2}"),
+    ("lipstick", "\u{1f484}"),
+    ("lizard", "\u{1f98e}"),
+    ("llama", "\u{1f999}"),
+    ("lobster", "\u{1f99e}"),
+    ("lock", "\u{1f512}"),
+    ("lock_with_ink_pen", "\u{1f50f}"),
+    ("lollipop", "\u{1f36d}"),
+    ("long_drum", "\u{1fa98}"),
+    ("lotion_bottle", "\u{1f9f4}"),
+    ("lotus", "\u{1fab7}"),
+    ("loudly_crying_face", "\u{1f62d}"),
+    ("loudspeaker", "\u{1f4e2}"),
+    ("love_hotel", "\u{1f3e9}"),
+    ("love_letter", "\u{1f48c}"),
+    ("low_battery", "\u{1faab}"),
+    ("low_brightness", "\u{1f505}"),
+    ("lower_left_ballpoint_pen", "\u{1f58a}\u{fe0f}"),
+    ("lower_left_crayon", "\u{1f58d}\u{fe0f}"),
+    ("lower_left_fountain_pen", "\u{1f58b}\u{fe0f}"),
+    ("lower_left_paintbrush", "\u{1f58c}\u{fe0f}"),
+    ("lower_left_pencil", "\u{1f589}"),
+    ("lower_right_shadowed_white_circle", "\u{1f53e}"),
+    ("luggage", "\u{1f9f3}"),
+    ("lungs", "\u{1fac1}"),
+    ("lying_face", "\u{1f925}"),
+    ("mag", "\u{1f50d}"),
+    ("mag_right", "\u{1f50e}"),
+    ("mage", "\u{1f9d9}"),
+    ("magic_wand", "\u{1fa84}"),
+    ("magnet", "\u{1f9f2}"),
+    ("mammoth", "\u{1f9a3}"),
+    ("man", "\u{1f468}"),
+    ("man_and_woman_holding_hands", "\u{1f46b}"),
+    ("man_dancing", "\u{1f57a}"),
+    ("man_in_business_suit_levitating", "\u{1f574}\u{fe0f}"),
+    ("man_in_tuxedo", "\u{1f935}"),
+    ("man_with_gua_pi_mao", "\u{1f472}"),
+    ("man_with_turban", "\u{1f473}"),
+    ("mango", "\u{1f96d}"),
+    ("mans_shoe", "\u{1f45e}"),
+    ("mantelpiece_clock", "\u{1f570}\u{fe0f}"),
+    ("manual_wheelchair", "\u{1f9bd}"),
+    ("maple_leaf", "\u{1f341}"),
+    ("martial_arts_uniform", "\u{1f94b}"),
+    ("mask", "\u{1f637}"),
+    ("mate_drink", "\u{1f9c9}"),
+    ("maximize", "\u{1f5d6}"),
+    ("meat_on_bone", "\u{1f356}"),
+    ("mechanical_arm", "\u{1f9be}"),
+    ("mechanical_leg", "\u{1f9bf}"),
+    ("medal_sports", "\u{1f3c5}"),
+    ("mega", "\u{1f4e3}"),
+    ("melon", "\u{1f348}"),
+    ("melting_face", "\u{1fae0}"),
+    ("memo", "\u{1f4dd}"),
+    ("menorah_with_nine_branches", "\u{1f54e}"),
+    ("mens", "\u{1f6b9}"),
+    ("merperson", "\u{1f9dc}"),
+    ("metal", "\u{1f918}"),
+    ("metro", "\u{1f687}"),
+    ("microbe", "\u{1f9a0}"),
+    ("microphone", "\u{1f3a4}"),
+    ("microscope", "\u{1f52c}"),
+    ("military_helmet", "\u{1fa96}"),
+    ("military_medal", "\u{1f396}\u{fe0f}"),
+    ("milky_way", "\u{1f30c}"),
+    ("minibus", "\u{1f690}"),
+    ("minidisc", "\u{1f4bd}"),
+    ("minimize", "\u{1f5d5}"),
+    ("mirror", "\u{1fa9e}"),
+    ("mirror_ball", "\u{1faa9}"),
+    ("mobile_phone", "\u{1f4f1}"),
+    ("mobile_phone_off", "\u{1f4f4}"),
+    ("mobile_phone_with_rightwards_arrow_at_left", "\u{1f4f2}"),
+    ("modern_pentathlon", "\u{1f93b}"),
+    ("money_bag", "\u{1f4b0}"),
+    ("money_mouth_face", "\u{1f911}"),
+    ("money_with_wings", "\u{1f4b8}"),
+    ("moneybag", "\u{1f4b0}"),
+    ("monkey", "\u{1f412}"),
+    ("monkey_face", "\u{1f435}"),
+    ("monorail", "\u{1f69d}"),
+    ("mood_bubble", "\u{1f5f0}"),
+    ("moon_cake", "\u{1f96e}"),
+    ("moon_viewing_ceremony", "\u{1f391}"),
+    ("mosque", "\u{1f54c}"),
+    ("mosquito", "\u{1f99f}"),
+    ("mother_christmas", "\u{1f936}"),
+    ("motor_boat", "\u{1f6e5}\u{fe0f}"),
+    ("motor_scooter", "\u{1f6f5}"),
+    ("motorized_wheelchair", "\u{1f9bc}"),
+    ("motorway", "\u{1f6e3}\u{fe0f}"),
+    ("mount_fuji", "\u{1f5fb}"),
+    ("mountain_bicyclist", "\u{1f6b5}"),
+    ("mountain_cableway", "\u{1f6a0}"),
+    ("mountain_railway", "\u{1f69e}"),
+    ("mouse", "\u{1f42d}"),
+    ("mouse2", "\u{1f401}"),
+    ("mouse_face", "\u{1f42d}"),
+    ("mouse_trap", "\u{1faa4}"),
+    ("mouth", "\u{1f444}"),
+    ("movie_camera", "\u{1f3a5}"),
+    ("moyai", "\u{1f5ff}"),
+    ("multiple_musical_notes", "\u{1f3b6}"),
+    ("muscle", "\u{1f4aa}"),
+    ("mushroom", "\u{1f344}"),
+    ("musical_keyboard", "\u{1f3b9}"),
+    ("musical_keyboard_with_jacks", "\u{1f398}\u{fe0f}"),
+    ("musical_note", "\u{1f3b5}"),
+    ("musical_score", "\u{1f3bc}"),
+    ("nail_care", "\u{1f485}"),
+    ("nail_polish", "\u{1f485}"),
+    ("name_badge", "\u{1f4db}"),
+    ("national_park", "\u{1f3de}\u{fe0f}"),
+    ("nauseated_face", "\u{1f922}"),
+    ("nazar_amulet", "\u{1f9ff}"),
+    ("necktie", "\u{1f454}"),
+    ("negative_squared_cross_mark", "\u{274e}"),
+    ("nerd_face", "\u{1f913}"),
+    ("nest_with_eggs", "\u{1faba}"),
+    ("nesting_dolls", "\u{1fa86}"),
+    ("neutral_chess_bishop", "\u{1fa03}"),
+    ("neutral_chess_bishop_rotated_ninety_degrees", "\u{1fa18}"),
+    ("neutral_chess_bishop_rotated_two_hundred_seventy_degrees", "\u{1fa42}"),
+    ("neutral_chess_equihopper", "\u{1fa4a}"),
+    ("neutral_chess_equihopper_rotated_ninety_degrees", "\u{1fa4d}"),
+    ("neutral_chess_king", "\u{1fa00}"),
+    ("neutral_chess_king_rotated_ninety_degrees", "\u{1fa15}"),
+    ("neutral_chess_king_rotated_two_hundred_seventy_degrees", "\u{1fa3f}"),
+    ("neutral_chess_knight", "\u{1fa04}"),
+    ("neutral_chess_knight_rotated_forty_five_degrees", "\u{1fa08}"),
+    ("neutral_chess_knight_rotated_ninety_degrees", "\u{1fa19}"),
+    ("neutral_chess_knight_rotated_one_hundred_thirty_five_degrees", "\u{1fa1d}"),
+    ("neutral_chess_knight_rotated_three_hundred_fifteen_degrees", "\u{1fa47}"),
+    ("neutral_chess_knight_rotated_two_hundred_seventy_degrees", "\u{1fa43}"),
+    ("neutral_chess_knight_rotated_two_hundred_twenty_five_degrees", "\u{1fa32}"),
+    ("neutral_chess_pawn", "\u{1fa05}"),
+    ("neutral_chess_pawn_rotated_ninety_degrees", "\u{1fa1a}"),
+    ("neutral_chess_pawn_rotated_two_hundred_seventy_degrees", "\u{1fa44}"),
+    ("neutral_chess_queen", "\u{1fa01}"),
+    ("neutral_chess_queen_rotated_ninety_degrees", "\u{1fa16}"),
+    ("neutral_chess_queen_rotated_two_hundred_seventy_degrees", "\u{1fa40}"),
+    ("neutral_chess_rook", "\u{1fa02}"),
+    ("neutral_chess_rook_rotated_ninety_degrees", "\u{1fa17}"),
+    ("neutral_chess_rook_rotated_two_hundred_seventy_degrees", "\u{1fa41}"),
+    ("neutral_chess_turned_bishop", "\u{1fa2d}"),
+    ("neutral_chess_turned_king", "\u{1fa2a}"),
+    ("neutral_chess_turned_knight", "\u{1fa2e}"),
+    ("neutral_chess_turned_pawn", "\u{1fa2f}"),
+    ("neutral_chess_turned_queen", "\u{1fa2b}"),
+    ("neutral_chess_turned_rook", "\u{1fa2c}"),
+    ("neutral_face", "\u{1f610}"),
+    ("new", "\u{1f195}"),
+    ("new_moon", "\u{1f311}"),
+    ("new_moon_with_face", "\u{1f31a}"),
+    ("newspaper", "\u{1f4f0}"),
+    ("night_with_stars", "\u{1f303}"),
+    ("ninja", "\u{1f977}"),
+    ("no_bell", "\u{1f515}"),
+    ("no_bicycles", "\u{1f6b3}"),
+    ("no_entry", "\u{26d4}"),
+    ("no_entry_sign", "\u{1f6ab}"),
+    ("no_mobile_phones", "\u{1f4f5}"),
+    ("no_mouth", "\u{1f636}"),
+    ("no_one_under_eighteen", "\u{1f51e}"),
+    ("no_pedestrians", "\u{1f6b7}"),
+    ("no_piracy", "\u{1f572}"),
+    ("no_smoking", "\u{1f6ad}"),
+    ("non_potable_water", "\u{1f6b1}"),
+    ("northeast_pointing_airplane", "\u{1f6ea}"),
+    ("nose", "\u{1f443}"),
+    ("notched_left_semicircle_with_three_dots", "\u{1f543}"),
+    ("notched_right_semicircle_with_three_dots", "\u{1f544}"),
+    ("note", "\u{1f5c8}"),
+    ("note_pad", "\u{1f5ca}"),
+    ("note_page", "\u{1f5c9}"),
+    ("notebook", "\u{1f4d3}"),
+    ("notebook_with_decorative_cover", "\u{1f4d4}"),
+    ("notes", "\u{1f3b6}"),
+    ("nut_and_bolt", "\u{1f529}"),
+    ("o", "\u{2b55}"),
+    ("ocean", "\u{1f30a}"),
+    ("octagonal", "\u{1f6d1}"),
+    ("octopus", "\u{1f419}"),
+    ("oden", "\u{1f362}"),
+    ("office", "\u{1f3e2}"),
+    ("office_building", "\u{1f3e2}"),
+    ("oil_drum", "\u{1f6e2}\u{fe0f}"),
+    ("ok", "\u{1f197}"),
+    ("ok_hand", "\u{1f44c}"),
+    ("old_key", "\u{1f5dd}\u{fe0f}"),
+    ("old_personal_computer", "\u{1f5b3}"),
+    ("older_adult", "\u{1f9d3}"),
+    ("older_man", "\u{1f474}"),
+    ("older_woman", "\u{1f475}"),
+    ("olive", "\u{1fad2}"),
+    ("om", "\u{1f549}\u{fe0f}"),
+    ("on", "\u{1f51b}"),
+    ("on_with_exclamation_mark_with_left_right_arrow_above", "\u{1f51b}"),
+    ("oncoming_automobile", "\u{1f698}"),
+    ("oncoming_bus", "\u{1f68d}"),
+    ("oncoming_fire_engine", "\u{1f6f1}"),
+    ("oncoming_police_car", "\u{1f694}"),
+    ("oncoming_taxi", "\u{1f696}"),
+    ("one_button_mouse", "\u{1f5af}"),
+    ("one_piece_swimsuit", "\u{1fa71}"),
+    ("onion", "\u{1f9c5}"),
+    ("open_book", "\u{1f4d6}"),
+    ("open_file_folder", "\u{1f4c2}"),
+    ("open_folder", "\u{1f5c1}"),
+    ("open_hands", "\u{1f450}"),
+    ("open_lock", "\u{1f513}"),
+    ("open_mailbox_with_lowered_flag", "\u{1f4ed}"),
+    ("open_mailbox_with_raised_flag", "\u{1f4ec}"),
+    ("open_mouth", "\u{1f62e}"),
+    ("optical_disc", "\u{1f4bf}"),
+    ("optical_disc_icon", "\u{1f5b8}"),
+    ("orange_book", "\u{1f4d9}"),
+    ("orange_heart", "\u{1f9e1}"),
+    ("orangutan", "\u{1f9a7}"),
+    ("otter", "\u{1f9a6}"),
+    ("outbox_tray", "\u{1f4e4}"),
+    ("overheated_face", "\u{1f975}"),
+    ("overlap", "\u{1f5d7}"),
+    ("owl", "\u{1f989}"),
+    ("ox", "\u{1f402}"),
+    ("oyster", "\u{1f9aa}"),
+    ("package", "\u{1f4e6}"),
+    ("page", "\u{1f5cf}"),
+    ("page_facing_up", "\u{1f4c4}"),
+    ("page_with_circled_text", "\u{1f5df}"),
+    ("page_with_curl", "\u{1f4c3}"),
+    ("pager", "\u{1f4df}"),
+    ("pages", "\u{1f5d0}"),
+    ("pagoda", "\u{1f6d4}"),
+    ("palm_down_hand", "\u{1faf3}"),
+    ("palm_tree", "\u{1f334}"),
+    ("palm_up_hand", "\u{1faf4}"),
+    ("palms_up_together", "\u{1f932}"),
+    ("pancakes", "\u{1f95e}"),
+    ("panda_face", "\u{1f43c}"),
+    ("paperclip", "\u{1f4ce}"),
+    ("parachute", "\u{1fa82}"),
+    ("parrot", "\u{1f99c}"),
+    ("party_popper", "\u{1f389}"),
+    ("partying_face", "\u{1f973}"),
+    ("passenger_ship", "\u{1f6f3}\u{fe0f}"),
+    ("passport_control", "\u{1f6c2}"),
+    ("paw_prints", "\u{1f43e}"),
+    ("peach", "\u{1f351}"),
+    ("peacock", "\u{1f99a}"),
+    ("peanuts", "\u{1f95c}"),
+    ("pear", "\u{1f350}"),
+    ("pedestrian", "\u{1f6b6}"),
+    ("pen_over_stamped_envelope", "\u{1f586}"),
+    ("pencil", "\u{1f4dd}"),
+    ("pencil2", "\u{270f}\u{fe0f}"),
+    ("penguin", "\u{1f427}"),
+    ("pensive", "\u{1f614}"),
+    ("pensive_face", "\u{1f614}"),
+    ("people_hugging", "\u{1fac2}"),
+    ("performing_arts", "\u{1f3ad}"),
+    ("persevere", "\u{1f623}"),
+    ("persevering_face", "\u{1f623}"),
+    ("person_bowing_deeply", "\u{1f647}"),
+    ("person_climbing", "\u{1f9d7}"),
+    ("person_doing_cartwheel", "\u{1f938}"),
+    ("person_frowning", "\u{1f64d}"),
+    ("person_in_lotus_position", "\u{1f9d8}"),
+    ("person_in_steamy_room", "\u{1f9d6}"),
+    ("person_raising_both_hands_in_celebration", "\u{1f64c}"),
+    ("person_with_blond_hair", "\u{1f471}"),
+    ("person_with_crown", "\u{1fac5}"),
+    ("person_with_folded_hands", "\u{1f64f}"),
+    ("person_with_headscarf", "\u{1f9d5}"),
+    ("person_with_pouting_face", "\u{1f64e}"),
+    ("personal_computer", "\u{1f4bb}"),
+    ("petri_dish", "\u{1f9eb}"),
+    ("phone", "\u{260e}\u{fe0f}"),
+    ("pickup_truck", "\u{1f6fb}"),
+    ("pie", "\u{1f967}"),
+    ("pig", "\u{1f437}"),
+    ("pig2", "\u{1f416}"),
+    ("pig_face", "\u{1f437}"),
+    ("pig_nose", "\u{1f43d}"),
+    ("pile_of_poo", "\u{1f4a9}"),
+    ("pill", "\u{1f48a}"),
+    ("pinata", "\u{1fa85}"),
+    ("pinched_fingers", "\u{1f90c}"),
+    ("pinching_hand", "\u{1f90f}"),
+    ("pine_decoration", "\u{1f38d}"),
+    ("pineapple", "\u{1f34d}"),
+    ("pistol", "\u{1f52b}"),
+    ("pizza", "\u{1f355}"),
+    ("placard", "\u{1faa7}"),
+    ("place_of_worship", "\u{1f6d0}"),
+    ("playground_slide", "\u{1f6dd}"),
+    ("pleading_face", "\u{1f97a}"),
+    ("plunger", "\u{1faa0}"),
+    ("pocket_calculator", "\u{1f5a9}"),
+    ("point_down", "\u{1f447}"),
+    ("point_left", "\u{1f448}"),
+    ("point_right", "\u{1f449}"),
+    ("point_up", "\u{261d}\u{fe0f}"),
+    ("point_up_2", "\u{1f446}"),
+    ("police_car", "\u{1f693}"),
+    ("police_cars_revolving_light", "\u{1f6a8}"),
+    ("police_officer", "\u{1f46e}"),
+    ("poodle", "\u{1f429}"),
+    ("poop", "\u{1f4a9}"),
+    ("popcorn", "\u{1f37f}"),
+    ("portable_stereo", "\u{1f4fe}"),
+    ("postal_horn", "\u{1f4ef}"),
+    ("postbox", "\u{1f4ee}"),
+    ("pot_of_food", "\u{1f372}"),
+    ("potable_water", "\u{1f6b0}"),
+    ("potato", "\u{1f954}"),
+    ("potted_plant", "\u{1fab4}"),
+    ("pouch", "\u{1f45d}"),
+    ("poultry_leg", "\u{1f357}"),
+    ("pouring_liquid", "\u{1fad7}"),
+    ("pout", "\u{1f621}"),
+    ("pouting_cat", "\u{1f63e}"),
+    ("pouting_cat_face", "\u{1f63e}"),
+    ("pouting_face", "\u{1f621}"),
+    ("pray", "\u{1f64f}"),
+    ("prayer_beads", "\u{1f4ff}"),
+    ("pregnant_man", "\u{1fac3}"),
+    ("pregnant_person", "\u{1fac4}"),
+    ("pregnant_woman", "\u{1f930}"),
+    ("pretzel", "\u{1f968}"),
+    ("prince", "\u{1f934}"),
+    ("princess", "\u{1f478}"),
+    ("printer", "\u{1f5a8}\u{fe0f}"),
+    ("printer_icon", "\u{1f5b6}"),
+    ("probing_cane", "\u{1f9af}"),
+    ("prohibited", "\u{1f6c7}"),
+    ("public_address_loudspeaker", "\u{1f4e2}"),
+    ("punch", "\u{1f44a}"),
+    ("purple_heart", "\u{1f49c}"),
+    ("purse", "\u{1f45b}"),
+    ("pushpin", "\u{1f4cc}"),
+    ("put_litter_in_its_place", "\u{1f6ae}"),
+    ("question", "\u{2753}"),
+    ("rabbit", "\u{1f430}"),
+    ("rabbit2", "\u{1f407}"),
+    ("rabbit_face", "\u{1f430}"),
+    ("raccoon", "\u{1f99d}"),
+    ("racehorse", "\u{1f40e}"),
+    ("racing_car", "\u{1f3ce}\u{fe0f}"),
+    ("racing_motorcycle", "\u{1f3cd}\u{fe0f}"),
+    ("radio", "\u{1f4fb}"),
+    ("radio_button", "\u{1f518}"),
+    ("rage", "\u{1f621}"),
+    ("railway_car", "\u{1f683}"),
+    ("railway_track", "\u{1f6e4}\u{fe0f}"),
+    ("rainbow", "\u{1f308}"),
+    ("raised_back_of_hand", "\u{1f91a}"),
+    ("raised_hand", "\u{270b}"),
+    ("raised_hand_with_fingers_splayed", "\u{1f590}\u{fe0f}"),
+    ("raised_hand_with_part_between_middle_and_ring_fingers", "\u{1f596}"),
+    ("raised_hands", "\u{1f64c}"),
+    ("ram", "\u{1f40f}"),
+    ("rat", "\u{1f400}"),
+    ("razor", "\u{1fa92}"),
+    ("receipt", "\u{1f9fe}"),
+    ("recreational_vehicle", "\u{1f699}"),
+    ("recycle", "\u{267b}\u{fe0f}"),
+    ("red_apple", "\u{1f34e}"),
+    ("red_car", "\u{1f697}"),
+    ("red_circle", "\u{1f534}"),
+    ("red_gift_envelope", "\u{1f9e7}"),
+    ("registered", "\u{ae}\u{fe0f}"),
+    ("relaxed", "\u{263a}\u{fe0f}"),
+    ("relieved", "\u{1f60c}"),
+    ("relieved_face", "\u{1f60c}"),
+    ("reminder_ribbon", "\u{1f397}\u{fe0f}"),
+    ("restroom", "\u{1f6bb}"),
+    ("reversed_hand_with_middle_finger_extended", "\u{1f595}"),
+    ("reversed_raised_hand_with_fingers_splayed", "\u{1f591}"),
+    ("reversed_thumbs_down", "\u{1f593}"),
+    ("reversed_thumbs_up", "\u{1f592}"),
+    ("reversed_victory_hand", "\u{1f594}"),
+    ("revolving_hearts", "\u{1f49e}"),
+    ("rhinoceros", "\u{1f98f}"),
+    ("ribbon", "\u{1f380}"),
+    ("rice_ball", "\u{1f359}"),
+    ("rice_cracker", "\u{1f358}"),
+    ("rifle", "\u{1f946}"),
+    ("right_anger_bubble", "\u{1f5ef}\u{fe0f}"),
+    ("right_facing_fist", "\u{1f91c}"),
+    ("right_hand_telephone_receiver", "\u{1f57d}"),
+    ("right_pointing_magnifying_glass", "\u{1f50e}"),
+    ("right_speaker", "\u{1f568}"),
+    ("right_speaker_with_one_sound_wave", "\u{1f569}"),
+    ("right_speaker_with_three_sound_waves", "\u{1f56a}"),
+    ("right_speech_bubble", "\u{1f5e9}"),
+    ("right_thought_bubble", "\u{1f5ed}"),
+    ("rightwards_hand", "\u{1faf1}"),
+    ("ring", "\u{1f48d}"),
+    ("ring_buoy", "\u{1f6df}"),
+    ("ringed_planet", "\u{1fa90}"),
+    ("ringing_bell", "\u{1f56d}"),
+    ("roasted_sweet_potato", "\u{1f360}"),
+    ("robot", "\u{1f916}"),
+    ("robot_face", "\u{1f916}"),
+    ("rock", "\u{1faa8}"),
+    ("rocket", "\u{1f680}"),
+    ("rofl", "\u{1f923}"),
+    ("roll_eyes", "\u{1f644}"),
+    ("roll_of_paper", "\u{1f9fb}"),
+    ("rolled_up_newspaper", "\u{1f5de}\u{fe0f}"),
+    ("roller_coaster", "\u{1f3a2}"),
+    ("roller_skate", "\u{1f6fc}"),
+    ("rolling_on_the_floor_laughing", "\u{1f923}"),
+    ("rooster", "\u{1f413}"),
+    ("rose", "\u{1f339}"),
+    ("rosette", "\u{1f3f5}\u{fe0f}"),
+    ("rotating_light", "\u{1f6a8}"),
+    ("round_pushpin", "\u{1f4cd}"),
+    ("rowboat", "\u{1f6a3}"),
+    ("rugby_football", "\u{1f3c9}"),
+    ("runner", "\u{1f3c3}"),
+    ("running_shirt_with_sash", "\u{1f3bd}"),
+    ("safety_pin", "\u{1f9f7}"),
+    ("safety_vest", "\u{1f9ba}"),
+    ("sailboat", "\u{26f5}"),
+    ("sake_bottle_and_cup", "\u{1f376}"),
+    ("salt_shaker", "\u{1f9c2}"),
+    ("saluting_face", "\u{1fae1}"),
+    ("sandwich", "\u{1f96a}"),
+    ("santa", "\u{1f385}"),
+    ("sari", "\u{1f97b}"),
+    ("satellite", "\u{1f6f0}\u{fe0f}"),
+    ("satellite_antenna", "\u{1f4e1}"),
+    ("satisfied", "\u{1f606}"),
+    ("sauropod", "\u{1f995}"),
+    ("saxophone", "\u{1f3b7}"),
+    ("scarf", "\u{1f9e3}"),
+    ("school", "\u{1f3eb}"),
+    ("school_satchel", "\u{1f392}"),
+    ("scissors", "\u{2702}\u{fe0f}"),
+    ("scooter", "\u{1f6f4}"),
+    ("scorpion", "\u{1f982}"),
+    ("scream", "\u{1f631}"),
+    ("scream_cat", "\u{1f640}"),
+    ("screen", "\u{1f5b5}"),
+    ("screwdriver", "\u{1fa9b}"),
+    ("scroll", "\u{1f4dc}"),
+    ("seal", "\u{1f9ad}"),
+    ("seat", "\u{1f4ba}"),
+    ("second_place_medal", "\u{1f948}"),
+    ("see_no_evil", "\u{1f648}"),
+    ("see_no_evil_monkey", "\u{1f648}"),
+    ("seedling", "\u{1f331}"),
+    ("selfie", "\u{1f933}"),
+    ("serious_face_with_symbols_covering_mouth", "\u{1f92c}"),
+    ("sewing_needle", "\u{1faa1}"),
+    ("shallow_pan_of_food", "\u{1f958}"),
+    ("shark", "\u{1f988}"),
+    ("shaved_ice", "\u{1f367}"),
+    ("sheep", "\u{1f411}"),
+    ("shield", "\u{1f6e1}\u{fe0f}"),
+    ("ship", "\u{1f6a2}"),
+    ("shit", "\u{1f4a9}"),
+    ("shocked_face_with_exploding_head", "\u{1f92f}"),
+    ("shooting_star", "\u{1f320}"),
+    ("shopping_bags", "\u{1f6cd}\u{fe0f}"),
+    ("shopping_trolley", "\u{1f6d2}"),
+    ("shortcake", "\u{1f370}"),
+    ("shorts", "\u{1fa73}"),
+    ("shower", "\u{1f6bf}"),
+    ("shrimp", "\u{1f990}"),
+    ("shrug", "\u{1f937}"),
+    ("sideways_black_down_pointing_index", "\u{1f5a1}"),
+    ("sideways_black_left_pointing_index", "\u{1f59a}"),
+    ("sideways_black_right_pointing_index", "\u{1f59b}"),
+    ("sideways_black_up_pointing_index", "\u{1f5a0}"),
+    ("sideways_white_down_pointing_index", "\u{1f59f}"),
+    ("sideways_white_left_pointing_index", "\u{1f598}"),
+    ("sideways_white_right_pointing_index", "\u{1f599}"),
+    ("sideways_white_up_pointing_index", "\u{1f59e}"),
+    ("sign_of_the_horns", "\u{1f918}"),
+    ("silhouette_of_japan", "\u{1f5fe}"),
+    ("six_pointed_star_with_middle_dot", "\u{1f52f}"),
+    ("skateboard", "\u{1f6f9}"),
+    ("ski_and_ski_boot", "\u{1f3bf}"),
+    ("skull", "\u{1f480}"),
+    ("skunk", "\u{1f9a8}"),
+    ("sled", "\u{1f6f7}"),
+    ("sleeping", "\u{1f634}"),
+    ("sleeping_accommodation", "\u{1f6cc}"),
+    ("sleeping_face", "\u{1f634}"),
+    ("sleepy", "\u{1f62a}"),
+    ("sleepy_face", "\u{1f62a}"),
+    ("sleuth_or_spy", "\u{1f575}\u{fe0f}"),
+    ("slice_of_pizza", "\u{1f355}"),
+    ("slightly_frowning_face", "\u{1f641}"),
+    ("slightly_smiling_face", "\u{1f642}"),
+    ("slot_machine", "\u{1f3b0}"),
+    ("sloth", "\u{1f9a5}"),
+    ("small_airplane", "\u{1f6e9}\u{fe0f}"),
+    ("small_blue_diamond", "\u{1f539}"),
+    ("small_orange_diamond", "\u{1f538}"),
+    ("smile", "\u{1f604}"),
+    ("smile_cat", "\u{1f638}"),
+    ("smiley", "\u{1f603}"),
+    ("smiley_cat", "\u{1f63a}"),
+    ("smiling_cat_face_with_heart_shaped_eyes", "\u{1f63b}"),
+    ("smiling_cat_face_with_open_mouth", "\u{1f63a}"),
+    ("smiling_face_with_halo", "\u{1f607}"),
+    ("smiling_face_with_heart_shaped_eyes", "\u{1f60d}"),
+    ("smiling_face_with_horns", "\u{1f608}"),
+    ("smiling_face_with_open_mouth", "\u{1f603}"),
+    ("smiling_face_with_open_mouth_and_cold_sweat", "\u{1f605}"),
+    ("smiling_face_with_open_mouth_and_smiling_eyes", "\u{1f604}"),
+    ("smiling_face_with_open_mouth_and_tightly_closed_eyes", "\u{1f606}"),
+    ("smiling_face_with_smiling_eyes", "\u{1f60a}"),
+    ("smiling_face_with_smiling_eyes_and_hand_covering_mouth", "\u{1f92d}"),
+    ("smiling_face_with_smiling_eyes_and_three_hearts", "\u{1f970}"),
+    ("smiling_face_with_sunglasses", "\u{1f60e}"),
+    ("smiling_face_with_tear", "\u{1f972}"),
+    ("smiling_face_with_three_hearts", "\u{1f970}"),
+    ("smiling_imp", "\u{1f608}"),
+    ("smirk", "\u{1f60f}"),
+    ("smirk_cat", "\u{1f63c}"),
+    ("smirking_face", "\u{1f60f}"),
+    ("smoking", "\u{1f6ac}"),
+    ("snail", "\u{1f40c}"),
+    ("snake", "\u{1f40d}"),
+    ("sneezing_face", "\u{1f927}"),
+    ("snow_capped_mountain", "\u{1f3d4}\u{fe0f}"),
+    ("snowboarder", "\u{1f3c2}"),
+    ("snowflake", "\u{2744}\u{fe0f}"),
+    ("snowman", "\u{26c4}"),
+    ("sob", "\u{1f62d}"),
+    ("socks", "\u{1f9e6}"),
+    ("soft_ice_cream", "\u{1f366}"),
+    ("soft_shell_floppy_disk", "\u{1f5ac}"),
+    ("softball", "\u{1f94e}"),
+    ("soon", "\u{1f51c}"),
+    ("soon_with_rightwards_arrow_above", "\u{1f51c}"),
+    ("sos", "\u{1f198}"),
+    ("space_invader", "\u{1f47e}"),
+    ("spaghetti", "\u{1f35d}"),
+    ("sparkles", "\u{2728}"),
+    ("sparkling_heart", "\u{1f496}"),
+    ("speak_no_evil", "\u{1f64a}"),
+    ("speak_no_evil_monkey", "\u{1f64a}"),
+    ("speaker", "\u{1f508}"),
+    ("speaker_with_cancellation_stroke", "\u{1f507}"),
+    ("speaker_with_one_sound_wave", "\u{1f509}"),
+    ("speaker_with_three_sound_waves", "\u{1f50a}"),
+    ("speaking_head_in_silhouette", "\u{1f5e3}\u{fe0f}"),
+    ("speech_balloon", "\u{1f4ac}"),
+    ("speedboat", "\u{1f6a4}"),
+    ("spider", "\u{1f577}\u{fe0f}"),
+    ("spider_web", "\u{1f578}\u{fe0f}"),
+    ("spiral_calendar_pad", "\u{1f5d3}\u{fe0f}"),
+    ("spiral_note_pad", "\u{1f5d2}\u{fe0f}"),
+    ("spiral_shell", "\u{1f41a}"),
+    ("splashing_sweat", "\u{1f4a6}"),
+    ("sponge", "\u{1f9fd}"),
+    ("spool_of_thread", "\u{1f9f5}"),
+    ("spoon", "\u{1f944}"),
+    ("sports_medal", "\u{1f3c5}"),
+    ("spouting_whale", "\u{1f433}"),
+    ("squid", "\u{1f991}"),
+    ("stadium", "\u{1f3df}\u{fe0f}"),
+    ("stamped_envelope", "\u{1f583}"),
+    ("standing_person", "\u{1f9cd}"),
+    ("star", "\u{2b50}"),
+    ("star2", "\u{1f31f}"),
+    ("star_struck", "\u{1f929}"),
+    ("station", "\u{1f689}"),
+    ("statue_of_liberty", "\u{1f5fd}"),
+    ("steam_locomotive", "\u{1f682}"),
+    ("steaming_bowl", "\u{1f35c}"),
+    ("stethoscope", "\u{1fa7a}"),
+    ("stock_chart", "\u{1f5e0}"),
+    ("straight_ruler", "\u{1f4cf}"),
+    ("strawberry", "\u{1f353}"),
+    ("stuck_out_tongue", "\u{1f61b}"),
+    ("stuck_out_tongue_closed_eyes", "\u{1f61d}"),
+    ("stuck_out_tongue_winking_eye", "\u{1f61c}"),
+    ("studio_microphone", "\u{1f399}\u{fe0f}"),
+    ("stuffed_flatbread", "\u{1f959}"),
+    ("stupa", "\u{1f6d3}"),
+    ("sun_with_face", "\u{1f31e}"),
+    ("sunflower", "\u{1f33b}"),
+    ("sunglasses", "\u{1f60e}"),
+    ("sunny", "\u{2600}\u{fe0f}"),
+    ("sunrise", "\u{1f305}"),
+    ("sunrise_over_mountains", "\u{1f304}"),
+    ("sunset_over_buildings", "\u{1f307}"),
+    ("superhero", "\u{1f9b8}"),
+    ("supervillain", "\u{1f9b9}"),
+    ("surfer", "\u{1f3c4}"),
+    ("sushi", "\u{1f363}"),
+    ("suspension_railway", "\u{1f69f}"),
+    ("swan", "\u{1f9a2}"),
+    ("sweat", "\u{1f613}"),
+    ("sweat_drops", "\u{1f4a6}"),
+    ("sweat_smile", "\u{1f605}"),
+    ("swimmer", "\u{1f3ca}"),
+    ("symbol_for_marks_chapter", "\u{1f545}"),
+    ("synagogue", "\u{1f54d}"),
+    ("syringe", "\u{1f489}"),
+    ("t_rex", "\u{1f996}"),
+    ("t_shirt", "\u{1f455}"),
+    ("table_tennis_paddle_and_ball", "\u{1f3d3}"),
+    ("taco", "\u{1f32e}"),
+    ("tada", "\u{1f389}"),
+    ("takeout_box", "\u{1f961}"),
+    ("tamale", "\u{1fad4}"),
+    ("tanabata_tree", "\u{1f38b}"),
+    ("tangerine", "\u{1f34a}"),
+    ("tape_cartridge", "\u{1f5ad}"),
+    ("taxi", "\u{1f695}"),
+    ("tea", "\u{1f375}"),
+    ("teacup_without_handle", "\u{1f375}"),
+    ("teapot", "\u{1fad6}"),
+    ("tear_off_calendar", "\u{1f4c6}"),
+    ("teddy_bear", "\u{1f9f8}"),
+    ("telephone", "\u{260e}\u{fe0f}"),
+    ("telephone_on_top_of_modem", "\u{1f580}"),
+    ("telephone_receiver", "\u{1f4de}"),
+    ("telephone_receiver_with_page", "\u{1f57c}"),
+    ("telescope", "\u{1f52d}"),
+    ("television", "\u{1f4fa}"),
+    ("tennis_racquet_and_ball", "\u{1f3be}"),
+    ("test_tube", "\u{1f9ea}"),
+    ("thermometer", "\u{1f321}\u{fe0f}"),
+    ("thinking", "\u{1f914}"),
+    ("thinking_face", "\u{1f914}"),
+    ("third_place_medal", "\u{1f949}"),
+    ("thong_sandal", "\u{1fa74}"),
+    ("thought_balloon", "\u{1f4ad}"),
+    ("three_button_mouse", "\u{1f5b1}\u{fe0f}"),
+    ("three_networked_computers", "\u{1f5a7}"),
+    ("three_rays_above", "\u{1f5e4}"),
+    ("three_rays_below", "\u{1f5e5}"),
+    ("three_rays_left", "\u{1f5e6}"),
+    ("three_rays_right", "\u{1f5e7}"),
+    ("three_speech_bubbles", "\u{1f5eb}"),
+    ("thumbs_down", "\u{1f44e}"),
+    ("thumbs_up", "\u{1f44d}"),
+    ("thumbsdown", "\u{1f44e}"),
+    ("thumbsup", "\u{1f44d}"),
+    ("ticket", "\u{1f3ab}"),
+    ("tiger", "\u{1f42f}"),
+    ("tiger2", "\u{1f405}"),
+    ("tiger_face", "\u{1f42f}"),
+    ("tired_face", "\u{1f62b}"),
+    ("tm", "\u{2122}\u{fe0f}"),
+    ("toilet", "\u{1f6bd}"),
+    ("tokyo_tower", "\u{1f5fc}"),
+    ("tomato", "\u{1f345}"),
+    ("tongue", "\u{1f445}"),
+    ("toolbox", "\u{1f9f0}"),
+    ("tooth", "\u{1f9b7}"),
+    ("toothbrush", "\u{1faa5}"),
+    ("top", "\u{1f51d}"),
+    ("top_hat", "\u{1f3a9}"),
+    ("top_with_upwards_arrow_above", "\u{1f51d}"),
+    ("trackball", "\u{1f5b2}\u{fe0f}"),
+    ("tractor", "\u{1f69c}"),
+    ("train", "\u{1f68b}"),
+    ("train2", "\u{1f686}"),
+    ("tram", "\u{1f68a}"),
+    ("tram_car", "\u{1f68b}"),
+    ("triangle_with_rounded_corners", "\u{1f6c6}"),
+    ("triangular_flag_on_post", "\u{1f6a9}"),
+    ("triangular_ruler", "\u{1f4d0}"),
+    ("trident_emblem", "\u{1f531}"),
+    ("triumph", "\u{1f624}"),
+    ("troll", "\u{1f9cc}"),
+    ("trolleybus", "\u{1f68e}"),
+    ("trophy", "\u{1f3c6}"),
+    ("tropical_drink", "\u{1f379}"),
+    ("tropical_fish", "\u{1f420}"),
+    ("truck", "\u{1f69a}"),
+    ("trumpet", "\u{1f3ba}"),
+    ("tulip", "\u{1f337}"),
+    ("tumbler_glass", "\u{1f943}"),
+    ("turkey", "\u{1f983}"),
+    ("turned_ok_hand", "\u{1f58f}"),
+    ("turtle", "\u{1f422}"),
+    ("tv", "\u{1f4fa}"),
+    ("twisted_rightwards_arrows", "\u{1f500}"),
+    ("two_button_mouse", "\u{1f5b0}"),
+    ("two_hearts", "\u{1f495}"),
+    ("two_men_holding_hands", "\u{1f46c}"),
+    ("two_speech_bubbles", "\u{1f5ea}"),
+    ("two_women_holding_hands", "\u{1f46d}"),
+    ("umbrella", "\u{2614}"),
+    ("unamused", "\u{1f612}"),
+    ("unamused_face", "\u{1f612}"),
+    ("unicorn", "\u{1f984}"),
+    ("unicorn_face", "\u{1f984}"),
+    ("unlock", "\u{1f513}"),
+    ("up", "\u{1f199}"),
+    ("up_pointing_airplane", "\u{1f6e7}"),
+    ("up_pointing_military_airplane", "\u{1f6e6}"),
+    ("up_pointing_red_triangle", "\u{1f53a}"),
+    ("up_pointing_small_airplane", "\u{1f6e8}"),
+    ("up_pointing_small_red_triangle", "\u{1f53c}"),
+    ("upper_right_shadowed_white_circle", "\u{1f53f}"),
+    ("upside_down_face", "\u{1f643}"),
+    ("v", "\u{270c}\u{fe0f}"),
+    ("vampire", "\u{1f9db}"),
+    ("vertical_traffic_light", "\u{1f6a6}"),
+    ("vibration_mode", "\u{1f4f3}"),
+    ("video_camera", "\u{1f4f9}"),
+    ("video_game", "\u{1f3ae}"),
+    ("videocassette", "\u{1f4fc}"),
+    ("violin", "\u{1f3bb}"),
+    ("volcano", "\u{1f30b}"),
+    ("volleyball", "\u{1f3d0}"),
+    ("vs", "\u{1f19a}"),
+    ("waffle", "\u{1f9c7}"),
+    ("waning_crescent_moon", "\u{1f318}"),
+    ("waning_gibbous_moon", "\u{1f316}"),
+    ("warning", "\u{26a0}\u{fe0f}"),
+    ("wastebasket", "\u{1f5d1}\u{fe0f}"),
+    ("watch", "\u{231a}"),
+    ("water_buffalo", "\u{1f403}"),
+    ("water_closet", "\u{1f6be}"),
+    ("water_polo", "\u{1f93d}"),
+    ("water_wave", "\u{1f30a}"),
+    ("watermelon", "\u{1f349}"),
+    ("wave", "\u{1f44b}"),
+    ("waving_black_flag", "\u{1f3f4}"),
+    ("waving_hand", "\u{1f44b}"),
+    ("waving_white_flag", "\u{1f3f3}\u{fe0f}"),
+    ("waxing_crescent_moon", "\u{1f312}"),
+    ("waxing_gibbous_moon", "\u{1f314}"),
+    ("weary", "\u{1f629}"),
+    ("weary_cat_face", "\u{1f640}"),
+    ("weary_face", "\u{1f629}"),
+    ("wedding", "\u{1f492}"),
+    ("weight_lifter", "\u{1f3cb}\u{fe0f}"),
+    ("whale", "\u{1f433}"),
+    ("whale2", "\u{1f40b}"),
+    ("wheel", "\u{1f6de}"),
+    ("white_check_mark", "\u{2705}"),
+    ("white_chess_bishop_rotated_ninety_degrees", "\u{1fa0c}"),
+    ("white_chess_bishop_rotated_two_hundred_seventy_degrees", "\u{1fa36}"),
+    ("white_chess_equihopper", "\u{1fa48}"),
+    ("white_chess_equihopper_rotated_ninety_degrees", "\u{1fa4b}"),
+    ("white_chess_king_rotated_ninety_degrees", "\u{1fa09}"),
+    ("white_chess_king_rotated_two_hundred_seventy_degrees", "\u{1fa33}"),
+    ("white_chess_knight_bishop", "\u{1fa50}"),
+    ("white_chess_knight_queen", "\u{1fa4e}"),
+    ("white_chess_knight_rook", "\u{1fa4f}"),
+    ("white_chess_knight_rotated_forty_five_degrees", "\u{1fa06}"),
+    ("white_chess_knight_rotated_ninety_degrees", "\u{1fa0d}"),
+    ("white_chess_knight_rotated_one_hundred_thirty_five_degrees", "\u{1fa1b}"),
+    ("white_chess_knight_rotated_three_hundred_fifteen_degrees", "\u{1fa45}"),
+    ("white_chess_knight_rotated_two_hundred_seventy_degrees", "\u{1fa37}"),
+    ("white_chess_knight_rotated_two_hundred_twenty_five_degrees", "\u{1fa30}"),
+    ("white_chess_pawn_rotated_ninety_degrees", "\u{1fa0e}"),
+    ("white_chess_pawn_rotated_two_hundred_seventy_degrees", "\u{1fa38}"),
+    ("white_chess_queen_rotated_ninety_degrees", "\u{1fa0a}"),
+    ("white_chess_queen_rotated_two_hundred_seventy_degrees", "\u{1fa34}"),
+    ("white_chess_rook_rotated_ninety_degrees", "\u{1fa0b}"),
+    ("white_chess_rook_rotated_two_hundred_seventy_degrees", "\u{1fa35}"),
+    ("white_chess_turned_bishop", "\u{1fa21}"),
+    ("white_chess_turned_king", "\u{1fa1e}"),
+    ("white_chess_turned_knight", "\u{1fa22}"),
+    ("white_chess_turned_pawn", "\u{1fa23}"),
+    ("white_chess_turned_queen", "\u{1fa1f}"),
+    ("white_chess_turned_rook", "\u{1fa20}"),
+    ("white_circle", "\u{26aa}"),
+    ("white_down_pointing_backhand_index", "\u{1f447}"),
+    ("white_down_pointing_left_hand_index", "\u{1f597}"),
+    ("white_flag", "\u{1f3f3}\u{fe0f}"),
+    ("white_flower", "\u{1f4ae}"),
+    ("white_hard_shell_floppy_disk", "\u{1f5ab}"),
+    ("white_heart", "\u{1f90d}"),
+    ("white_latin_cross", "\u{1f546}"),
+    ("white_left_pointing_backhand_index", "\u{1f448}"),
+    ("white_pennant", "\u{1f3f1}"),
+    ("white_right_pointing_backhand_index", "\u{1f449}"),
+    ("white_square_button", "\u{1f533}"),
+    ("white_sun", "\u{1f323}\u{fe0f}"),
+    ("white_sun_behind_cloud", "\u{1f325}\u{fe0f}"),
+    ("white_sun_behind_cloud_with_rain", "\u{1f326}\u{fe0f}"),
+    ("white_sun_with_small_cloud", "\u{1f324}\u{fe0f}"),
+    ("white_touchtone_telephone", "\u{1f57e}"),
+    ("white_up_pointing_backhand_index", "\u{1f446}"),
+    ("wilted_flower", "\u{1f940}"),
+    ("wind_blowing_face", "\u{1f32c}\u{fe0f}"),
+    ("wind_chime", "\u{1f390}"),
+    ("window", "\u{1fa9f}"),
+    ("wine_glass", "\u{1f377}"),
+    ("wink", "\u{1f609}"),
+    ("winking_face", "\u{1f609}"),
+    ("wired_keyboard", "\u{1f5ae}"),
+    ("wolf", "\u{1f43a}"),
+    ("wolf_face", "\u{1f43a}"),
+    ("woman", "\u{1f469}"),
+    ("woman_with_bunny_ears", "\u{1f46f}"),
+    ("womans_boots", "\u{1f462}"),
+    ("womans_clothes", "\u{1f45a}"),
+    ("womans_hat", "\u{1f452}"),
+    ("womans_sandal", "\u{1f461}"),
+    ("womens", "\u{1f6ba}"),
+    ("wood", "\u{1fab5}"),
+    ("world_map", "\u{1f5fa}\u{fe0f}"),
+    ("worm", "\u{1fab1}"),
+    ("worried", "\u{1f61f}"),
+    ("worried_face", "\u{1f61f}"),
+    ("wrapped_present", "\u{1f381}"),
+    ("wrench", "\u{1f527}"),
+    ("wrestlers", "\u{1f93c}"),
+    ("writing_hand", "\u{270d}\u{fe0f}"),
+    ("x", "\u{274c}"),
+    ("x_ray", "\u{1fa7b}"),
+    ("xiangqi_black_cannon", "\u{1fa6c}"),
+    ("xiangqi_black_chariot", "\u{1fa6b}"),
+    ("xiangqi_black_elephant", "\u{1fa69}"),
+    ("xiangqi_black_general", "\u{1fa67}"),
+    ("xiangqi_black_horse", "\u{1fa6a}"),
+    ("xiangqi_black_mandarin", "\u{1fa68}"),
+    ("xiangqi_black_soldier", "\u{1fa6d}"),
+    ("xiangqi_red_cannon", "\u{1fa65}"),
+    ("xiangqi_red_chariot", "\u{1fa64}"),
+    ("xiangqi_red_elephant", "\u{1fa62}"),
+    ("xiangqi_red_general", "\u{1fa60}"),
+    ("xiangqi_red_horse", "\u{1fa63}"),
+    ("xiangqi_red_mandarin", "\u{1fa61}"),
+    ("xiangqi_red_soldier", "\u{1fa66}"),
+    ("yawning_face", "\u{1f971}"),
+    ("yellow_heart", "\u{1f49b}"),
+    ("yo_yo", "\u{1fa80}"),
+    ("yum", "\u{1f60b}"),
+    ("zap", "\u{26a1}"),
+    ("zebra_face", "\u{1f993}"),
+    ("zipper_mouth_face", "\u{1f910}"),
+    ("zombie", "\u{1f9df}"),
+    ("zzz", "\u{1f4a4}"),
+];
@@ -0,0 +1,536 @@
+use std::borrow::Cow;
+
+use pulldown_cmark::{Event, Tag};
+
+use utils;
+use super::emoji::EMOJI;
+
+
+// The markdown extensions of the `[markdown]` table that pulldown-cmark does not have, applied on
+// the events of a chapter before they are rendered.
+
+/// Puts the text between two `~~` in a `<del>`, both have to be in the same paragraph, heading,
+/// list item or table cell
+pub fn strikethrough<'a>(events: Vec<Event<'a>>) -> Vec<Event<'a>> {
+    let mut result = Vec::with_capacity(events.len());
+    // The `~~` waiting for the one closing it
+    let mut open: Option<usize> = None;
+    let mut in_code = false;
+
+    for event in events {
+        match event {
+            Event::Start(Tag::CodeBlock(_)) |
+            Event::Start(Tag::Code) => in_code = true,
+            Event::End(Tag::CodeBlock(_)) |
+            Event::End(Tag::Code) => in_code = false,
+            Event::Text(ref text) if !in_code && text.contains("~~") => {
+                for (i, part) in text.split("~~").enumerate() {
+                    if i > 0 {
+                        match open.take() {
+                            Some(start) => {
+                                result[start] = Event::InlineHtml(Cow::Borrowed("<del>"));
+                                result.push(Event::InlineHtml(Cow::Borrowed("</del>")));
+                            },
+                            None => {
+                                open = Some(result.len());
+                                result.push(Event::Text(Cow::Borrowed("~~")));
+                            },
+                        }
+                    }
+                    if !part.is_empty() {
+                        result.push(Event::Text(Cow::Owned(part.to_owned())));
+                    }
+                }
+                continue;
+            },
+            Event::End(Tag::Emphasis) |
+            Event::End(Tag::Strong) |
+            Event::End(Tag::Link(..)) => {},
+            // A `~~` left open at the end of a block stays as it is
+            Event::End(_) => open = None,
+            _ => {},
+        }
+        result.push(event);
+    }
+
+    result
+}
+
+/// Links the urls starting with `http://`, `https://` or `www.` written in the text, outside of code
+/// and links
+pub fn autolinks<'a>(events: Vec<Event<'a>>) -> Vec<Event<'a>> {
+    let mut result = Vec::with_capacity(events.len());
+    let mut in_code = false;
+    let mut in_link = false;
+    // pulldown-cmark splits the text at the characters that could start an emphasis, like the `_` of urls
+    let mut text = String::new();
+
+    for event in events {
+        match event {
+            Event::Text(ref t) if !in_code && !in_link => {
+                text.push_str(t);
+                continue;
+            },
+            _ if !text.is_empty() => {
+                link_urls(&text, &mut result);
+                text.clear();
+            },
+            _ => {},
+        }
+        match event {
+            Event::Start(Tag::CodeBlock(_)) |
+            Event::Start(Tag::Code) => in_code = true,
+            Event::End(Tag::CodeBlock(_)) |
+            Event::End(Tag::Code) => in_code = false,
+            Event::Start(Tag::Link(..)) => in_link = true,
+            Event::End(Tag::Link(..)) => in_link = false,
+            _ => {},
+        }
+        result.push(event);
+    }
+    if !text.is_empty() {
+        link_urls(&text, &mut result);
+    }
+
+    result
+}
+
+fn link_urls<'a>(text: &str, events: &mut Vec<Event<'a>>) {
+    let mut rest = text;
+
+    while let Some((start, len)) = find_url(rest) {
+        if start > 0 {
+            events.push(Event::Text(Cow::Owned(rest[..start].to_owned())));
+        }
+        let url = &rest[start..start + len];
+        let dest = if url.starts_with("www.") { format!("http://{}", url) } else { url.to_owned() };
+        events.push(Event::Start(Tag::Link(Cow::Owned(dest.clone()), Cow::Borrowed(""))));
+        events.push(Event::Text(Cow::Owned(url.to_owned())));
+        events.push(Event::End(Tag::Link(Cow::Owned(dest), Cow::Borrowed(""))));
+        rest = &rest[start + len..];
+    }
+
+    if !rest.is_empty() {
+        events.push(Event::Text(Cow::Owned(rest.to_owned())));
+    }
+}
+
+// The start and the length of the first url of the text, at the start of a word
+fn find_url(text: &str) -> Option<(usize, usize)> {
+    let mut from = 0;
+    loop {
+        let start = ["http://", "https://", "www."]
+            .iter()
+            .filter_map(|prefix| text[from..].find(prefix).map(|i| (from + i, prefix.len())))
+            .min();
+        let (start, prefix) = start?;
+        from = start + prefix;
+
+        match text[..start].chars().last() {
+            Some(c) if !c.is_whitespace() && !"(*_".contains(c) => continue,
+            _ => {},
+        }
+
+        let end = text[start..].find(|c: char| c.is_whitespace() || c == '<').map_or(text.len(), |i| start + i);
+        let mut url = &text[start..end];
+        // Punctuation at the end is part of the sentence, and so is a closing parenthesis without an opening
+        // one in the url
+        loop {
+            let trimmed = url.trim_end_matches(['.', ',', ':', ';', '!', '?', '\'', '"', '*', '_']);
+            let trimmed = if trimmed.ends_with(')') && trimmed.matches('(').count() < trimmed.matches(')').count() {
+                &trimmed[..trimmed.len() - 1]
+            } else {
+                trimmed
+            };
+            if trimmed.len() == url.len() {
+                break;
+            }
+            url = trimmed;
+        }
+
+        if url.len() > prefix {
+            return Some((start, url.len()));
+        }
+    }
+}
+
+/// Replaces the straight quotes by curly quotes, `--` by an en dash, `---` by an em dash and `...`
+/// by an ellipsis, except in code
+pub fn smart_punctuation<'a>(events: Vec<Event<'a>>) -> Vec<Event<'a>> {
+    let mut in_code = false;
+    // Last character of the text before, to know if a quote opens or closes
+    let mut previous: Option<char> = None;
+
+    events.into_iter()
+        .map(|event| {
+            match event {
+                Event::Start(Tag::CodeBlock(_)) |
+                Event::Start(Tag::Code) => in_code = true,
+                Event::End(Tag::CodeBlock(_)) => in_code = false,
+                Event::End(Tag::Code) => {
+                    in_code = false;
+                    previous = Some('`');
+                },
+                Event::Text(text) => {
+                    if in_code {
+                        return Event::Text(text);
+                    }
+                    return Event::Text(Cow::Owned(smarten(&text, &mut previous)));
+                },
+                Event::SoftBreak |
+                Event::HardBreak => previous = Some(' '),
+                Event::Start(Tag::Emphasis) |
+                Event::Start(Tag::Strong) |
+                Event::Start(Tag::Link(..)) |
+                Event::End(Tag::Emphasis) |
+                Event::End(Tag::Strong) |
+                Event::End(Tag::Link(..)) => {},
+                Event::Start(_) |
+                Event::End(_) => previous = None,
+                _ => {},
+            }
+            event
+        })
+        .collect()
+}
+
+// The characters after which a quote opens
+const OPENING: &'static str = "([{-\u{2013}\u{2014}\u{201c}\u{2018}";
+
+fn smarten(text: &str, previous: &mut Option<char>) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let mut s = String::with_capacity(text.len());
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let run = chars[i..].iter().take_while(|&&r| r == c).count();
+        let opening = match *previous {
+            Some(p) => p.is_whitespace() || OPENING.contains(p),
+            None => true,
+        };
+
+        match c {
+            '-' if run == 2 => s.push('\u{2013}'),
+            '-' if run == 3 => s.push('\u{2014}'),
+            '.' if run == 3 => s.push('\u{2026}'),
+            '"' => s.push(if opening { '\u{201c}' } else { '\u{201d}' }),
+            '\'' => s.push(if opening { '\u{2018}' } else { '\u{2019}' }),
+            _ => {
+                s.extend(&chars[i..i + run]);
+            },
+        }
+
+        let consumed = if c == '"' || c == '\'' { 1 } else { run };
+        *previous = s.chars().last();
+        i += consumed;
+    }
+
+    s
+}
+
+/// Replaces the shortcodes like `:crab:` by their emoji, except in code
+pub fn emoji<'a>(events: Vec<Event<'a>>) -> Vec<Event<'a>> {
+    let mut in_code = false;
+
+    events.into_iter()
+        .map(|event| {
+            match event {
+                Event::Start(Tag::CodeBlock(_)) |
+                Event::Start(Tag::Code) => in_code = true,
+                Event::End(Tag::CodeBlock(_)) |
+                Event::End(Tag::Code) => in_code = false,
+                Event::Text(ref text) if !in_code && text.contains(':') => {
+                    return Event::Text(Cow::Owned(replace_shortcodes(text)));
+                },
+                _ => {},
+            }
+            event
+        })
+        .collect()
+}
+
+fn replace_shortcodes(text: &str) -> String {
+    let mut s = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find(':') {
+        s.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let name = rest[1..].find(':').map(|end| &rest[1..end + 1]);
+        let emoji = name.filter(|name| {
+                !name.is_empty() &&
+                name.bytes().all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b"_+-".contains(&b))
+            })
+            .and_then(|name| EMOJI.binary_search_by(|&(n, _)| n.cmp(name)).ok().map(|i| (name, EMOJI[i].1)));
+
+        match emoji {
+            Some((name, emoji)) => {
+                s.push_str(emoji);
+                rest = &rest[name.len() + 2..];
+            },
+            // The colon closing this one can still start a shortcode
+            None => {
+                s.push(':');
+                rest = &rest[1..];
+            },
+        }
+    }
+    s.push_str(rest);
+
+    s
+}
+
+/// Puts the paragraphs with only an image in a `<figure>`, with its title as `<figcaption>`, e.g.
+/// `![alt](img.png "Caption")`, or the emphasis on the line after the image, e.g. `*Caption*`, which can have
+/// markdown in it. The figures get the ids `image-1`, `image-2`... in the order of the chapter, so that a theme
+/// can number them.
+pub fn image_captions<'a>(events: Vec<Event<'a>>) -> Vec<Event<'a>> {
+    let mut result = Vec::with_capacity(events.len());
+    let mut count = 0;
+    let mut events = events.into_iter();
+    while let Some(event) = events.next() {
+        match event {
+            Event::Start(Tag::Paragraph) => {},
+            _ => {
+                result.push(event);
+                continue;
+            },
+        }
+
+        // The paragraphs are not nested
+        let mut paragraph = vec![];
+        for event in events.by_ref() {
+            match event {
+                Event::End(Tag::Paragraph) => break,
+                _ => paragraph.push(event),
+            }
+        }
+
+        match image_figure(paragraph) {
+            Ok((image, caption)) => {
+                count += 1;
+                result.push(Event::Html(Cow::Owned(format!("<figure class=\"image-figure\" id=\"image-{}\">",
+                                                           count))));
+                result.extend(image);
+                result.push(Event::Html(Cow::Borrowed("<figcaption>")));
+                result.extend(caption);
+                result.push(Event::Html(Cow::Borrowed("</figcaption></figure>\n")));
+            },
+            Err(paragraph) => {
+                result.push(Event::Start(Tag::Paragraph));
+                result.extend(paragraph);
+                result.push(Event::End(Tag::Paragraph));
+            },
+        }
+    }
+
+    result
+}
+
+// The events of the image and of the caption of a paragraph that is a figure, or the events of the paragraph
+// when it is not one. The title of the image is its caption when there is no emphasis after it, it is dropped
+// from the image either way.
+fn image_figure<'a>(mut paragraph: Vec<Event<'a>>) -> Result<(Vec<Event<'a>>, Vec<Event<'a>>), Vec<Event<'a>>> {
+    let title = match paragraph.first() {
+        Some(&Event::Start(Tag::Image(_, ref title))) => title.to_string(),
+        _ => return Err(paragraph),
+    };
+    let image_end = match paragraph.iter().position(|e| matches!(*e, Event::End(Tag::Image(..)))) {
+        Some(end) => end,
+        None => return Err(paragraph),
+    };
+
+    let rest = &paragraph[image_end + 1..];
+    let caption_events = if rest.is_empty() {
+        if title.trim().is_empty() {
+            return Err(paragraph);
+        }
+        0
+    } else {
+        // A line break, then an emphasis up to the end of the paragraph, `*a* b *c*` is not one emphasis
+        let break_first = matches!(rest[0], Event::SoftBreak | Event::HardBreak);
+        let emphasis = rest.len() > 2 && matches!(rest[1], Event::Start(Tag::Emphasis)) &&
+                       matches!(rest[rest.len() - 1], Event::End(Tag::Emphasis)) &&
+                       !rest[2..rest.len() - 1].iter().any(|e| matches!(*e, Event::End(Tag::Emphasis)));
+        if !break_first || !emphasis {
+            return Err(paragraph);
+        }
+        rest.len()
+    };
+
+    let mut caption: Vec<Event<'a>> = paragraph.split_off(image_end + 1);
+    if caption_events == 0 {
+        caption.push(Event::Text(Cow::Owned(title)));
+    } else {
+        caption.pop();
+        caption.drain(..2);
+    }
+    let mut image = paragraph;
+    for event in image.iter_mut() {
+        match *event {
+            Event::Start(Tag::Image(_, ref mut title)) |
+            Event::End(Tag::Image(_, ref mut title)) => *title = Cow::Borrowed(""),
+            _ => {},
+        }
+    }
+    Ok((image, caption))
+}
+
+/// Sets the id and the classes of the headings ending with `{#id .class}`
+pub fn heading_attributes<'a>(mut events: Vec<Event<'a>>) -> Vec<Event<'a>> {
+    // Where the heading starts, the numbered headings start with the html of their number
+    let mut start: Option<usize> = None;
+
+    for i in 0..events.len() {
+        let level = match events[i] {
+            Event::Start(Tag::Header(_)) => {
+                start = Some(i);
+                continue;
+            },
+            Event::Html(ref html) if is_heading_tag(html) => {
+                start = Some(i);
+                continue;
+            },
+            Event::End(Tag::Header(level)) => level,
+            _ => continue,
+        };
+
+        let start = match start.take() {
+            Some(start) if start + 1 < i => start,
+            _ => continue,
+        };
+        let attributes = match events[i - 1] {
+            Event::Text(ref text) => parse_attributes(text),
+            _ => None,
+        };
+        let (text, attributes) = match attributes {
+            Some(found) => found,
+            None => continue,
+        };
+
+        let tag = format!("<h{}{}>", level, attributes);
+        events[i - 1] = Event::Text(Cow::Owned(text));
+        events[start] = match events[start] {
+            Event::Html(ref html) => Event::Html(Cow::Owned(tag + &html[4..])),
+            _ => Event::Html(Cow::Owned(tag)),
+        };
+    }
+
+    events
+}
+
+// `<h2>`, as written by `HeadingNumbers`
+fn is_heading_tag(html: &str) -> bool {
+    let bytes = html.as_bytes();
+    bytes.len() >= 4 && bytes[0] == b'<' && bytes[1] == b'h' && bytes[2].is_ascii_digit() && bytes[3] == b'>'
+}
+
+// Splits the `{#id .class}` at the end of the text of a heading, returns the text without it and the
+// html attributes
+fn parse_attributes(text: &str) -> Option<(String, String)> {
+    let trimmed = text.trim_end();
+    if !trimmed.ends_with('}') {
+        return None;
+    }
+    let open = trimmed.rfind('{')?;
+
+    let mut id = None;
+    let mut classes = vec![];
+    for token in trimmed[open + 1..trimmed.len() - 1].split_whitespace() {
+        if token.len() > 1 && token.starts_with('#') {
+            id = Some(&token[1..]);
+        } else if token.len() > 1 && token.starts_with('.') {
+            classes.push(&token[1..]);
+        } else {
+            return None;
+        }
+    }
+    if id.is_none() && classes.is_empty() {
+        return None;
+    }
+
+    let mut attributes = String::new();
+    if let Some(id) = id {
+        attributes.push_str(&format!(" id=\"{}\"", utils::escape_html(id)));
+    }
+    if !classes.is_empty() {
+        attributes.push_str(&format!(" class=\"{}\"", utils::escape_html(&classes.join(" "))));
+    }
+
+    Some((trimmed[..open].trim_end().to_owned(), attributes))
+}
+
+
+// ---------------------------------------------------------------------------------------------------------------
+//      Tests
+//
+
+#[cfg(test)]
+fn render(markdown: &str, pass: fn(Vec<Event>) -> Vec<Event>) -> String {
+    use pulldown_cmark::{Parser, html};
+
+    let mut s = String::new();
+    html::push_html(&mut s, pass(Parser::new(markdown).collect()).into_iter());
+    s
+}
+
+#[test]
+fn test_strikethrough() {
+    assert_eq!(render("a ~~b *c* d~~ e", strikethrough), "<p>a <del>b <em>c</em> d</del> e</p>\n");
+    assert_eq!(render("~~a~~~~b~~", strikethrough), "<p><del>a</del><del>b</del></p>\n");
+    assert_eq!(render("a ~~b\n\nc~~ `~~d~~`", strikethrough), "<p>a ~~b</p>\n<p>c~~ <code>~~d~~</code></p>\n");
+}
+
+#[test]
+fn test_autolinks() {
+    assert_eq!(render("See https://example.com/a_(b), or (www.rust-lang.org).", autolinks),
+               "<p>See <a href=\"https://example.com/a_(b)\">https://example.com/a_(b)</a>, or \
+                (<a href=\"http://www.rust-lang.org\">www.rust-lang.org</a>).</p>\n");
+    assert_eq!(render("[https://a.com](https://a.com) `http://b.com` xhttp://c.com http://", autolinks),
+               "<p><a href=\"https://a.com\">https://a.com</a> <code>http://b.com</code> xhttp://c.com http://</p>\n");
+}
+
+#[test]
+fn test_smart_punctuation() {
+    assert_eq!(render("\"It's\" -- 'a' --- b... *\"c\"*", smart_punctuation),
+               "<p>\u{201c}It\u{2019}s\u{201d} \u{2013} \u{2018}a\u{2019} \u{2014} b\u{2026} \
+                <em>\u{201c}c\u{201d}</em></p>\n");
+    assert_eq!(render("`\"a\" -- b`\n\n    'c'", smart_punctuation),
+               "<p><code>&quot;a&quot; -- b</code></p>\n<pre><code>'c'</code></pre>\n");
+}
+
+#[test]
+fn test_emoji() {
+    assert_eq!(render(":crab: :+1::tada: 10:30:00 :not_an_emoji: `:crab:`", emoji),
+               "<p>\u{1f980} \u{1f44d}\u{1f389} 10:30:00 :not_an_emoji: <code>:crab:</code></p>\n");
+    assert_eq!(render("a: :heart:", emoji), "<p>a: \u{2764}\u{fe0f}</p>\n");
+    assert!(EMOJI.windows(2).all(|w| w[0].0 < w[1].0));
+}
+
+#[test]
+fn test_heading_attributes() {
+    assert_eq!(render("# Title {#intro .wide .dark}\n\n## `code` {#code}", heading_attributes),
+               "<h1 id=\"intro\" class=\"wide dark\">Title</h1>\n<h2 id=\"code\"><code>code</code></h2>\n");
+    assert_eq!(render("# Set {a, b}", heading_attributes), "<h1>Set {a, b}</h1>\n");
+
+    fn numbered(events: Vec<Event>) -> Vec<Event> {
+        let mut numbers = super::headings::HeadingNumbers::new("1.");
+        heading_attributes(events.into_iter().map(|e| numbers.number_event(e)).collect())
+    }
+    assert_eq!(render("## Usage {.wide}", numbered),
+               "<h2 class=\"wide\"><span class=\"heading-number\">1.1</span> Usage</h2>\n");
+}
+
+#[test]
+fn test_image_captions() {
+    assert_eq!(render("![Logo](logo.png \"The <logo>\")\n\nText ![a](a.png \"b\")", image_captions),
+               "<figure class=\"image-figure\" id=\"image-1\"><img src=\"logo.png\" alt=\"Logo\" />\
+                <figcaption>The &lt;logo&gt;</figcaption></figure>\n\
+                <p>Text <img src=\"a.png\" alt=\"a\" title=\"b\" /></p>\n");
+    assert_eq!(render("![Menu](menu.png \"Ignored\")\n*The menu of `mdbook`*\n\n![](x.png)\n*a* b *c*", image_captions),
+               "<figure class=\"image-figure\" id=\"image-1\"><img src=\"menu.png\" alt=\"Menu\" />\
+                <figcaption>The menu of <code>mdbook</code></figcaption></figure>\n\
+                <p><img src=\"x.png\" alt=\"\" />\n<em>a</em> b <em>c</em></p>\n");
+}
@@ -0,0 +1,148 @@
+use book::htmlconfig::ExternalLinksConfig;
+
+
+/// Icon appended to the external links with `icon = true`
+const ICON: &'static str = "<i class=\"fa fa-external-link external-link-icon\" aria-hidden=\"true\"></i>";
+
+
+/// Changes the links of the rendered html of a chapter to other sites, the `http`, `https` and
+/// protocol-relative urls: they open in a new tab with `rel="noopener"` and get an icon after
+/// their text, depending on the config. The links to the exempted domains and their subdomains,
+/// and to the host of `site_url`, are left as they are.
+pub fn mark_external_links(html: &str, config: &ExternalLinksConfig, site_url: Option<&str>) -> String {
+    if !config.new_tab && !config.icon {
+        return html.to_owned();
+    }
+
+    let own_host = site_url.and_then(host);
+    let mut s = String::with_capacity(html.len());
+    let mut rest = html;
+
+    while let Some(start) = rest.find("<a ") {
+        s.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let end = match tag_end(rest) {
+            Some(end) => end,
+            None => break,
+        };
+        let tag = &rest[..end];
+        rest = &rest[end..];
+
+        let external = match (attribute(tag, "href").and_then(host), own_host.as_ref()) {
+            (Some(ref link_host), Some(own)) if link_host == own => false,
+            (Some(link_host), _) => !config.exempt_domains.iter().any(|d| in_domain(&link_host, d)),
+            (None, _) => false,
+        };
+        if !external {
+            s.push_str(tag);
+            continue;
+        }
+
+        // Before the `>`
+        s.push_str(&tag[..tag.len() - 1]);
+        if config.new_tab {
+            if attribute(tag, "target").is_none() {
+                s.push_str(" target=\"_blank\"");
+            }
+            if attribute(tag, "rel").is_none() {
+                s.push_str(" rel=\"noopener\"");
+            }
+        }
+        s.push('>');
+
+        if let Some(close) = rest.find("</a>").filter(|_| config.icon) {
+            s.push_str(&rest[..close]);
+            s.push_str(ICON);
+            rest = &rest[close..];
+        }
+    }
+    s.push_str(rest);
+
+    s
+}
+
+/// Length of the tag at the start of `html`, up to its `>` outside of the quoted values
+pub fn tag_end(html: &str) -> Option<usize> {
+    let mut quote = None;
+    for (i, c) in html.char_indices() {
+        match (quote, c) {
+            (None, '"') | (None, '\'') => quote = Some(c),
+            (Some(q), _) if q == c => quote = None,
+            (None, '>') => return Some(i + 1),
+            _ => {},
+        }
+    }
+    None
+}
+
+/// Whether the tag closes the element and the lowercase name of the element
+pub fn tag_name(tag: &str) -> (bool, String) {
+    let tag = tag.trim_start_matches('<');
+    let closing = tag.starts_with('/');
+    let name = tag.trim_start_matches('/')
+        .chars()
+        .take_while(|c| c.is_ascii_alphanumeric())
+        .collect::<String>()
+        .to_ascii_lowercase();
+    (closing, name)
+}
+
+/// Value of the quoted attribute `name` of a tag
+pub fn attribute<'a>(tag: &'a str, name: &str) -> Option<&'a str> {
+    ["\"", "'"].iter().filter_map(|quote| {
+        let prefix = format!(" {}={}", name, quote);
+        tag.find(&prefix).and_then(|start| {
+            let value = &tag[start + prefix.len()..];
+            value.find(quote).map(|end| &value[..end])
+        })
+    }).next()
+}
+
+// Host of an absolute or protocol-relative url, in lowercase
+fn host(url: &str) -> Option<String> {
+    let lowercase = url.trim().to_lowercase();
+    let after_scheme = lowercase.strip_prefix("http:")
+        .or_else(|| lowercase.strip_prefix("https:"))
+        .unwrap_or(&lowercase)
+        .strip_prefix("//")?;
+
+    let authority = after_scheme.split(['/', '?', '#']).next().unwrap_or_default();
+    let host = authority.rsplit('@').next().unwrap_or_default().split(':').next().unwrap_or_default();
+    if host.is_empty() { None } else { Some(host.to_owned()) }
+}
+
+// Whether `host` is `domain` or one of its subdomains
+fn in_domain(host: &str, domain: &str) -> bool {
+    let domain = domain.trim_matches('.').to_lowercase();
+    host == domain || host.ends_with(&format!(".{}", domain))
+}
+
+
+// ---------------------------------------------------------------------------------
+//      Tests
+//
+
+#[test]
+fn test_mark_external_links() {
+    let mut config = ExternalLinksConfig::new();
+    config.new_tab = true;
+    config.icon = true;
+    config.exempt_domains = vec!["rust-lang.org".to_owned()];
+
+    let html = "<p><a href=\"https://github.com/azerupi/mdBook\" title=\"a > b\">mdBook</a> \
+                <a href=\"cli/init.html\">init</a> <a href=\"https://doc.rust-lang.org/std/\">std</a> \
+                <a href=\"//docs.example.com/guide\">guide</a> <a href='http://example.com' rel=\"nofollow\">\
+                example</a> <a href=\"mailto:me@example.com\">me</a></p>";
+    assert_eq!(mark_external_links(html, &config, Some("https://docs.example.com/")),
+               "<p><a href=\"https://github.com/azerupi/mdBook\" title=\"a > b\" target=\"_blank\" \
+                rel=\"noopener\">mdBook<i class=\"fa fa-external-link external-link-icon\" aria-hidden=\"true\"></i>\
+                </a> <a href=\"cli/init.html\">init</a> <a href=\"https://doc.rust-lang.org/std/\">std</a> \
+                <a href=\"//docs.example.com/guide\">guide</a> <a href='http://example.com' rel=\"nofollow\" \
+                target=\"_blank\">example<i class=\"fa fa-external-link external-link-icon\" aria-hidden=\"true\">\
+                </i></a> <a href=\"mailto:me@example.com\">me</a></p>");
+
+    assert_eq!(host("HTTPS://user@Example.com:8080/path"), Some("example.com".to_owned()));
+    assert!(in_domain("doc.rust-lang.org", "rust-lang.org"));
+    assert!(!in_domain("evilrust-lang.org", "rust-lang.org"));
+    assert_eq!(mark_external_links(html, &ExternalLinksConfig::new(), None), html);
+}
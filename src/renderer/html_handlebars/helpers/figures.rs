@@ -0,0 +1,135 @@
+use std::path::Path;
+use std::collections::HashMap;
+
+use utils;
+use renderer::Warning;
+use super::directives::{replace_directives, find_directives};
+
+
+/// Numbered figures and tables. A caption is written below a figure or above a table with
+/// `{{#figure label Caption text}}` or `{{#table label Caption text}}` on its own line, and
+/// `{{#fig label}}` references it with a link showing its number, e.g. "Figure 3.2".
+///
+/// Figures and tables are numbered separately, per chapter, after the number of the chapter.
+pub struct Figures {
+    labels: HashMap<String, Figure>,
+}
+
+#[derive(Clone)]
+struct Figure {
+    // "Figure 3.2"
+    number: String,
+    link: String,
+}
+
+const KINDS: [(&'static str, &'static str); 2] = [("figure", "Figure"), ("table", "Table")];
+
+impl Figures {
+    pub fn new() -> Self {
+        Figures { labels: HashMap::new() }
+    }
+
+    /// Numbers the figures and tables of a chapter, `section` is the number of the chapter
+    pub fn add_chapter(&mut self, path: &Path, section: Option<&str>, markdown: &str, warnings: &mut Vec<Warning>) {
+        let page = utils::fs::path_to_link(path);
+
+        for &(kind, name) in KINDS.iter() {
+            // The captions without a label are left as they are and not numbered
+            let mut count = 0;
+            for arguments in &find_directives(markdown, kind) {
+                let label = arguments.split_whitespace().next().unwrap_or_default();
+                if label.is_empty() {
+                    continue;
+                }
+                count += 1;
+
+                let number = match section {
+                    Some(section) => format!("{} {}.{}", name, section.trim_end_matches('.'), count),
+                    None => format!("{} {}", name, count),
+                };
+                let figure = Figure {
+                    number: number,
+                    link: format!("{}#{}-{}", page, kind, label),
+                };
+
+                if self.labels.insert(label.to_owned(), figure).is_some() {
+                    warnings.push(Warning::new(path, format!("the label `{}` is used by several figures or tables", label)));
+                }
+            }
+        }
+    }
+
+    /// Replaces the captions and the references in the markdown of a chapter
+    pub fn render(&self, s: &str, chapter: &Path, warnings: &mut Vec<Warning>) -> String {
+        let mut s = s.to_owned();
+
+        for &(kind, _) in KINDS.iter() {
+            s = replace_directives(&s, kind, |arguments| {
+                let mut parts = arguments.splitn(2, char::is_whitespace);
+                let label = parts.next().unwrap_or_default();
+                let caption = parts.next().unwrap_or_default().trim();
+
+                self.labels.get(label).map(|figure| {
+                    format!("<p class=\"caption {kind}-caption\" id=\"{kind}-{label}\"><strong>{number}:</strong> \
+                             {caption}</p>",
+                            kind = kind,
+                            label = utils::escape_html(label),
+                            number = figure.number,
+                            caption = utils::escape_html(caption))
+                })
+            });
+        }
+
+        replace_directives(&s, "fig", |label| {
+            match self.labels.get(label) {
+                Some(figure) => Some(format!("[{}]({})", figure.number, figure.link)),
+                None => {
+                    warnings.push(Warning::new(chapter, format!("no figure or table with the label `{}` for {{{{#fig}}}}", label)));
+                    None
+                },
+            }
+        })
+    }
+}
+
+
+// ---------------------------------------------------------------------------------
+//      Tests
+//
+
+#[test]
+fn test_figures_numbering() {
+    let mut figures = Figures::new();
+    let markdown = "![](a.png)\n\n{{#figure sidebar The sidebar}}\n\n{{#table keys Keys}}\n\n{{#figure menu The <menu>}}";
+    figures.add_chapter(Path::new("format/theme.md"), Some("3.4."), markdown, &mut vec![]);
+
+    assert_eq!(figures.render(markdown, Path::new("format/theme.md"), &mut vec![]),
+               "![](a.png)\n\n\
+                <p class=\"caption figure-caption\" id=\"figure-sidebar\"><strong>Figure 3.4.1:</strong> The sidebar</p>\n\n\
+                <p class=\"caption table-caption\" id=\"table-keys\"><strong>Table 3.4.1:</strong> Keys</p>\n\n\
+                <p class=\"caption figure-caption\" id=\"figure-menu\"><strong>Figure 3.4.2:</strong> The &lt;menu&gt;</p>");
+}
+
+#[test]
+fn test_figures_references() {
+    let mut figures = Figures::new();
+    let mut warnings = vec![];
+    figures.add_chapter(Path::new("intro.md"), None, "{{#figure logo The logo}}", &mut warnings);
+    figures.add_chapter(Path::new("other.md"), None, "{{#table logo The logo}}", &mut warnings);
+
+    assert_eq!(figures.render("See {{#fig logo}} and {{#fig missing}}.", Path::new("README.md"), &mut warnings),
+               "See [Table 1](other.html#table-logo) and {{#fig missing}}.");
+    assert_eq!(warnings,
+               vec![Warning::new(Path::new("other.md"), "the label `logo` is used by several figures or tables"),
+                    Warning::new(Path::new("README.md"), "no figure or table with the label `missing` for {{#fig}}")]);
+}
+
+#[test]
+fn test_figures_without_label() {
+    let mut figures = Figures::new();
+    let markdown = "{{#figure first The first}}\n\n{{#figure}}\n\n{{#figure third The third}}";
+    figures.add_chapter(Path::new("intro.md"), Some("3."), markdown, &mut vec![]);
+
+    assert_eq!(figures.render("{{#fig first}} and {{#fig third}}", Path::new("intro.md"), &mut vec![]),
+               "[Figure 3.1](intro.html#figure-first) and [Figure 3.2](intro.html#figure-third)");
+}
@@ -0,0 +1,87 @@
+use std::borrow::Cow;
+
+use pulldown_cmark::{Event, Tag};
+
+
+/// Prefixes the `h2` and `h3` headings of a chapter with hierarchical numbers derived from the
+/// number of the chapter, e.g. `2.4.1`. The number is put in a `<span class="heading-number">`
+/// so that `book.js` can leave it out of the heading anchors. The `h3` headings before the first
+/// `h2` are numbered like `h2` headings, without the missing level.
+pub struct HeadingNumbers {
+    section: String,
+    h2: usize,
+    h3: usize,
+    // Whether there was an `h2`, the `h3` headings after it are numbered inside of it
+    after_h2: bool,
+}
+
+impl HeadingNumbers {
+    /// `section` is the number of the chapter, e.g. `"2.4."`
+    pub fn new(section: &str) -> Self {
+        HeadingNumbers {
+            section: section.trim_end_matches('.').to_owned(),
+            h2: 0,
+            h3: 0,
+            after_h2: false,
+        }
+    }
+
+    pub fn number_event<'a>(&mut self, event: Event<'a>) -> Event<'a> {
+        let (level, number) = match event {
+            Event::Start(Tag::Header(2)) => {
+                self.h2 += 1;
+                self.h3 = 0;
+                self.after_h2 = true;
+                (2, format!("{}.{}", self.section, self.h2))
+            },
+            Event::Start(Tag::Header(3)) if !self.after_h2 => {
+                self.h2 += 1;
+                (3, format!("{}.{}", self.section, self.h2))
+            },
+            Event::Start(Tag::Header(3)) => {
+                self.h3 += 1;
+                (3, format!("{}.{}.{}", self.section, self.h2, self.h3))
+            },
+            event => return event,
+        };
+
+        Event::Html(Cow::Owned(format!("<h{}><span class=\"heading-number\">{}</span> ", level, number)))
+    }
+}
+
+
+// ---------------------------------------------------------------------------------
+//      Tests
+//
+
+#[test]
+fn test_heading_numbers() {
+    use pulldown_cmark::{Parser, html};
+
+    let mut numbers = HeadingNumbers::new("2.4.");
+    let mut s = String::new();
+    let markdown = "# Title\n\n## First\n\n### Sub\n\n### Sub\n\n## Second\n\n#### Deep";
+    html::push_html(&mut s, Parser::new(markdown).map(|e| numbers.number_event(e)));
+
+    assert_eq!(s,
+               "<h1>Title</h1>\n\
+                <h2><span class=\"heading-number\">2.4.1</span> First</h2>\n\
+                <h3><span class=\"heading-number\">2.4.1.1</span> Sub</h3>\n\
+                <h3><span class=\"heading-number\">2.4.1.2</span> Sub</h3>\n\
+                <h2><span class=\"heading-number\">2.4.2</span> Second</h2>\n\
+                <h4>Deep</h4>\n");
+}
+
+#[test]
+fn test_heading_numbers_without_h2() {
+    use pulldown_cmark::{Parser, html};
+
+    let mut numbers = HeadingNumbers::new("3.");
+    let mut s = String::new();
+    html::push_html(&mut s, Parser::new("### Intro\n\n## First\n\n### Sub").map(|e| numbers.number_event(e)));
+
+    assert_eq!(s,
+               "<h3><span class=\"heading-number\">3.1</span> Intro</h3>\n\
+                <h2><span class=\"heading-number\">3.2</span> First</h2>\n\
+                <h3><span class=\"heading-number\">3.2.1</span> Sub</h3>\n");
+}
@@ -0,0 +1,125 @@
+use std::path::{Path, PathBuf};
+
+use renderer::Warning;
+use renderer::html_handlebars::helpers::directives::replace_all_directives;
+use utils;
+
+
+/// Replaces the `{{#include path}}` directives of a chapter by the content of the files, which can include other
+/// files too. The path is relative to the directory of the file containing the directive, or else to one of the
+/// `shared` directories, like a `common/` directory used by every book of a workspace.
+///
+/// Only the files of the source directory and of the shared directories can be included, and a file including
+/// itself, directly or not, is left out with a warning.
+pub fn render_includes(s: &str,
+                       file: &Path,
+                       src: &Path,
+                       shared: &[PathBuf],
+                       chapter: &Path,
+                       warnings: &mut Vec<Warning>)
+                       -> String {
+    let roots: Vec<PathBuf> = Some(src)
+        .into_iter()
+        .chain(shared.iter().map(|dir| dir.as_path()))
+        .filter_map(|dir| dir.canonicalize().ok())
+        .collect();
+    let mut stack = vec![file.canonicalize().unwrap_or_else(|_| file.to_owned())];
+
+    include_files(s, &roots, shared, &mut stack, chapter, warnings)
+}
+
+// `stack` holds the files being included, the last one contains `s`
+fn include_files(s: &str,
+                 roots: &[PathBuf],
+                 shared: &[PathBuf],
+                 stack: &mut Vec<PathBuf>,
+                 chapter: &Path,
+                 warnings: &mut Vec<Warning>)
+                 -> String {
+    let dir = stack.last().and_then(|file| file.parent()).map(|dir| dir.to_owned()).unwrap_or_default();
+
+    replace_all_directives(s, "include", |argument| {
+        let file = match Some(dir.join(argument))
+            .into_iter()
+            .chain(shared.iter().map(|dir| dir.join(argument)))
+            .filter_map(|path| path.canonicalize().ok())
+            .find(|path| path.is_file()) {
+            Some(file) => file,
+            None => {
+                warnings.push(Warning::new(chapter, format!("no file `{}` for {{{{#include}}}}", argument)));
+                return None;
+            },
+        };
+
+        if !roots.iter().any(|root| file.starts_with(root)) {
+            warnings.push(Warning::new(chapter,
+                                       format!("`{}` is outside of the source directory and of the shared includes, \
+                                                it is not included",
+                                               argument)));
+            return None;
+        }
+        if stack.contains(&file) {
+            warnings.push(Warning::new(chapter, format!("`{}` includes itself, the cycle is not followed", argument)));
+            return None;
+        }
+
+        let content = match utils::fs::file_to_string(&file) {
+            Ok(content) => content,
+            Err(e) => {
+                let message = format!("could not read `{}` for {{{{#include}}}}: {}", argument, e);
+                warnings.push(Warning::new(chapter, message));
+                return None;
+            },
+        };
+
+        stack.push(file);
+        let included = include_files(&content, roots, shared, stack, chapter, warnings);
+        stack.pop();
+        Some(included)
+    })
+}
+
+
+// ---------------------------------------------------------------------------------
+//      Tests
+//
+
+#[test]
+fn test_render_includes() {
+    extern crate tempdir;
+
+    use std::fs::{self, File};
+    use std::io::Write;
+
+    let tmp = tempdir::TempDir::new("mdbook").expect("Could not create a temp dir");
+    let src = tmp.path().join("book/src");
+    let common = tmp.path().join("common");
+    fs::create_dir_all(src.join("guide")).unwrap();
+    fs::create_dir_all(&common).unwrap();
+
+    let files = [(src.join("guide/intro.md"), "Intro {{#include notice.md}}"),
+                 (common.join("notice.md"), "[notice {{#include version.md}}]"),
+                 (src.join("guide/version.md"), "1.0"),
+                 (common.join("version.md"), "2.0"),
+                 (common.join("loop.md"), "loop {{#include loop.md}}"),
+                 (tmp.path().join("secret.md"), "secret")];
+    for &(ref path, content) in &files {
+        File::create(path).unwrap().write_all(content.as_bytes()).unwrap();
+    }
+
+    let chapter = Path::new("guide/intro.md");
+    let mut warnings = vec![];
+    let s = "{{#include version.md}} {{#include notice.md}} \\{{#include version.md}} {{#include missing.md}} \
+             {{#include loop.md}} {{#include ../../../secret.md}}";
+    let html = render_includes(s, &src.join(chapter), &src, &[common], chapter, &mut warnings);
+
+    assert_eq!(html,
+               "1.0 [notice 2.0] {{#include version.md}} {{#include missing.md}} loop {{#include loop.md}} \
+                {{#include ../../../secret.md}}");
+    assert_eq!(warnings,
+               vec![Warning::new(chapter, "no file `missing.md` for {{#include}}"),
+                    Warning::new(chapter, "`loop.md` includes itself, the cycle is not followed"),
+                    Warning::new(chapter,
+                                 "`../../../secret.md` is outside of the source directory and of the shared includes, \
+                                  it is not included")]);
+}
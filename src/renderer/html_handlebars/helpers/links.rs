@@ -0,0 +1,333 @@
+use std::path::{Path, PathBuf, Component};
+use std::collections::{HashMap, HashSet};
+
+use pulldown_cmark::{Parser, Event, Tag};
+
+use renderer::Warning;
+use renderer::html_handlebars::check::is_generated;
+use super::externals::{attribute, tag_end, tag_name};
+use super::sanitize::decode_entities;
+use utils;
+
+
+/// Rewrites the links to other `.md` files of the book to the `.html` files that are generated
+/// for them.
+///
+/// `chapter` is the path of the rendered chapter and `chapters` the paths of all the chapters in
+/// `SUMMARY.md`, both relative to the source directory. `mount` is the directory of the embedded book
+/// containing the chapter, its links and images are relative to it, or an empty path.
+pub fn rewrite_event<'a>(event: Event<'a>,
+                         chapter: &Path,
+                         mount: &Path,
+                         chapters: &HashSet<PathBuf>,
+                         warnings: &mut Vec<Warning>)
+                         -> Event<'a> {
+    match event {
+        Event::Start(Tag::Link(dest, title)) => {
+            match rewrite_link(&dest, chapter, mount, chapters, warnings) {
+                Some(rewritten) => Event::Start(Tag::Link(rewritten.into(), title)),
+                None => Event::Start(Tag::Link(dest, title)),
+            }
+        },
+        Event::Start(Tag::Image(dest, title)) => {
+            match mounted_link(&dest, mount) {
+                Some(rewritten) => Event::Start(Tag::Image(rewritten.into(), title)),
+                None => Event::Start(Tag::Image(dest, title)),
+            }
+        },
+        event => event,
+    }
+}
+
+/// Returns the new destination of a link pointing to a markdown file, or `None` if the link
+/// has to be left as it is.
+///
+/// Links are looked up relative to the chapter first and relative to the source directory, or the
+/// directory of the embedded book, second. The rewritten link is always relative to the root of the
+/// book because each page sets `<base href="{{ path_to_root }}">`.
+fn rewrite_link(dest: &str,
+                chapter: &Path,
+                mount: &Path,
+                chapters: &HashSet<PathBuf>,
+                warnings: &mut Vec<Warning>)
+                -> Option<String> {
+    if dest.contains("://") || dest.starts_with("mailto:") || dest.starts_with('/') {
+        return None;
+    }
+
+    let (target, fragment) = match dest.find('#') {
+        Some(i) => (&dest[..i], &dest[i..]),
+        None => (dest, ""),
+    };
+
+    if !target.ends_with(".md") {
+        return mounted_link(dest, mount);
+    }
+
+    let path = match linked_chapter(target, chapter, mount, chapters) {
+        Some(path) => path,
+        None => {
+            warnings.push(Warning::new(chapter,
+                                       format!("the link to `{}` does not point to a chapter of SUMMARY.md", dest)));
+            match chapter.parent().and_then(|dir| normalize(&dir.join(target))) {
+                Some(path) => path,
+                None => return Some(format!("{}{}", &target[..target.len() - 3], ".html") + fragment),
+            }
+        },
+    };
+
+    Some(utils::fs::path_to_link(&path) + fragment)
+}
+
+/// The chapter of `SUMMARY.md` a link to the markdown file `target` points to, relative to the chapter or else
+/// relative to the source directory, or the directory of the embedded book `mount`
+pub fn linked_chapter(target: &str, chapter: &Path, mount: &Path, chapters: &HashSet<PathBuf>) -> Option<PathBuf> {
+    let relative_to_chapter = chapter.parent().and_then(|dir| normalize(&dir.join(target)));
+    let relative_to_root = normalize(&mount.join(target));
+
+    relative_to_chapter.into_iter()
+        .chain(relative_to_root)
+        .find(|path| chapters.contains(path))
+}
+
+// A link of an embedded book relative to its root, from the root of the book embedding it
+fn mounted_link(dest: &str, mount: &Path) -> Option<String> {
+    if mount == Path::new("") || dest.is_empty() || dest.starts_with('#') || dest.contains("://") ||
+       dest.starts_with("mailto:") || dest.starts_with("data:") || dest.starts_with('/') {
+        return None;
+    }
+    Some(format!("{}/{}", utils::fs::path_to_url(mount), dest))
+}
+
+/// Reports the images and the other files a chapter points to that are not in the source directory `src`, so
+/// that the book is not published with broken images. These are the markdown images and the links to a file that
+/// is not a page, like `downloads/guide.pdf`, and the `src` of the `<img>`, `<video>`, `<audio>`, `<source>` and
+/// `<track>` tags and the `href` of the `<a>` tags of the html. Like the links, they are relative to the root of
+/// the book because of `<base href="{{ path_to_root }}">`.
+///
+/// `source` is the file of the chapter as it is written, the warnings give the line of the missing file in it
+/// when it is there and not in an included file.
+pub fn check_assets(markdown: &str, source: &str, src: &Path, chapter: &Path, warnings: &mut Vec<Warning>) {
+    let mut assets = vec![];
+    for event in Parser::new_ext(markdown, utils::markdown_options()) {
+        match event {
+            Event::Start(Tag::Image(dest, _)) => assets.push(("image", dest.into_owned())),
+            Event::Start(Tag::Link(dest, _)) if is_asset(&dest) => assets.push(("file", dest.into_owned())),
+            Event::Html(html) |
+            Event::InlineHtml(html) => html_assets(&html, &mut assets),
+            _ => {},
+        }
+    }
+
+    // Where to look for the next occurrence of a file in the source
+    let mut searched: HashMap<String, usize> = HashMap::new();
+    for (kind, dest) in assets {
+        if dest.contains("://") || dest.starts_with("//") || dest.starts_with("data:") || dest.starts_with('/') ||
+           dest.starts_with('#') || dest.starts_with("mailto:") || dest.is_empty() {
+            continue;
+        }
+        let target = dest.split(['#', '?']).next().unwrap_or_default();
+        let page = normalize(Path::new(target)).unwrap_or_default();
+        if src.join(target).is_file() || is_generated(&page) {
+            continue;
+        }
+
+        let from = searched.get(&dest).cloned().unwrap_or(0);
+        let line = find_path(&source[from..], &dest).map(|offset| {
+            searched.insert(dest.clone(), from + offset + dest.len());
+            source[..from + offset].matches('\n').count() + 1
+        });
+        let warning = Warning::new(chapter, format!("the {} `{}` does not exist", kind, dest));
+        warnings.push(match line {
+            Some(line) => warning.at_line(line),
+            None => warning,
+        });
+    }
+}
+
+// Offset of the path in the text, not as the end of a longer path like `images/logo.png` for `logo.png`
+fn find_path(text: &str, path: &str) -> Option<usize> {
+    text.match_indices(path)
+        .map(|(offset, _)| offset)
+        .find(|&offset| {
+            text[..offset].chars().next_back().is_none_or(|c| !c.is_alphanumeric() && !"/.-_".contains(c))
+        })
+}
+
+/// Whether a link points to a file that is not a page of the book, a file with an extension other than `.md`
+/// and `.html`
+pub fn is_asset(dest: &str) -> bool {
+    let target = dest.split(['#', '?']).next().unwrap_or_default();
+    match Path::new(target).extension().and_then(|ext| ext.to_str()) {
+        Some(ext) => !["md", "html", "htm"].contains(&&ext.to_ascii_lowercase()[..]),
+        None => false,
+    }
+}
+
+// The files of the tags of the html
+fn html_assets(html: &str, assets: &mut Vec<(&'static str, String)>) {
+    let mut i = 0;
+    while let Some(offset) = html[i..].find('<') {
+        let start = i + offset;
+        let end = match tag_end(&html[start..]) {
+            Some(end) => start + end,
+            None => break,
+        };
+        let tag = &html[start..end];
+        let (closing, name) = tag_name(tag);
+        i = end;
+        if closing {
+            continue;
+        }
+
+        let found = match &name[..] {
+            "img" => attribute(tag, "src").map(|src| ("image", src)),
+            "video" | "audio" | "source" | "track" => attribute(tag, "src").map(|src| ("file", src)),
+            "a" => attribute(tag, "href").filter(|href| is_asset(href)).map(|href| ("file", href)),
+            _ => None,
+        };
+        if let Some((kind, dest)) = found {
+            assets.push((kind, decode_entities(dest)));
+        }
+        if name == "video" {
+            if let Some(poster) = attribute(tag, "poster") {
+                assets.push(("image", decode_entities(poster)));
+            }
+        }
+    }
+}
+
+/// Resolves the `.` and `..` components of a relative path, `None` if it leaves the source directory
+pub fn normalize(path: &Path) -> Option<PathBuf> {
+    let mut normalized = PathBuf::new();
+
+    for component in path.components() {
+        match component {
+            Component::Normal(c) => normalized.push(c),
+            Component::CurDir => {},
+            Component::ParentDir => {
+                if !normalized.pop() {
+                    return None;
+                }
+            },
+            _ => return None,
+        }
+    }
+
+    Some(normalized)
+}
+
+
+// ---------------------------------------------------------------------------------
+//      Tests
+//
+
+#[cfg(test)]
+fn test_chapters() -> HashSet<PathBuf> {
+    ["README.md", "cli/init.md", "cli/build.md", "format/config.md"]
+        .iter()
+        .map(PathBuf::from)
+        .collect()
+}
+
+#[test]
+fn test_rewrite_link_relative_to_chapter() {
+    let chapters = test_chapters();
+    let chapter = Path::new("cli/init.md");
+
+    assert_eq!(rewrite_link("./build.md#options", chapter, Path::new(""), &chapters, &mut vec![]),
+               Some("cli/build.html#options".to_owned()));
+    assert_eq!(rewrite_link("../format/config.md", chapter, Path::new(""), &chapters, &mut vec![]),
+               Some("format/config.html".to_owned()));
+}
+
+#[test]
+fn test_rewrite_link_relative_to_root() {
+    let chapters = test_chapters();
+
+    assert_eq!(rewrite_link("cli/build.md", Path::new("cli/init.md"), Path::new(""), &chapters, &mut vec![]),
+               Some("cli/build.html".to_owned()));
+}
+
+#[test]
+fn test_rewrite_link_unknown_chapter() {
+    let chapters = test_chapters();
+    let chapter = Path::new("cli/init.md");
+
+    let mut warnings = vec![];
+    assert_eq!(rewrite_link("missing.md", chapter, Path::new(""), &chapters, &mut warnings),
+               Some("cli/missing.html".to_owned()));
+    assert_eq!(rewrite_link("../../outside.md", chapter, Path::new(""), &chapters, &mut warnings),
+               Some("../../outside.html".to_owned()));
+    assert_eq!(warnings.len(), 2);
+    assert_eq!(warnings[0],
+               Warning::new(chapter, "the link to `missing.md` does not point to a chapter of SUMMARY.md"));
+}
+
+#[test]
+fn test_rewrite_link_ignored() {
+    let chapters = test_chapters();
+    let chapter = Path::new("cli/init.md");
+
+    assert_eq!(rewrite_link("https://example.com/README.md", chapter, Path::new(""), &chapters, &mut vec![]),
+               None);
+    assert_eq!(rewrite_link("build.html", chapter, Path::new(""), &chapters, &mut vec![]), None);
+    assert_eq!(rewrite_link("#options", chapter, Path::new(""), &chapters, &mut vec![]), None);
+}
+
+#[test]
+fn test_rewrite_link_of_embedded_book() {
+    let chapters: HashSet<PathBuf> = ["README.md", "net/README.md", "net/tcp/intro.md", "net/udp.md"]
+        .iter()
+        .map(PathBuf::from)
+        .collect();
+    let chapter = Path::new("net/tcp/intro.md");
+    let mount = Path::new("net");
+
+    assert_eq!(rewrite_link("../udp.md", chapter, mount, &chapters, &mut vec![]), Some("net/udp.html".to_owned()));
+    assert_eq!(rewrite_link("README.md", chapter, mount, &chapters, &mut vec![]),
+               Some("net/README.html".to_owned()));
+    assert_eq!(rewrite_link("images/tcp.svg", chapter, mount, &chapters, &mut vec![]),
+               Some("net/images/tcp.svg".to_owned()));
+    assert_eq!(rewrite_link("#options", chapter, mount, &chapters, &mut vec![]), None);
+}
+
+#[test]
+fn test_rewrite_event() {
+    use pulldown_cmark::{Parser, html};
+
+    let chapters = test_chapters();
+    let mut warnings = vec![];
+    let events = Parser::new("See [build](build.md).")
+        .map(|e| rewrite_event(e, Path::new("cli/init.md"), Path::new(""), &chapters, &mut warnings));
+    let mut s = String::new();
+    html::push_html(&mut s, events);
+
+    assert_eq!(s, "<p>See <a href=\"cli/build.html\">build</a>.</p>\n");
+}
+
+#[test]
+fn test_check_assets() {
+    extern crate tempdir;
+    use std::fs::{self, File};
+
+    let src = tempdir::TempDir::new("mdbook-images").unwrap();
+    fs::create_dir(src.path().join("images")).unwrap();
+    File::create(src.path().join("images/logo.png")).unwrap();
+
+    let markdown = "![](images/logo.png) ![](images/logo.png#dark) ![](https://example.com/a.png) ![](logo.png)\n\n\
+                    [The guide](downloads/guide.pdf) [Build](cli/build.md) [Archive](book.zip)\n\n\
+                    <video src=\"demo.webm\" poster=\"images/logo.png\"></video>\n\n\
+                    Again ![](logo.png)\n";
+    let source = format!("+++\ntitle = \"Init\"\n+++\n\n{}", markdown);
+    let mut warnings = vec![];
+    check_assets(markdown, &source, src.path(), Path::new("cli/init.md"), &mut warnings);
+
+    let chapter = Path::new("cli/init.md");
+    assert_eq!(warnings,
+               vec![Warning::new(chapter, "the image `logo.png` does not exist").at_line(5),
+                    Warning::new(chapter, "the file `downloads/guide.pdf` does not exist").at_line(7),
+                    Warning::new(chapter, "the file `demo.webm` does not exist").at_line(9),
+                    Warning::new(chapter, "the image `logo.png` does not exist").at_line(11)]);
+    assert_eq!(warnings[1].to_string(), "cli/init.md:7: the file `downloads/guide.pdf` does not exist");
+}
@@ -0,0 +1,187 @@
+use std::collections::HashMap;
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+use book::htmlconfig::MathConfig;
+use renderer::html_handlebars::helpers::externals::{tag_end, tag_name};
+use renderer::html_handlebars::helpers::sanitize::decode_entities;
+use renderer::Warning;
+use utils::process::wait_with_timeout;
+
+
+// Elements whose text is not math
+const SKIPPED: &'static [&'static str] = &["code", "pre", "script", "style"];
+
+// Seconds after which the KaTeX command is killed
+const TIMEOUT: u64 = 30;
+
+/// An expression of the html of a chapter, `\( ... \)` inline or `\[ ... \]` displayed as a block
+#[derive(Debug, Clone, PartialEq)]
+pub struct Math {
+    /// Start and end of the expression in the html, with its delimiters
+    pub start: usize,
+    pub end: usize,
+    /// TeX of the expression, without the html entities
+    pub tex: String,
+    pub display: bool,
+}
+
+/// Renders the math of the chapters at build time with the KaTeX command line, to html and MathML that read
+/// without any script, instead of MathJax in the browser. The expressions repeated in the book are rendered once.
+pub struct Katex<'c> {
+    config: &'c MathConfig,
+    rendered: HashMap<(String, bool), String>,
+    // The command could not be run, its warning was already given
+    broken: bool,
+}
+
+impl<'c> Katex<'c> {
+    pub fn new(config: &'c MathConfig) -> Self {
+        Katex {
+            config: config,
+            rendered: HashMap::new(),
+            broken: false,
+        }
+    }
+
+    /// Replaces the expressions of the html of a chapter by their rendering, the ones KaTeX rejects are left
+    /// as they are with a warning
+    pub fn render_math(&mut self, html: &str, chapter: &Path, warnings: &mut Vec<Warning>) -> String {
+        let mut rendered = String::with_capacity(html.len());
+        let mut last = 0;
+        for math in find_math(html) {
+            if self.broken {
+                break;
+            }
+            match self.render(&math.tex, math.display) {
+                Ok(output) => {
+                    rendered.push_str(&html[last..math.start]);
+                    rendered.push_str(&output);
+                    last = math.end;
+                },
+                Err(e) => warnings.push(Warning::new(chapter, format!("could not render `{}`, {}", math.tex, e))),
+            }
+        }
+        rendered.push_str(&html[last..]);
+        rendered
+    }
+
+    fn render(&mut self, tex: &str, display: bool) -> Result<String, String> {
+        if let Some(output) = self.rendered.get(&(tex.to_owned(), display)) {
+            return Ok(output.clone());
+        }
+
+        let mut program: Vec<&str> = self.config.katex_command.split_whitespace().collect();
+        if program.is_empty() {
+            self.broken = true;
+            return Err("the `katex-command` of [output.html.math] is empty".to_owned());
+        }
+        if display {
+            program.push("--display-mode");
+        }
+
+        let mut child = match Command::new(program[0])
+            .args(&program[1..])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn() {
+            Ok(child) => child,
+            Err(e) => {
+                self.broken = true;
+                return Err(format!("could not run `{}`: {}", program[0], e));
+            },
+        };
+        if let Some(mut stdin) = child.stdin.take() {
+            try!(stdin.write_all(tex.as_bytes()).map_err(|e| e.to_string()));
+        }
+        let (success, stdout, stderr) = try!(wait_with_timeout(child, Duration::from_secs(TIMEOUT)));
+        if !success {
+            return Err(format!("KaTeX exited with an error:\n{}", stderr.trim_end()));
+        }
+
+        let output = stdout.trim_end().to_owned();
+        self.rendered.insert((tex.to_owned(), display), output.clone());
+        Ok(output)
+    }
+}
+
+/// The expressions of the html of a chapter, outside of the code. An expression ends in the text it starts in,
+/// the ones cut by the emphasis markdown found in them are left out.
+pub fn find_math(html: &str) -> Vec<Math> {
+    let mut expressions = vec![];
+    let mut skipped = 0usize;
+    let mut i = 0;
+    while i < html.len() {
+        let text_end = html[i..].find('<').map_or(html.len(), |offset| i + offset);
+        if skipped == 0 {
+            find_in_text(html, i, text_end, &mut expressions);
+        }
+        if text_end == html.len() {
+            break;
+        }
+
+        let tag_close = match tag_end(&html[text_end..]) {
+            Some(end) => text_end + end,
+            None => break,
+        };
+        let (closing, name) = tag_name(&html[text_end..tag_close]);
+        if SKIPPED.contains(&&name[..]) {
+            if closing {
+                skipped = skipped.saturating_sub(1);
+            } else {
+                skipped += 1;
+            }
+        }
+        i = tag_close;
+    }
+    expressions
+}
+
+fn find_in_text(html: &str, start: usize, end: usize, expressions: &mut Vec<Math>) {
+    let mut i = start;
+    while let Some(offset) = html[i..end].find('\\') {
+        let open = i + offset;
+        let (display, close) = match html[open + 1..end].chars().next() {
+            Some('(') => (false, "\\)"),
+            Some('[') => (true, "\\]"),
+            _ => {
+                i = open + 1;
+                continue;
+            },
+        };
+
+        match html[open + 2..end].find(close) {
+            Some(length) => {
+                let math_end = open + 2 + length + close.len();
+                expressions.push(Math {
+                    start: open,
+                    end: math_end,
+                    tex: decode_entities(&html[open + 2..open + 2 + length]).trim().to_owned(),
+                    display: display,
+                });
+                i = math_end;
+            },
+            None => return,
+        }
+    }
+}
+
+
+// ---------------------------------------------------------------------------------
+//      Tests
+//
+
+#[test]
+fn test_find_math() {
+    let html = "<p>Inline \\( x &lt; \\frac{1}{2} \\) and</p>\n<p>\\[ \\mu = \\sum x_i \\]</p>\n\
+                <pre><code>\\( kept \\)</code></pre>\n<p>\\( a <em>b</em> c \\) \\\\ not math</p>\n";
+    let expressions = find_math(html);
+
+    let found: Vec<_> = expressions.iter().map(|m| (&html[m.start..m.end], &m.tex[..], m.display)).collect();
+    assert_eq!(found,
+               vec![("\\( x &lt; \\frac{1}{2} \\)", "x < \\frac{1}{2}", false),
+                    ("\\[ \\mu = \\sum x_i \\]", "\\mu = \\sum x_i", true)]);
+}
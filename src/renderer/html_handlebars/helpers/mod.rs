@@ -1,3 +1,25 @@
 pub mod navigation;
 pub mod toc;
 pub mod playpen;
+pub mod include;
+pub mod links;
+pub mod crossref;
+pub mod directives;
+pub mod figures;
+pub mod details;
+pub mod embeds;
+pub mod headings;
+pub mod extensions;
+pub mod sanitize;
+pub mod externals;
+pub mod emoji;
+pub mod run;
+pub mod math;
+pub mod quiz;
+pub mod codeblocks;
+pub mod docitems;
+pub mod variables;
+pub mod conditionals;
+pub mod template;
+pub mod script;
+pub mod rawhtml;
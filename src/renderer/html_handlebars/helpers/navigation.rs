@@ -4,196 +4,67 @@ use std::collections::{VecDeque, BTreeMap};
 use serde_json;
 use serde_json::value::ToJson;
 use handlebars::{Handlebars, RenderError, RenderContext, Helper, Context, Renderable};
+use utils;
 
 
-// Handlebars helper for navigation
-
-pub fn previous(c: &Context, _h: &Helper, r: &Handlebars, rc: &mut RenderContext) -> Result<(), RenderError> {
-    debug!("[fn]: previous (handlebars helper)");
-
-    debug!("[*]: Get data from context");
-    // get value from context data
-    // rc.get_path() is current json parent path, you should always use it like this
-    // param is the key of value you want to display
-    let chapters = c.navigate(rc.get_path(), &VecDeque::new(), "chapters");
-
-    let current = c.navigate(rc.get_path(), &VecDeque::new(), "path")
-        .to_string()
-        .replace("\"", "");
-
-
-    debug!("[*]: Decode chapters from JSON");
-    // Decode json format
-    let decoded: Vec<BTreeMap<String, String>> = match serde_json::from_str(&chapters.to_string()) {
-        Ok(data) => data,
-        Err(_) => return Err(RenderError::new("Could not decode the JSON data")),
-    };
-    let mut previous: Option<BTreeMap<String, String>> = None;
-
-
-    debug!("[*]: Search for current Chapter");
-    // Search for current chapter and return previous entry
-    for item in decoded {
-
-        match item.get("path") {
-            Some(path) if !path.is_empty() => {
-                if path == &current {
-
-                    debug!("[*]: Found current chapter");
-                    if let Some(previous) = previous {
-
-                        debug!("[*]: Creating BTreeMap to inject in context");
-                        // Create new BTreeMap to extend the context: 'title' and 'link'
-                        let mut previous_chapter = BTreeMap::new();
-
-                        // Chapter title
-                        match previous.get("name") {
-                            Some(n) => {
-                                debug!("[*]: Inserting title: {}", n);
-                                previous_chapter.insert("title".to_owned(), n.to_json())
-                            },
-                            None => {
-                                debug!("[*]: No title found for chapter");
-                                return Err(RenderError::new("No title found for chapter in JSON data"));
-                            },
-                        };
-
-                        // Chapter link
-
-                        match previous.get("path") {
-                            Some(p) => {
-                                // Hack for windows who tends to use `\` as separator instead of `/`
-                                let path = Path::new(p).with_extension("html");
-                                debug!("[*]: Inserting link: {:?}", path);
-
-                                match path.to_str() {
-                                    Some(p) => {
-                                        previous_chapter.insert("link".to_owned(), p.replace("\\", "/").to_json());
-                                    },
-                                    None => return Err(RenderError::new("Link could not be converted to str")),
-                                }
-                            },
-                            None => return Err(RenderError::new("No path found for chapter in JSON data")),
-                        }
-
-                        debug!("[*]: Inject in context");
-                        // Inject in current context
-                        let updated_context = c.extend(&previous_chapter);
-
-                        debug!("[*]: Render template");
-                        // Render template
-                        match _h.template() {
-                            Some(t) => {
-                                try!(t.render(&updated_context, r, rc));
-                            },
-                            None => return Err(RenderError::new("Error with the handlebars template")),
-                        }
-
-                    }
-
-                    break;
-                } else {
-                    previous = Some(item.clone());
-                }
-            },
-            _ => continue,
-
-        }
-
-    }
-
-    Ok(())
+/// The `title` and `link` of a chapter, given to the template of the chapters before and after it as
+/// `previous` and `next`
+pub fn chapter_link(name: &str, path: &Path) -> BTreeMap<String, serde_json::Value> {
+    let mut link = BTreeMap::new();
+    link.insert("title".to_owned(), name.to_json());
+    link.insert("link".to_owned(), utils::fs::path_to_link(path).to_json());
+    link
 }
 
 
+// Handlebars helper for navigation
 
+pub fn previous(c: &Context, h: &Helper, r: &Handlebars, rc: &mut RenderContext) -> Result<(), RenderError> {
+    debug!("[fn]: previous (handlebars helper)");
+    render_link(c, h, r, rc, "previous")
+}
 
-pub fn next(c: &Context, _h: &Helper, r: &Handlebars, rc: &mut RenderContext) -> Result<(), RenderError> {
+pub fn next(c: &Context, h: &Helper, r: &Handlebars, rc: &mut RenderContext) -> Result<(), RenderError> {
     debug!("[fn]: next (handlebars helper)");
+    render_link(c, h, r, rc, "next")
+}
 
-    debug!("[*]: Get data from context");
-    // get value from context data
-    // rc.get_path() is current json parent path, you should always use it like this
-    // param is the key of value you want to display
-    let chapters = c.navigate(rc.get_path(), &VecDeque::new(), "chapters");
-
-    let current = c.navigate(rc.get_path(), &VecDeque::new(), "path")
-        .to_string()
-        .replace("\"", "");
-
-    debug!("[*]: Decode chapters from JSON");
-    // Decode json format
-    let decoded: Vec<BTreeMap<String, String>> = match serde_json::from_str(&chapters.to_string()) {
-        Ok(data) => data,
-        Err(_) => return Err(RenderError::new("Could not decode the JSON data")),
+// Renders the block with the `title` and `link` of the `previous` or `next` chapter, when the page has one
+fn render_link(c: &Context, h: &Helper, r: &Handlebars, rc: &mut RenderContext, key: &str) -> Result<(), RenderError> {
+    let link = match *c.navigate(rc.get_path(), &VecDeque::new(), key) {
+        serde_json::Value::Object(ref link) => {
+            link.iter().map(|(k, v)| (k.clone(), v.clone())).collect::<BTreeMap<_, _>>()
+        },
+        _ => return Ok(()),
     };
-    let mut previous: Option<BTreeMap<String, String>> = None;
-
-    debug!("[*]: Search for current Chapter");
-    // Search for current chapter and return previous entry
-    for item in decoded {
-
-        match item.get("path") {
 
-            Some(path) if !path.is_empty() => {
+    debug!("[*]: Inject in context");
+    let updated_context = c.extend(&link);
 
-                if let Some(previous) = previous {
-
-                    let previous_path = match previous.get("path") {
-                        Some(p) => p,
-                        None => return Err(RenderError::new("No path found for chapter in JSON data")),
-                    };
-
-                    if previous_path == &current {
-
-                        debug!("[*]: Found current chapter");
-                        debug!("[*]: Creating BTreeMap to inject in context");
-                        // Create new BTreeMap to extend the context: 'title' and 'link'
-                        let mut next_chapter = BTreeMap::new();
-
-                        match item.get("name") {
-                            Some(n) => {
-                                debug!("[*]: Inserting title: {}", n);
-                                next_chapter.insert("title".to_owned(), n.to_json());
-                            },
-                            None => return Err(RenderError::new("No title found for chapter in JSON data")),
-                        }
-
-
-                        let link = Path::new(path).with_extension("html");
-                        debug!("[*]: Inserting link: {:?}", link);
-
-                        match link.to_str() {
-                            Some(l) => {
-                                // Hack for windows who tends to use `\` as separator instead of `/`
-                                next_chapter.insert("link".to_owned(), l.replace("\\", "/").to_json());
-                            },
-                            None => return Err(RenderError::new("Link could not converted to str")),
-                        }
-
-                        debug!("[*]: Inject in context");
-                        // Inject in current context
-                        let updated_context = c.extend(&next_chapter);
+    match h.template() {
+        Some(t) => t.render(&updated_context, r, rc),
+        None => Err(RenderError::new("Error with the handlebars template")),
+    }
+}
 
-                        debug!("[*]: Render template");
 
-                        // Render template
-                        match _h.template() {
-                            Some(t) => {
-                                try!(t.render(&updated_context, r, rc));
-                            },
-                            None => return Err(RenderError::new("Error with the handlebars template")),
-                        }
+// ---------------------------------------------------------------------------------------------------------------
+//      Tests
+//
 
-                        break;
-                    }
-                }
+#[test]
+fn test_navigation() {
+    let mut handlebars = Handlebars::new();
+    handlebars.register_helper("previous", Box::new(previous));
+    handlebars.register_helper("next", Box::new(next));
+    let template = "{{#previous}}{{link}}{{/previous}}|{{#next}}{{title}}{{/next}}|{{title}}";
+    handlebars.register_template_string("index", template.to_owned()).unwrap();
 
-                previous = Some(item.clone());
-            },
+    let mut data = BTreeMap::new();
+    data.insert("title".to_owned(), "Book".to_json());
+    assert_eq!(handlebars.render("index", &data).unwrap(), "||Book");
 
-            _ => continue,
-        }
-    }
-    Ok(())
+    data.insert("previous".to_owned(), chapter_link("Intro", Path::new("guide\\intro.md")).to_json());
+    data.insert("next".to_owned(), chapter_link("Usage", Path::new("guide/usage.md")).to_json());
+    assert_eq!(handlebars.render("index", &data).unwrap(), "guide/intro.html|Usage|Book");
 }
@@ -2,8 +2,10 @@ use std::path::{Path, PathBuf};
 use std::fs::File;
 use std::io::Read;
 
+use renderer::Warning;
 
-pub fn render_playpen(s: &str, path: &Path) -> String {
+
+pub fn render_playpen(s: &str, path: &Path, chapter: &Path, warnings: &mut Vec<Warning>) -> String {
     // When replacing one thing in a string by something with a different length, the indices
     // after that will not correspond, we therefore have to store the difference to correct this
     let mut previous_end_index = 0;
@@ -20,7 +22,7 @@ pub fn render_playpen(s: &str, path: &Path) -> String {
 
         // Check if the file exists
         if !playpen.rust_file.exists() || !playpen.rust_file.is_file() {
-            warn!("[-] No file exists for {{{{#playpen }}}}\n    {}", playpen.rust_file.to_str().unwrap());
+            warnings.push(Warning::new(chapter, format!("no file {} for {{{{#playpen}}}}", playpen.rust_file.display())));
             continue;
         }
 
@@ -35,7 +37,7 @@ pub fn render_playpen(s: &str, path: &Path) -> String {
             continue;
         };
 
-        let replacement = String::new() + "<pre class=\"playpen\"><code class=\"language-rust\">" + &file_content +
+        let replacement = String::new() + "<pre class=\"playpen\"><code class=\"language-rust\">" + file_content.as_str() +
                           "</code></pre>";
 
         replaced.push_str(&s[previous_end_index..playpen.start_index]);
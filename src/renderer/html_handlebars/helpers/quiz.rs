@@ -0,0 +1,297 @@
+use std::borrow::Cow;
+use std::path::Path;
+use std::collections::BTreeMap;
+
+use pulldown_cmark::{Event, Tag};
+use serde_json;
+use toml;
+
+use renderer::Warning;
+use utils;
+
+
+/// Renders the ```` ```quiz ```` code blocks as self-grading questions. A block defines its
+/// questions in TOML, a multiple-choice question has `choices` and the index of the right one in
+/// `answer`, or a list of indices when several choices are right. A question without choices is
+/// filled in, `answer` is then the text expected, or a list of the accepted texts:
+///
+/// ```toml
+/// [[questions]]
+/// prompt = "Which macro prints a line?"
+/// choices = ["`print!`", "`println!`", "`format!`"]
+/// answer = 1
+/// explanation = "`print!` does not add a newline."
+///
+/// [[questions]]
+/// prompt = "The keyword of a mutable binding: `let ___ x = 5;`"
+/// answer = "mut"
+/// ```
+///
+/// The answers are checked by `book.js`. They are also written below the questions, hidden on
+/// the pages and shown when the book is printed.
+pub struct Quizzes {
+    count: usize,
+}
+
+enum Answer {
+    Choices(Vec<usize>),
+    Text(Vec<String>),
+}
+
+struct Question {
+    prompt: String,
+    choices: Vec<String>,
+    answer: Answer,
+    explanation: Option<String>,
+}
+
+impl Quizzes {
+    pub fn new() -> Self {
+        Quizzes { count: 0 }
+    }
+
+    /// Replaces the quiz blocks of a chapter by their html, the blocks that are not valid are left as they are
+    pub fn render_blocks<'a>(&mut self,
+                             events: Vec<Event<'a>>,
+                             chapter: &Path,
+                             warnings: &mut Vec<Warning>)
+                             -> Vec<Event<'a>> {
+        let mut result = Vec::with_capacity(events.len());
+        // The events of the quiz block being read, with its definition
+        let mut block: Option<(Vec<Event<'a>>, String)> = None;
+
+        for event in events {
+            match event {
+                Event::Start(Tag::CodeBlock(ref info)) if info.split(',').next().map(|l| l.trim()) == Some("quiz") => {
+                    block = Some((vec![], String::new()));
+                },
+                Event::Text(ref text) if block.is_some() => {
+                    if let Some((_, ref mut definition)) = block {
+                        definition.push_str(text);
+                    }
+                },
+                Event::End(Tag::CodeBlock(_)) if block.is_some() => {
+                    let (mut block_events, definition) = block.take().unwrap();
+                    block_events.push(event);
+
+                    match parse_questions(&definition) {
+                        Ok(questions) => {
+                            self.count += 1;
+                            result.push(Event::Html(Cow::Owned(render_quiz(&questions, self.count))));
+                        },
+                        Err(e) => {
+                            warnings.push(Warning::new(chapter, format!("invalid quiz: {}", e)));
+                            result.extend(block_events);
+                        },
+                    }
+                    continue;
+                },
+                _ => {},
+            }
+
+            match block {
+                Some((ref mut block_events, _)) => block_events.push(event),
+                None => result.push(event),
+            }
+        }
+
+        result
+    }
+}
+
+fn parse_questions(definition: &str) -> Result<Vec<Question>, String> {
+    let mut parser = toml::Parser::new(definition);
+    let table = match parser.parse() {
+        Some(table) => table,
+        None => {
+            let errors: Vec<String> = parser.errors.iter().map(|e| e.desc.clone()).collect();
+            return Err(errors.join(", "));
+        },
+    };
+
+    let questions = match table.get("questions").and_then(|q| q.as_slice()) {
+        Some(questions) if !questions.is_empty() => questions,
+        _ => return Err("no `[[questions]]`".to_owned()),
+    };
+
+    questions.iter()
+        .enumerate()
+        .map(|(i, question)| {
+            question.as_table()
+                .ok_or_else(|| format!("question {} is not a table", i + 1))
+                .and_then(parse_question)
+                .map_err(|e| format!("question {}: {}", i + 1, e))
+        })
+        .collect()
+}
+
+fn parse_question(question: &BTreeMap<String, toml::Value>) -> Result<Question, String> {
+    let prompt = try!(question.get("prompt").and_then(|p| p.as_str()).ok_or("no `prompt`"));
+    let choices: Vec<String> = question.get("choices")
+        .and_then(|c| c.as_slice())
+        .map(|c| c.iter().filter_map(|c| c.as_str()).map(|c| c.to_owned()).collect())
+        .unwrap_or_default();
+
+    let answers = match question.get("answer") {
+        Some(answer) => answer.as_slice().map_or_else(|| vec![answer.clone()], |answers| answers.to_vec()),
+        None => return Err("no `answer`".to_owned()),
+    };
+
+    let answer = if choices.is_empty() {
+        let texts: Vec<String> = answers.iter().filter_map(|a| a.as_str()).map(|a| a.to_owned()).collect();
+        if texts.len() != answers.len() || texts.is_empty() {
+            return Err("the `answer` of a question without `choices` has to be a text".to_owned());
+        }
+        Answer::Text(texts)
+    } else {
+        let indices: Vec<usize> = answers.iter()
+            .filter_map(|a| a.as_integer())
+            .filter(|&a| a >= 0 && (a as usize) < choices.len())
+            .map(|a| a as usize)
+            .collect();
+        if indices.len() != answers.len() || indices.is_empty() {
+            return Err("the `answer` of a multiple-choice question has to be the index of a choice, from 0"
+                .to_owned());
+        }
+        Answer::Choices(indices)
+    };
+
+    Ok(Question {
+        prompt: prompt.to_owned(),
+        choices: choices,
+        answer: answer,
+        explanation: question.get("explanation").and_then(|e| e.as_str()).map(|e| e.to_owned()),
+    })
+}
+
+fn render_quiz(questions: &[Question], quiz: usize) -> String {
+    let mut s = String::from("<div class=\"quiz\">\n");
+
+    for (i, question) in questions.iter().enumerate() {
+        let name = format!("quiz-{}-{}", quiz, i + 1);
+        let (kind, answer_data, answer_text) = match question.answer {
+            Answer::Choices(ref indices) => {
+                let kind = if indices.len() > 1 { "multiple" } else { "choice" };
+                let text: Vec<String> = indices.iter().map(|&i| inline_markdown(&question.choices[i])).collect();
+                (kind, serde_json::to_string(indices).unwrap_or_default(), text.join(", "))
+            },
+            Answer::Text(ref texts) => {
+                ("text", serde_json::to_string(texts).unwrap_or_default(), utils::escape_html(&texts[0]))
+            },
+        };
+
+        s.push_str(&format!("<div class=\"quiz-question\" data-kind=\"{}\" data-answer=\"{}\">\n",
+                            kind,
+                            utils::escape_html(&answer_data)));
+        s.push_str(&format!("<div class=\"quiz-prompt\">{}</div>\n", utils::render_markdown(&question.prompt)));
+
+        if question.choices.is_empty() {
+            s.push_str(&format!("<input class=\"quiz-input\" type=\"text\" name=\"{}\" aria-label=\"Answer\">\n",
+                                name));
+        } else {
+            let input = if kind == "multiple" { "checkbox" } else { "radio" };
+            s.push_str("<ul class=\"quiz-choices\">\n");
+            for (j, choice) in question.choices.iter().enumerate() {
+                s.push_str(&format!("<li><label><input type=\"{}\" name=\"{}\" value=\"{}\"> {}</label></li>\n",
+                                    input,
+                                    name,
+                                    j,
+                                    inline_markdown(choice)));
+            }
+            s.push_str("</ul>\n");
+        }
+
+        s.push_str("<button class=\"quiz-check\" type=\"button\">Check</button> <span class=\"quiz-result\"></span>\n");
+        s.push_str(&format!("<div class=\"quiz-answer\">Answer: {}</div>\n", answer_text));
+        if let Some(ref explanation) = question.explanation {
+            s.push_str(&format!("<div class=\"quiz-explanation\">{}</div>\n", utils::render_markdown(explanation)));
+        }
+        s.push_str("</div>\n");
+    }
+
+    s.push_str("</div>\n");
+    s
+}
+
+// The html of a line of markdown, without the paragraph around it
+fn inline_markdown(text: &str) -> String {
+    let html = utils::render_markdown(text);
+    let html = html.trim_end();
+    match html.strip_prefix("<p>").and_then(|h| h.strip_suffix("</p>")) {
+        Some(inline) if !inline.contains("<p>") => inline.to_owned(),
+        _ => html.to_owned(),
+    }
+}
+
+
+// ---------------------------------------------------------------------------------
+//      Tests
+//
+
+#[test]
+fn test_parse_questions() {
+    let definition = "[[questions]]\nprompt = \"a\"\nchoices = [\"x\", \"y\", \"z\"]\nanswer = [0, 2]\n\n\
+                      [[questions]]\nprompt = \"b\"\nanswer = \"mut\"\nexplanation = \"c\"";
+    let questions = parse_questions(definition).unwrap();
+    assert_eq!(questions.len(), 2);
+    match questions[0].answer {
+        Answer::Choices(ref indices) => assert_eq!(indices, &[0, 2]),
+        _ => panic!("multiple-choice question expected"),
+    }
+    match questions[1].answer {
+        Answer::Text(ref texts) => assert_eq!(texts, &["mut".to_owned()]),
+        _ => panic!("fill-in question expected"),
+    }
+    assert_eq!(questions[1].explanation, Some("c".to_owned()));
+
+    assert!(parse_questions("prompt = \"a\"").is_err());
+    assert!(parse_questions("[[questions]]\nprompt = \"a\"\nchoices = [\"x\"]\nanswer = 1").is_err());
+    assert!(parse_questions("[[questions]]\nprompt = \"a\"\nanswer = 1").is_err());
+    assert!(parse_questions("[[questions]]\nprompt = ").is_err());
+}
+
+#[test]
+fn test_render_quiz_blocks() {
+    use pulldown_cmark::Parser;
+
+    let markdown = "Quiz:\n\n```quiz\n[[questions]]\nprompt = \"Which *macro*?\"\n\
+                    choices = [\"`print!`\", \"`println!`\"]\nanswer = 1\n```\n\n```quiz\nnot toml\n```";
+    let mut quizzes = Quizzes::new();
+    let mut warnings = vec![];
+    let events = quizzes.render_blocks(Parser::new(markdown).collect(), Path::new("quiz.md"), &mut warnings);
+
+    let mut html = String::new();
+    ::pulldown_cmark::html::push_html(&mut html, events.into_iter());
+    assert_eq!(html,
+               "<p>Quiz:</p>\n<div class=\"quiz\">\n\
+                <div class=\"quiz-question\" data-kind=\"choice\" data-answer=\"[1]\">\n\
+                <div class=\"quiz-prompt\"><p>Which <em>macro</em>?</p>\n</div>\n\
+                <ul class=\"quiz-choices\">\n\
+                <li><label><input type=\"radio\" name=\"quiz-1-1\" value=\"0\"> <code>print!</code></label></li>\n\
+                <li><label><input type=\"radio\" name=\"quiz-1-1\" value=\"1\"> <code>println!</code></label></li>\n\
+                </ul>\n\
+                <button class=\"quiz-check\" type=\"button\">Check</button> <span class=\"quiz-result\"></span>\n\
+                <div class=\"quiz-answer\">Answer: <code>println!</code></div>\n\
+                </div>\n</div>\n\
+                <pre><code class=\"language-quiz\">not toml\n</code></pre>\n");
+    assert_eq!(warnings.len(), 1);
+}
+
+#[test]
+fn test_sanitize_quizzes() {
+    use pulldown_cmark::Parser;
+    use super::sanitize::sanitize;
+
+    let markdown = "```quiz\n[[questions]]\nprompt = \"Which <b onclick='x()'>macro</b>?\"\n\
+                    choices = [\"`print!`\", \"`println!`\"]\nanswer = 1\n\n\
+                    [[questions]]\nprompt = \"Keyword?\"\nanswer = [\"mut\"]\n```";
+    let mut warnings = vec![];
+    let events = Quizzes::new().render_blocks(Parser::new(markdown).collect(), Path::new("quiz.md"), &mut warnings);
+    let mut html = String::new();
+    ::pulldown_cmark::html::push_html(&mut html, events.into_iter());
+
+    // The markup of the quiz is kept, only the html of its author is sanitized
+    assert_eq!(sanitize(&html), html.replace(" onclick='x()'", ""));
+    assert!(html.contains("data-kind=\"text\" data-answer=\"[&quot;mut&quot;]\""));
+    assert!(html.contains("<input class=\"quiz-input\" type=\"text\" name=\"quiz-1-2\" aria-label=\"Answer\">"));
+}
@@ -0,0 +1,112 @@
+use std::path::Path;
+
+use renderer::html_handlebars::helpers::externals::{tag_end, tag_name};
+
+
+// Elements whose content is not text of the page
+const HIDDEN: &'static [&'static str] = &["script", "style", "template", "noscript"];
+
+// Elements starting a paragraph of the text
+const BLOCKS: &'static [&'static str] = &["address", "article", "aside", "blockquote", "br", "dd", "details",
+                                          "div", "dl", "dt", "figcaption", "figure", "footer", "form", "header",
+                                          "hr", "li", "main", "nav", "ol", "p", "pre", "section", "summary",
+                                          "table", "td", "th", "tr", "ul"];
+
+/// Whether a chapter of `SUMMARY.md` is written in html, with the `.html` extension. Its content is wrapped in
+/// the template as it is written, without the processing of the markdown.
+pub fn is_html_chapter(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext == "html")
+}
+
+/// The text of the html of a chapter as markdown, its headings and a paragraph for every block, for the search
+/// index, the word count and the description of the chapter. The scripts and the styles are left out.
+pub fn text_markdown(html: &str) -> String {
+    let mut markdown = String::new();
+    let mut text = String::new();
+    let mut hidden: Option<String> = None;
+    let mut i = 0;
+    while let Some(offset) = html[i..].find('<') {
+        let start = i + offset;
+        if hidden.is_none() {
+            text.push_str(&html[i..start]);
+        }
+        if html[start..].starts_with("<!--") {
+            i = html[start..].find("-->").map_or(html.len(), |end| start + end + 3);
+            continue;
+        }
+        let end = match tag_end(&html[start..]) {
+            Some(end) => start + end,
+            None => break,
+        };
+        i = end;
+
+        let tag = &html[start..end];
+        let (closing, name) = tag_name(tag);
+        if let Some(element) = hidden.take() {
+            if !closing || element != name {
+                hidden = Some(element);
+            }
+            continue;
+        }
+        if !closing && HIDDEN.contains(&&name[..]) && !tag.ends_with("/>") {
+            hidden = Some(name);
+        } else if let Some(level) = heading_level(&name) {
+            push_paragraph(&mut markdown, &text, if closing { level } else { 0 });
+            text.clear();
+        } else if BLOCKS.contains(&&name[..]) {
+            push_paragraph(&mut markdown, &text, 0);
+            text.clear();
+        }
+    }
+    if hidden.is_none() {
+        text.push_str(&html[i..]);
+    }
+    push_paragraph(&mut markdown, &text, 0);
+    markdown
+}
+
+// Adds the text as a paragraph, or as a heading of `level` when it is not 0, its entities are kept and the
+// characters of the markdown syntax escaped
+fn push_paragraph(markdown: &mut String, text: &str, level: usize) {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    if words.is_empty() {
+        return;
+    }
+    for _ in 0..level {
+        markdown.push('#');
+    }
+    if level > 0 {
+        markdown.push(' ');
+    }
+    for c in words.join(" ").chars() {
+        if "\\`*_[]#!".contains(c) {
+            markdown.push('\\');
+        }
+        markdown.push(c);
+    }
+    markdown.push_str("\n\n");
+}
+
+fn heading_level(name: &str) -> Option<usize> {
+    if name.len() == 2 && name.starts_with('h') {
+        name[1..].parse().ok().filter(|level| (1..7).contains(level))
+    } else {
+        None
+    }
+}
+
+
+// ---------------------------------------------------------------------------------
+//      Tests
+//
+
+#[test]
+fn test_text_markdown() {
+    let html = "<h1 id=\"demo\">Live <em>demo</em></h1>\n<!-- <p>Hidden</p> -->\n\
+                <div class=\"demo\">\n    <p>Drag the *points* &amp; watch</p>\n    <canvas></canvas>\n\
+                <script>var width = 640;</script>\n    Done.<br>Really</div>\n<style>p { color: red; }</style>";
+    assert_eq!(text_markdown(html),
+               "# Live demo\n\nDrag the \\*points\\* &amp; watch\n\nDone.\n\nReally\n\n");
+    assert!(is_html_chapter(Path::new("demos/canvas.html")));
+    assert!(!is_html_chapter(Path::new("demos/canvas.md")));
+}
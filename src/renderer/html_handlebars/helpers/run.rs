@@ -0,0 +1,211 @@
+use std::env;
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{self, Command, Stdio};
+use std::time::Duration;
+use std::borrow::Cow;
+
+use pulldown_cmark::{Event, Tag};
+
+use book::htmlconfig::RunConfig;
+use renderer::Warning;
+use utils;
+use utils::process::wait_with_timeout;
+
+
+/// Runs the code blocks annotated with `run`, e.g. ```` ```rust,run ````, and puts what they
+/// print below them. Rust blocks are compiled with `rustc`, the other languages are run with the
+/// command configured for them in `[output.html.run.commands]`.
+///
+/// The programs run in an empty temporary directory, without the environment variables of mdBook
+/// except `PATH`, and are killed after `timeout` seconds.
+pub struct CodeRunner<'c> {
+    config: &'c RunConfig,
+    runs: usize,
+}
+
+impl<'c> CodeRunner<'c> {
+    pub fn new(config: &'c RunConfig) -> Self {
+        CodeRunner { config: config, runs: 0 }
+    }
+
+    pub fn run_blocks<'a>(&mut self,
+                          events: Vec<Event<'a>>,
+                          chapter: &Path,
+                          warnings: &mut Vec<Warning>)
+                          -> Vec<Event<'a>> {
+        let mut with_output = Vec::with_capacity(events.len());
+        let mut block: Option<(String, String)> = None;
+
+        for event in events {
+            match event {
+                Event::Start(Tag::CodeBlock(ref info)) if is_run_block(info) => {
+                    let language = info.split(',').next().unwrap_or_default().trim().to_owned();
+                    block = Some((language, String::new()));
+                },
+                Event::Text(ref text) if block.is_some() => {
+                    if let Some((_, ref mut code)) = block {
+                        code.push_str(text);
+                    }
+                },
+                Event::End(Tag::CodeBlock(_)) if block.is_some() => {
+                    let (language, code) = block.take().unwrap();
+                    with_output.push(event);
+
+                    if let Some(output) = self.run(&language, &code, chapter, warnings) {
+                        with_output.push(Event::Html(Cow::Owned(format!("<pre class=\"run-output\"><code>{}</code></pre>\n",
+                                                                       utils::escape_html(&output)))));
+                    }
+                    continue;
+                },
+                _ => {},
+            }
+
+            with_output.push(event);
+        }
+
+        with_output
+    }
+
+    // Standard output of the program, `None` with a warning when it could not be run
+    fn run(&mut self, language: &str, code: &str, chapter: &Path, warnings: &mut Vec<Warning>) -> Option<String> {
+        self.runs += 1;
+        let dir = env::temp_dir().join(format!("mdbook-run-{}-{}", process::id(), self.runs));
+
+        let result = fs::create_dir_all(&dir)
+            .map_err(|e| e.to_string())
+            .and_then(|_| self.run_in(&dir, language, code));
+        let _ = fs::remove_dir_all(&dir);
+
+        match result {
+            Ok(output) => Some(output),
+            Err(e) => {
+                warnings.push(Warning::new(chapter, format!("could not run a `{}` code block: {}", language, e)));
+                None
+            },
+        }
+    }
+
+    fn run_in(&self, dir: &Path, language: &str, code: &str) -> Result<String, String> {
+        let program = if language == "rust" {
+            let source = dir.join("main.rs");
+            try!(write_file(&source, &rust_program(code)));
+
+            let output = try!(Command::new("rustc")
+                .arg(&source)
+                .arg("-o")
+                .arg(dir.join("main"))
+                .current_dir(dir)
+                .output()
+                .map_err(|e| format!("could not run rustc: {}", e)));
+            if !output.status.success() {
+                return Err(format!("it does not compile:\n{}", String::from_utf8_lossy(&output.stderr)));
+            }
+
+            vec![dir.join("main").to_string_lossy().into_owned()]
+        } else {
+            let interpreter = match self.config.commands.get(language) {
+                Some(interpreter) => interpreter,
+                None => return Err(format!("there is no command for `{}` in [output.html.run.commands]", language)),
+            };
+
+            let source = dir.join(format!("snippet.{}", language));
+            try!(write_file(&source, code));
+
+            let mut program: Vec<String> = interpreter.split_whitespace().map(|s| s.to_owned()).collect();
+            program.push(source.to_string_lossy().into_owned());
+            program
+        };
+
+        let mut command = Command::new(&program[0]);
+        command.args(&program[1..])
+            .current_dir(dir)
+            .env_clear()
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+        if let Some(path) = env::var_os("PATH") {
+            command.env("PATH", path);
+        }
+
+        let child = try!(command.spawn().map_err(|e| format!("could not run `{}`: {}", program[0], e)));
+        let (success, stdout, stderr) = try!(wait_with_timeout(child, Duration::from_secs(self.config.timeout)));
+
+        if !success {
+            return Err(format!("it exited with an error:\n{}", stderr));
+        }
+
+        Ok(stdout)
+    }
+}
+
+// `rust,run` or `sh,run`
+fn is_run_block(info: &str) -> bool {
+    info.split(',').any(|token| token.trim() == "run")
+}
+
+// Like rustdoc, lines hidden with `# ` are part of the program and `fn main` can be left out
+fn rust_program(code: &str) -> String {
+    let code = code.lines()
+        .map(|line| {
+            if line.trim() == "#" {
+                ""
+            } else if let Some(hidden) = line.trim_start().strip_prefix("# ") {
+                hidden
+            } else {
+                line
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    if code.contains("fn main") {
+        code
+    } else {
+        format!("fn main() {{\n{}\n}}\n", code)
+    }
+}
+
+fn write_file(path: &PathBuf, content: &str) -> Result<(), String> {
+    File::create(path)
+        .and_then(|mut f| f.write_all(content.as_bytes()))
+        .map_err(|e| e.to_string())
+}
+
+// ---------------------------------------------------------------------------------
+//      Tests
+//
+
+#[test]
+fn test_rust_program() {
+    assert_eq!(rust_program("# let x = 5;\nprintln!(\"{}\", x);"),
+               "fn main() {\nlet x = 5;\nprintln!(\"{}\", x);\n}\n");
+    assert_eq!(rust_program("fn main() {}"), "fn main() {}");
+}
+
+#[test]
+fn test_is_run_block() {
+    assert!(is_run_block("rust,run"));
+    assert!(is_run_block("sh, run"));
+    assert!(!is_run_block("rust,ignore"));
+    assert!(!is_run_block("running"));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_run_blocks() {
+    use pulldown_cmark::{Parser, html};
+
+    let mut config = RunConfig::new();
+    config.commands.insert("sh".to_owned(), "sh".to_owned());
+    let mut runner = CodeRunner::new(&config);
+
+    let events = runner.run_blocks(Parser::new("```sh,run\necho '<hello>'\n```\n").collect(),
+                                   Path::new("x.md"),
+                                   &mut vec![]);
+    let mut s = String::new();
+    html::push_html(&mut s, events.into_iter());
+
+    assert!(s.ends_with("</code></pre>\n<pre class=\"run-output\"><code>&lt;hello&gt;\n</code></pre>\n"), "{}", s);
+}
@@ -0,0 +1,293 @@
+use utils;
+
+
+/// The tags kept by the sanitizer, the other tags are removed but not their content. The quizzes use the buttons,
+/// inputs and labels.
+const TAGS: &'static [&'static str] = &["a", "abbr", "b", "blockquote", "br", "button", "caption", "cite", "code",
+                                        "dd", "del", "details", "div", "dl", "dt", "em", "figcaption", "figure", "h1",
+                                        "h2", "h3", "h4", "h5", "h6", "hr", "i", "img", "input", "ins", "kbd", "label",
+                                        "li", "mark", "ol", "p", "pre", "q", "s", "section", "small", "span",
+                                        "strong", "sub", "summary", "sup", "table", "tbody", "td", "tfoot", "th",
+                                        "thead", "time", "tr", "u", "ul"];
+
+/// The tags removed with their content
+const DROPPED: &'static [&'static str] = &["script", "style", "iframe", "object", "embed", "textarea", "noscript",
+                                           "template", "title"];
+
+/// The attributes every kept tag can have
+const ATTRIBUTES: &'static [&'static str] = &["class", "id", "title", "lang", "dir"];
+
+/// The attributes of some tags, on top of `ATTRIBUTES`
+const TAG_ATTRIBUTES: &'static [(&'static str, &'static [&'static str])] = &[("a", &["href", "name"]),
+                                                                             ("button", &["type"]),
+                                                                             ("div", &["data-kind", "data-answer",
+                                                                                       "data-embed"]),
+                                                                             ("i", &["aria-hidden"]),
+                                                                             ("img", &["src", "alt", "width",
+                                                                                       "height"]),
+                                                                             ("input", &["type", "name", "value",
+                                                                                         "aria-label"]),
+                                                                             ("ol", &["start"]),
+                                                                             ("td", &["align", "colspan", "rowspan"]),
+                                                                             ("th", &["align", "colspan", "rowspan"]),
+                                                                             ("time", &["datetime"])];
+
+/// The attributes holding a url, the address of the embeds is one
+const URL_ATTRIBUTES: &'static [&'static str] = &["href", "src", "data-embed"];
+
+/// The schemes the links, images and embeds can use, the relative urls are always allowed
+const SCHEMES: &'static [&'static str] = &["http", "https", "mailto"];
+
+
+/// Removes from the rendered html of a chapter the tags and attributes that are not in the allowlist,
+/// for the books rendering markdown written by untrusted contributors. Scripts and styles are removed
+/// with their content, the event handlers and inline styles of the kept tags with the other
+/// attributes, and links using other schemes than `http`, `https` and `mailto` lose their url.
+pub fn sanitize(html: &str) -> String {
+    let mut s = String::with_capacity(html.len());
+    let mut rest = html;
+
+    while let Some(start) = rest.find('<') {
+        s.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        if rest.starts_with("<!--") {
+            // Comments
+            rest = rest.find("-->").map_or("", |end| &rest[end + 3..]);
+        } else if rest.starts_with("<!") || rest.starts_with("<?") {
+            // Doctypes, CDATA and processing instructions
+            rest = rest.find('>').map_or("", |end| &rest[end + 1..]);
+        } else if let Some((tag, len)) = Tag::parse(rest) {
+            rest = &rest[len..];
+            if !tag.closing && DROPPED.contains(&&tag.name[..]) {
+                rest = skip_content(rest, &tag.name);
+            } else if TAGS.contains(&&tag.name[..]) {
+                tag.write(&mut s);
+            }
+        } else {
+            s.push_str("&lt;");
+            rest = &rest[1..];
+        }
+    }
+    s.push_str(rest);
+
+    s
+}
+
+// The html after the closing tag of the element `name`
+fn skip_content<'a>(html: &'a str, name: &str) -> &'a str {
+    let closing = format!("</{}", name);
+    let lowercase = html.to_ascii_lowercase();
+    match lowercase.find(&closing) {
+        Some(start) => html[start..].find('>').map_or("", |end| &html[start + end + 1..]),
+        None => "",
+    }
+}
+
+struct Tag {
+    name: String,
+    closing: bool,
+    self_closing: bool,
+    attributes: Vec<(String, Option<String>)>,
+}
+
+impl Tag {
+    // Parses the tag at the start of `html`, returns it with its length
+    fn parse(html: &str) -> Option<(Tag, usize)> {
+        let bytes = html.as_bytes();
+        let closing = bytes.get(1) == Some(&b'/');
+        let mut i = if closing { 2 } else { 1 };
+
+        let name_start = i;
+        while i < bytes.len() && (bytes[i] as char).is_ascii_alphanumeric() {
+            i += 1;
+        }
+        if i == name_start || !(bytes[name_start] as char).is_ascii_alphabetic() {
+            return None;
+        }
+        let name = html[name_start..i].to_lowercase();
+
+        let mut tag = Tag {
+            name: name,
+            closing: closing,
+            self_closing: false,
+            attributes: vec![],
+        };
+
+        loop {
+            while i < bytes.len() && bytes[i].is_ascii_whitespace() {
+                i += 1;
+            }
+            match bytes.get(i) {
+                None => return None,
+                Some(&b'>') => return Some((tag, i + 1)),
+                Some(&b'/') => {
+                    tag.self_closing = true;
+                    i += 1;
+                    continue;
+                },
+                _ => {},
+            }
+
+            let attr_start = i;
+            while i < bytes.len() && !bytes[i].is_ascii_whitespace() && !b"=>/".contains(&bytes[i]) {
+                i += 1;
+            }
+            let attr = html[attr_start..i].to_lowercase();
+            if attr.is_empty() {
+                // A stray `=`
+                i += 1;
+                continue;
+            }
+
+            while i < bytes.len() && bytes[i].is_ascii_whitespace() {
+                i += 1;
+            }
+            if bytes.get(i) != Some(&b'=') {
+                tag.attributes.push((attr, None));
+                continue;
+            }
+            i += 1;
+            while i < bytes.len() && bytes[i].is_ascii_whitespace() {
+                i += 1;
+            }
+
+            let value = match bytes.get(i) {
+                Some(&quote) if quote == b'"' || quote == b'\'' => {
+                    let end = html[i + 1..].find(quote as char)? + i + 1;
+                    let value = &html[i + 1..end];
+                    i = end + 1;
+                    value
+                },
+                _ => {
+                    let value_start = i;
+                    while i < bytes.len() && !bytes[i].is_ascii_whitespace() && bytes[i] != b'>' {
+                        i += 1;
+                    }
+                    &html[value_start..i]
+                },
+            };
+            tag.self_closing = false;
+            tag.attributes.push((attr, Some(decode_entities(value))));
+        }
+    }
+
+    // Writes the tag with its allowed attributes
+    fn write(&self, s: &mut String) {
+        if self.closing {
+            s.push_str(&format!("</{}>", self.name));
+            return;
+        }
+
+        let tag_attributes = TAG_ATTRIBUTES.iter()
+            .find(|&&(tag, _)| tag == self.name)
+            .map_or(&[][..], |&(_, attributes)| attributes);
+
+        s.push('<');
+        s.push_str(&self.name);
+        for (attr, value) in &self.attributes {
+            if !ATTRIBUTES.contains(&&attr[..]) && !tag_attributes.contains(&&attr[..]) {
+                continue;
+            }
+            match *value {
+                Some(ref value) if URL_ATTRIBUTES.contains(&&attr[..]) && !is_safe_url(value) => {},
+                Some(ref value) => s.push_str(&format!(" {}=\"{}\"", attr, utils::escape_html(value))),
+                None => {
+                    s.push(' ');
+                    s.push_str(attr);
+                },
+            }
+        }
+        s.push_str(if self.self_closing { " />" } else { ">" });
+    }
+}
+
+// Relative urls and the ones with an allowed scheme
+fn is_safe_url(url: &str) -> bool {
+    // Browsers ignore the whitespace and control characters in the scheme
+    let url: String = url.chars().filter(|c| !c.is_whitespace() && !c.is_control()).collect();
+    let before_path = url.split(['/', '?', '#']).next().unwrap_or_default();
+
+    match before_path.find(':') {
+        Some(colon) => SCHEMES.contains(&&before_path[..colon].to_lowercase()[..]),
+        // An entity left in the scheme could be a colon
+        None => !before_path.contains('&'),
+    }
+}
+
+/// Decodes the numeric character references and the entities of the characters escaped by
+/// pulldown-cmark, the other entities are left as they are
+pub fn decode_entities(value: &str) -> String {
+    let mut s = String::with_capacity(value.len());
+    let mut rest = value;
+
+    while let Some(start) = rest.find('&') {
+        s.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let decoded = rest.find(';').and_then(|end| {
+            let entity = &rest[1..end];
+            let c = match entity {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                _ if entity.starts_with("#x") || entity.starts_with("#X") => {
+                    u32::from_str_radix(&entity[2..], 16).ok().and_then(::std::char::from_u32)
+                },
+                _ if entity.starts_with('#') => entity[1..].parse().ok().and_then(::std::char::from_u32),
+                _ => None,
+            };
+            c.map(|c| (c, end + 1))
+        });
+
+        match decoded {
+            Some((c, len)) => {
+                s.push(c);
+                rest = &rest[len..];
+            },
+            None => {
+                s.push('&');
+                rest = &rest[1..];
+            },
+        }
+    }
+    s.push_str(rest);
+
+    s
+}
+
+
+// ---------------------------------------------------------------------------------------------------------------
+//      Tests
+//
+
+#[test]
+fn test_sanitize() {
+    assert_eq!(sanitize("<p class=\"note\" onclick=\"steal()\">Hi <b style=\"x\">there</b></p>\n<hr />"),
+               "<p class=\"note\">Hi <b>there</b></p>\n<hr />");
+    assert_eq!(sanitize("a<script>alert('<p>')</script>b<STYLE>p {}</Style>c<!-- <p> -->d"), "abcd");
+    assert_eq!(sanitize("<form action=\"x\"><input name=\"q\" formaction=\"y\">Text</form> 1 < 2"),
+               "<input name=\"q\">Text 1 &lt; 2");
+    assert_eq!(sanitize("<img src=x onerror=alert(1) alt='a \"b\"'>"),
+               "<img src=\"x\" alt=\"a &quot;b&quot;\">");
+}
+
+#[test]
+fn test_sanitize_urls() {
+    assert_eq!(sanitize("<a href=\"cli/init.html#usage\">a</a>"), "<a href=\"cli/init.html#usage\">a</a>");
+    assert_eq!(sanitize("<a href=\"https://example.com/a:b\">a</a>"), "<a href=\"https://example.com/a:b\">a</a>");
+    assert_eq!(sanitize("<a href=\"mailto:me@example.com\">a</a>"), "<a href=\"mailto:me@example.com\">a</a>");
+    assert_eq!(sanitize("<a href=\"javascript:alert(1)\">a</a>"), "<a>a</a>");
+    assert_eq!(sanitize("<a href=\" Java\tScript&#58;alert(1)\">a</a>"), "<a>a</a>");
+    assert_eq!(sanitize("<a href=\"javascript&colon;alert(1)\">a</a>"), "<a>a</a>");
+    assert_eq!(sanitize("<img src=\"data:image/svg+xml,...\">"), "<img>");
+}
+
+#[test]
+fn test_sanitize_non_ascii() {
+    assert_eq!(sanitize("<span title=à>ok</span>"), "<span title=\"à\">ok</span>");
+    assert_eq!(sanitize("<span title=Voilà… lang=fr>ok</span>"), "<span title=\"Voilà…\" lang=\"fr\">ok</span>");
+    assert_eq!(sanitize("<span légende=à title=…>ok</span>"), "<span title=\"…\">ok</span>");
+}
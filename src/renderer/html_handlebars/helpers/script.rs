@@ -0,0 +1,141 @@
+use std::path::Path;
+use std::error::Error;
+use std::io;
+
+use book::frontmatter::FrontMatter;
+
+#[cfg(feature = "scripting")]
+use std::fs::File;
+#[cfg(feature = "scripting")]
+use std::io::Read;
+#[cfg(feature = "scripting")]
+use rhai::{Engine, Scope, AST, Dynamic, Map};
+#[cfg(feature = "scripting")]
+use toml;
+
+
+/// Script of the book, relative to its root, transforming the content of the chapters
+pub const SCRIPT: &'static str = "preprocess.rhai";
+
+/// A [Rhai](https://rhai.rs) script defining a `preprocess(content, chapter)` function, that is called with
+/// the markdown of every chapter and returns the markdown to render instead.
+///
+/// `chapter` is a map with the `name`, `path` and `section` of the chapter, and its `front_matter`.
+#[cfg(feature = "scripting")]
+pub struct Script {
+    engine: Engine,
+    ast: AST,
+}
+
+#[cfg(feature = "scripting")]
+impl Script {
+    pub fn load(path: &Path) -> Result<Script, Box<Error>> {
+        let mut source = String::new();
+        try!(try!(File::open(path)).read_to_string(&mut source));
+
+        let engine = Engine::new();
+        let ast = try!(engine.compile(&source).map_err(|e| {
+            io::Error::new(io::ErrorKind::Other, format!("{}: {}", path.display(), e))
+        }));
+        Ok(Script { engine: engine, ast: ast })
+    }
+
+    pub fn run(&self,
+               content: &str,
+               name: &str,
+               path: &Path,
+               section: Option<&str>,
+               front_matter: &FrontMatter)
+               -> Result<String, Box<Error>> {
+        let mut chapter = Map::new();
+        chapter.insert("name".into(), name.to_owned().into());
+        chapter.insert("path".into(), path.to_string_lossy().into_owned().into());
+        chapter.insert("section".into(), section.unwrap_or("").to_owned().into());
+        let values = front_matter.values().iter().map(|(k, v)| (k.as_str().into(), to_dynamic(v))).collect::<Map>();
+        chapter.insert("front_matter".into(), values.into());
+
+        let mut scope = Scope::new();
+        self.engine
+            .call_fn::<String>(&mut scope, &self.ast, "preprocess", (content.to_owned(), chapter))
+            .map_err(|e| {
+                Box::new(io::Error::new(io::ErrorKind::Other,
+                                        format!("{} failed on {}: {}", SCRIPT, path.display(), e))) as Box<Error>
+            })
+    }
+}
+
+#[cfg(feature = "scripting")]
+fn to_dynamic(value: &toml::Value) -> Dynamic {
+    match *value {
+        toml::Value::String(ref s) |
+        toml::Value::Datetime(ref s) => s.clone().into(),
+        toml::Value::Integer(i) => i.into(),
+        toml::Value::Float(f) => f.into(),
+        toml::Value::Boolean(b) => b.into(),
+        toml::Value::Array(ref a) => a.iter().map(to_dynamic).collect::<Vec<_>>().into(),
+        toml::Value::Table(ref t) => t.iter().map(|(k, v)| (k.as_str().into(), to_dynamic(v))).collect::<Map>().into(),
+    }
+}
+
+/// Without the `scripting` feature the books with a script can not be built
+#[cfg(not(feature = "scripting"))]
+pub struct Script;
+
+#[cfg(not(feature = "scripting"))]
+impl Script {
+    pub fn load(path: &Path) -> Result<Script, Box<Error>> {
+        Err(Box::new(io::Error::new(io::ErrorKind::Other,
+                                    format!("{} can not be run, mdBook was built without the `scripting` feature",
+                                            path.display()))))
+    }
+
+    pub fn run(&self, content: &str, _: &str, _: &Path, _: Option<&str>, _: &FrontMatter) -> Result<String, Box<Error>> {
+        Ok(content.to_owned())
+    }
+}
+
+
+// ---------------------------------------------------------------------------------------------------------------
+//      Tests
+//
+
+#[cfg(feature = "scripting")]
+#[test]
+fn test_script() {
+    extern crate tempdir;
+    use std::io::Write;
+
+    let dir = tempdir::TempDir::new("mdbook-script").unwrap();
+    let path = dir.path().join(SCRIPT);
+    File::create(&path).unwrap().write_all(br#"
+        fn preprocess(content, chapter) {
+            let level = chapter.front_matter.level ?? "all";
+            content.replace("%%", "&check;");
+            `${chapter.section} ${chapter.name} (${level}): ${content}`
+        }
+    "#).unwrap();
+
+    let script = Script::load(&path).unwrap();
+    let (front_matter, markdown) = FrontMatter::split("+++\nlevel = \"beginner\"\n+++\nDone %%").unwrap();
+    assert_eq!(script.run(markdown, "Intro", Path::new("intro.md"), Some("1."), &front_matter).unwrap(),
+               "1. Intro (beginner): Done &check;");
+    assert_eq!(script.run("%%", "Preface", Path::new("preface.md"), None, &FrontMatter::new()).unwrap(),
+               " Preface (all): &check;");
+}
+
+#[cfg(feature = "scripting")]
+#[test]
+fn test_script_errors() {
+    extern crate tempdir;
+    use std::io::Write;
+
+    let dir = tempdir::TempDir::new("mdbook-script").unwrap();
+    let path = dir.path().join(SCRIPT);
+    File::create(&path).unwrap().write_all(b"fn preprocess(content, chapter) { content +").unwrap();
+    assert!(Script::load(&path).err().unwrap().to_string().contains(SCRIPT));
+
+    File::create(&path).unwrap().write_all(b"fn preprocess(content, chapter) { throw \"no\"; }").unwrap();
+    let script = Script::load(&path).unwrap();
+    let err = script.run("", "Intro", Path::new("intro.md"), None, &FrontMatter::new()).unwrap_err();
+    assert!(err.to_string().contains("intro.md"));
+}
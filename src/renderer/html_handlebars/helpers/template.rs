@@ -0,0 +1,45 @@
+use std::collections::VecDeque;
+
+use serde_json;
+use serde_json::value::ToJson;
+use handlebars::{Handlebars, HelperDef, RenderError, RenderContext, Helper, Context};
+
+
+/// Handlebars helper defined by a template of the theme, `theme/helpers/name.hbs` for `{{name}}`.
+///
+/// The template is rendered with the data of the page, the hash parameters of the call and its positional
+/// parameters as `args`: `{{note "Beware" level="warning"}}` can use `{{level}}`, `{{args.[0]}}` and `{{title}}`.
+pub struct TemplateHelper {
+    template: String,
+}
+
+impl TemplateHelper {
+    /// Helper rendering the registered template `template`
+    pub fn new(template: &str) -> Self {
+        TemplateHelper { template: template.to_owned() }
+    }
+}
+
+impl HelperDef for TemplateHelper {
+    fn call(&self, c: &Context, h: &Helper, r: &Handlebars, rc: &mut RenderContext) -> Result<(), RenderError> {
+        debug!("[fn]: {} (theme helper)", h.name());
+
+        let mut data = match *c.navigate(rc.get_path(), &VecDeque::new(), "this") {
+            serde_json::Value::Object(ref m) => m.clone(),
+            _ => serde_json::Map::new(),
+        };
+        for (key, value) in h.hash() {
+            data.insert(key.clone(), value.value().clone());
+        }
+        let args = h.params().iter().map(|p| p.value().clone()).collect::<Vec<_>>();
+        data.insert("args".to_owned(), args.to_json());
+
+        // The error is given its own location, the template calling the helper would replace its name
+        let rendered = try!(r.render(&self.template, &data).map_err(|e| match (e.line_no, e.column_no) {
+            (Some(line), Some(col)) => RenderError::new(format!("{}.hbs:{}:{}: {}", self.template, line, col, e.desc)),
+            _ => RenderError::new(format!("{}.hbs: {}", self.template, e.desc)),
+        }));
+        try!(rc.writer.write_all(rendered.as_bytes()));
+        Ok(())
+    }
+}
@@ -1,9 +1,11 @@
 use std::path::Path;
+use std::ops::Range;
 use std::collections::{VecDeque, BTreeMap};
 
 use serde_json;
 use handlebars::{Handlebars, HelperDef, RenderError, RenderContext, Helper, Context};
 use pulldown_cmark::{Parser, html, Event, Tag};
+use utils;
 
 // Handlebars helper to construct TOC
 #[derive(Clone, Copy)]
@@ -17,14 +19,28 @@ impl HelperDef for RenderToc {
         // param is the key of value you want to display
         let chapters = c.navigate(rc.get_path(), &VecDeque::new(), "chapters");
         let current = c.navigate(rc.get_path(), &VecDeque::new(), "path").to_string().replace("\"", "");
+        let toc_depth = c.navigate(rc.get_path(), &VecDeque::new(), "toc_depth").as_u64();
+        let navbar = c.navigate(rc.get_path(), &VecDeque::new(), "navbar").as_bool().unwrap_or(false);
         try!(rc.writer.write("<ul class=\"chapter\">".as_bytes()));
 
         // Decode json format
-        let decoded: Vec<BTreeMap<String, String>> = serde_json::from_str(&chapters.to_string()).unwrap();
+        let mut decoded: Vec<BTreeMap<String, String>> = serde_json::from_str(&chapters.to_string()).unwrap();
+
+        // With the navbar, the sidebar only lists the part of the current chapter
+        if navbar {
+            if let Some(part) = current_part(&decoded, &current) {
+                decoded = decoded[part].to_vec();
+            }
+        }
+
+        // Leave out the chapters nested deeper than `toc-depth`
+        if let Some(depth) = toc_depth {
+            decoded.retain(|item| item.get("spacer").is_some() || item_level(item) as u64 <= depth);
+        }
 
         let mut current_level = 1;
 
-        for item in decoded {
+        for (i, item) in decoded.iter().enumerate() {
 
             // Spacer
             if let Some(_) = item.get("spacer") {
@@ -32,11 +48,7 @@ impl HelperDef for RenderToc {
                 continue;
             }
 
-            let level = if let Some(s) = item.get("section") {
-                s.len() / 2
-            } else {
-                1
-            };
+            let level = item_level(item);
 
             if level > current_level {
                 while level > current_level {
@@ -44,36 +56,29 @@ impl HelperDef for RenderToc {
                     try!(rc.writer.write("<ul class=\"section\">".as_bytes()));
                     current_level += 1;
                 }
-                try!(rc.writer.write("<li>".as_bytes()));
             } else if level < current_level {
                 while level < current_level {
                     try!(rc.writer.write("</ul>".as_bytes()));
                     try!(rc.writer.write("</li>".as_bytes()));
                     current_level -= 1;
                 }
-                try!(rc.writer.write("<li>".as_bytes()));
-            } else {
-                try!(rc.writer.write("<li".as_bytes()));
-                if let None = item.get("section") {
-                    try!(rc.writer.write(" class=\"affix\"".as_bytes()));
-                }
-                try!(rc.writer.write(">".as_bytes()));
             }
 
+            try!(rc.writer.write("<li".as_bytes()));
+            if let None = item.get("section") {
+                try!(rc.writer.write(" class=\"affix\"".as_bytes()));
+            } else if item.get("kind").map(|k| &k[..]) == Some("appendix") {
+                try!(rc.writer.write(" class=\"appendix\"".as_bytes()));
+            }
+            try!(rc.writer.write(">".as_bytes()));
+
             // Link
             let path_exists = if let Some(path) = item.get("path") {
                 if !path.is_empty() {
                     try!(rc.writer.write("<a href=\"".as_bytes()));
 
                     // Add link
-                    try!(rc.writer.write(Path::new(item.get("path")
-                                                       .expect("Error: path should be Some(_)"))
-                                             .with_extension("html")
-                                             .to_str()
-                                             .unwrap()
-                                             // Hack for windows who tends to use `\` as separator instead of `/`
-                                             .replace("\\", "/")
-                                             .as_bytes()));
+                    try!(rc.writer.write(utils::fs::path_to_link(Path::new(path)).as_bytes()));
 
                     try!(rc.writer.write("\"".as_bytes()));
 
@@ -98,31 +103,26 @@ impl HelperDef for RenderToc {
             }
 
             if let Some(name) = item.get("name") {
-                // Render only inline code blocks
-
-                // filter all events that are not inline code blocks
-                let parser = Parser::new(&name).filter(|event| {
-                    match event {
-                        &Event::Start(Tag::Code) |
-                        &Event::End(Tag::Code) => true,
-                        &Event::InlineHtml(_) => true,
-                        &Event::Text(_) => true,
-                        _ => false,
-                    }
-                });
-
-                // render markdown to html
-                let mut markdown_parsed_name = String::with_capacity(name.len() * 3 / 2);
-                html::push_html(&mut markdown_parsed_name, parser);
-
-                // write to the handlebars template
-                try!(rc.writer.write(markdown_parsed_name.as_bytes()));
+                try!(rc.writer.write(name_html(name).as_bytes()));
             }
 
             if path_exists {
                 try!(rc.writer.write("</a>".as_bytes()));
             }
 
+            // Chapters with sub-chapters get a button to collapse / expand the section
+            let has_sub_items = match decoded.get(i + 1) {
+                Some(next) if next.get("spacer").is_none() => item_level(next) > level,
+                _ => false,
+            };
+
+            if has_sub_items {
+                try!(rc.writer.write("<a class=\"toggle\" role=\"button\" tabindex=\"0\" aria-expanded=\"true\" \
+                                      aria-label=\"Toggle section\"><i class=\"fa fa-angle-down\" \
+                                      aria-hidden=\"true\"></i></a>"
+                    .as_bytes()));
+            }
+
             try!(rc.writer.write("</li>".as_bytes()));
 
         }
@@ -136,3 +136,111 @@ impl HelperDef for RenderToc {
         Ok(())
     }
 }
+
+// Handlebars helper to construct the navbar, the top level chapters with the part of the current chapter active
+#[derive(Clone, Copy)]
+pub struct RenderNavbar;
+
+impl HelperDef for RenderNavbar {
+    fn call(&self, c: &Context, _h: &Helper, _: &Handlebars, rc: &mut RenderContext) -> Result<(), RenderError> {
+        let chapters = c.navigate(rc.get_path(), &VecDeque::new(), "chapters");
+        let current = c.navigate(rc.get_path(), &VecDeque::new(), "path").to_string().replace("\"", "");
+        let decoded: Vec<BTreeMap<String, String>> = serde_json::from_str(&chapters.to_string()).unwrap();
+        let part = current_part(&decoded, &current).map(|part| part.start);
+
+        try!(rc.writer.write("<ul class=\"navbar-parts\">".as_bytes()));
+        for (i, item) in decoded.iter().enumerate() {
+            if item.contains_key("spacer") || item_level(item) > 1 {
+                continue;
+            }
+            let name = item.get("name").map(|name| name_html(name)).unwrap_or_default();
+            let li = match item.get("path").filter(|path| !path.is_empty()) {
+                Some(path) => {
+                    let link = utils::fs::path_to_link(Path::new(path));
+                    let active = if part == Some(i) { " class=\"active\"" } else { "" };
+                    format!("<li><a href=\"{}\"{}>{}</a></li>", link, active, name)
+                },
+                None => format!("<li>{}</li>", name),
+            };
+            try!(rc.writer.write(li.as_bytes()));
+        }
+        try!(rc.writer.write("</ul>".as_bytes()));
+        Ok(())
+    }
+}
+
+// The items of the part containing the chapter at `path`: its top level chapter and the chapters nested in it
+fn current_part(items: &[BTreeMap<String, String>], path: &str) -> Option<Range<usize>> {
+    let position = items.iter().position(|item| item.get("path").map(|p| &p[..]) == Some(path))?;
+    let start = (0..position + 1).rev().find(|&i| !items[i].contains_key("spacer") && item_level(&items[i]) == 1)?;
+    let end = (start + 1..items.len())
+        .find(|&i| items[i].contains_key("spacer") || item_level(&items[i]) == 1)
+        .unwrap_or(items.len());
+    Some(start..end)
+}
+
+// Renders only inline code blocks and emphasis of the name of a chapter
+fn name_html(name: &str) -> String {
+    // filter all events that are not inline code blocks or emphasis
+    let parser = Parser::new(name).filter(|event| {
+        match event {
+            &Event::Start(Tag::Code) |
+            &Event::End(Tag::Code) |
+            &Event::Start(Tag::Emphasis) |
+            &Event::End(Tag::Emphasis) |
+            &Event::Start(Tag::Strong) |
+            &Event::End(Tag::Strong) => true,
+            &Event::InlineHtml(_) => true,
+            &Event::Text(_) => true,
+            _ => false,
+        }
+    });
+
+    // render markdown to html
+    let mut markdown_parsed_name = String::with_capacity(name.len() * 3 / 2);
+    html::push_html(&mut markdown_parsed_name, parser);
+    markdown_parsed_name
+}
+
+// Nesting level of a chapter in the TOC, the sections "1.2." and "A.2." for example are on level 2
+fn item_level(item: &BTreeMap<String, String>) -> usize {
+    if let Some(s) = item.get("section") {
+        s.matches('.').count()
+    } else {
+        1
+    }
+}
+
+
+// ---------------------------------------------------------------------------------
+//      Tests
+//
+
+#[test]
+fn test_current_part() {
+    let item = |path: &str, section: Option<&str>| {
+        let mut item = BTreeMap::new();
+        item.insert("path".to_owned(), path.to_owned());
+        if let Some(section) = section {
+            item.insert("section".to_owned(), section.to_owned());
+        }
+        item
+    };
+    let mut spacer = BTreeMap::new();
+    spacer.insert("spacer".to_owned(), "_spacer_".to_owned());
+
+    let items = vec![item("README.md", None),
+                     item("guide/README.md", Some("1.")),
+                     item("guide/install.md", Some("1.1.")),
+                     item("guide/install/linux.md", Some("1.1.1.")),
+                     item("reference/README.md", Some("2.")),
+                     item("reference/cli.md", Some("2.1.")),
+                     spacer,
+                     item("credits.md", None)];
+
+    assert_eq!(current_part(&items, "README.md"), Some(0..1));
+    assert_eq!(current_part(&items, "guide/install/linux.md"), Some(1..4));
+    assert_eq!(current_part(&items, "reference/README.md"), Some(4..6));
+    assert_eq!(current_part(&items, "credits.md"), Some(7..8));
+    assert_eq!(current_part(&items, "print.md"), None);
+}
@@ -0,0 +1,118 @@
+use std::env;
+use std::path::Path;
+use std::collections::BTreeMap;
+
+use renderer::Warning;
+
+
+/// Prefix of the environment variables the chapters can read, so that the other variables of the
+/// environment, like the secrets of a CI, can not end up in the book
+const ENV_PREFIX: &'static str = "MDBOOK_VAR_";
+
+/// Replaces the `{{ book.name }}` variables of a chapter by their value in `variables`, and the
+/// `{{ env.NAME }}` variables by the value of the environment variable `MDBOOK_VAR_NAME`. A
+/// backslash in front of a variable, `\{{ book.name }}`, escapes it.
+///
+/// Other `{{ ... }}` are left untouched, so that handlebars expressions can still be shown in the
+/// book. Unknown variables are left as they are, with a warning.
+pub fn replace_variables(s: &str,
+                         variables: &BTreeMap<String, String>,
+                         chapter: &Path,
+                         warnings: &mut Vec<Warning>)
+                         -> String {
+    let mut replaced = String::with_capacity(s.len());
+    let mut rest = s;
+
+    while let Some(start) = rest.find("{{") {
+        let (before, variable) = rest.split_at(start);
+
+        let end = match variable.find("}}") {
+            Some(end) if !variable[..end].contains('\n') => end + "}}".len(),
+            _ => {
+                replaced.push_str(before);
+                replaced.push_str("{{");
+                rest = &variable[2..];
+                continue;
+            },
+        };
+
+        let name = variable[2..end - 2].trim();
+        rest = &variable[end..];
+
+        let (namespace, key) = match name.find('.') {
+            Some(dot) if is_variable(name) => (&name[..dot], &name[dot + 1..]),
+            _ => {
+                replaced.push_str(before);
+                replaced.push_str(&variable[..end]);
+                continue;
+            },
+        };
+
+        if let Some(before) = before.strip_suffix('\\') {
+            replaced.push_str(before);
+            replaced.push_str(&variable[..end]);
+            continue;
+        }
+        replaced.push_str(before);
+
+        let value = if namespace == "book" {
+            variables.get(key).cloned()
+        } else {
+            env::var(format!("{}{}", ENV_PREFIX, key)).ok()
+        };
+
+        match value {
+            Some(value) => replaced.push_str(&value),
+            None if namespace == "book" => {
+                warnings.push(Warning::new(chapter, format!("unknown variable `{}`", name)));
+                replaced.push_str(&variable[..end]);
+            },
+            None => {
+                warnings.push(Warning::new(chapter,
+                                           format!("no environment variable `{}{}` for `{}`", ENV_PREFIX, key, name)));
+                replaced.push_str(&variable[..end]);
+            },
+        }
+    }
+
+    replaced.push_str(rest);
+    replaced
+}
+
+// `book.name` or `env.NAME`
+fn is_variable(name: &str) -> bool {
+    let key = if let Some(key) = name.strip_prefix("book.") {
+        key
+    } else if let Some(key) = name.strip_prefix("env.") {
+        key
+    } else {
+        return false;
+    };
+
+    !key.is_empty() && key.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '-')
+}
+
+
+// ---------------------------------------------------------------------------------
+//      Tests
+//
+
+#[test]
+fn test_replace_variables() {
+    let mut variables = BTreeMap::new();
+    variables.insert("version".to_owned(), "1.2.0".to_owned());
+    env::set_var("MDBOOK_VAR_TEST_VARIABLE", "from env");
+    env::set_var("MDBOOK_SECRET", "secret");
+
+    let s = "v{{ book.version }}, {{book.version}} \\{{ book.version }} {{ env.TEST_VARIABLE }} \
+             {{ title }} {{#playpen a.rs}} {{ book.missing }} {{ book.version\n}} {{ env.MDBOOK_SECRET }}";
+
+    let mut warnings = vec![];
+    assert_eq!(replace_variables(s, &variables, Path::new("intro.md"), &mut warnings),
+               "v1.2.0, 1.2.0 {{ book.version }} from env \
+                {{ title }} {{#playpen a.rs}} {{ book.missing }} {{ book.version\n}} {{ env.MDBOOK_SECRET }}");
+    assert_eq!(warnings,
+               vec![Warning::new(Path::new("intro.md"), "unknown variable `book.missing`"),
+                    Warning::new(Path::new("intro.md"),
+                                 "no environment variable `MDBOOK_VAR_MDBOOK_SECRET` for `env.MDBOOK_SECRET`")]);
+}
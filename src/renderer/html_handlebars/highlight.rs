@@ -0,0 +1,222 @@
+use std::collections::{BTreeSet, HashSet};
+use std::path::Path;
+
+use pulldown_cmark::{Event, Tag};
+
+use renderer::Warning;
+
+const REGISTER: &'static str = "hljs.registerLanguage(";
+
+/// The classes highlight.js leaves as they are, and the `output` blocks of `mdbook test`
+const PLAIN: &'static [&'static str] = &["text", "plain", "nohighlight", "no-highlight", "output"];
+
+
+/// A highlight.js bundle, the core of the library followed by the `hljs.registerLanguage("name", ...)` calls
+/// of its languages, like the one of the default theme
+pub struct Bundle<'a> {
+    core: &'a str,
+    languages: Vec<Language<'a>>,
+}
+
+struct Language<'a> {
+    name: &'a str,
+    /// The whole `hljs.registerLanguage(...);` call
+    source: &'a str,
+}
+
+impl<'a> Bundle<'a> {
+    /// Returns `None` when the file is not a bundle, e.g. a custom build of highlight.js
+    pub fn parse(source: &'a str) -> Option<Self> {
+        let start = source.find(REGISTER)?;
+        let mut languages = vec![];
+
+        let mut rest = &source[start..];
+        while !rest.is_empty() {
+            let end = rest[REGISTER.len()..].find(REGISTER).map_or(rest.len(), |i| i + REGISTER.len());
+            let call = &rest[..end];
+            let name = call[REGISTER.len()..]
+                .trim_start_matches(|c: char| c == '"' || c == '\'' || c.is_whitespace())
+                .split(['"', '\''])
+                .next()
+                .unwrap_or_default();
+            languages.push(Language { name: name, source: call });
+            rest = &rest[end..];
+        }
+
+        Some(Bundle {
+            core: &source[..start],
+            languages: languages,
+        })
+    }
+
+    /// The bundle with only the `languages`, given by name or alias, and the languages they embed, like the
+    /// html of `handlebars`. Also returns the languages the bundle does not have.
+    pub fn select(&self, languages: &[String]) -> (String, Vec<String>) {
+        let mut selected = BTreeSet::new();
+        let mut unknown = vec![];
+
+        // The embedded languages missing from the bundle, like `actionscript` in `xml`, are not reported
+        let mut queue = languages.iter().map(|l| (l.to_lowercase(), true)).collect::<Vec<_>>();
+        while let Some((wanted, requested)) = queue.pop() {
+            match self.languages.iter().position(|l| l.name == wanted || l.aliases().contains(&&wanted[..])) {
+                Some(i) => {
+                    if selected.insert(i) {
+                        queue.extend(self.languages[i].sub_languages().iter().map(|l| (l.to_string(), false)));
+                    }
+                },
+                None if requested => unknown.push(wanted),
+                None => {},
+            }
+        }
+
+        let mut s = self.core.to_owned();
+        for i in selected {
+            s.push_str(self.languages[i].source);
+        }
+        (s, unknown)
+    }
+
+    /// The names and aliases of the languages of the bundle, in lowercase
+    pub fn language_names(&self) -> HashSet<String> {
+        let mut names = HashSet::new();
+        for language in &self.languages {
+            names.insert(language.name.to_lowercase());
+            names.extend(language.aliases().iter().map(|a| a.to_lowercase()));
+        }
+        names
+    }
+}
+
+/// Removes the language of the code blocks that highlight.js does not know, given the `languages` of the
+/// bundle, so that they are rendered as plain text
+pub fn plain_unknown_languages<'a>(events: Vec<Event<'a>>,
+                                   languages: &HashSet<String>,
+                                   chapter: &Path,
+                                   warnings: &mut Vec<Warning>)
+                                   -> Vec<Event<'a>> {
+    events.into_iter()
+        .map(|event| match event {
+            Event::Start(Tag::CodeBlock(info)) => {
+                // The language is followed by the attributes of the block, like `rust,ignore`
+                let language = info.split(|c: char| c == ',' || c.is_whitespace()).next().unwrap_or_default();
+                let language = language.to_lowercase();
+                if language.is_empty() || languages.contains(&language) || PLAIN.contains(&&language[..]) {
+                    return Event::Start(Tag::CodeBlock(info));
+                }
+                warnings.push(Warning::new(chapter, format!("unknown language `{}`, the code block is not highlighted",
+                                                            language)));
+                Event::Start(Tag::CodeBlock("".into()))
+            },
+            event => event,
+        })
+        .collect()
+}
+
+impl<'a> Language<'a> {
+    // `aliases:["rs"]` in the minified definitions
+    fn aliases(&self) -> Vec<&'a str> {
+        names_of(self.source, "aliases")
+    }
+
+    // The languages embedded with `sL:"xml"` or `sL:["xml","ruby"]`, `subLanguage` when not minified
+    fn sub_languages(&self) -> Vec<&'a str> {
+        let mut languages = names_of(self.source, "sL");
+        languages.extend(names_of(self.source, "subLanguage"));
+        languages
+    }
+}
+
+// The names given to `key` in a definition, as a string or an array of strings. Only the names written in the
+// definition are returned, not the variables.
+fn names_of<'a>(source: &'a str, key: &str) -> Vec<&'a str> {
+    let is_quote = |c: char| c == '"' || c == '\'';
+    let mut names = vec![];
+    let mut rest = source;
+
+    while let Some(start) = rest.find(key) {
+        rest = &rest[start + key.len()..];
+        let value = match rest.trim_start().strip_prefix(':') {
+            Some(value) => value.trim_start(),
+            None => continue,
+        };
+        let end = match value.chars().next() {
+            Some('[') => value.find(']').unwrap_or(0),
+            Some(c) if is_quote(c) => value[1..].find(c).map_or(0, |i| i + 1),
+            _ => 0,
+        };
+        let trimmed = |c: char| is_quote(c) || c == '[' || c.is_whitespace();
+        names.extend(value[..end].split(',').map(|n| n.trim_matches(trimmed)));
+    }
+    names.retain(|n| !n.is_empty());
+    names
+}
+
+
+// ---------------------------------------------------------------------------------------------------------------
+//      Tests
+//
+
+#[test]
+fn test_select_languages() {
+    let source = "/*! core */var hljs={};\
+                  hljs.registerLanguage(\"rust\",function(e){return{aliases:[\"rs\"],c:[]}});\
+                  hljs.registerLanguage(\"xml\",function(e){return{aliases:[\"html\",\"xhtml\"],c:[]}});\
+                  hljs.registerLanguage(\"ini\",function(e){return{aliases:[\"toml\"],c:[]}});\
+                  hljs.registerLanguage(\"handlebars\",function(e){return{sL:[\"xml\",\"mustache\"],c:[]}});";
+    let bundle = Bundle::parse(source).unwrap();
+
+    let (trimmed, unknown) = bundle.select(&["RS".to_owned(), "toml".to_owned(), "klingon".to_owned()]);
+    assert_eq!(trimmed,
+               "/*! core */var hljs={};\
+                hljs.registerLanguage(\"rust\",function(e){return{aliases:[\"rs\"],c:[]}});\
+                hljs.registerLanguage(\"ini\",function(e){return{aliases:[\"toml\"],c:[]}});");
+    assert_eq!(unknown, vec!["klingon"]);
+
+    let (trimmed, unknown) = bundle.select(&["handlebars".to_owned()]);
+    assert!(unknown.is_empty());
+    assert!(trimmed.contains("registerLanguage(\"xml\"") && !trimmed.contains("registerLanguage(\"rust\""));
+
+    assert!(Bundle::parse("var custom = 1;").is_none());
+}
+
+#[test]
+fn test_plain_unknown_languages() {
+    let bundle = Bundle::parse("hljs.registerLanguage(\"rust\",function(e){return{aliases:[\"rs\"]}});\
+                                hljs.registerLanguage('gleam', function(hljs) { return { aliases: ['gl'] }; });")
+        .unwrap();
+    let languages = bundle.language_names();
+
+    let markdown = "```rust,ignore\n```\n\n```RS\n```\n\n```gleam\n```\n\n```gl\n```\n\n```\n```\n\n\
+                    ```text\n```\n\n```klingon extra\n```";
+    let mut warnings = vec![];
+    let events = plain_unknown_languages(::pulldown_cmark::Parser::new(markdown).collect(),
+                                         &languages,
+                                         Path::new("intro.md"),
+                                         &mut warnings);
+
+    let mut html = String::new();
+    ::pulldown_cmark::html::push_html(&mut html, events.into_iter());
+    assert_eq!(html,
+               "<pre><code class=\"language-rust,ignore\"></code></pre>\n\
+                <pre><code class=\"language-RS\"></code></pre>\n\
+                <pre><code class=\"language-gleam\"></code></pre>\n\
+                <pre><code class=\"language-gl\"></code></pre>\n\
+                <pre><code></code></pre>\n\
+                <pre><code class=\"language-text\"></code></pre>\n\
+                <pre><code></code></pre>\n");
+    assert_eq!(warnings.len(), 1);
+}
+
+#[test]
+fn test_default_bundle() {
+    let source = ::std::str::from_utf8(::theme::HIGHLIGHT_JS).unwrap();
+    let bundle = Bundle::parse(source).unwrap();
+    assert_eq!(bundle.languages.len(), 67);
+
+    let (trimmed, unknown) = bundle.select(&["rust".to_owned(), "toml".to_owned(), "sh".to_owned()]);
+    assert!(unknown.is_empty());
+    assert!(trimmed.len() < source.len() / 4);
+    for name in &["rust", "ini", "bash"] {
+        assert!(trimmed.contains(&format!("{}\"{}\"", REGISTER, name)));
+    }
+}
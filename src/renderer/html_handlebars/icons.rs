@@ -0,0 +1,127 @@
+use std::path::Path;
+use std::fs::File;
+use std::io::Read;
+
+use book::htmlconfig::HtmlConfig;
+use renderer::Warning;
+
+
+/// Files of the icons, at the root of the book
+pub const FAVICON_PNG: &'static str = "favicon.png";
+pub const FAVICON_ICO: &'static str = "favicon.ico";
+pub const FAVICON_SVG: &'static str = "favicon.svg";
+pub const APPLE_TOUCH_ICON: &'static str = "apple-touch-icon.png";
+
+const PNG_SIGNATURE: &'static [u8] = b"\x89PNG\r\n\x1a\n";
+
+/// Whether the `favicon` of the config is an SVG image, by its extension. The other ones are PNG images.
+pub fn is_svg(path: &Path) -> bool {
+    path.extension().and_then(|ext| ext.to_str()).is_some_and(|ext| ext.eq_ignore_ascii_case("svg"))
+}
+
+/// Whether the book has an `apple-touch-icon.png`: the `apple-touch-icon` of the config, or the favicon when it is
+/// a PNG image
+pub fn has_apple_touch_icon(config: &HtmlConfig) -> bool {
+    config.apple_touch_icon.is_some() || config.favicon.as_ref().is_some_and(|favicon| !is_svg(favicon))
+}
+
+/// The icon files of the book with their content. `favicon.png` is the `favicon` of the config when it is a PNG
+/// image, or the one of the theme. With a `favicon` in the config, `favicon.ico` has the PNG image in it, an SVG
+/// favicon is `favicon.svg`, and the icon of the home screens of iOS is `apple-touch-icon.png`. The images that can
+/// not be read are warnings, the icons of the theme are used instead.
+pub fn icon_files(root: &Path,
+                  config: &HtmlConfig,
+                  theme_favicon: &[u8],
+                  warnings: &mut Vec<Warning>)
+                  -> Vec<(&'static str, Vec<u8>)> {
+    let mut files = vec![];
+    let mut png = theme_favicon.to_owned();
+
+    if let Some(ref favicon) = config.favicon {
+        if let Some(content) = read_image(root, favicon, "favicon", warnings) {
+            if is_svg(favicon) {
+                files.push((FAVICON_SVG, content));
+            } else {
+                png = content.clone();
+                if config.apple_touch_icon.is_none() {
+                    files.push((APPLE_TOUCH_ICON, content));
+                }
+            }
+        }
+        if let Some(ico) = ico_from_png(&png) {
+            files.push((FAVICON_ICO, ico));
+        }
+    }
+    if let Some(ref icon) = config.apple_touch_icon {
+        if let Some(content) = read_image(root, icon, "apple-touch-icon", warnings) {
+            files.push((APPLE_TOUCH_ICON, content));
+        }
+    }
+
+    files.push((FAVICON_PNG, png));
+    files
+}
+
+// The content of an image of the config, relative to the root of the book, which has to be a PNG image unless it
+// is an SVG one
+fn read_image(root: &Path, path: &Path, key: &str, warnings: &mut Vec<Warning>) -> Option<Vec<u8>> {
+    let mut content = vec![];
+    if File::open(root.join(path)).and_then(|mut f| f.read_to_end(&mut content)).is_err() {
+        warnings.push(Warning::global(format!("the {} `{}` can not be read", key, path.display())));
+        return None;
+    }
+    if !is_svg(path) && png_size(&content).is_none() {
+        warnings.push(Warning::global(format!("the {} `{}` is not a PNG image, nor an SVG image with the `.svg` \
+                                               extension",
+                                              key,
+                                              path.display())));
+        return None;
+    }
+    Some(content)
+}
+
+/// Width and height of a PNG image, `None` when it is not one
+pub fn png_size(png: &[u8]) -> Option<(u32, u32)> {
+    // The `IHDR` chunk comes first, after its length
+    if png.len() < 24 || !png.starts_with(PNG_SIGNATURE) || &png[12..16] != b"IHDR" {
+        return None;
+    }
+    let number = |bytes: &[u8]| bytes.iter().fold(0u32, |n, &b| (n << 8) | u32::from(b));
+    Some((number(&png[16..20]), number(&png[20..24])))
+}
+
+/// A `.ico` file with the PNG image in it, as all the browsers read them
+pub fn ico_from_png(png: &[u8]) -> Option<Vec<u8>> {
+    let (width, height) = png_size(png)?;
+    // A size of 256 pixels or more is written 0
+    let size = |pixels: u32| if pixels >= 256 { 0 } else { pixels as u8 };
+
+    let mut ico = vec![0, 0, 1, 0, 1, 0];
+    ico.extend_from_slice(&[size(width), size(height), 0, 0]);
+    ico.extend_from_slice(&1u16.to_le_bytes());
+    ico.extend_from_slice(&32u16.to_le_bytes());
+    ico.extend_from_slice(&(png.len() as u32).to_le_bytes());
+    ico.extend_from_slice(&22u32.to_le_bytes());
+    ico.extend_from_slice(png);
+    Some(ico)
+}
+
+
+// ---------------------------------------------------------------------------------
+//      Tests
+//
+
+#[test]
+fn test_ico_from_png() {
+    use theme;
+
+    assert!(png_size(b"GIF89a").is_none());
+    let (width, height) = png_size(theme::FAVICON).unwrap();
+
+    let ico = ico_from_png(theme::FAVICON).unwrap();
+    assert_eq!(&ico[..6], &[0, 0, 1, 0, 1, 0]);
+    assert_eq!(&ico[6..8], &[width as u8, height as u8]);
+    assert_eq!(&ico[14..18], &(theme::FAVICON.len() as u32).to_le_bytes());
+    assert_eq!(&ico[18..22], &[22, 0, 0, 0]);
+    assert_eq!(&ico[22..], theme::FAVICON);
+}
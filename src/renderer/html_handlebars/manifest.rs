@@ -0,0 +1,90 @@
+use std::path::Path;
+use std::fs::File;
+use std::io::Read;
+use std::error::Error;
+use std::collections::BTreeMap;
+
+use serde_json;
+use serde_json::value::ToJson;
+
+use book::htmlconfig::ManifestConfig;
+use renderer::Warning;
+
+
+/// File name of the manifest, at the root of the book
+pub const MANIFEST: &'static str = "manifest.json";
+
+/// Generates the Web App Manifest of the book, the icons are read from `dest` to find their sizes.
+/// Without icons in the config, the favicon of the theme is used.
+pub fn web_app_manifest(title: &str,
+                        description: &str,
+                        config: &ManifestConfig,
+                        dest: &Path,
+                        warnings: &mut Vec<Warning>)
+                        -> Result<String, Box<Error>> {
+    let default_icons = vec!["favicon.png".to_owned()];
+    let icons = if config.icons.is_empty() { &default_icons } else { &config.icons };
+
+    let mut icon_list = vec![];
+    for icon in icons {
+        let mut content = vec![];
+        if let Err(e) = File::open(dest.join(icon)).and_then(|mut f| f.read_to_end(&mut content)) {
+            warnings.push(Warning::new(Path::new(icon), format!("could not read the icon of the manifest: {}", e)));
+            continue;
+        }
+
+        let mut entry = BTreeMap::new();
+        entry.insert("src".to_owned(), icon.to_json());
+        entry.insert("type".to_owned(), mime_type(icon).to_json());
+        entry.insert("sizes".to_owned(), png_size(&content).map_or("any".to_owned(), |(w, h)| format!("{}x{}", w, h)).to_json());
+        icon_list.push(entry);
+    }
+
+    let mut manifest = BTreeMap::new();
+    manifest.insert("name".to_owned(), title.to_json());
+    manifest.insert("short_name".to_owned(), config.short_name.as_ref().map_or(title, |s| &s[..]).to_json());
+    if !description.is_empty() {
+        manifest.insert("description".to_owned(), description.to_json());
+    }
+    manifest.insert("start_url".to_owned(), "index.html".to_json());
+    manifest.insert("scope".to_owned(), "./".to_json());
+    manifest.insert("display".to_owned(), "standalone".to_json());
+    manifest.insert("theme_color".to_owned(), config.theme_color.to_json());
+    manifest.insert("background_color".to_owned(), config.background_color.to_json());
+    manifest.insert("icons".to_owned(), icon_list.to_json());
+
+    Ok(try!(serde_json::to_string_pretty(&manifest)))
+}
+
+fn mime_type(icon: &str) -> &'static str {
+    match Path::new(icon).extension().and_then(|e| e.to_str()) {
+        Some("svg") => "image/svg+xml",
+        Some("ico") => "image/x-icon",
+        Some("jpg") | Some("jpeg") => "image/jpeg",
+        Some("webp") => "image/webp",
+        _ => "image/png",
+    }
+}
+
+// Width and height from the header of a PNG image
+fn png_size(content: &[u8]) -> Option<(u32, u32)> {
+    if content.len() < 24 || &content[..8] != b"\x89PNG\r\n\x1a\n" || &content[12..16] != b"IHDR" {
+        return None;
+    }
+
+    let number = |b: &[u8]| b.iter().fold(0u32, |n, byte| (n << 8) | *byte as u32);
+    Some((number(&content[16..20]), number(&content[20..24])))
+}
+
+
+// ---------------------------------------------------------------------------------
+//      Tests
+//
+
+#[test]
+fn test_png_size() {
+    use theme;
+
+    assert_eq!(png_size(theme::FAVICON), Some((196, 196)));
+    assert_eq!(png_size(b"<svg></svg>"), None);
+}
@@ -2,3 +2,24 @@ pub use self::hbs_renderer::HtmlHandlebars;
 
 mod hbs_renderer;
 mod helpers;
+mod templates;
+mod search;
+mod glossary;
+mod bookindex;
+mod offline;
+mod manifest;
+mod icons;
+mod chapters;
+mod archive;
+mod print;
+mod highlight;
+mod tags;
+mod atom;
+mod changes;
+mod style;
+mod redirects;
+mod robots;
+mod structured_data;
+mod split;
+mod sitemap;
+mod check;
@@ -0,0 +1,97 @@
+use std::path::Path;
+use std::fs::File;
+use std::io::Read;
+use std::error::Error;
+
+use serde_json;
+use sha2::{Digest, Sha256};
+
+use renderer::html_handlebars::archive::ARCHIVE;
+use utils;
+
+
+/// File name of the service worker, at the root of the book
+pub const SERVICE_WORKER: &'static str = "sw.js";
+
+// `{{cache}}` is replaced by the name of the cache and `{{files}}` by the files of the book, as a
+// JSON array
+const TEMPLATE: &'static str = r#"// Generated by mdBook, caches the whole book so that it can be read offline
+var CACHE = "mdbook-" + self.registration.scope + "-{{cache}}";
+var FILES = {{files}};
+
+self.addEventListener("install", function (event) {
+    event.waitUntil(caches.open(CACHE).then(function (cache) {
+        return cache.addAll(FILES);
+    }));
+});
+
+// The caches of the previous builds of this book are removed
+self.addEventListener("activate", function (event) {
+    var prefix = "mdbook-" + self.registration.scope + "-";
+    event.waitUntil(caches.keys().then(function (names) {
+        return Promise.all(names.filter(function (name) {
+            return name.indexOf(prefix) === 0 && name !== CACHE;
+        }).map(function (name) {
+            return caches.delete(name);
+        }));
+    }));
+});
+
+self.addEventListener("fetch", function (event) {
+    event.respondWith(caches.match(event.request, { ignoreSearch: true }).then(function (response) {
+        return response || fetch(event.request);
+    }));
+});
+"#;
+
+/// Generates a service worker caching every file of the rendered book in `dest`. The name of the
+/// cache contains a hash of the files, so that browsers replace the cached book when it is built
+/// again with changes.
+pub fn service_worker(dest: &Path) -> Result<String, Box<Error>> {
+    // Neither the hidden files nor the archive of the book are needed to read it
+    let mut files: Vec<String> = try!(utils::fs::list_files(dest))
+        .into_iter()
+        .filter(|f| !f.starts_with('.') && !f.contains("/.") && f != SERVICE_WORKER && f != "CNAME" && f != ARCHIVE)
+        .collect();
+
+    let mut hasher = Sha256::new();
+    for file in &files {
+        let mut content = vec![];
+        try!(try!(File::open(dest.join(file))).read_to_end(&mut content));
+        hasher.update(file.as_bytes());
+        hasher.update(&content);
+    }
+    let hash = hasher.finalize().iter().take(8).map(|b| format!("{:02x}", b)).collect::<String>();
+
+    // The root of the book is cached too, it is what readers usually open
+    files.insert(0, "./".to_owned());
+
+    Ok(TEMPLATE.replace("{{cache}}", &hash).replace("{{files}}", &try!(serde_json::to_string(&files))))
+}
+
+
+
+// ---------------------------------------------------------------------------------
+//      Tests
+//
+
+#[test]
+fn test_service_worker() {
+    extern crate tempdir;
+    use std::fs;
+    use std::io::Write;
+
+    let dest = tempdir::TempDir::new("mdbook-offline").unwrap();
+    fs::create_dir(dest.path().join("cli")).unwrap();
+    File::create(dest.path().join("index.html")).unwrap().write_all(b"<h1>Intro</h1>").unwrap();
+    File::create(dest.path().join("cli/build.html")).unwrap();
+    File::create(dest.path().join(".nojekyll")).unwrap();
+    File::create(dest.path().join(SERVICE_WORKER)).unwrap();
+
+    let first = service_worker(dest.path()).unwrap();
+    assert!(first.contains(r#"var FILES = ["./","cli/build.html","index.html"];"#));
+
+    // The cache changes with the content
+    File::create(dest.path().join("index.html")).unwrap().write_all(b"<h1>Introduction</h1>").unwrap();
+    assert!(service_worker(dest.path()).unwrap() != first);
+}
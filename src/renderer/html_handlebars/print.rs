@@ -0,0 +1,98 @@
+use std::path::{Path, PathBuf};
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+
+use utils;
+
+
+/// File name of the print version, at the root of the book
+pub const PRINT: &'static str = "print.html";
+
+/// Written in place of the content when the template of the page is rendered
+pub const CONTENT_MARKER: &'static str = "<!-- mdbook-print-content -->";
+
+
+/// The print version of the book, written while the chapters are rendered instead of being kept in memory
+/// until the end. The page is written to a temporary file renamed to `print.html` by `finish`, so that a
+/// chapter rendered to `print.html` is overwritten like before.
+pub struct PrintPage {
+    // `None` for a dry run
+    file: Option<BufWriter<File>>,
+    path: PathBuf,
+    // The end of the page, after the content
+    footer: String,
+}
+
+impl PrintPage {
+    /// Starts the page in `dest`, `page` is the template rendered with `CONTENT_MARKER` as content. Without
+    /// the marker, when a custom template does not show the content, the chapters are not added.
+    pub fn create(dest: &Path, page: &str) -> io::Result<Self> {
+        let path = dest.join(format!("{}.tmp", PRINT));
+        let (header, footer) = match page.find(CONTENT_MARKER) {
+            Some(i) => (&page[..i], &page[i + CONTENT_MARKER.len()..]),
+            None => (page, ""),
+        };
+
+        let mut file = BufWriter::new(try!(utils::fs::create_file(&path)));
+        try!(file.write_all(header.as_bytes()));
+
+        Ok(PrintPage {
+            file: Some(file),
+            path: path,
+            footer: footer.to_owned(),
+        })
+    }
+
+    /// A page that writes nothing, for the dry runs
+    pub fn discarded() -> Self {
+        PrintPage {
+            file: None,
+            path: PathBuf::new(),
+            footer: String::new(),
+        }
+    }
+
+    /// Appends the html of a chapter
+    pub fn push(&mut self, html: &str) -> io::Result<()> {
+        match self.file {
+            Some(ref mut file) => file.write_all(html.as_bytes()),
+            None => Ok(()),
+        }
+    }
+
+    /// Writes the end of the page and moves it to `print.html`
+    pub fn finish(self) -> io::Result<()> {
+        let mut file = match self.file {
+            Some(file) => file,
+            None => return Ok(()),
+        };
+        try!(file.write_all(self.footer.as_bytes()));
+        try!(file.flush());
+        let print = self.path.with_file_name(PRINT);
+        fs::rename(&self.path, print)
+    }
+}
+
+
+// ---------------------------------------------------------------------------------
+//      Tests
+//
+
+#[test]
+fn test_print_page() {
+    extern crate tempdir;
+    use std::io::Read;
+
+    let tmp = tempdir::TempDir::new("mdbook").expect("Could not create a temp dir");
+    let page = format!("<main>{}</main>", CONTENT_MARKER);
+    let mut print = PrintPage::create(tmp.path(), &page).unwrap();
+    print.push("<h1>One</h1>").unwrap();
+    print.push("<h1>Two</h1>").unwrap();
+    assert!(!tmp.path().join(PRINT).exists());
+    print.finish().unwrap();
+
+    let mut html = String::new();
+    File::open(tmp.path().join(PRINT)).unwrap().read_to_string(&mut html).unwrap();
+    assert_eq!(html, "<main><h1>One</h1><h1>Two</h1></main>");
+    assert_eq!(utils::fs::list_files(tmp.path()).unwrap(), vec![PRINT.to_owned()]);
+}
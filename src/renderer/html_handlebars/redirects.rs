@@ -0,0 +1,56 @@
+use std::path::{Component, Path, PathBuf};
+
+use utils;
+
+
+/// The page written for a former location of a chapter, e.g. `old/path.html` for the alias `old/path.html`
+/// and `old/path/index.html` for `old/path/`. Returns `None` for the absolute aliases and the ones leaving
+/// the book with `..`.
+pub fn alias_page(alias: &str) -> Option<PathBuf> {
+    let path = Path::new(alias.trim_start_matches('/'));
+    if path.components().any(|c| c != Component::CurDir && !matches!(c, Component::Normal(_))) {
+        return None;
+    }
+
+    let path: PathBuf = path.components().filter(|c| *c != Component::CurDir).collect();
+    if path == PathBuf::new() {
+        return None;
+    }
+    if alias.ends_with('/') || path.extension().is_none() {
+        Some(path.join("index.html"))
+    } else {
+        Some(path)
+    }
+}
+
+/// The redirect stub of `page` to the chapter written to `target`, both relative to the root of the book
+pub fn redirect_html(page: &Path, target: &Path, title: &str) -> String {
+    let url = format!("{}{}",
+                      utils::fs::path_to_root(page),
+                      utils::fs::path_to_url(target));
+    format!("<!DOCTYPE html>\n<meta charset=\"utf-8\">\n<title>{1}</title>\n\
+             <meta http-equiv=\"refresh\" content=\"0; url={0}\">\n<link rel=\"canonical\" href=\"{0}\">\n\
+             <a href=\"{0}\">{1}</a>\n",
+            utils::escape_html(&url),
+            utils::escape_html(title))
+}
+
+
+// ---------------------------------------------------------------------------------
+//      Tests
+//
+
+#[test]
+fn test_redirects() {
+    assert_eq!(alias_page("old/path.html"), Some(PathBuf::from("old/path.html")));
+    assert_eq!(alias_page("/old/path/"), Some(PathBuf::from("old/path/index.html")));
+    assert_eq!(alias_page("./guide"), Some(PathBuf::from("guide/index.html")));
+    assert_eq!(alias_page("../outside.html"), None);
+    assert_eq!(alias_page("/"), None);
+
+    assert_eq!(redirect_html(Path::new("old/path.html"), Path::new("cli/init.html"), "The init command"),
+               "<!DOCTYPE html>\n<meta charset=\"utf-8\">\n<title>The init command</title>\n\
+                <meta http-equiv=\"refresh\" content=\"0; url=../cli/init.html\">\n\
+                <link rel=\"canonical\" href=\"../cli/init.html\">\n\
+                <a href=\"../cli/init.html\">The init command</a>\n");
+}
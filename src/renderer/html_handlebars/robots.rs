@@ -0,0 +1,46 @@
+use book::htmlconfig::RobotsConfig;
+
+
+/// File name of the rules for the crawlers, at the root of the site
+pub const ROBOTS: &'static str = "robots.txt";
+
+/// The `robots.txt` of the book, with the paths of the config as they are written and the address of the sitemap
+/// when there is one
+pub fn robots_txt(config: &RobotsConfig, sitemap: Option<&str>) -> String {
+    let mut s = format!("User-agent: {}\n", config.user_agent);
+    for path in &config.allow {
+        s.push_str(&format!("Allow: {}\n", path));
+    }
+    for path in &config.disallow {
+        s.push_str(&format!("Disallow: {}\n", path));
+    }
+    // An empty `Disallow` allows everything
+    if config.disallow.is_empty() {
+        s.push_str("Disallow:\n");
+    }
+    if let Some(sitemap) = sitemap {
+        s.push_str(&format!("\nSitemap: {}\n", sitemap));
+    }
+    s
+}
+
+
+// ---------------------------------------------------------------------------------
+//      Tests
+//
+
+#[test]
+fn test_robots_txt() {
+    let mut config = RobotsConfig {
+        user_agent: "*".to_owned(),
+        disallow: vec!["/internal/".to_owned(), "/drafts/".to_owned()],
+        allow: vec!["/drafts/published.html".to_owned()],
+    };
+    assert_eq!(robots_txt(&config, None),
+               "User-agent: *\nAllow: /drafts/published.html\nDisallow: /internal/\nDisallow: /drafts/\n");
+
+    config.disallow.clear();
+    config.allow.clear();
+    assert_eq!(robots_txt(&config, Some("https://docs.example.com/sitemap.xml")),
+               "User-agent: *\nDisallow:\n\nSitemap: https://docs.example.com/sitemap.xml\n");
+}
@@ -0,0 +1,158 @@
+use std::path::Path;
+use std::collections::BTreeMap;
+
+use serde_json;
+use serde_json::value::ToJson;
+use pulldown_cmark::{Parser, Event, Tag};
+
+use book::htmlconfig::SearchConfig;
+use utils;
+
+
+/// Page listing all the results of a search, at the root of the book
+pub const SEARCH_PAGE: &'static str = "search.html";
+
+/// Index used by the search bar of the default theme. Every chapter is split in sections at its
+/// headings so that results can link directly to the matching part of a page.
+pub struct SearchIndex {
+    docs: Vec<BTreeMap<String, String>>,
+}
+
+impl SearchIndex {
+    pub fn new() -> Self {
+        SearchIndex { docs: vec![] }
+    }
+
+    /// Adds the sections of a chapter, `path` is the path of the markdown file relative to the
+    /// source directory
+    pub fn add_chapter(&mut self, path: &Path, title: &str, markdown: &str) {
+        let page = utils::fs::path_to_link(path);
+
+        let mut heading = String::new();
+        let mut body = String::new();
+        let mut in_heading = false;
+
+        for event in Parser::new_ext(markdown, utils::markdown_options()) {
+            match event {
+                Event::Start(Tag::Header(_)) => {
+                    self.add_section(&page, title, &heading, &body);
+                    heading.clear();
+                    body.clear();
+                    in_heading = true;
+                },
+                Event::End(Tag::Header(_)) => in_heading = false,
+                Event::Text(text) => {
+                    if in_heading {
+                        heading.push_str(&text);
+                    } else {
+                        body.push_str(&text);
+                    }
+                },
+                Event::End(Tag::Paragraph) |
+                Event::End(Tag::Item) |
+                Event::End(Tag::TableCell) |
+                Event::End(Tag::CodeBlock(_)) |
+                Event::SoftBreak |
+                Event::HardBreak => body.push(' '),
+                _ => {},
+            }
+        }
+
+        self.add_section(&page, title, &heading, &body);
+    }
+
+    fn add_section(&mut self, page: &str, title: &str, heading: &str, body: &str) {
+        let heading = heading.trim();
+        let body = body.split_whitespace().collect::<Vec<_>>().join(" ");
+
+        if heading.is_empty() && body.is_empty() {
+            return;
+        }
+
+        let url = if heading.is_empty() {
+            page.to_owned()
+        } else {
+            format!("{}#{}", page, heading_anchor(heading))
+        };
+
+        let mut doc = BTreeMap::new();
+        doc.insert("url".to_owned(), url);
+        doc.insert("title".to_owned(), title.to_owned());
+        doc.insert("heading".to_owned(), heading.to_owned());
+        doc.insert("body".to_owned(), body);
+        self.docs.push(doc);
+    }
+
+    /// Serializes the index to a script that assigns it, with the search options, to
+    /// `window.search_index`. A script is used instead of a JSON file so that the search also
+    /// works for books opened from the file system.
+    pub fn to_javascript(&self, config: &SearchConfig) -> Result<String, serde_json::Error> {
+        let mut options = BTreeMap::new();
+        options.insert("boost_heading".to_owned(), config.boost_heading.to_json());
+        options.insert("boost_body".to_owned(), config.boost_body.to_json());
+        options.insert("teaser_word_count".to_owned(), config.teaser_word_count.to_json());
+        options.insert("limit_results".to_owned(), config.limit_results.to_json());
+
+        let mut index = BTreeMap::new();
+        index.insert("config".to_owned(), options.to_json());
+        index.insert("docs".to_owned(), self.docs.to_json());
+
+        Ok(format!("window.search_index = {};\n", serde_json::to_string(&index)?))
+    }
+}
+
+/// Content of `search.html`. `book.js` searches the `q` parameter of its address, e.g.
+/// `search.html?q=summary`, and lists the results grouped by chapter, `page_size` chapters on each page,
+/// the `page` parameter being the current one. Its searches are links that can be shared.
+pub fn search_page_html(page_size: u32) -> String {
+    format!("<div class=\"search-page\" data-page-size=\"{}\">\n\
+             <form class=\"search-page-form\" action=\"{}\" method=\"get\" role=\"search\">\n\
+             <input type=\"search\" name=\"q\" placeholder=\"Search this book ...\" aria-label=\"Search this book\">\n\
+             <button type=\"submit\">Search</button>\n\
+             </form>\n\
+             <p class=\"search-page-header\" aria-live=\"polite\"></p>\n\
+             <ol class=\"search-page-results\"></ol>\n\
+             <nav class=\"search-page-pages\" aria-label=\"Pages of the results\"></nav>\n\
+             </div>\n",
+            page_size,
+            SEARCH_PAGE)
+}
+
+/// Anchor of a heading, as created by `book.js` for the header links
+pub fn heading_anchor(heading: &str) -> String {
+    heading.trim()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '_' { c } else { '-' })
+        .collect()
+}
+
+
+// ---------------------------------------------------------------------------------
+//      Tests
+//
+
+#[test]
+fn test_heading_anchor() {
+    assert_eq!(heading_anchor(" Hiding code lines "), "Hiding-code-lines");
+    assert_eq!(heading_anchor("init & co."), "init---co-");
+}
+
+#[test]
+fn test_search_index_sections() {
+    let mut index = SearchIndex::new();
+    index.add_chapter(Path::new("cli/init.md"),
+                      "init",
+                      "Intro text\n\n# The init command\n\nCreates *files*.\n\n## --theme\n\nCopies the theme.");
+
+    let urls: Vec<&str> = index.docs.iter().map(|d| &d["url"][..]).collect();
+    assert_eq!(urls, vec!["cli/init.html", "cli/init.html#The-init-command", "cli/init.html#--theme"]);
+    assert_eq!(index.docs[1]["body"], "Creates files.");
+}
+
+#[test]
+fn test_search_page_html() {
+    let html = search_page_html(10);
+    assert!(html.starts_with("<div class=\"search-page\" data-page-size=\"10\">"));
+    assert!(html.contains("<form class=\"search-page-form\" action=\"search.html\" method=\"get\" role=\"search\">"));
+    assert!(html.contains("<input type=\"search\" name=\"q\""));
+}
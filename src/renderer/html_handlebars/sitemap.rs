@@ -0,0 +1,44 @@
+use std::path::{Path, PathBuf};
+
+use utils;
+
+
+/// File name of the sitemap, at the root of the book
+pub const SITEMAP: &'static str = "sitemap.xml";
+
+/// The address of a page at the `site-url` of the book, `page` is the html file relative to the root of the book
+pub fn page_url(site_url: &str, page: &Path) -> String {
+    let page = page.strip_prefix("./").unwrap_or(page);
+    format!("{}/{}", site_url.trim_end_matches('/'), utils::fs::path_to_url(page))
+}
+
+/// The sitemap of the pages for the search engines, in their order
+pub fn sitemap_xml(site_url: &str, pages: &[PathBuf]) -> String {
+    let mut xml = "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+                   <urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n"
+        .to_owned();
+    for page in pages {
+        xml.push_str(&format!("  <url><loc>{}</loc></url>\n", utils::escape_html(&page_url(site_url, page))));
+    }
+    xml.push_str("</urlset>\n");
+    xml
+}
+
+
+// ---------------------------------------------------------------------------------
+//      Tests
+//
+
+#[test]
+fn test_sitemap_xml() {
+    assert_eq!(page_url("https://docs.example.com/guide/", Path::new("./cli/init.html")),
+               "https://docs.example.com/guide/cli/init.html");
+
+    let pages = vec![PathBuf::from("README.html"), PathBuf::from("tags/q&a.html")];
+    assert_eq!(sitemap_xml("https://docs.example.com", &pages),
+               "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+                <urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n  \
+                <url><loc>https://docs.example.com/README.html</loc></url>\n  \
+                <url><loc>https://docs.example.com/tags/q&amp;a.html</loc></url>\n\
+                </urlset>\n");
+}
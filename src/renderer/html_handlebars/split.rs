@@ -0,0 +1,242 @@
+use std::path::{Path, PathBuf};
+use std::collections::BTreeMap;
+
+use serde_json;
+
+use renderer::html_handlebars::helpers::externals::{attribute, tag_end, tag_name};
+use renderer::html_handlebars::helpers::sanitize::decode_entities;
+use renderer::html_handlebars::search::heading_anchor;
+use utils;
+
+
+/// A page of a chapter split at its headings
+#[derive(Debug, Clone, PartialEq)]
+pub struct Part {
+    /// Text of the heading starting the page, `None` for the first page
+    pub title: Option<String>,
+    /// Page relative to the root of the book: the page of the chapter for the first part, then the name of the
+    /// chapter followed by `-2`, `-3`...
+    pub page: PathBuf,
+    pub html: String,
+    /// Anchors of its headings and of its elements with an `id`
+    pub anchors: Vec<String>,
+}
+
+// Elements whose headings do not start a page
+const CONTAINERS: &'static [&'static str] = &["blockquote", "details", "div", "li", "table"];
+
+/// Splits the html of a chapter before its `h1` headings, and before its `h2` headings too with `level` 2. A
+/// heading right after another one stays on its page, and the headings nested in lists, quotes, `<details>` or
+/// `<div>` do not start a page.
+///
+/// The links to the anchors of another page of the chapter are changed to link to that page.
+pub fn split_chapter(html: &str, path: &Path, level: u32) -> Vec<Part> {
+    // Start of every part in the html, and the text of the heading starting it
+    let mut starts: Vec<(usize, Option<String>)> = vec![(0, None)];
+    let mut depth = 0usize;
+    let mut i = 0;
+    while let Some(offset) = html[i..].find('<') {
+        let start = i + offset;
+        let end = match tag_end(&html[start..]) {
+            Some(end) => start + end,
+            None => break,
+        };
+        let tag = &html[start..end];
+        let (closing, name) = tag_name(tag);
+        if CONTAINERS.contains(&&name[..]) {
+            if closing {
+                depth = depth.saturating_sub(1);
+            } else if !tag.ends_with("/>") {
+                depth += 1;
+            }
+        } else if !closing && depth == 0 && heading_level(&name).is_some_and(|l| l <= level) {
+            let previous = html[starts[starts.len() - 1].0..start].trim_end();
+            if !previous.is_empty() && !ends_with_heading(previous) {
+                starts.push((start, Some(heading_text(&html[end..]))));
+            }
+        }
+        i = end;
+    }
+
+    let stem = path.file_stem().map(|s| s.to_string_lossy().into_owned()).unwrap_or_default();
+    let mut parts: Vec<Part> = starts.iter()
+        .enumerate()
+        .map(|(k, &(start, ref title))| {
+            let end = starts.get(k + 1).map_or(html.len(), |&(end, _)| end);
+            let page = if k == 0 {
+                path.with_extension("html")
+            } else {
+                path.with_file_name(format!("{}-{}.html", stem, k + 1))
+            };
+            Part {
+                title: title.clone(),
+                page: page,
+                html: html[start..end].to_owned(),
+                anchors: anchors(&html[start..end]),
+            }
+        })
+        .collect();
+
+    // `#anchor` links to the other pages
+    let moved: Vec<(String, String)> = parts.iter()
+        .flat_map(|part| part.anchors.iter().map(move |anchor| (anchor.clone(), page_link(&part.page))))
+        .collect();
+    for part in &mut parts {
+        for (anchor, page) in &moved {
+            if !part.anchors.contains(anchor) {
+                let link = format!("href=\"{}#{}\"", page, anchor);
+                part.html = part.html.replace(&format!("href=\"#{}\"", anchor), &link);
+            }
+        }
+    }
+
+    parts
+}
+
+/// Links to the pages of the chapter, put after the content of the page `current`. It carries the anchors of the
+/// other pages for `book.js`, which follows the links to them that point to the wrong page, like the links to the
+/// headings of the chapter.
+pub fn pages_html(parts: &[Part], current: usize, chapter: &str) -> String {
+    let mut anchors = BTreeMap::new();
+    for part in parts {
+        for anchor in &part.anchors {
+            if !parts[current].anchors.contains(anchor) {
+                anchors.insert(anchor.clone(), page_link(&part.page));
+            }
+        }
+    }
+
+    let mut html = format!("<nav class=\"chapter-pages\" aria-label=\"Pages of the chapter\" data-anchors=\"{}\">\n",
+                           utils::escape_html(&serde_json::to_string(&anchors).unwrap_or_default()));
+    if let Some(previous) = current.checked_sub(1).and_then(|i| parts.get(i)) {
+        html.push_str(&format!("<a class=\"previous-page\" href=\"{}\" rel=\"prev\"><i class=\"fa fa-angle-left\" \
+                                aria-hidden=\"true\"></i> {}</a>\n",
+                               page_link(&previous.page),
+                               utils::escape_html(part_title(previous, chapter))));
+    }
+    html.push_str("<ol>\n");
+    for (i, part) in parts.iter().enumerate() {
+        let (class, current) = if i == current { (" class=\"active\"", " aria-current=\"page\"") } else { ("", "") };
+        html.push_str(&format!("<li{}><a href=\"{}\"{}>{}</a></li>\n",
+                               class,
+                               page_link(&part.page),
+                               current,
+                               utils::escape_html(part_title(part, chapter))));
+    }
+    html.push_str("</ol>\n");
+    if let Some(next) = parts.get(current + 1) {
+        html.push_str(&format!("<a class=\"next-page\" href=\"{}\" rel=\"next\">{} <i class=\"fa fa-angle-right\" \
+                                aria-hidden=\"true\"></i></a>\n",
+                               page_link(&next.page),
+                               utils::escape_html(part_title(next, chapter))));
+    }
+    html.push_str("</nav>\n");
+    html
+}
+
+fn part_title<'a>(part: &'a Part, chapter: &'a str) -> &'a str {
+    part.title.as_ref().map_or(chapter, |title| &title[..])
+}
+
+fn page_link(page: &Path) -> String {
+    utils::fs::path_to_url(page).trim_start_matches("./").to_owned()
+}
+
+fn heading_level(name: &str) -> Option<u32> {
+    if name.len() == 2 && name.starts_with('h') {
+        name[1..].parse().ok().filter(|level| (1..7).contains(level))
+    } else {
+        None
+    }
+}
+
+fn ends_with_heading(html: &str) -> bool {
+    html.ends_with('>') && html.rsplit('<').next().is_some_and(|tag| heading_level(&tag_name(tag).1).is_some())
+}
+
+// Text of the heading starting `html`, after its opening tag, without its number like `book.js`
+fn heading_text(html: &str) -> String {
+    let inner = &html[..html.find("</h").unwrap_or(html.len())];
+    let mut text = String::new();
+    let mut rest = inner;
+    while let Some(start) = rest.find('<') {
+        text.push_str(&rest[..start]);
+        let end = tag_end(&rest[start..]).map_or(rest.len(), |end| start + end);
+        if rest[start..end].contains("class=\"heading-number\"") {
+            // Skip the number up to the end of its span
+            rest = &rest[end..];
+            rest = &rest[rest.find("</span>").map_or(rest.len(), |end| end + "</span>".len())..];
+        } else {
+            rest = &rest[end..];
+        }
+    }
+    text.push_str(rest);
+    decode_entities(&text).trim().to_owned()
+}
+
+/// The anchors of the elements of the html with an `id` and of its headings, created by `book.js` from their
+/// text when they have no `id`
+pub fn anchors(html: &str) -> Vec<String> {
+    let mut anchors = vec![];
+    let mut i = 0;
+    while let Some(offset) = html[i..].find('<') {
+        let start = i + offset;
+        let end = match tag_end(&html[start..]) {
+            Some(end) => start + end,
+            None => break,
+        };
+        let tag = &html[start..end];
+        match attribute(tag, "id") {
+            Some(id) => anchors.push(id.to_owned()),
+            None => {
+                let (closing, name) = tag_name(tag);
+                if !closing && heading_level(&name).is_some_and(|level| level <= 5) {
+                    anchors.push(heading_anchor(&heading_text(&html[end..])));
+                }
+            },
+        }
+        i = end;
+    }
+    anchors
+}
+
+
+// ---------------------------------------------------------------------------------
+//      Tests
+//
+
+#[test]
+fn test_split_chapter() {
+    let html = "<h1>Reference</h1>\n<p>Intro, see <a href=\"#flags\">the flags</a>.</p>\n\
+                <h2>Options</h2>\n<h3 id=\"flags\">Flags</h3>\n<p>All of them.</p>\n\
+                <blockquote>\n<h2>Quoted</h2>\n</blockquote>\n\
+                <h2><span class=\"heading-number\">1.2</span> Files &amp; dirs</h2>\n<p>Back to \
+                <a href=\"#Reference\">the top</a>.</p>\n";
+    let parts = split_chapter(html, Path::new("./cli/reference.md"), 2);
+
+    let pages: Vec<_> = parts.iter().map(|p| (p.title.clone(), p.page.clone(), p.anchors.clone())).collect();
+    assert_eq!(pages,
+               vec![(None, PathBuf::from("./cli/reference.html"), vec!["Reference".to_owned()]),
+                    (Some("Options".to_owned()),
+                     PathBuf::from("./cli/reference-2.html"),
+                     vec!["Options".to_owned(), "flags".to_owned(), "Quoted".to_owned()]),
+                    (Some("Files & dirs".to_owned()),
+                     PathBuf::from("./cli/reference-3.html"),
+                     vec!["Files---dirs".to_owned()])]);
+    assert!(parts[0].html.contains("<a href=\"cli/reference-2.html#flags\">the flags</a>"));
+    assert!(parts[2].html.contains("<a href=\"cli/reference.html#Reference\">the top</a>"));
+    assert_eq!(parts.iter().map(|p| &p.html[..]).collect::<String>(),
+               html.replace("#flags", "cli/reference-2.html#flags")
+                   .replace("#Reference", "cli/reference.html#Reference"));
+
+    // Level 1 only splits at the `h1` headings
+    assert_eq!(split_chapter(html, Path::new("cli/reference.md"), 1).len(), 1);
+
+    let nav = pages_html(&parts, 1, "Reference");
+    assert!(nav.contains("data-anchors=\"{&quot;Files---dirs&quot;:&quot;cli/reference-3.html&quot;,\
+                          &quot;Reference&quot;:&quot;cli/reference.html&quot;}\""));
+    assert!(nav.contains("<a class=\"previous-page\" href=\"cli/reference.html\" rel=\"prev\">"));
+    assert!(nav.contains("<li class=\"active\"><a href=\"cli/reference-2.html\" aria-current=\"page\">Options</a>\
+                          </li>"));
+    assert!(nav.contains("Files &amp; dirs <i class=\"fa fa-angle-right\""));
+}
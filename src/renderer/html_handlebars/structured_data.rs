@@ -0,0 +1,140 @@
+use std::path::Path;
+use std::collections::BTreeMap;
+
+use serde_json;
+use serde_json::value::{ToJson, Value};
+
+use book::BookConfig;
+use renderer::html_handlebars::sitemap;
+
+
+/// A chapter, as described to the search engines
+pub struct Article<'a> {
+    pub title: &'a str,
+    pub description: &'a str,
+    pub authors: &'a [String],
+    pub date: Option<&'a str>,
+    /// Position of the chapter in the reading order of the book, from 1
+    pub position: usize,
+    /// Page of the chapter, relative to the root of the book
+    pub path: &'a Path,
+}
+
+/// The schema.org `Book` JSON-LD of the pages that are not a chapter, like `print.html`, to put in a
+/// `<script type="application/ld+json">`. The url is only there when the book has a `site-url`.
+pub fn book_json_ld(config: &BookConfig) -> String {
+    let mut book = book_object(config);
+    book.insert("@context".to_owned(), "https://schema.org".to_json());
+    to_script(&book)
+}
+
+/// The schema.org `TechArticle` JSON-LD of a chapter, part of the `Book`, with the url of the page when the
+/// book has a `site-url`
+pub fn chapter_json_ld(config: &BookConfig, article: &Article) -> String {
+    let mut json = BTreeMap::new();
+    json.insert("@context".to_owned(), "https://schema.org".to_json());
+    json.insert("@type".to_owned(), "TechArticle".to_json());
+    json.insert("headline".to_owned(), article.title.to_json());
+    if !article.description.is_empty() {
+        json.insert("description".to_owned(), article.description.to_json());
+    }
+    if !article.authors.is_empty() {
+        json.insert("author".to_owned(), persons(article.authors));
+    }
+    if let Some(date) = article.date {
+        json.insert("datePublished".to_owned(), date.to_json());
+    }
+    json.insert("position".to_owned(), article.position.to_json());
+    if let Some(ref site_url) = config.html_config.site_url {
+        let url = sitemap::page_url(site_url, &article.path.with_extension("html"));
+        json.insert("url".to_owned(), url.to_json());
+        json.insert("mainEntityOfPage".to_owned(), url.to_json());
+    }
+    json.insert("isPartOf".to_owned(), book_object(config).to_json());
+    to_script(&json)
+}
+
+fn book_object(config: &BookConfig) -> BTreeMap<String, Value> {
+    let mut book = BTreeMap::new();
+    book.insert("@type".to_owned(), "Book".to_json());
+    book.insert("name".to_owned(), config.title.to_json());
+    if !config.description.is_empty() {
+        book.insert("description".to_owned(), config.description.to_json());
+    }
+    let authors = config.get_authors();
+    if !authors.is_empty() {
+        book.insert("author".to_owned(), persons(&authors));
+    }
+    if let Some(ref isbn) = config.isbn {
+        book.insert("isbn".to_owned(), isbn.to_json());
+    }
+    if let Some(ref version) = config.version {
+        book.insert("bookEdition".to_owned(), version.to_json());
+    }
+    if let Some(ref date) = config.date {
+        book.insert("datePublished".to_owned(), date.to_json());
+    }
+    if let Some(ref site_url) = config.html_config.site_url {
+        book.insert("url".to_owned(), format!("{}/", site_url.trim_end_matches('/')).to_json());
+    }
+    book
+}
+
+fn persons(names: &[String]) -> Value {
+    names.iter()
+        .map(|name| {
+            let mut person = BTreeMap::new();
+            person.insert("@type".to_owned(), "Person".to_json());
+            person.insert("name".to_owned(), name.to_json());
+            person
+        })
+        .collect::<Vec<_>>()
+        .to_json()
+}
+
+// `<` only appears in the strings, where `\u003c` is the same character, so that a title containing
+// `</script>` does not end the script
+fn to_script(json: &BTreeMap<String, Value>) -> String {
+    serde_json::to_string(json).unwrap_or_default().replace("<", "\\u003c")
+}
+
+
+// ---------------------------------------------------------------------------------
+//      Tests
+//
+
+#[test]
+fn test_chapter_json_ld() {
+    let mut config = BookConfig::new(Path::new("."));
+    config.title = "The </script> book".to_owned();
+    config.html_config.site_url = Some("https://docs.example.com/".to_owned());
+
+    let authors = vec!["Ana".to_owned()];
+    let article = Article {
+        title: "init",
+        description: "",
+        authors: &authors,
+        date: Some("2018-05-01"),
+        position: 3,
+        path: Path::new("./cli/init.md"),
+    };
+    let script = chapter_json_ld(&config, &article);
+    assert!(!script.contains("</script>"));
+
+    let json: Value = serde_json::from_str(&script).unwrap();
+    let field = |key: &str| json.lookup(key).and_then(|v| v.as_str()).map(|s| s.to_owned());
+    assert_eq!(field("@type"), Some("TechArticle".to_owned()));
+    assert_eq!(field("headline"), Some("init".to_owned()));
+    assert_eq!(field("description"), None);
+    assert_eq!(field("url"), Some("https://docs.example.com/cli/init.html".to_owned()));
+    let author = json.lookup("author").and_then(|a| a.as_array()).map(|a| a[0].clone()).unwrap();
+    assert_eq!(author.lookup("name").and_then(|n| n.as_str()), Some("Ana"));
+    assert_eq!(json.lookup("position").and_then(|p| p.as_u64()), Some(3));
+    assert_eq!(field("isPartOf.name"), Some("The </script> book".to_owned()));
+    assert_eq!(field("isPartOf.url"), Some("https://docs.example.com/".to_owned()));
+
+    config.html_config.site_url = None;
+    let json: Value = serde_json::from_str(&book_json_ld(&config)).unwrap();
+    assert_eq!(json.lookup("@type").and_then(|v| v.as_str()), Some("Book"));
+    assert!(json.lookup("url").is_none());
+}
@@ -0,0 +1,30 @@
+use book::htmlconfig::StyleConfig;
+
+
+/// File name of the CSS variables of `[output.html.style]`, at the root of the book
+pub const VARIABLES_CSS: &'static str = "variables.css";
+
+/// The stylesheet overriding the CSS variables of the theme, loaded after `book.css`
+pub fn variables_css(config: &StyleConfig) -> String {
+    let mut css = String::from(":root {\n");
+    for (name, value) in &config.variables {
+        css.push_str(&format!("    --{}: {};\n", name, value));
+    }
+    css.push_str("}\n");
+    css
+}
+
+
+// ---------------------------------------------------------------------------------
+//      Tests
+//
+
+#[test]
+fn test_variables_css() {
+    let mut config = StyleConfig::new();
+    config.variables.insert("sidebar-width".to_owned(), "250px".to_owned());
+    config.variables.insert("accent-color".to_owned(), "#c0392b".to_owned());
+
+    assert_eq!(variables_css(&config),
+               ":root {\n    --accent-color: #c0392b;\n    --sidebar-width: 250px;\n}\n");
+}
@@ -0,0 +1,139 @@
+use std::path::{Path, PathBuf};
+use std::collections::BTreeMap;
+
+use utils;
+
+
+/// Directory of the tag pages, at the root of the book
+pub const TAGS_DIR: &'static str = "tags";
+
+
+/// Tags of the chapters, given by `tags = ["async", "ffi"]` in their front matter. Every tag gets a page
+/// listing its chapters in `tags/`, and `tags/index.html` lists all the tags as a cloud.
+pub struct Tags {
+    // Keyed by the file name of the tag page, the tags differing only by case or punctuation are the same
+    tags: BTreeMap<String, Tag>,
+}
+
+struct Tag {
+    name: String,
+    chapters: Vec<(String, String)>,
+}
+
+impl Tags {
+    pub fn new() -> Self {
+        Tags { tags: BTreeMap::new() }
+    }
+
+    pub fn add_chapter(&mut self, path: &Path, name: &str, tags: &[&str]) {
+        let page = utils::fs::path_to_link(path);
+
+        for tag in tags.iter().map(|t| t.trim()).filter(|t| !t.is_empty()) {
+            let chapters = &mut self.tags
+                .entry(tag_slug(tag))
+                .or_insert_with(|| Tag { name: tag.to_owned(), chapters: vec![] })
+                .chapters;
+            if !chapters.iter().any(|(p, _)| *p == page) {
+                chapters.push((page.clone(), name.to_owned()));
+            }
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tags.is_empty()
+    }
+
+    /// Path of the page of every tag, relative to the source directory, with the html of the page
+    pub fn pages(&self) -> Vec<(PathBuf, String, String)> {
+        self.tags
+            .iter()
+            .map(|(slug, tag)| {
+                let mut html = format!("<h1>Tag: {}</h1>\n<ul class=\"tag-chapters\">\n",
+                                       utils::escape_html(&tag.name));
+                for (link, name) in &tag.chapters {
+                    html.push_str(&format!("<li><a href=\"{}\">{}</a></li>\n", link, utils::escape_html(name)));
+                }
+                html.push_str("</ul>\n");
+                (Path::new(TAGS_DIR).join(format!("{}.md", slug)), tag.name.clone(), html)
+            })
+            .collect()
+    }
+
+    /// Html of `tags/index.html`, the tags with more chapters are bigger
+    pub fn index_html(&self) -> String {
+        let most = self.tags.values().map(|t| t.chapters.len()).max().unwrap_or(1);
+
+        let mut html = String::from("<h1>Tags</h1>\n<ul class=\"tag-cloud\">\n");
+        for (slug, tag) in &self.tags {
+            let size = 1.0 + (tag.chapters.len() - 1) as f64 / (most as f64).max(2.0) * 1.5;
+            html.push_str(&format!("<li><a class=\"tag\" href=\"{}/{}.html\" style=\"font-size: {:.2}em\">{}</a> \
+                                    <span class=\"tag-count\">{}</span></li>\n",
+                                   TAGS_DIR,
+                                   slug,
+                                   size,
+                                   utils::escape_html(&tag.name),
+                                   tag.chapters.len()));
+        }
+        html.push_str("</ul>\n");
+        html
+    }
+}
+
+/// Name of the page of a tag, in lowercase with the other characters than letters and digits replaced by `-`
+pub fn tag_slug(tag: &str) -> String {
+    let mut slug = String::new();
+    for c in tag.chars().flat_map(|c| c.to_lowercase()) {
+        if c.is_alphanumeric() {
+            slug.push(c);
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    let slug = slug.trim_end_matches('-');
+    if slug.is_empty() { "tag".to_owned() } else { slug.to_owned() }
+}
+
+/// Link to the page of the tag, relative to the root of the book
+pub fn tag_link(tag: &str) -> String {
+    format!("{}/{}.html", TAGS_DIR, tag_slug(tag))
+}
+
+
+// ---------------------------------------------------------------------------------
+//      Tests
+//
+
+#[test]
+fn test_tag_slug() {
+    assert_eq!(tag_slug("async"), "async");
+    assert_eq!(tag_slug(" Error Handling!"), "error-handling");
+    assert_eq!(tag_slug("C++"), "c");
+    assert_eq!(tag_slug("?"), "tag");
+    assert_eq!(tag_link("FFI"), "tags/ffi.html");
+}
+
+#[test]
+fn test_tag_pages() {
+    let mut tags = Tags::new();
+    tags.add_chapter(Path::new("async/intro.md"), "Async", &["async", "Futures"]);
+    tags.add_chapter(Path::new("ffi.md"), "FFI", &["FFI", "Async", ""]);
+    tags.add_chapter(Path::new("ffi.md"), "FFI", &["ffi"]);
+
+    let pages = tags.pages();
+    assert_eq!(pages.len(), 3);
+    assert_eq!(pages[0].0, Path::new("tags/async.md"));
+    assert_eq!(pages[0].1, "async");
+    assert_eq!(pages[0].2,
+               "<h1>Tag: async</h1>\n<ul class=\"tag-chapters\">\n\
+                <li><a href=\"async/intro.html\">Async</a></li>\n<li><a href=\"ffi.html\">FFI</a></li>\n</ul>\n");
+    assert_eq!(pages[1].0, Path::new("tags/ffi.md"));
+
+    assert_eq!(tags.index_html(),
+               "<h1>Tags</h1>\n<ul class=\"tag-cloud\">\n\
+                <li><a class=\"tag\" href=\"tags/async.html\" style=\"font-size: 1.75em\">async</a> \
+                <span class=\"tag-count\">2</span></li>\n\
+                <li><a class=\"tag\" href=\"tags/ffi.html\" style=\"font-size: 1.00em\">FFI</a> \
+                <span class=\"tag-count\">1</span></li>\n\
+                <li><a class=\"tag\" href=\"tags/futures.html\" style=\"font-size: 1.00em\">Futures</a> \
+                <span class=\"tag-count\">1</span></li>\n</ul>\n");
+}
@@ -0,0 +1,145 @@
+use std::path::PathBuf;
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::io;
+
+use handlebars::{Handlebars, TemplateError, RenderError};
+use serde_json::value::ToJson;
+
+use theme::Theme;
+
+
+/// The handlebars templates of a theme, with their source to report the errors at the right place.
+///
+/// `index` and the other page templates are registered under their name, the templates of the helpers
+/// under `helpers/name` and the partials under `partials/name`.
+pub struct Templates {
+    // The directories of the theme, the themes it extends first
+    dirs: Vec<PathBuf>,
+    sources: BTreeMap<String, String>,
+}
+
+impl Templates {
+    pub fn new(theme: &Theme) -> Result<Self, Box<Error>> {
+        let mut sources = BTreeMap::new();
+        sources.insert("index".to_owned(), try!(String::from_utf8(theme.index.clone())));
+        for (name, template) in &theme.templates {
+            sources.insert(name.clone(), try!(String::from_utf8(template.clone())));
+        }
+        for (name, template) in &theme.helpers {
+            sources.insert(format!("helpers/{}", name), try!(String::from_utf8(template.clone())));
+        }
+        for (name, partial) in &theme.partials {
+            sources.insert(format!("partials/{}", name), try!(String::from_utf8(partial.clone())));
+        }
+
+        Ok(Templates {
+            dirs: theme.dirs.clone(),
+            sources: sources,
+        })
+    }
+
+    /// Names of the templates rendering whole pages, `index` and the ones chapters can select
+    pub fn pages(&self) -> Vec<&str> {
+        self.sources
+            .keys()
+            .filter(|name| !name.starts_with("helpers/") && !name.starts_with("partials/"))
+            .map(|name| &name[..])
+            .collect()
+    }
+
+    pub fn register(&self, handlebars: &mut Handlebars) -> Result<(), Box<Error>> {
+        for (name, source) in &self.sources {
+            try!(handlebars.register_template_string(name, source.clone()).map_err(|e| self.template_error(name, e)));
+        }
+        Ok(())
+    }
+
+    pub fn render<T: ToJson>(&self, handlebars: &Handlebars, name: &str, data: &T) -> Result<String, Box<Error>> {
+        handlebars.render(name, data).map_err(|e| self.render_error(name, e))
+    }
+
+    fn template_error(&self, name: &str, e: TemplateError) -> Box<Error> {
+        let message = match (e.line_no, e.column_no) {
+            (Some(line), Some(col)) => format!("{}:{}:{}: {}", self.file(name), line, col, e.reason),
+            _ => format!("{}: {}", self.file(name), e.reason),
+        };
+        Box::new(io::Error::new(io::ErrorKind::Other, message))
+    }
+
+    fn render_error(&self, name: &str, e: RenderError) -> Box<Error> {
+        let name = e.template_name.as_ref().map(|n| &n[..]).unwrap_or(name);
+        let message = match (e.line_no, e.column_no) {
+            (Some(line), Some(col)) => {
+                match self.sources.get(name).and_then(|source| expression_at(source, line, col)) {
+                    Some(expression) => {
+                        format!("{}:{}:{}: error in `{}`: {}", self.file(name), line, col, expression, e.desc)
+                    },
+                    None => format!("{}:{}:{}: {}", self.file(name), line, col, e.desc),
+                }
+            },
+            _ => format!("{}: {}", self.file(name), e.desc),
+        };
+        Box::new(io::Error::new(io::ErrorKind::Other, message))
+    }
+
+    // The file of the template, from the last theme directory having it, the templates missing from the theme
+    // directories are the default ones
+    fn file(&self, name: &str) -> String {
+        let file = format!("{}.hbs", name);
+        match self.dirs.iter().rev().map(|dir| dir.join(&file)).find(|path| path.exists()) {
+            Some(path) => path.display().to_string(),
+            None => format!("{} of the default theme", file),
+        }
+    }
+}
+
+// The `{{...}}` expression starting at `line` and `col` of `source`, both start at 1
+fn expression_at(source: &str, line: usize, col: usize) -> Option<&str> {
+    let text = source.lines().nth(line - 1)?;
+    let start = text.char_indices().nth(col - 1).map(|(i, _)| i)?;
+    let offset = source.lines().take(line - 1).map(|l| l.len() + 1).sum::<usize>() + start;
+
+    let rest = &source[offset..];
+    if !rest.starts_with("{{") {
+        return None;
+    }
+    let end = rest.find("}}")?;
+    let end = if rest[end..].starts_with("}}}") { end + 3 } else { end + 2 };
+    Some(&rest[..end])
+}
+
+
+// ---------------------------------------------------------------------------------------------------------------
+//      Tests
+//
+
+#[test]
+fn test_expression_at() {
+    let source = "<ul>\n    {{#toc}}{{/toc}}\n    {{{content}}} {{ title }}\n</ul>";
+    assert_eq!(expression_at(source, 2, 5), Some("{{#toc}}"));
+    assert_eq!(expression_at(source, 3, 5), Some("{{{content}}}"));
+    assert_eq!(expression_at(source, 3, 19), Some("{{ title }}"));
+    assert_eq!(expression_at(source, 1, 1), None);
+    assert_eq!(expression_at(source, 9, 1), None);
+}
+
+#[test]
+fn test_template_errors() {
+    use std::path::Path;
+
+    let mut theme = Theme::new(Path::new("no-theme"));
+    theme.index = b"<p>\n  {{#if title}}{{/fi}}\n</p>".to_vec();
+    let templates = Templates::new(&theme).unwrap();
+    let err = templates.register(&mut Handlebars::new()).unwrap_err();
+    assert!(err.to_string().starts_with("index.hbs of the default theme:"), "{}", err);
+
+    theme.index = b"<p>\n  {{title}} {{#unknown title}}{{/unknown}}\n</p>".to_vec();
+    let templates = Templates::new(&theme).unwrap();
+    let mut handlebars = Handlebars::new();
+    templates.register(&mut handlebars).unwrap();
+    let err = templates.render(&handlebars, "index", &BTreeMap::<String, String>::new()).unwrap_err();
+    assert!(err.to_string().starts_with("index.hbs of the default theme:2:13: error in `{{#unknown title}}`:"),
+            "{}",
+            err);
+}
@@ -1,9 +1,104 @@
+#[cfg(feature = "html")]
 pub use self::html_handlebars::HtmlHandlebars;
 
+#[cfg(feature = "html")]
 mod html_handlebars;
 
+use std::path::{Path, PathBuf};
 use std::error::Error;
+use std::fmt;
+#[cfg(not(feature = "html"))]
+use std::io;
 
 pub trait Renderer {
-    fn render(&self, book: &::book::MDBook) -> Result<(), Box<Error>>;
+    /// Renders the book, problems that do not stop the rendering are returned as warnings
+    fn render(&self, book: &::book::MDBook) -> Result<Vec<Warning>, Box<Error>>;
+
+    /// Checks the templates of the theme without rendering the book, renderers without templates have
+    /// nothing to check
+    fn check_theme(&self, _book: &::book::MDBook) -> Result<(), Box<Error>> {
+        Ok(())
+    }
+
+    /// Checks the links of the chapters and the files of the source directory without rendering the book, the
+    /// problems are returned as warnings. Renderers without links have nothing to check.
+    fn check(&self, _book: &::book::MDBook) -> Result<Vec<Warning>, Box<Error>> {
+        Ok(vec![])
+    }
+
+    /// The pages the renderer writes besides the ones of the chapters, relative to the output directory, with what
+    /// they are. The build fails before writing anything when one of them would overwrite a chapter, a static file
+    /// or another one.
+    fn pages(&self, _book: &::book::MDBook) -> Result<Vec<(PathBuf, String)>, Box<Error>> {
+        Ok(vec![])
+    }
+}
+
+/// The renderer of a book built without the `html` feature, it only asks for another renderer
+#[cfg(not(feature = "html"))]
+pub struct NoRenderer;
+
+#[cfg(not(feature = "html"))]
+impl Renderer for NoRenderer {
+    fn render(&self, _book: &::book::MDBook) -> Result<Vec<Warning>, Box<Error>> {
+        Err(Box::new(io::Error::new(io::ErrorKind::Other,
+                                    "mdBook was built without the `html` feature, set a renderer with \
+                                     `set_renderer()`")))
+    }
+}
+
+/// The renderer of a new book: the HTML renderer, or `NoRenderer` without the `html` feature
+#[cfg(feature = "html")]
+pub fn default_renderer() -> Box<Renderer> {
+    Box::new(HtmlHandlebars::new())
+}
+
+#[cfg(not(feature = "html"))]
+pub fn default_renderer() -> Box<Renderer> {
+    Box::new(NoRenderer)
+}
+
+/// Something wrong in the book that did not stop the renderer, like a link to a missing chapter
+#[derive(Debug, Clone, PartialEq)]
+pub struct Warning {
+    /// The chapter or the file concerned, relative to the source directory
+    pub path: Option<PathBuf>,
+    /// Line of the file it is about, from 1
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+impl Warning {
+    pub fn new<S: Into<String>>(path: &Path, message: S) -> Self {
+        Warning {
+            path: Some(path.to_owned()),
+            line: None,
+            message: message.into(),
+        }
+    }
+
+    /// A warning about the whole book
+    pub fn global<S: Into<String>>(message: S) -> Self {
+        Warning {
+            path: None,
+            line: None,
+            message: message.into(),
+        }
+    }
+
+    /// The same warning, about the line `line` of its file
+    pub fn at_line(mut self, line: usize) -> Self {
+        self.line = Some(line);
+        self
+    }
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match (self.path.as_ref(), self.line) {
+            (Some(path), Some(line)) => write!(f, "{}:{}: {}", path.display(), line, self.message),
+            (Some(path), None) => write!(f, "{}: {}", path.display(), self.message),
+            (None, _) => write!(f, "{}", self.message),
+        }
+    }
 }
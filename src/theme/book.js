@@ -27,50 +27,409 @@ $( document ).ready(function() {
     // even if highlighting doesn't apply
     $('code').addClass('hljs');
 
-    var KEY_CODES = {
-        PREVIOUS_KEY: 37,
-        NEXT_KEY: 39
-    };
+    // With `line-anchors`, every line of the code blocks gets an id, e.g. `code-3.L42` for the line 42 of the
+    // block `code-3`, and its number in the margin links to it. The spans of highlight.js spanning several
+    // lines are closed at the end of every line and opened again at the start of the next one.
+    $('pre.line-anchors > code').each(function(i, block) {
+        var page = $(location).attr('href').split("#")[0];
+        var block_id = $(block).parent().attr('id');
+        var open = [];
+        block.innerHTML = $.map(block.innerHTML.replace(/\n$/, '').split('\n'), function(line, n) {
+            var id = block_id + '.L' + (n + 1);
+            var html = '<span class="code-line" id="' + id + '"><a class="code-line-number" href="' + page + '#' +
+                id + '" data-line="' + (n + 1) + '" tabindex="-1" aria-hidden="true"></a>' + open.join('') + line;
+            $.each(line.match(/<span[^>]*>|<\/span>/g) || [], function(_, tag) {
+                if (tag === '</span>') { open.pop(); } else { open.push(tag); }
+            });
+            return html + new Array(open.length + 1).join('</span>') + '</span>';
+        }).join('');
+    });
+
+    // The line of the address is highlighted
+    function show_code_line() {
+        $('.code-line.target').removeClass('target');
+        var line = document.getElementById(decodeURIComponent(location.hash.substring(1)));
+        if (line && $(line).hasClass('code-line')) {
+            $(line).addClass('target');
+            line.scrollIntoView();
+        }
+    }
+    show_code_line();
+    $(window).on('hashchange', show_code_line);
+
+    var ESCAPE_KEY = 27;
+
+    // Keyboard shortcuts, the help opened with `?` is made from this list
+    var SHORTCUTS = [
+        { label: '\u2190', description: 'Go to the previous chapter', key_code: 37,
+          action: function() { follow_link($('.nav-chapters.previous')); } },
+        { label: '\u2192', description: 'Go to the next chapter', key_code: 39,
+          action: function() { follow_link($('.nav-chapters.next')); } },
+        { label: 'S', description: 'Search the book', key_code: 83,
+          available: function() { return search_wrapper.length > 0; },
+          action: function() { show_search(); } },
+        { label: 'T', description: 'Change the theme', key_code: 84,
+          action: function() {
+              $("#theme-toggle").click();
+              $('.theme-popup .theme').first().focus();
+          } },
+        { label: 'Esc', description: 'Close the search, the theme menu or this help', key_code: ESCAPE_KEY,
+          action: function() {
+              hide_search();
+              if ($('.theme-popup').length) { $("#theme-toggle").click(); }
+              hide_shortcuts();
+          } },
+        { label: '?', description: 'Show the keyboard shortcuts', key: '?', key_code: 191, shift: true,
+          action: function() { toggle_shortcuts(); } }
+    ];
+
+    function follow_link(link) {
+        if (link.length) { window.location.href = link.attr('href'); }
+    }
+
+    function shortcut_of(e) {
+        return SHORTCUTS.filter(function(shortcut) {
+            if (shortcut.available && !shortcut.available()) { return false; }
+            // `key` is the character typed, the same on every keyboard layout
+            if (shortcut.key && e.key) { return e.key === shortcut.key; }
+            return e.keyCode === shortcut.key_code && !!shortcut.shift === e.shiftKey;
+        })[0];
+    }
 
     $(document).on('keydown', function (e) {
-        switch (e.keyCode) {
-            case KEY_CODES.NEXT_KEY:
-                e.preventDefault();
-                if($('.nav-chapters.next').length) {
-                    window.location.href = $('.nav-chapters.next').attr('href');
-                }
-                break;
-            case KEY_CODES.PREVIOUS_KEY:
-                e.preventDefault();
-                if($('.nav-chapters.previous').length) {
-                    window.location.href = $('.nav-chapters.previous').attr('href');
-                }
-                break;
+        // Don't interfere with typing in the search bar
+        if ($(e.target).is('input, textarea')) {
+            if (e.keyCode === ESCAPE_KEY) { hide_search(); }
+            return;
+        }
+        if (e.altKey || e.ctrlKey || e.metaKey) { return; }
+
+        var shortcut = shortcut_of(e);
+        if (shortcut) {
+            e.preventDefault();
+            shortcut.action();
         }
     });
 
+    // Help listing the shortcuts
+    function toggle_shortcuts() {
+        if ($('.shortcuts-overlay').length) {
+            hide_shortcuts();
+            return;
+        }
+
+        var list = $('<dl></dl>');
+        SHORTCUTS.forEach(function(shortcut) {
+            if (shortcut.available && !shortcut.available()) { return; }
+            list.append($('<dt></dt>').append($('<kbd></kbd>').text(shortcut.label)))
+                .append($('<dd></dd>').text(shortcut.description));
+        });
+        var help = $('<div class="shortcuts" role="dialog" aria-label="Keyboard shortcuts" tabindex="-1"></div>')
+            .append($('<h2>Keyboard shortcuts</h2>'))
+            .append(list);
+        $('<div class="shortcuts-overlay"></div>')
+            .append(help)
+            .click(function(e) { if (e.target === this) { hide_shortcuts(); } })
+            .appendTo('body');
+        help.focus();
+    }
+
+    function hide_shortcuts() {
+        $('.shortcuts-overlay').remove();
+    }
+
     // Interesting DOM Elements
     var html = $("html");
     var sidebar = $("#sidebar");
+    var sidebar_toggle = $("#sidebar-toggle");
     var page_wrapper = $("#page-wrapper");
     var content = $("#content");
 
 
-    // Add anchors for all content headers
-    content.find("h1, h2, h3, h4, h5").wrap(function(){
-        var wrapper = $("<a class=\"header\">");
-        var header_name = $(this).text().trim().replace(/\W/g, '-')
-        wrapper.attr("name", header_name);
-        // Add so that when you click the link actually shows up in the url bar...
-        // Remove any existing anchor then append the new one
+    // Elements with role="button" are not focusable buttons for the browser,
+    // activate them with enter and space like a real button
+    $(document).on('keydown', '[role="button"]', function (e) {
+        if (e.keyCode === 13 || e.keyCode === 32) {
+            e.preventDefault();
+            $(this).click();
+        }
+    });
+
+
+    // Skip link, the <base> tag prevents a plain "#content" link from working
+    $(".skip-link").click(function(e){
+        e.preventDefault();
+        content.attr('tabindex', -1).focus();
+    });
+
+
+    // Search
+    var search_wrapper = $("#search-wrapper");
+    var search_toggle = $("#search-toggle");
+    var searchbar = $("#searchbar");
+    var searchresults = $("#searchresults");
+    var searchresults_header = $("#searchresults-header");
+    // With DocSearch the results are shown in a dropdown by the DocSearch script
+    var use_docsearch = search_wrapper.data('docsearch') === true;
+    // `search.html`, listing all the results
+    var search_page_path = search_wrapper.data('search-page');
+
+    function show_search() {
+        search_wrapper.removeClass("hidden");
+        search_toggle.attr('aria-expanded', true);
+        searchbar.focus();
+        if (!use_docsearch) {
+            load_search_index(function(){ update_search_results(); });
+        }
+    }
+
+    function hide_search() {
+        if (search_wrapper.hasClass("hidden")) { return; }
+        search_wrapper.addClass("hidden");
+        search_toggle.attr('aria-expanded', false).focus();
+    }
+
+    search_toggle.click(function(){
+        if (search_wrapper.hasClass("hidden")) { show_search(); } else { hide_search(); }
+    });
+
+    // The index can be big, only load it when the search is used
+    function load_search_index(callback) {
+        if (window.search_index) { callback(); return; }
+
+        var script = document.createElement('script');
+        script.src = 'searchindex.js';
+        script.onload = callback;
+        document.head.appendChild(script);
+    }
+
+    if (!use_docsearch) {
+        searchbar.on('input', update_search_results);
+    }
+
+    // Enter opens the page of all the results
+    searchbar.on('keydown', function(e){
+        if (e.keyCode !== 13 || !search_page_path || use_docsearch || $.trim(searchbar.val()) === '') { return; }
+        e.preventDefault();
+        location.href = search_page_url(searchbar.val(), 1);
+    });
+
+    function search_page_url(query, page) {
+        return search_page_path + '?q=' + encodeURIComponent(query) + (page > 1 ? '&page=' + page : '');
+    }
+
+    function update_search_results() {
+        if (!window.search_index) { return; }
+
+        var query = searchbar.val();
+        var results = search(window.search_index, query, window.search_index.config.limit_results);
+
+        searchresults.empty();
+        if ($.trim(query) === '') {
+            searchresults_header.text('');
+            return;
+        }
+
+        searchresults_header.text(results.length + (results.length === 1 ? ' result' : ' results') + ' for \'' + query + '\'');
+        $.each(results, function(_, result){
+            var doc = result.doc;
+            var link = $('<a>').attr('href', doc.url).text(doc.heading && doc.heading !== doc.title ? doc.title + ' » ' + doc.heading : doc.title);
+            var teaser = $('<span class="teaser">').html(search_teaser(doc.body, result.terms, window.search_index.config.teaser_word_count));
+            searchresults.append($('<li>').append(link).append(teaser));
+        });
+        if (search_page_path && results.length) {
+            var all = $('<a>').attr('href', search_page_url(query, 1)).text('All results');
+            searchresults.append($('<li class="searchresults-all">').append(all));
+        }
+    }
+
+    // Scores every section of the index, a section only matches if it contains all the terms of the query.
+    // Returns the `limit` best ones, or all of them without a limit.
+    function search(index, query, limit) {
+        var terms = $.grep(query.toLowerCase().split(/\s+/), function(term){ return term.length > 0; });
+        if (terms.length === 0) { return []; }
+
+        var results = [];
+        $.each(index.docs, function(_, doc){
+            var heading = (doc.title + ' ' + doc.heading).toLowerCase();
+            var body = doc.body.toLowerCase();
+            var score = 0;
+
+            for (var i = 0; i < terms.length; i++) {
+                var in_heading = count_occurrences(heading, terms[i]);
+                var in_body = count_occurrences(body, terms[i]);
+                if (in_heading + in_body === 0) { return; }
+
+                score += in_heading * index.config.boost_heading + in_body * index.config.boost_body;
+            }
+
+            results.push({ doc: doc, score: score, terms: terms });
+        });
+
+        results.sort(function(a, b){ return b.score - a.score; });
+        return limit ? results.slice(0, limit) : results;
+    }
+
+    function count_occurrences(text, term) {
+        var count = 0;
+        for (var i = text.indexOf(term); i !== -1; i = text.indexOf(term, i + term.length)) { count++; }
+        return count;
+    }
+
+    // Excerpt of the body around the first matching word, with the matching words emphasized
+    function search_teaser(body, terms, word_count) {
+        var words = body.split(' ');
+        var matches = function(word){
+            word = word.toLowerCase();
+            return $.grep(terms, function(term){ return word.indexOf(term) !== -1; }).length > 0;
+        };
+
+        var first = 0;
+        while (first < words.length && !matches(words[first])) { first++; }
+        if (first === words.length) { first = 0; }
+
+        var start = Math.max(0, first - Math.floor(word_count / 2));
+        var teaser = $.map(words.slice(start, start + word_count), function(word){
+            var escaped = $('<div>').text(word).html();
+            return matches(word) ? '<em>' + escaped + '</em>' : escaped;
+        }).join(' ');
+
+        return (start > 0 ? '... ' : '') + teaser + (start + word_count < words.length ? ' ...' : '');
+    }
+
+    // The page of all the results, `search.html?q=...&page=2`. The results are grouped by chapter, in the order
+    // of their best result, and `data-page-size` chapters are listed on each page.
+    var search_page = content.find(".search-page");
+    if (search_page.length) {
+        var search_query = url_parameter('q') || '';
+        search_page.find('input[name="q"]').val(search_query);
+        if ($.trim(search_query) !== '') {
+            load_search_index(show_search_page);
+        }
+    }
+
+    function url_parameter(name) {
+        var params = location.search.substring(1).split('&');
+        for (var i = 0; i < params.length; i++) {
+            var pair = params[i].split('=');
+            if (decodeURIComponent(pair[0]) === name) {
+                return decodeURIComponent((pair[1] || '').replace(/\+/g, ' '));
+            }
+        }
+        return null;
+    }
+
+    function show_search_page() {
+        var results = search(window.search_index, search_query);
+        var chapters = [];
+        var by_page = {};
+        $.each(results, function(_, result){
+            var page = result.doc.url.split('#')[0];
+            if (!by_page.hasOwnProperty(page)) {
+                by_page[page] = { title: result.doc.title, url: page, results: [] };
+                chapters.push(by_page[page]);
+            }
+            by_page[page].results.push(result);
+        });
+
+        var page_size = search_page.data('page-size') || 10;
+        var pages = Math.max(Math.ceil(chapters.length / page_size), 1);
+        var current = Math.min(Math.max(parseInt(url_parameter('page'), 10) || 1, 1), pages);
+
+        search_page.find('.search-page-header').text(results.length + (results.length === 1 ? ' result' : ' results') +
+            ' in ' + chapters.length + (chapters.length === 1 ? ' chapter' : ' chapters') + ' for \'' + search_query + '\'');
+
+        var list = search_page.find('.search-page-results');
+        $.each(chapters.slice((current - 1) * page_size, current * page_size), function(_, chapter){
+            var sections = $('<ul>');
+            $.each(chapter.results, function(_, result){
+                var link = $('<a>').attr('href', result.doc.url).text(result.doc.heading || result.doc.title);
+                var teaser = $('<span class="teaser">').html(search_teaser(result.doc.body, result.terms,
+                    window.search_index.config.teaser_word_count));
+                sections.append($('<li>').append(link).append(teaser));
+            });
+            var title = $('<a class="search-page-chapter">').attr('href', chapter.url).text(chapter.title);
+            list.append($('<li>').append(title).append(sections));
+        });
+
+        var nav = search_page.find('.search-page-pages');
+        for (var i = 1; pages > 1 && i <= pages; i++) {
+            if (i === current) {
+                nav.append($('<span aria-current="page">').text(i));
+            } else {
+                nav.append($('<a>').attr('href', search_page_url(search_query, i)).text(i));
+            }
+        }
+    }
+
+
+    // Reader preferences for the font of the content, saved in localStorage.
+    // index.hbs already applies them before the page is displayed.
+    var FONT_SIZE = { MIN: 70, MAX: 200, STEP: 10 };
+    var font_size = parseInt(localStorage.getItem('font-size'), 10) || 100;
+
+    function set_font_size(size) {
+        font_size = Math.min(Math.max(size, FONT_SIZE.MIN), FONT_SIZE.MAX);
+        document.documentElement.style.setProperty('--content-font-size', font_size + '%');
+        localStorage.setItem('font-size', font_size);
+    }
+
+    $("#font-smaller").click(function(){ set_font_size(font_size - FONT_SIZE.STEP); });
+    $("#font-larger").click(function(){ set_font_size(font_size + FONT_SIZE.STEP); });
+
+    var font_family_toggle = $("#font-family-toggle");
+    font_family_toggle.attr('aria-pressed', html.hasClass("font-serif"));
+    font_family_toggle.click(function(){
+        html.toggleClass("font-serif");
+        localStorage.setItem('font-family', html.hasClass("font-serif") ? 'serif' : 'sans');
+        font_family_toggle.attr('aria-pressed', html.hasClass("font-serif"));
+    });
+
+
+    // The links to the headings of a chapter split in several pages go to its first page, follow them to the
+    // page of the heading
+    var chapter_pages = content.find(".chapter-pages");
+    if (chapter_pages.length && location.hash.length > 1) {
+        var anchor = decodeURIComponent(location.hash.substring(1));
+        // The lines of the code blocks are on the page of their block
+        var page = chapter_pages.data("anchors")[anchor] || chapter_pages.data("anchors")[anchor.split(".L")[0]];
+        if (page) { location.replace(page + location.hash); }
+    }
+
+    // Add anchors for all content headers, set by `[output.html.headings]`: the whole heading links to
+    // its anchor, or an icon after its text does, and the headings of a lower level than the minimum
+    // only get an anchor
+    var permalink = content.data("permalink") || "heading";
+    var min_level = content.data("heading-min-level") || 1;
+    if (content.data("permalink-visibility") == "always") { content.addClass("permalinks-visible"); }
+    content.find("h1, h2, h3, h4, h5").each(function(){
+        // Numbered headings keep the anchor they have without numbers, and the headings with an
+        // id from `{#id}` use it instead
+        var header_name = this.id || $(this).clone().children(".heading-number").remove().end()
+            .text().trim().replace(/\W/g, '-')
+        // Remove any existing anchor then append the new one so that the link shows up in the url bar,
         // ensuring eg. no spaces are present within it ie. they become %20
-        wrapper.attr("href", $(location).attr('href').split("#")[0] + "#" + header_name );
-        return wrapper;
+        var href = $(location).attr('href').split("#")[0] + "#" + header_name;
+        var linked = parseInt(this.tagName.substring(1), 10) >= min_level;
+        if (permalink == "heading" && linked) {
+            var wrapper = $("<a class=\"header\">");
+            wrapper.attr("name", header_name);
+            wrapper.attr("href", href);
+            $(this).wrap(wrapper);
+            return;
+        }
+
+        this.id = header_name;
+        if (linked) {
+            $("<a class=\"header-link\" aria-label=\"Link to this heading\"><i class=\"fa fa-link\" aria-hidden=\"true\"></i></a>")
+                .attr("href", href)
+                .appendTo(this);
+        }
     });
 
 
     // Toggle sidebar
-    $("#sidebar-toggle").click(function(event){
+    sidebar_toggle.click(function(event){
         if ( html.hasClass("sidebar-hidden") ) {
             html.removeClass("sidebar-hidden").addClass("sidebar-visible");
             localStorage.setItem('sidebar', 'visible');
@@ -86,22 +445,170 @@ $( document ).ready(function() {
                 localStorage.setItem('sidebar', 'visible');
             }
         }
+
+        update_sidebar_accessibility(html.hasClass("sidebar-visible"));
+    });
+
+    // Keep screen readers and keyboard navigation in sync with the visibility of the sidebar
+    function update_sidebar_accessibility(visible) {
+        sidebar_toggle.attr('aria-expanded', visible);
+        sidebar.attr('aria-hidden', !visible);
+        sidebar.find('a').attr('tabindex', visible ? null : -1);
+
+        // Don't leave the focus on a link that is not visible anymore
+        if (!visible && $.contains(sidebar[0], document.activeElement)) {
+            sidebar_toggle.focus();
+        }
+    }
+
+    update_sidebar_accessibility(sidebar.position().left === 0);
+
+
+    // Collapse / expand sections of the table of contents
+    var section_toggles = sidebar.find(".toggle");
+
+    function set_section_expanded(toggle, expanded) {
+        toggle.attr('aria-expanded', expanded);
+        toggle.find('i').toggleClass('fa-angle-down', expanded).toggleClass('fa-angle-right', !expanded);
+        toggle.parent().next('li').children('.section').toggle(expanded);
+    }
+
+    section_toggles.click(function(){
+        var toggle = $(this);
+        set_section_expanded(toggle, toggle.attr('aria-expanded') !== 'true');
+        save_sidebar_state();
     });
 
 
-    // Scroll sidebar to current active section
+    // The sidebar is the same on every page, remember its scroll position and the
+    // collapsed sections for the duration of the session to restore them on the next page
+    function save_sidebar_state() {
+        var collapsed = [];
+        section_toggles.each(function(i){
+            if ($(this).attr('aria-expanded') !== 'true') { collapsed.push(i); }
+        });
+        sessionStorage.setItem('sidebar-collapsed', JSON.stringify(collapsed));
+        sessionStorage.setItem('sidebar-scroll', sidebar.scrollTop());
+    }
+
+    var collapsed_sections = JSON.parse(sessionStorage.getItem('sidebar-collapsed') || '[]');
+    $.each(collapsed_sections, function(_, i){
+        set_section_expanded(section_toggles.eq(i), false);
+    });
+
+    var sidebar_scroll = sessionStorage.getItem('sidebar-scroll');
+    if (sidebar_scroll !== null) {
+        sidebar.scrollTop(parseInt(sidebar_scroll, 10));
+    }
+
+    sidebar.on('scroll', save_sidebar_state);
+
+
+    // Make sure the current chapter is visible in the sidebar
     var activeSection = sidebar.find(".active");
     if(activeSection.length) {
-        sidebar.scrollTop(activeSection.offset().top);
+        // Expand the sections containing it
+        activeSection.parents('li').each(function(){
+            var toggle = $(this).prev('li').children('.toggle');
+            if (toggle.length && toggle.attr('aria-expanded') !== 'true') {
+                set_section_expanded(toggle, true);
+            }
+        });
+
+        // and scroll to it if it is outside of the visible part of the sidebar
+        var top = activeSection.offset().top - sidebar.offset().top;
+        if (top < 0 || top + activeSection.outerHeight() > sidebar.height()) {
+            sidebar.scrollTop(sidebar.scrollTop() + top - sidebar.height() / 2);
+        }
     }
 
 
+    // Reading progress and back to top button, the page wrapper scrolls on some browsers and the
+    // document on the others
+    var progress_bar = $("#progress-bar");
+    var back_to_top = $("#back-to-top");
+    var page_wrapper = $("#page-wrapper");
+    var scrolling = $("html, body").add(page_wrapper);
+
+    function update_scroll_progress() {
+        var element = document.scrollingElement || document.documentElement;
+        var top = Math.max(element.scrollTop, page_wrapper.scrollTop());
+        var height = Math.max(element.scrollHeight - element.clientHeight,
+                              page_wrapper.prop('scrollHeight') - page_wrapper.innerHeight());
+        var progress = height > 0 ? Math.min(100, Math.round(top / height * 100)) : 100;
+
+        progress_bar.css('width', progress + '%').attr('aria-valuenow', progress);
+        back_to_top.toggleClass('hidden', top < $(window).height());
+    }
+
+    if (progress_bar.length || back_to_top.length) {
+        $(window).on('scroll resize', update_scroll_progress);
+        page_wrapper.on('scroll', update_scroll_progress);
+        update_scroll_progress();
+    }
+
+    back_to_top.on('click keydown', function(e){
+        if (e.type === 'keydown' && e.keyCode !== 13 && e.keyCode !== 32) { return; }
+        e.preventDefault();
+        scrolling.animate({ scrollTop: 0 }, 300);
+        $("#content").attr('tabindex', -1).focus();
+    });
+
+
     // Print button
+    // Version picker, the versions are built next to each other so the same page is opened in the
+    // directory of the selected version
+    $("#version-picker").change(function(){
+        var page = $(this).data("path").replace(/\.md$/, ".html");
+        window.location.href = "../" + $(this).val() + "/" + page;
+    });
+
     $("#print-button").click(function(){
         var printWindow = window.open("print.html");
     });
 
+    // The videos and pages embedded with `{{#video}}` and `{{#embed}}` are only loaded when the reader clicks on
+    // their placeholder, the iframe address is its `data-embed`
+    $(".embed[data-embed] .embed-placeholder").click(function(e){
+        e.preventDefault();
+        var embed = $(this).closest(".embed");
+        $('<iframe allowfullscreen allow="autoplay; fullscreen; picture-in-picture"></iframe>')
+            .attr("src", embed.data("embed"))
+            .attr("title", embed.attr("title"))
+            .appendTo(embed.empty());
+        embed.find("iframe").focus();
+    });
+
+    // Quizzes, the answers are the `data-answer` of the questions: the indices of the right choices or
+    // the accepted texts
+    $(".quiz-check").click(function(){
+        var question = $(this).closest(".quiz-question");
+        var answer = question.data("answer");
+        var correct;
+
+        if (question.data("kind") == "text") {
+            var given = $.trim(question.find(".quiz-input").val()).toLowerCase();
+            correct = answer.some(function(a){ return $.trim(a).toLowerCase() == given; });
+        } else {
+            var checked = question.find("input:checked").map(function(){ return parseInt(this.value, 10); }).get();
+            correct = checked.length == answer.length && answer.every(function(a){ return checked.indexOf(a) != -1; });
+        }
+
+        question.toggleClass("correct", correct).toggleClass("incorrect", !correct);
+        question.find(".quiz-result").text(correct ? "Correct!" : "Not quite, try again.");
+    });
+
+    // The collapsible sections are printed expanded, the ones closed on the page are closed again after printing
+    var closedDetails = $();
+    $(window).on("beforeprint", function(){
+        closedDetails = $("details:not([open])").attr("open", "");
+    });
+    $(window).on("afterprint", function(){
+        closedDetails.removeAttr("open");
+    });
+
     if( url.substring(url.lastIndexOf('/')+1) == "print.html" ) {
+        $("details").attr("open", "");
         window.print();
     }
 
@@ -110,15 +617,17 @@ $( document ).ready(function() {
     $("#theme-toggle").click(function(){
         if($('.theme-popup').length) {
             $('.theme-popup').remove();
+            $(this).attr('aria-expanded', false);
         } else {
             var popup = $('<div class="theme-popup"></div>')
-                .append($('<div class="theme" id="light">Light <span class="default">(default)</span><div>'))
-                .append($('<div class="theme" id="rust">Rust</div>'))
-                .append($('<div class="theme" id="coal">Coal</div>'))
-                .append($('<div class="theme" id="navy">Navy</div>'));
+                .append($('<div class="theme" id="light" role="button" tabindex="0">Light <span class="default">(default)</span></div>'))
+                .append($('<div class="theme" id="rust" role="button" tabindex="0">Rust</div>'))
+                .append($('<div class="theme" id="coal" role="button" tabindex="0">Coal</div>'))
+                .append($('<div class="theme" id="navy" role="button" tabindex="0">Navy</div>'));
 
 
             popup.insertAfter(this);
+            $(this).attr('aria-expanded', true);
 
             $('.theme').click(function(){
                 var theme = $(this).attr('id');
@@ -140,7 +649,8 @@ $( document ).ready(function() {
 
         localStorage.setItem('theme', theme);
 
-        $('body').removeClass().addClass(theme);
+        // Only the class of the previous theme is removed, the classes of the chapter stay
+        $('body').removeClass('light rust coal navy').addClass(theme);
     }
 
 
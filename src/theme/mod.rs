@@ -1,6 +1,9 @@
-use std::path::Path;
-use std::fs::File;
+use std::path::{Path, PathBuf};
+use std::fs::{self, File};
 use std::io::Read;
+use std::collections::BTreeMap;
+
+use toml;
 
 
 pub static INDEX: &'static [u8] = include_bytes!("index.hbs");
@@ -11,13 +14,16 @@ pub static HIGHLIGHT_JS: &'static [u8] = include_bytes!("highlight.js");
 pub static TOMORROW_NIGHT_CSS: &'static [u8] = include_bytes!("tomorrow-night.css");
 pub static HIGHLIGHT_CSS: &'static [u8] = include_bytes!("highlight.css");
 pub static JQUERY: &'static [u8] = include_bytes!("jquery-2.1.4.min.js");
+/// The partials of `index.hbs`, by name, that a theme can override with `partials/name.hbs`
+pub static PARTIALS: &'static [(&'static str, &'static [u8])] = &[("head", include_bytes!("partials/head.hbs")),
+                                                                  ("menu-bar", include_bytes!("partials/menu-bar.hbs")),
+                                                                  ("sidebar", include_bytes!("partials/sidebar.hbs"))];
 pub static FONT_AWESOME: &'static [u8] = include_bytes!("_FontAwesome/css/font-awesome.min.css");
 pub static FONT_AWESOME_EOT: &'static [u8] = include_bytes!("_FontAwesome/fonts/fontawesome-webfont.eot");
 pub static FONT_AWESOME_SVG: &'static [u8] = include_bytes!("_FontAwesome/fonts/fontawesome-webfont.svg");
 pub static FONT_AWESOME_TTF: &'static [u8] = include_bytes!("_FontAwesome/fonts/fontawesome-webfont.ttf");
 pub static FONT_AWESOME_WOFF: &'static [u8] = include_bytes!("_FontAwesome/fonts/fontawesome-webfont.woff");
 pub static FONT_AWESOME_WOFF2: &'static [u8] = include_bytes!("_FontAwesome/fonts/fontawesome-webfont.woff2");
-pub static FONT_AWESOME_OTF: &'static [u8] = include_bytes!("_FontAwesome/fonts/FontAwesome.otf");
 
 /// The `Theme` struct should be used instead of the static variables because the `new()` method
 /// will look if the user has a theme directory in his source folder and use the users theme instead
@@ -34,11 +40,24 @@ pub struct Theme {
     pub tomorrow_night_css: Vec<u8>,
     pub highlight_js: Vec<u8>,
     pub jquery: Vec<u8>,
+    /// The other `*.hbs` files of the theme, by name, that chapters can select with `template`
+    pub templates: BTreeMap<String, Vec<u8>>,
+    /// The `helpers/*.hbs` files of the theme, by name, registered as handlebars helpers
+    pub helpers: BTreeMap<String, Vec<u8>>,
+    /// The `partials/*.hbs` files of the theme, by name, included by the templates with `{{> partials/name}}`
+    pub partials: BTreeMap<String, Vec<u8>>,
+    /// The directories the theme is loaded from, the themes it extends first
+    pub dirs: Vec<PathBuf>,
 }
 
+/// File of a theme directory extending another theme, with the `extends` key
+pub const THEME_CONFIG: &'static str = "theme.toml";
+
+// Themes extending each other, more than this is a loop
+const MAX_PARENTS: usize = 16;
+
 impl Theme {
     pub fn new(src: &Path) -> Self {
-
         // Default theme
         let mut theme = Theme {
             index: INDEX.to_owned(),
@@ -49,57 +68,185 @@ impl Theme {
             tomorrow_night_css: TOMORROW_NIGHT_CSS.to_owned(),
             highlight_js: HIGHLIGHT_JS.to_owned(),
             jquery: JQUERY.to_owned(),
+            templates: BTreeMap::new(),
+            helpers: BTreeMap::new(),
+            partials: PARTIALS.iter().map(|&(name, partial)| (name.to_owned(), partial.to_owned())).collect(),
+            dirs: vec![],
         };
-
-        // Check if the given path exists
-        if !src.exists() || !src.is_dir() {
-            return theme;
+        for dir in theme_dirs(src) {
+            theme.load(&dir);
+            theme.dirs.push(dir);
         }
+        theme
+    }
 
-        // Check for individual files if they exist
+    // Replaces the files of the theme by the ones of the directory `src`. The stylesheets of a theme with a
+    // `theme.toml` are appended to the ones of the theme it extends instead.
+    fn load(&mut self, src: &Path) {
+        let append = src.join(THEME_CONFIG).exists();
+        let read = |file: &str, content: &mut Vec<u8>, append: bool| {
+            if let Ok(mut f) = File::open(&src.join(file)) {
+                if append {
+                    content.push(b'\n');
+                } else {
+                    content.clear(); // Reset the value, because read_to_string appends...
+                }
+                let _ = f.read_to_end(content);
+            }
+        };
 
-        // index.hbs
-        if let Ok(mut f) = File::open(&src.join("index.hbs")) {
-            theme.index.clear(); // Reset the value, because read_to_string appends...
-            let _ = f.read_to_end(&mut theme.index);
-        }
+        read("index.hbs", &mut self.index, false);
+        read("book.js", &mut self.js, false);
+        read("book.css", &mut self.css, append);
+        read("favicon.png", &mut self.favicon, false);
+        read("highlight.js", &mut self.highlight_js, false);
+        read("highlight.css", &mut self.highlight_css, append);
+        read("tomorrow-night.css", &mut self.tomorrow_night_css, append);
 
-        // book.js
-        if let Ok(mut f) = File::open(&src.join("book.js")) {
-            theme.js.clear();
-            let _ = f.read_to_end(&mut theme.js);
-        }
+        // Other templates, helpers and partials, they replace the ones with the same name
+        let mut templates = read_templates(src);
+        templates.remove("index");
+        self.templates.extend(templates);
+        self.helpers.extend(read_templates(&src.join("helpers")));
+        self.partials.extend(read_templates(&src.join("partials")));
+    }
+}
 
-        // book.css
-        if let Ok(mut f) = File::open(&src.join("book.css")) {
-            theme.css.clear();
-            let _ = f.read_to_end(&mut theme.css);
+/// The directories of the theme in `src` and of the themes it extends, the first one extending the default theme
+/// first. A missing theme directory has none.
+pub fn theme_dirs(src: &Path) -> Vec<PathBuf> {
+    let mut dirs = vec![];
+    let mut dir = src.to_owned();
+    while dir.is_dir() {
+        dirs.insert(0, dir.clone());
+        match extended_theme(&dir) {
+            Some(parent) => {
+                if dirs.len() > MAX_PARENTS {
+                    warn!("[*]: The themes of {} extend each other in a loop", src.display());
+                    break;
+                }
+                if !parent.is_dir() {
+                    warn!("[*]: The theme {} extends {}, which is not a directory", dir.display(), parent.display());
+                }
+                dir = parent;
+            },
+            None => break,
         }
+    }
+    dirs
+}
 
-        // favicon.png
-        if let Ok(mut f) = File::open(&src.join("favicon.png")) {
-            theme.favicon.clear();
-            let _ = f.read_to_end(&mut theme.favicon);
-        }
+// The theme extended by the theme directory `dir`, the `extends` key of its `theme.toml` relative to it. A
+// `theme.toml` without it, or with `extends = "default"`, extends the default theme.
+fn extended_theme(dir: &Path) -> Option<PathBuf> {
+    let mut content = String::new();
+    if File::open(dir.join(THEME_CONFIG)).and_then(|mut f| f.read_to_string(&mut content)).is_err() {
+        return None;
+    }
+    let config = match toml::Parser::new(&content).parse() {
+        Some(config) => config,
+        None => {
+            warn!("[*]: {} is not valid TOML", dir.join(THEME_CONFIG).display());
+            return None;
+        },
+    };
+    match config.get("extends").and_then(|v| v.as_str()) {
+        Some("default") | None => None,
+        Some(parent) => {
+            let path = dir.join(parent);
+            Some(path.canonicalize().unwrap_or(path))
+        },
+    }
+}
 
-        // highlight.js
-        if let Ok(mut f) = File::open(&src.join("highlight.js")) {
-            theme.highlight_js.clear();
-            let _ = f.read_to_end(&mut theme.highlight_js);
+/// Reads the `*.hbs` files of a directory, by name without the extension
+fn read_templates(dir: &Path) -> BTreeMap<String, Vec<u8>> {
+    let mut templates = BTreeMap::new();
+    if let Ok(entries) = fs::read_dir(dir) {
+        for path in entries.filter_map(|e| e.ok()).map(|e| e.path()) {
+            if path.extension().and_then(|e| e.to_str()) != Some("hbs") {
+                continue;
+            }
+            let name = match path.file_stem().and_then(|n| n.to_str()) {
+                Some(name) => name.to_owned(),
+                None => continue,
+            };
+            if let Ok(mut f) = File::open(&path) {
+                let mut template = Vec::new();
+                let _ = f.read_to_end(&mut template);
+                templates.insert(name, template);
+            }
         }
+    }
+    templates
+}
 
-        // highlight.css
-        if let Ok(mut f) = File::open(&src.join("highlight.css")) {
-            theme.highlight_css.clear();
-            let _ = f.read_to_end(&mut theme.highlight_css);
-        }
+// ------------------------------------------------------------------------------------------------
 
-        // tomorrow-night.css
-        if let Ok(mut f) = File::open(&src.join("tomorrow-night.css")) {
-            theme.tomorrow_night_css.clear();
-            let _ = f.read_to_end(&mut theme.tomorrow_night_css);
-        }
+// tests
 
-        theme
+#[cfg(test)]
+mod tests {
+    extern crate tempdir;
+
+    use super::Theme;
+    use std::fs::{self, File};
+    use std::io::Write;
+
+    #[test]
+    fn it_loads_the_other_templates() {
+        let tmp = tempdir::TempDir::new("mdbook-theme").unwrap();
+        File::create(tmp.path().join("index.hbs")).unwrap().write_all(b"index").unwrap();
+        File::create(tmp.path().join("landing.hbs")).unwrap().write_all(b"landing").unwrap();
+        File::create(tmp.path().join("book.css")).unwrap();
+
+        let theme = Theme::new(tmp.path());
+        assert_eq!(theme.index, b"index");
+        assert_eq!(theme.templates.keys().collect::<Vec<_>>(), vec!["landing"]);
+        assert_eq!(theme.templates["landing"], b"landing");
+        assert!(theme.helpers.is_empty());
+    }
+
+    #[test]
+    fn it_extends_another_theme() {
+        let tmp = tempdir::TempDir::new("mdbook-theme").unwrap();
+        let base = tmp.path().join("base");
+        let child = tmp.path().join("child");
+        fs::create_dir_all(base.join("partials")).unwrap();
+        fs::create_dir_all(child.join("partials")).unwrap();
+        File::create(base.join("book.css")).unwrap().write_all(b".base {}").unwrap();
+        File::create(base.join("book.js")).unwrap().write_all(b"base();").unwrap();
+        File::create(base.join("partials/sidebar.hbs")).unwrap().write_all(b"base sidebar").unwrap();
+        File::create(base.join("partials/footer.hbs")).unwrap().write_all(b"base footer").unwrap();
+        File::create(child.join("theme.toml")).unwrap().write_all(b"extends = \"../base\"\n").unwrap();
+        File::create(child.join("book.css")).unwrap().write_all(b".child {}").unwrap();
+        File::create(child.join("partials/footer.hbs")).unwrap().write_all(b"child footer").unwrap();
+
+        let theme = Theme::new(&child);
+        assert_eq!(theme.dirs, vec![base.canonicalize().unwrap(), child.clone()]);
+        assert_eq!(theme.css, b".base {}\n.child {}");
+        assert_eq!(theme.js, b"base();");
+        assert_eq!(theme.index, super::INDEX);
+        assert_eq!(theme.partials["sidebar"], b"base sidebar");
+        assert_eq!(theme.partials["footer"], b"child footer");
+        assert_eq!(theme.partials["head"], super::PARTIALS[0].1);
+
+        // The stylesheets of a theme extending the default one are appended to the default ones
+        File::create(child.join("theme.toml")).unwrap().write_all(b"extends = \"default\"\n").unwrap();
+        let theme = Theme::new(&child);
+        assert_eq!(theme.dirs, vec![child]);
+        assert!(theme.css.starts_with(super::CSS) && theme.css.ends_with(b"\n.child {}"));
+    }
+
+    #[test]
+    fn it_loads_the_helpers() {
+        let tmp = tempdir::TempDir::new("mdbook-theme").unwrap();
+        fs::create_dir(tmp.path().join("helpers")).unwrap();
+        File::create(tmp.path().join("helpers/note.hbs")).unwrap().write_all(b"<p>{{text}}</p>").unwrap();
+        File::create(tmp.path().join("helpers/README.md")).unwrap();
+
+        let theme = Theme::new(tmp.path());
+        assert!(theme.templates.is_empty());
+        assert_eq!(theme.helpers.keys().collect::<Vec<_>>(), vec!["note"]);
     }
 }
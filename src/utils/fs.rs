@@ -2,10 +2,48 @@ use std::path::{Path, Component};
 use std::error::Error;
 use std::io::{self, Read};
 use std::fs::{self, File};
+use std::string::FromUtf8Error;
 
-/// Takes a path to a file and try to read the file into a String
+use book::bookconfig::InvalidUtf8;
+use utils::ignore::IgnorePatterns;
+
+/// Takes a path to a file and try to read the file into a String. A file that is not valid UTF-8 is an
+/// error giving the offset of its first invalid byte.
 
 pub fn file_to_string(path: &Path) -> Result<String, Box<Error>> {
+    match try!(read_utf8(path)) {
+        Ok(content) => Ok(content),
+        Err(e) => Err(Box::new(invalid_utf8_error(path, &e, ""))),
+    }
+}
+
+/// Reads the file of a chapter, a file that is not valid UTF-8 is decoded as `invalid_utf8` says. The
+/// second value is then the warning saying how it was decoded.
+pub fn chapter_to_string(path: &Path, invalid_utf8: InvalidUtf8) -> Result<(String, Option<String>), Box<Error>> {
+    let invalid = match try!(read_utf8(path)) {
+        Ok(content) => return Ok((content, None)),
+        Err(e) => e,
+    };
+
+    let offset = invalid.utf8_error().valid_up_to();
+    match invalid_utf8 {
+        InvalidUtf8::Error => {
+            let hint = ", set `invalid-utf8` in book.toml to read it anyway";
+            Err(Box::new(invalid_utf8_error(path, &invalid, hint)))
+        },
+        InvalidUtf8::Lossy => {
+            Ok((String::from_utf8_lossy(invalid.as_bytes()).into_owned(),
+                Some(format!("is not valid UTF-8 from byte {}, the invalid bytes are replaced by U+FFFD", offset))))
+        },
+        InvalidUtf8::Latin1 => {
+            Ok((invalid.as_bytes().iter().map(|&b| b as char).collect(),
+                Some(format!("is not valid UTF-8 from byte {}, it is read as Latin-1", offset))))
+        },
+    }
+}
+
+// The content of a file, or its bytes when it is not valid UTF-8
+fn read_utf8(path: &Path) -> Result<Result<String, FromUtf8Error>, Box<Error>> {
     let mut file = match File::open(path) {
         Ok(f) => f,
         Err(e) => {
@@ -14,14 +52,23 @@ pub fn file_to_string(path: &Path) -> Result<String, Box<Error>> {
         },
     };
 
-    let mut content = String::new();
-
-    if let Err(e) = file.read_to_string(&mut content) {
+    let mut bytes = vec![];
+    if let Err(e) = file.read_to_end(&mut bytes) {
         debug!("[*]: Failed to read {:?}", path);
         return Err(Box::new(e));
     }
 
-    Ok(content)
+    Ok(String::from_utf8(bytes))
+}
+
+fn invalid_utf8_error(path: &Path, invalid: &FromUtf8Error, hint: &str) -> io::Error {
+    let offset = invalid.utf8_error().valid_up_to();
+    io::Error::new(io::ErrorKind::InvalidData,
+                   format!("{} is not valid UTF-8: the byte 0x{:02x} at offset {} is invalid{}",
+                           path.display(),
+                           invalid.as_bytes()[offset],
+                           offset,
+                           hint))
 }
 
 /// Takes a path and returns a path containing just enough `../` to point to the root of the given path.
@@ -64,6 +111,18 @@ pub fn path_to_root(path: &Path) -> String {
         })
 }
 
+/// The path with `/` separators, for the links and the addresses of the book. Windows tends to use `\` as
+/// separator instead.
+pub fn path_to_url(path: &Path) -> String {
+    path.to_string_lossy().replace("\\", "/")
+}
+
+/// The link to the page of a chapter from the root of the book, its path with the `.html` extension and `/`
+/// separators
+pub fn path_to_link(path: &Path) -> String {
+    path_to_url(&path.with_extension("html"))
+}
+
 
 
 /// This function creates a file and returns it. But before creating the file it checks every
@@ -99,60 +158,257 @@ pub fn remove_dir_content(dir: &Path) -> Result<(), Box<Error>> {
     Ok(())
 }
 
+/// Removes the content of a directory except the copies of the files of `src` made by
+/// `copy_files_except_ext`, the files with the same path relative to `src` and an extension that is
+/// not in `ext_blacklist`. The files generated by the previous build and the copies of the files
+/// that were removed from `src` are removed, the other copies are kept so that they are only copied
+/// again when they changed. The copies of the files matched by `ignore` are removed too.
+
+pub fn remove_dir_content_except_copies(dir: &Path,
+                                        src: &Path,
+                                        ext_blacklist: &[&str],
+                                        ignore: &IgnorePatterns)
+                                        -> Result<(), Box<Error>> {
+    for item in try!(fs::read_dir(dir)) {
+        let item = try!(item).path();
+        let source = match item.file_name() {
+            Some(name) => src.join(name),
+            None => continue,
+        };
+
+        // The links are created again, a link to a directory is not followed
+        let is_symlink = try!(fs::symlink_metadata(&item)).file_type().is_symlink();
+        if is_symlink {
+            try!(fs::remove_file(&item).or_else(|_| fs::remove_dir(&item)));
+        } else if item.is_dir() {
+            // The destination can be in the source directory
+            if source.is_dir() && source != dir && !ignore.is_ignored(&source) {
+                try!(remove_dir_content_except_copies(&item, &source, ext_blacklist, ignore));
+            } else {
+                try!(fs::remove_dir_all(item));
+            }
+        } else if !source.is_file() || has_extension_in(&source, ext_blacklist) || ignore.is_ignored(&source) {
+            try!(fs::remove_file(item));
+        }
+    }
+    Ok(())
+}
+
+fn has_extension_in(path: &Path, ext_blacklist: &[&str]) -> bool {
+    path.extension().and_then(|e| e.to_str()).is_some_and(|e| ext_blacklist.contains(&e))
+}
+
+// Whether the copy `b` of `a` has the same content, the copies of unchanged files are not written again. A copy of
+// the same size that is newer than the file is up to date, the content is only compared when the file was modified
+// after it.
+fn same_content(a: &Path, b: &Path) -> bool {
+    match (fs::metadata(a), fs::metadata(b)) {
+        (Ok(a_meta), Ok(b_meta)) if a_meta.len() == b_meta.len() => {
+            match (a_meta.modified(), b_meta.modified()) {
+                (Ok(a_modified), Ok(b_modified)) if a_modified <= b_modified => return true,
+                _ => {},
+            }
+        },
+        _ => return false,
+    }
+
+    let mut a_content = vec![];
+    let mut b_content = vec![];
+    File::open(a).and_then(|mut f| f.read_to_end(&mut a_content)).is_ok() &&
+    File::open(b).and_then(|mut f| f.read_to_end(&mut b_content)).is_ok() && a_content == b_content
+}
+
+/// Paths of all the files in `dir` and its sub-directories, relative to `dir` with `/` separators
+/// and sorted
+
+pub fn list_files(dir: &Path) -> Result<Vec<String>, Box<Error>> {
+    fn walk(root: &Path, dir: &Path, files: &mut Vec<String>) -> Result<(), Box<Error>> {
+        for entry in try!(fs::read_dir(dir)) {
+            let path = try!(entry).path();
+            if path.is_dir() {
+                try!(walk(root, &path, files));
+            } else if let Ok(relative) = path.strip_prefix(root) {
+                files.push(path_to_url(relative));
+            }
+        }
+        Ok(())
+    }
+
+    let mut files = vec![];
+    try!(walk(dir, dir, &mut files));
+    files.sort();
+    Ok(files)
+}
+
+/// The files of `from` that [`copy_files_except_ext()`](fn.copy_files_except_ext.html) copies recursively to
+/// `to`, relative to `from` with `/` separators and sorted
+
+pub fn files_except_ext(from: &Path,
+                        to: &Path,
+                        ext_blacklist: &[&str],
+                        ignore: &IgnorePatterns)
+                        -> Result<Vec<String>, Box<Error>> {
+    Ok(try!(list_files(from))
+        .into_iter()
+        .filter(|file| {
+            let path = from.join(file);
+            !path.starts_with(to) && !ignore.is_ignored(&path) && !has_extension_in(&path, ext_blacklist)
+        })
+        .collect())
+}
+
 ///
 ///
 /// Copies all files of a directory to another one except the files with the extensions given in the
-/// `ext_blacklist` array
-
-pub fn copy_files_except_ext(from: &Path, to: &Path, recursive: bool, ext_blacklist: &[&str]) -> Result<(), Box<Error>> {
+/// `ext_blacklist` array. Files that already have a copy with the same content are not copied again.
+/// The symbolic links are followed, or copied as links with `preserve_symlinks`, and the permissions
+/// of the files, like the executable bit, are kept. The files and directories matched by `ignore`
+/// are not copied. A file that can not be copied does not stop the copy of the others, the error
+/// lists all of them with their paths.
+
+pub fn copy_files_except_ext(from: &Path,
+                             to: &Path,
+                             recursive: bool,
+                             ext_blacklist: &[&str],
+                             preserve_symlinks: bool,
+                             ignore: &IgnorePatterns)
+                             -> Result<(), Box<Error>> {
     debug!("[fn] copy_files_except_ext");
     // Check that from and to are different
     if from == to {
         return Ok(());
     }
+
+    let entries = try!(fs::read_dir(from).map_err(|e| {
+        io::Error::new(io::ErrorKind::Other, format!("Could not read {}: {}", from.display(), e))
+    }));
+
+    let mut errors = vec![];
     debug!("[*] Loop");
-    for entry in try!(fs::read_dir(from)) {
-        let entry = try!(entry);
-        debug!("[*] {:?}", entry.path());
-        let metadata = try!(entry.metadata());
-
-        // If the entry is a dir and the recursive option is enabled, call itself
-        if metadata.is_dir() && recursive {
-            if entry.path() == to.to_path_buf() {
+    for entry in entries {
+        let path = match entry {
+            Ok(entry) => entry.path(),
+            Err(e) => {
+                errors.push(format!("Could not read {}: {}", from.display(), e));
                 continue;
-            }
-            debug!("[*] is dir");
+            },
+        };
+        debug!("[*] {:?}", path);
+        if ignore.is_ignored(&path) {
+            debug!("[*] {:?} is ignored", path);
+            continue;
+        }
 
-            // check if output dir already exists
-            if !to.join(entry.file_name()).exists() {
-                try!(fs::create_dir(&to.join(entry.file_name())));
-            }
+        let target = to.join(path.file_name().expect("a file should have a file name..."));
+        if let Err(e) = copy_entry(&path, &target, to, recursive, ext_blacklist, preserve_symlinks, ignore) {
+            errors.push(e.to_string());
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(Box::new(io::Error::new(io::ErrorKind::Other, errors.join("\n"))))
+    }
+}
+
+// Copies a file, or a directory when `recursive`, of the directory copied to `to`
+fn copy_entry(path: &Path,
+              target: &Path,
+              to: &Path,
+              recursive: bool,
+              ext_blacklist: &[&str],
+              preserve_symlinks: bool,
+              ignore: &IgnorePatterns)
+              -> Result<(), Box<Error>> {
+    let is_symlink = fs::symlink_metadata(path).map(|m| m.file_type().is_symlink()).unwrap_or(false);
+    if is_symlink && preserve_symlinks {
+        return copy_symlink(path, target);
+    }
+
+    let metadata = try!(fs::metadata(path).map_err(|e| {
+        io::Error::new(io::ErrorKind::Other, format!("Could not read {}: {}", path.display(), e))
+    }));
 
-            try!(copy_files_except_ext(&from.join(entry.file_name()),
-                                       &to.join(entry.file_name()),
-                                       true,
-                                       ext_blacklist));
-        } else if metadata.is_file() {
-
-            // Check if it is in the blacklist
-            if let Some(ext) = entry.path().extension() {
-                if ext_blacklist.contains(&ext.to_str().unwrap()) {
-                    continue;
-                }
+    // If the entry is a dir and the recursive option is enabled, call itself
+    if metadata.is_dir() && recursive {
+        if path == to {
+            return Ok(());
+        }
+        debug!("[*] is dir");
+
+        // A link to one of the parent directories would be copied forever
+        if is_symlink {
+            let linked = try!(fs::canonicalize(path));
+            if path.parent().and_then(|p| fs::canonicalize(p).ok()).is_some_and(|p| p.starts_with(&linked)) {
+                return Err(Box::new(io::Error::new(io::ErrorKind::Other,
+                                                   format!("Could not copy {}: it links to one of its parent \
+                                                            directories",
+                                                           path.display()))));
             }
-            debug!("[*] creating path for file: {:?}",
-                   &to.join(entry.path().file_name().expect("a file should have a file name...")));
+        }
 
-            info!("[*] Copying file: {:?}\n    to {:?}",
-                  entry.path(),
-                  &to.join(entry.path().file_name().expect("a file should have a file name...")));
-            try!(fs::copy(entry.path(),
-                          &to.join(entry.path().file_name().expect("a file should have a file name..."))));
+        // check if output dir already exists
+        if !target.exists() {
+            try!(fs::create_dir(target).map_err(|e| {
+                io::Error::new(io::ErrorKind::Other, format!("Could not create {}: {}", target.display(), e))
+            }));
         }
+
+        return copy_files_except_ext(path, target, true, ext_blacklist, preserve_symlinks, ignore);
+    }
+
+    if !metadata.is_file() || has_extension_in(path, ext_blacklist) {
+        return Ok(());
     }
+
+    // The link of a previous build that preserved the links would be written through
+    if fs::symlink_metadata(target).map(|m| m.file_type().is_symlink()).unwrap_or(false) {
+        try!(fs::remove_file(target));
+    }
+
+    let copied = if same_content(path, target) {
+        debug!("[*] {:?} is up to date", target);
+        fs::set_permissions(target, metadata.permissions())
+    } else {
+        info!("[*] Copying file: {:?}\n    to {:?}", path, target);
+        fs::copy(path, target).map(|_| ())
+    };
+    try!(copied.map_err(|e| {
+        io::Error::new(io::ErrorKind::Other,
+                       format!("Could not copy {} to {}: {}", path.display(), target.display(), e))
+    }));
     Ok(())
 }
 
+// Creates a link to the file the link `path` points to, relative links are copied as they are
+fn copy_symlink(path: &Path, target: &Path) -> Result<(), Box<Error>> {
+    let linked = try!(fs::read_link(path));
+
+    if let Ok(metadata) = fs::symlink_metadata(target) {
+        if metadata.is_dir() {
+            try!(fs::remove_dir_all(target));
+        } else {
+            try!(fs::remove_file(target));
+        }
+    }
+
+    debug!("[*] Linking {:?} to {:?}", target, linked);
+    #[cfg(unix)]
+    let created = ::std::os::unix::fs::symlink(&linked, target);
+    #[cfg(windows)]
+    let created = if path.is_dir() {
+        ::std::os::windows::fs::symlink_dir(&linked, target)
+    } else {
+        ::std::os::windows::fs::symlink_file(&linked, target)
+    };
+
+    try!(created.map_err(|e| {
+        io::Error::new(io::ErrorKind::Other,
+                       format!("Could not link {} to {}: {}", target.display(), linked.display(), e))
+    }));
+    Ok(())
+}
 
 // ------------------------------------------------------------------------------------------------
 // ------------------------------------------------------------------------------------------------
@@ -163,8 +419,18 @@ pub fn copy_files_except_ext(from: &Path, to: &Path, recursive: bool, ext_blackl
 mod tests {
     extern crate tempdir;
 
-    use super::copy_files_except_ext;
+    use super::{copy_files_except_ext, files_except_ext, path_to_link, path_to_url, remove_dir_content_except_copies};
+    use utils::ignore::IgnorePatterns;
     use std::fs;
+    use std::path::Path;
+    use std::io::{Read, Write};
+
+    #[test]
+    fn it_writes_the_links_with_slashes() {
+        assert_eq!(path_to_link(Path::new("cli\\init.md")), "cli/init.html");
+        assert_eq!(path_to_link(Path::new("format/config.md")), "format/config.html");
+        assert_eq!(path_to_url(Path::new("img\\logo.png")), "img/logo.png");
+    }
 
     #[test]
     fn copy_files_except_ext_test() {
@@ -204,7 +470,8 @@ mod tests {
             panic!("Could not create output/sub_dir_exists")
         }
 
-        match copy_files_except_ext(&tmp.path(), &tmp.path().join("output"), true, &["md"]) {
+        let output = tmp.path().join("output");
+        match copy_files_except_ext(&tmp.path(), &output, true, &["md"], false, &IgnorePatterns::default()) {
             Err(e) => panic!("Error while executing the function:\n{:?}", e),
             Ok(_) => {},
         }
@@ -226,5 +493,125 @@ mod tests {
             panic!("output/sub_dir/file.png should exist")
         }
 
+        // The files of a dry run are the ones copied, without the output directory
+        assert_eq!(files_except_ext(&tmp.path(), &output, &["md"], &IgnorePatterns::default()).unwrap(),
+                   vec!["file.png", "file.txt", "sub_dir/file.png", "sub_dir_exists/file.txt"]);
+    }
+
+    #[test]
+    fn it_keeps_the_unchanged_copies() {
+        let tmp = tempdir::TempDir::new("").expect("Could not create a temp dir");
+        let src = tmp.path().join("src");
+        let dest = tmp.path().join("book");
+        fs::create_dir_all(src.join("img")).unwrap();
+        fs::create_dir_all(dest.join("img")).unwrap();
+        fs::create_dir_all(dest.join("old")).unwrap();
+
+        let write = |path: &::std::path::Path, content: &str| {
+            fs::File::create(path).unwrap().write_all(content.as_bytes()).unwrap();
+        };
+        write(&src.join("intro.md"), "# Intro");
+        write(&src.join("img/logo.png"), "logo");
+        write(&dest.join("intro.html"), "generated");
+        write(&dest.join("intro.md"), "# Intro");
+        write(&dest.join("img/logo.png"), "logo");
+        write(&dest.join("img/changed.png"), "old");
+        write(&dest.join("img/removed.png"), "removed");
+        write(&dest.join("old/page.html"), "generated");
+        // Changed after the previous build, with the same size
+        ::std::thread::sleep(::std::time::Duration::from_millis(20));
+        write(&src.join("img/changed.png"), "new");
+
+        remove_dir_content_except_copies(&dest, &src, &["md"], &IgnorePatterns::default()).unwrap();
+        let mut kept = super::list_files(&dest).unwrap();
+        assert_eq!(kept, vec!["img/changed.png".to_owned(), "img/logo.png".to_owned()]);
+
+        // The unchanged copy is not written again
+        let modified = fs::metadata(dest.join("img/logo.png")).unwrap().modified().unwrap();
+        ::std::thread::sleep(::std::time::Duration::from_millis(20));
+        copy_files_except_ext(&src, &dest, true, &["md"], false, &IgnorePatterns::default()).unwrap();
+        assert_eq!(fs::metadata(dest.join("img/logo.png")).unwrap().modified().unwrap(), modified);
+
+        let mut changed = String::new();
+        fs::File::open(dest.join("img/changed.png")).unwrap().read_to_string(&mut changed).unwrap();
+        assert_eq!(changed, "new");
+
+        kept = super::list_files(&dest).unwrap();
+        assert_eq!(kept.len(), 2);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn it_copies_the_links_and_permissions() {
+        use std::os::unix::fs::{symlink, PermissionsExt};
+
+        let tmp = tempdir::TempDir::new("").expect("Could not create a temp dir");
+        let src = tmp.path().join("src");
+        fs::create_dir_all(src.join("scripts")).unwrap();
+        fs::File::create(src.join("scripts/run.sh")).unwrap().write_all(b"echo hi").unwrap();
+        fs::set_permissions(src.join("scripts/run.sh"), fs::Permissions::from_mode(0o755)).unwrap();
+        symlink("scripts/run.sh", src.join("run.sh")).unwrap();
+        symlink("scripts", src.join("bin")).unwrap();
+
+        let followed = tmp.path().join("followed");
+        fs::create_dir(&followed).unwrap();
+        copy_files_except_ext(&src, &followed, true, &["md"], false, &IgnorePatterns::default()).unwrap();
+        assert!(!fs::symlink_metadata(followed.join("run.sh")).unwrap().file_type().is_symlink());
+        assert!(followed.join("bin/run.sh").is_file());
+        let mode = fs::metadata(followed.join("scripts/run.sh")).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o755);
+
+        let preserved = tmp.path().join("preserved");
+        fs::create_dir(&preserved).unwrap();
+        copy_files_except_ext(&src, &preserved, true, &["md"], true, &IgnorePatterns::default()).unwrap();
+        assert_eq!(fs::read_link(preserved.join("run.sh")).unwrap(), ::std::path::Path::new("scripts/run.sh"));
+        assert!(fs::symlink_metadata(preserved.join("bin")).unwrap().file_type().is_symlink());
+
+        // The other files are copied and the error names the broken link
+        symlink("missing.png", src.join("broken.png")).unwrap();
+        symlink("..", src.join("scripts/parent")).unwrap();
+        let error = copy_files_except_ext(&src, &followed, true, &["md"], false, &IgnorePatterns::default())
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("broken.png"));
+        assert!(error.contains("parent"));
+        assert!(followed.join("scripts/run.sh").is_file());
+    }
+
+    #[test]
+    fn it_skips_the_ignored_files() {
+        let tmp = tempdir::TempDir::new("").expect("Could not create a temp dir");
+        let src = tmp.path().join("src");
+        let dest = tmp.path().join("book");
+        fs::create_dir_all(src.join("img/sources")).unwrap();
+        fs::create_dir_all(dest.join("img")).unwrap();
+        for file in &["img/logo.png", "img/logo.psd", "img/.logo.png.swp", "img/sources/logo.svg", "notes.txt"] {
+            fs::File::create(src.join(file)).unwrap();
+        }
+        fs::File::create(dest.join("img/logo.psd")).unwrap();
+
+        let ignore = IgnorePatterns::new(&src, &["*.psd", ".*.swp", "img/sources/", "/notes.txt"]);
+        remove_dir_content_except_copies(&dest, &src, &["md"], &ignore).unwrap();
+        copy_files_except_ext(&src, &dest, true, &["md"], false, &ignore).unwrap();
+        assert_eq!(super::list_files(&dest).unwrap(), vec!["img/logo.png".to_owned()]);
+    }
+
+    #[test]
+    fn it_decodes_the_chapters_that_are_not_utf8() {
+        use book::bookconfig::InvalidUtf8;
+        use super::{chapter_to_string, file_to_string};
+
+        let tmp = tempdir::TempDir::new("").expect("Could not create a temp dir");
+        let path = tmp.path().join("caf\u{e9}.md");
+        fs::File::create(&path).unwrap().write_all(b"# Caf\xe9\n").unwrap();
+
+        let error = file_to_string(&path).unwrap_err().to_string();
+        assert!(error.ends_with("is not valid UTF-8: the byte 0xe9 at offset 5 is invalid"));
+        assert!(chapter_to_string(&path, InvalidUtf8::Error).unwrap_err().to_string().contains("`invalid-utf8`"));
+
+        let (content, warning) = chapter_to_string(&path, InvalidUtf8::Latin1).unwrap();
+        assert_eq!(content, "# Caf\u{e9}\n");
+        assert_eq!(warning.unwrap(), "is not valid UTF-8 from byte 5, it is read as Latin-1");
+        assert_eq!(chapter_to_string(&path, InvalidUtf8::Lossy).unwrap().0, "# Caf\u{fffd}\n");
     }
 }
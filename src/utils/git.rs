@@ -0,0 +1,27 @@
+use std::path::Path;
+use std::process::Command;
+use std::error::Error;
+use std::io;
+
+/// Runs git in `dir` and returns what it printed on its standard output
+pub fn git(dir: &Path, args: &[&str]) -> Result<String, Box<Error>> {
+    git_with_env(dir, args, &[])
+}
+
+/// Same as `git`, with extra environment variables, e.g. `GIT_INDEX_FILE`
+pub fn git_with_env(dir: &Path, args: &[&str], env: &[(&str, &Path)]) -> Result<String, Box<Error>> {
+    let mut command = Command::new("git");
+    command.args(args).current_dir(dir);
+    for &(key, value) in env {
+        command.env(key, value);
+    }
+
+    let output = try!(command.output());
+    if !output.status.success() {
+        return Err(Box::new(io::Error::new(io::ErrorKind::Other,
+                                           format!("git {} failed: {}",
+                                                   args.join(" "),
+                                                   String::from_utf8_lossy(&output.stderr).trim()))));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
@@ -0,0 +1,209 @@
+use std::path::{Component, Path, PathBuf};
+use std::fs;
+
+use utils::fs::path_to_url;
+
+/// File of the book root with the patterns of the source files left out of the book, one by line
+pub const IGNORE_FILE: &'static str = ".mdbookignore";
+
+
+/// Patterns of the files of the source directory that are not copied to the book and do not trigger a rebuild
+/// of `mdbook watch` and `mdbook serve`. They are written like the patterns of `.gitignore`:
+///
+/// - `*` matches anything but `/`, `?` matches one character and `**` matches anything, `/` included
+/// - a pattern without `/` matches the name of the files and directories in every directory, e.g. `*.psd`,
+///   the other ones match the path from the source directory, e.g. `/drafts` or `images/*.sketch`
+/// - a pattern ending with `/` only matches directories, the files in an ignored directory are ignored too
+/// - a pattern starting with `!` includes again the files matched by the previous patterns
+/// - the empty lines and the lines starting with `#` are skipped
+#[derive(Debug, Clone, Default)]
+pub struct IgnorePatterns {
+    // The paths are matched relative to the source directory, as given and canonicalized
+    roots: Vec<PathBuf>,
+    patterns: Vec<Pattern>,
+    // Files ignored whatever the patterns, relative to the root
+    files: Vec<PathBuf>,
+}
+
+#[derive(Debug, Clone)]
+struct Pattern {
+    glob: Vec<char>,
+    negated: bool,
+    only_dirs: bool,
+    // Matched against the whole relative path instead of the file name
+    anchored: bool,
+}
+
+impl IgnorePatterns {
+    /// The patterns of the lines, for the paths in `root`
+    pub fn new<S: AsRef<str>>(root: &Path, lines: &[S]) -> Self {
+        let mut roots = vec![root.to_owned()];
+        if let Ok(canonical) = fs::canonicalize(root) {
+            if canonical != root {
+                roots.push(canonical);
+            }
+        }
+
+        IgnorePatterns {
+            roots: roots,
+            patterns: lines.iter().filter_map(|line| Pattern::parse(line.as_ref())).collect(),
+            files: vec![],
+        }
+    }
+
+    /// Ignores the files at `paths` too, relative to the root, like the chapters written in html that are
+    /// rendered instead of copied
+    pub fn with_files<P: AsRef<Path>>(mut self, paths: &[P]) -> Self {
+        self.files.extend(paths.iter().map(|path| normal_path(path.as_ref())));
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.patterns.is_empty() && self.files.is_empty()
+    }
+
+    /// Whether the file or directory at `path`, in the root or relative to it, is ignored, or one of the
+    /// directories it is in. The absolute paths outside of the root are never ignored.
+    pub fn is_ignored(&self, path: &Path) -> bool {
+        if self.is_empty() {
+            return false;
+        }
+
+        let relative = match self.roots.iter().filter_map(|root| path.strip_prefix(root).ok()).next() {
+            Some(relative) => relative.to_owned(),
+            None if path.is_absolute() => return false,
+            None => path.to_owned(),
+        };
+        if self.files.contains(&normal_path(&relative)) {
+            return true;
+        }
+
+        // The directories the file is in first
+        let mut ancestor = PathBuf::new();
+        let components: Vec<_> = relative.components().collect();
+        for (i, component) in components.iter().enumerate() {
+            ancestor.push(component.as_os_str());
+            let is_dir = i + 1 < components.len() || self.roots.iter().any(|root| root.join(&ancestor).is_dir());
+            if self.matches(&ancestor, is_dir) {
+                return true;
+            }
+        }
+        false
+    }
+
+    // The last pattern matching the path decides
+    fn matches(&self, relative: &Path, is_dir: bool) -> bool {
+        let path: Vec<char> = path_to_url(relative).chars().collect();
+        let name: Vec<char> = relative.file_name().map(|n| n.to_string_lossy().chars().collect()).unwrap_or_default();
+
+        let mut ignored = false;
+        for pattern in &self.patterns {
+            if pattern.only_dirs && !is_dir {
+                continue;
+            }
+            let matched = if pattern.anchored { &path } else { &name };
+            if glob_matches(&pattern.glob, matched) {
+                ignored = !pattern.negated;
+            }
+        }
+        ignored
+    }
+}
+
+fn normal_path(path: &Path) -> PathBuf {
+    path.components().filter(|c| *c != Component::CurDir).collect()
+}
+
+impl Pattern {
+    fn parse(line: &str) -> Option<Self> {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            return None;
+        }
+
+        let (line, negated) = match line.strip_prefix('!') {
+            Some(line) => (line, true),
+            None => (line, false),
+        };
+        let (line, only_dirs) = match line.strip_suffix('/') {
+            Some(line) => (line, true),
+            None => (line, false),
+        };
+        let anchored = line.contains('/');
+        let line = line.trim_start_matches('/');
+        if line.is_empty() {
+            return None;
+        }
+
+        Some(Pattern {
+            glob: line.chars().collect(),
+            negated: negated,
+            only_dirs: only_dirs,
+            anchored: anchored,
+        })
+    }
+}
+
+fn glob_matches(glob: &[char], s: &[char]) -> bool {
+    match glob.first() {
+        None => s.is_empty(),
+        Some(&'*') if glob.get(1) == Some(&'*') => {
+            // `**/` also matches no directory at all
+            match glob[2..].split_first() {
+                Some((&'/', rest)) => {
+                    glob_matches(rest, s) || (1..s.len() + 1).any(|i| s[i - 1] == '/' && glob_matches(rest, &s[i..]))
+                },
+                _ => (0..s.len() + 1).any(|i| glob_matches(&glob[2..], &s[i..])),
+            }
+        },
+        Some(&'*') => {
+            let end = s.iter().position(|&c| c == '/').unwrap_or(s.len());
+            (0..end + 1).any(|i| glob_matches(&glob[1..], &s[i..]))
+        },
+        Some(&'?') => !s.is_empty() && s[0] != '/' && glob_matches(&glob[1..], &s[1..]),
+        Some(&c) => !s.is_empty() && s[0] == c && glob_matches(&glob[1..], &s[1..]),
+    }
+}
+
+
+// ---------------------------------------------------------------------------------
+//      Tests
+//
+
+#[test]
+fn test_glob_matches() {
+    let matches = |glob: &str, s: &str| {
+        glob_matches(&glob.chars().collect::<Vec<_>>(), &s.chars().collect::<Vec<_>>())
+    };
+    assert!(matches("*.psd", "logo.psd"));
+    assert!(!matches("*.psd", "logo.png"));
+    assert!(!matches("*.psd", "images/logo.psd"));
+    assert!(matches("images/*.sketch", "images/a.sketch"));
+    assert!(matches("**/notes.md", "notes.md"));
+    assert!(matches("**/notes.md", "a/b/notes.md"));
+    assert!(matches("a/**", "a/b/c"));
+    assert!(matches(".*.sw?", ".intro.md.swp"));
+}
+
+#[test]
+fn test_ignore_patterns() {
+    let patterns = IgnorePatterns::new(Path::new("/book/src"),
+                                       &["# Editors", "*~", ".*.swp", "", "*.psd", "!keep.psd", "/drafts/",
+                                         "notes/*.txt"]);
+
+    assert!(patterns.is_ignored(Path::new("/book/src/intro.md~")));
+    assert!(patterns.is_ignored(Path::new("images/.logo.png.swp")));
+    assert!(patterns.is_ignored(Path::new("images/logo.psd")));
+    assert!(!patterns.is_ignored(Path::new("images/keep.psd")));
+    assert!(patterns.is_ignored(Path::new("drafts/new/image.png")));
+    assert!(!patterns.is_ignored(Path::new("guide/drafts")));
+    assert!(patterns.is_ignored(Path::new("notes/todo.txt")));
+    assert!(!patterns.is_ignored(Path::new("guide/notes/todo.txt")));
+    assert!(!patterns.is_ignored(Path::new("/elsewhere/logo.psd")));
+    assert!(!patterns.is_ignored(Path::new("images/logo.png")));
+    assert!(IgnorePatterns::new(Path::new("src"), &["# nothing"]).is_empty());
+
+    let patterns = patterns.with_files(&["./demos/canvas.html"]);
+    assert!(patterns.is_ignored(Path::new("/book/src/demos/canvas.html")));
+    assert!(!patterns.is_ignored(Path::new("demos/index.html")));
+}
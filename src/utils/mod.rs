@@ -1,6 +1,11 @@
 pub mod fs;
+pub mod git;
+pub mod ignore;
+pub mod process;
 
-use pulldown_cmark::{Parser, html, Options, OPTION_ENABLE_TABLES, OPTION_ENABLE_FOOTNOTES};
+use pulldown_cmark::{Parser, Event, Tag, html, Options};
+
+use book::MarkdownConfig;
 
 
 ///
@@ -10,11 +15,97 @@ use pulldown_cmark::{Parser, html, Options, OPTION_ENABLE_TABLES, OPTION_ENABLE_
 pub fn render_markdown(text: &str) -> String {
     let mut s = String::with_capacity(text.len() * 3 / 2);
 
-    let mut opts = Options::empty();
-    opts.insert(OPTION_ENABLE_TABLES);
-    opts.insert(OPTION_ENABLE_FOOTNOTES);
-
-    let p = Parser::new_ext(&text, opts);
+    let p = Parser::new_ext(&text, markdown_options());
     html::push_html(&mut s, p);
     s
 }
+
+/// The pulldown-cmark extensions enabled by default, the `[markdown]` table of `book.toml` changes them for
+/// the rendered chapters
+pub fn markdown_options() -> Options {
+    MarkdownConfig::new().options()
+}
+
+// Reading speed used for the estimated reading times
+const WORDS_PER_MINUTE: usize = 200;
+
+/// Number of words in the text of a chapter, code blocks are not counted
+pub fn word_count(text: &str) -> usize {
+    let mut count = 0;
+    let mut in_code_block = false;
+
+    for event in Parser::new_ext(text, markdown_options()) {
+        match event {
+            Event::Start(Tag::CodeBlock(_)) => in_code_block = true,
+            Event::End(Tag::CodeBlock(_)) => in_code_block = false,
+            Event::Text(ref text) if !in_code_block => count += text.split_whitespace().count(),
+            _ => {},
+        }
+    }
+
+    count
+}
+
+/// Estimated reading time in minutes, at least a minute for any text
+pub fn reading_time(word_count: usize) -> usize {
+    word_count.div_ceil(WORDS_PER_MINUTE)
+}
+
+/// Plain text of the first paragraph of a chapter, cut after a word to at most `max` characters with an
+/// ellipsis. Returns `None` when the chapter has no paragraph with text.
+pub fn first_paragraph(text: &str, max: usize) -> Option<String> {
+    let mut s = String::new();
+    let mut in_paragraph = false;
+
+    for event in Parser::new_ext(text, markdown_options()) {
+        match event {
+            Event::Start(Tag::Paragraph) => in_paragraph = true,
+            Event::End(Tag::Paragraph) if !s.trim().is_empty() => break,
+            Event::End(Tag::Paragraph) => in_paragraph = false,
+            Event::Text(ref text) if in_paragraph => s.push_str(text),
+            Event::SoftBreak | Event::HardBreak if in_paragraph => s.push(' '),
+            _ => {},
+        }
+    }
+
+    let words: Vec<&str> = s.split_whitespace().collect();
+    if words.is_empty() {
+        return None;
+    }
+
+    let mut paragraph = String::new();
+    for word in &words {
+        if paragraph.chars().count() + word.chars().count() + 1 > max {
+            paragraph.push('…');
+            return Some(paragraph);
+        }
+        if !paragraph.is_empty() {
+            paragraph.push(' ');
+        }
+        paragraph.push_str(word);
+    }
+    Some(paragraph)
+}
+
+/// Escapes the characters that have a special meaning in html text and attribute values
+pub fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+
+// ---------------------------------------------------------------------------------
+//      Tests
+//
+
+#[test]
+fn test_word_count() {
+    assert_eq!(word_count("# Two words\n\nThree *more* `words`\n\n```rust\nfn main() {}\n```"), 5);
+}
+
+#[test]
+fn test_first_paragraph() {
+    let text = "# Title\n\n<div class=\"note\"></div>\n\nThe *init* command creates\na `book.toml`.\n\nMore.";
+    assert_eq!(first_paragraph(text, 160), Some("The init command creates a book.toml.".to_owned()));
+    assert_eq!(first_paragraph(text, 20), Some("The init command…".to_owned()));
+    assert_eq!(first_paragraph("# Only a title", 160), None);
+}